@@ -9,11 +9,39 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::RwLock;
+use tauri::Emitter;
 use tokio::fs as async_fs;
 
 // In-memory cache for accounts to avoid redundant disk reads
 static ACCOUNTS_CACHE: RwLock<Option<LauncherAccountsJson>> = RwLock::new(None);
 
+/// Emitted with `AccountsChanged` when an account is added, updated, removed or made active
+pub const ACCOUNTS_CHANGED_EVENT: &str = "accounts-changed";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountsChanged {
+    /// 'written' | 'removed' | 'activated'
+    pub change: String,
+    pub account_id: String,
+    /// Empty when no account is left
+    pub active_account_id: String,
+}
+
+fn emit_accounts_changed(change: &str, account_id: &str, active_account_id: &str) {
+    if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
+        if let Some(app_handle) = handle_guard.as_ref() {
+            let _ = app_handle.emit(
+                ACCOUNTS_CHANGED_EVENT,
+                AccountsChanged {
+                    change: change.to_string(),
+                    account_id: account_id.to_string(),
+                    active_account_id: active_account_id.to_string(),
+                },
+            );
+        }
+    }
+}
+
 // ...existing code...
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LauncherAccount {
@@ -265,7 +293,9 @@ pub async fn write_launcher_account(account: LauncherAccount) -> Result<(), Stri
         );
     }
 
+    let active_account_id = accounts.active_account_local_id.clone();
     write_launcher_accounts(accounts).await?;
+    emit_accounts_changed("written", &account.local_id, &active_account_id);
 
     Ok(())
 }
@@ -303,7 +333,9 @@ pub async fn remove_launcher_account(account_id: String) -> Result<(), String> {
             );
         }
 
+        let active_account_id = accounts.active_account_local_id.clone();
         write_launcher_accounts(accounts).await?;
+        emit_accounts_changed("removed", &account_id, &active_account_id);
     } else {
         Logger::console_log(LogLevel::Warning, "⚠️ Account not found", None);
         return Err("Account not found".to_string());
@@ -330,6 +362,7 @@ pub async fn set_active_launcher_account(account_id: String) -> Result<(), Strin
 
     accounts.active_account_local_id = account_id.clone();
     write_launcher_accounts(accounts).await?;
+    emit_accounts_changed("activated", &account_id, &account_id);
 
     Logger::console_log(
         LogLevel::Info,
@@ -2,6 +2,7 @@ use crate::auth::auth_util::{
     get_client_id, write_launcher_account, LauncherAccount, MinecraftProfile,
 };
 use crate::logging::{LogLevel, Logger};
+use crate::KableError;
 use base64::{engine::general_purpose, Engine as _};
 use chrono::Utc;
 use minecraft_msa_auth::MinecraftAuthorizationFlow;
@@ -111,7 +112,7 @@ pub struct MicrosoftToken {
 }
 
 /// Start the Microsoft device code authentication flow
-pub async fn start_microsoft_device_auth() -> Result<DeviceCodeResponse, String> {
+pub async fn start_microsoft_device_auth() -> Result<DeviceCodeResponse, KableError> {
     Logger::console_log(
         LogLevel::Info,
         "🔐 Starting Microsoft device code authentication...",
@@ -188,7 +189,11 @@ pub async fn start_microsoft_device_auth() -> Result<DeviceCodeResponse, String>
                 &format!("❌ Failed to request device code: {}", e),
                 None,
             );
-            format!("Failed to request device code: {}", e)
+            let message = format!("Failed to request device code: {}", e);
+            match e {
+                oauth2::RequestTokenError::ServerResponse(_) => KableError::auth(message),
+                _ => KableError::network(message),
+            }
         })?;
 
     let response = DeviceCodeResponse {
@@ -269,7 +274,7 @@ pub async fn start_microsoft_device_auth() -> Result<DeviceCodeResponse, String>
 /// Poll for the completion of Microsoft device code authentication
 pub async fn poll_microsoft_device_auth(
     device_code: String,
-) -> Result<Option<MicrosoftToken>, String> {
+) -> Result<Option<MicrosoftToken>, KableError> {
     Logger::console_log(
         LogLevel::Debug,
         "🔄 Polling Microsoft device authentication...",
@@ -303,9 +308,9 @@ pub async fn poll_microsoft_device_auth(
         }
         None => {
             Logger::console_log(LogLevel::Error, "❌ Device code not found in storage", None);
-            return Err(
-                "Device code not found. Please start authentication flow first.".to_string(),
-            );
+            return Err(KableError::not_found(
+                "Device code not found. Please start authentication flow first.",
+            ));
         }
     };
 
@@ -412,7 +417,9 @@ pub async fn poll_microsoft_device_auth(
                     let mut storage = DEVICE_AUTH_STORAGE.lock().unwrap();
                     storage.remove(&device_code);
                 }
-                Err("Device code has expired. Please start authentication flow again.".to_string())
+                Err(KableError::auth(
+                    "Device code has expired. Please start authentication flow again.",
+                ))
             } else {
                 let error_desc = err
                     .error_description()
@@ -423,7 +430,10 @@ pub async fn poll_microsoft_device_auth(
                     &format!("❌ OAuth2 server error: {} - {}", err.error(), error_desc),
                     None,
                 );
-                Err(format!("Authentication failed: {}", err.error()))
+                Err(KableError::auth(format!(
+                    "Authentication failed: {}",
+                    err.error()
+                )))
             }
         }
         Err(e) => {
@@ -432,7 +442,7 @@ pub async fn poll_microsoft_device_auth(
                 &format!("❌ Network or other error during polling: {}", e),
                 None,
             );
-            Err(format!("Polling failed: {}", e))
+            Err(KableError::network(format!("Polling failed: {}", e)))
         }
     }
 }
@@ -1,10 +1,11 @@
 pub use crate::auth::*;
+use crate::KableError;
 
 #[tauri::command]
 pub async fn refresh_microsoft_token(
     local_id: String,
-) -> Result<crate::auth::LauncherAccount, String> {
-    crate::auth::auth_util::refresh_microsoft_token(local_id).await
+) -> Result<crate::auth::LauncherAccount, KableError> {
+    Ok(crate::auth::auth_util::refresh_microsoft_token(local_id).await?)
 }
 
 // Tauri command wrappers for all public async functions in the auth module
@@ -12,18 +13,18 @@ pub async fn refresh_microsoft_token(
 #[tauri::command]
 pub async fn get_minecraft_account(
     auth_method: Option<AuthMethod>,
-) -> Result<LauncherAccount, String> {
-    crate::auth::get_minecraft_account(auth_method).await
+) -> Result<LauncherAccount, KableError> {
+    Ok(crate::auth::get_minecraft_account(auth_method).await?)
 }
 
 #[tauri::command]
-pub async fn get_launch_auth_account() -> Result<LauncherAccount, String> {
-    crate::auth::get_launch_auth_account().await
+pub async fn get_launch_auth_account() -> Result<LauncherAccount, KableError> {
+    Ok(crate::auth::get_launch_auth_account().await?)
 }
 
 #[tauri::command]
-pub async fn refresh_minecraft_account() -> Result<LauncherAccount, String> {
-    crate::auth::refresh_minecraft_account().await
+pub async fn refresh_minecraft_account() -> Result<LauncherAccount, KableError> {
+    Ok(crate::auth::refresh_minecraft_account().await?)
 }
 
 #[tauri::command]
@@ -75,13 +76,13 @@ pub async fn validate_and_cleanup_accounts() -> Result<String, String> {
 
 #[tauri::command]
 pub async fn start_microsoft_device_auth(
-) -> Result<crate::auth::device_code_flow::DeviceCodeResponse, String> {
-    crate::auth::device_code_flow::start_microsoft_device_auth().await
+) -> Result<crate::auth::device_code_flow::DeviceCodeResponse, KableError> {
+    Ok(crate::auth::device_code_flow::start_microsoft_device_auth().await?)
 }
 
 #[tauri::command]
 pub async fn poll_microsoft_device_auth(
     device_code: String,
-) -> Result<Option<crate::auth::device_code_flow::MicrosoftToken>, String> {
-    crate::auth::device_code_flow::poll_microsoft_device_auth(device_code).await
+) -> Result<Option<crate::auth::device_code_flow::MicrosoftToken>, KableError> {
+    Ok(crate::auth::device_code_flow::poll_microsoft_device_auth(device_code).await?)
 }
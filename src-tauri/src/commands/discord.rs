@@ -3,7 +3,11 @@
 /// Set Discord Rich Presence for browsing a section
 #[tauri::command]
 pub async fn discord_set_browsing(section: String) -> Result<(), String> {
-    crate::discord::set_browsing(&section)
+    let templates = crate::settings::load_settings()
+        .await
+        .map(|s| s.misc.discord_presence)
+        .unwrap_or_default();
+    crate::discord::set_browsing(&section, &templates)
 }
 
 /// Set Discord Rich Presence enabled/disabled
@@ -17,3 +21,9 @@ pub async fn discord_set_enabled(enabled: bool) -> Result<(), String> {
 pub async fn discord_clear() -> Result<(), String> {
     crate::discord::clear()
 }
+
+/// Get whether Discord Rich Presence is enabled and connected
+#[tauri::command]
+pub async fn discord_get_status() -> Result<crate::discord::DiscordStatus, String> {
+    crate::discord::get_status()
+}
@@ -14,6 +14,12 @@ pub async fn get_all_versions(force: bool) -> Versions {
     crate::installations::get_all_versions(force).await
 }
 
+/// Versions with a json in .minecraft/versions, without touching the network
+#[tauri::command]
+pub async fn get_local_versions() -> Versions {
+    crate::installations::get_local_versions().await
+}
+
 /// Force refresh version manifests from the network
 #[tauri::command]
 pub async fn refresh_version_manifests() -> Versions {
@@ -25,6 +31,16 @@ pub async fn get_version(version_id: String) -> Option<VersionData> {
     crate::installations::get_version(version_id).await
 }
 
+/// Checks whether a loader build supports a game version, with the reason when it doesn't
+#[tauri::command]
+pub async fn is_loader_compatible(
+    loader: LoaderKind,
+    loader_version: String,
+    game_version: String,
+) -> Result<LoaderCompatibility, String> {
+    crate::installations::is_loader_compatible(loader, &loader_version, &game_version).await
+}
+
 /// Returns all Kable installations, using cache. Ensures conversion if needed.
 #[tauri::command]
 pub async fn get_installations() -> Result<Vec<KableInstallation>, String> {
@@ -49,6 +65,93 @@ pub async fn get_installation(id: &str) -> Result<Option<KableInstallation>, Str
     crate::installations::get_installation(id).await
 }
 
+/// Returns the installation with this name (case-insensitive), errors when several match
+#[tauri::command]
+pub async fn get_installation_by_name(name: &str) -> Result<Option<KableInstallation>, String> {
+    crate::installations::get_installation_by_name(name).await
+}
+
+/// Fuzzy-searches installations by name, version, description and tags, returning ranked results with matched ranges
+#[tauri::command]
+pub async fn search_installations(query: String) -> Result<Vec<InstallationSearchResult>, String> {
+    crate::installations::search_installations(&query).await
+}
+
+/// Sanitized HTML of an installation's description, rendered from markdown when its description_format is 'markdown'
+#[tauri::command]
+pub async fn render_installation_description(id: String) -> Result<String, String> {
+    crate::installations::render_installation_description(&id).await
+}
+
+/// Aggregated playtime and launch statistics across all installations
+#[tauri::command]
+pub async fn get_playtime_stats() -> Result<PlaytimeStats, String> {
+    crate::installations::get_playtime_stats().await
+}
+
+/// What differs between two installations, "added" means only in b
+#[tauri::command]
+pub async fn diff_installations(a_id: String, b_id: String) -> Result<InstallationDiff, String> {
+    crate::installations::diff_installations(&a_id, &b_id).await
+}
+
+/// Mod, resource pack and shader counts of an installation's folders
+#[tauri::command]
+pub async fn get_installation_content_counts(
+    installation_id: String,
+) -> Result<InstallationContentCounts, String> {
+    crate::installations::get_installation_content_counts(&installation_id).await
+}
+
+/// Finds (and unless dry_run removes) versions, libraries and assets no installation uses
+#[tauri::command]
+pub async fn prune_unused_game_files(dry_run: bool) -> Result<PruneReport, String> {
+    crate::installations::prune_unused_game_files(dry_run).await
+}
+
+/// Versions used by installations with how many use them, loader versions and their parents included
+#[tauri::command]
+pub async fn get_versions_in_use() -> Result<Vec<crate::installations::VersionUsage>, String> {
+    crate::installations::get_versions_in_use().await
+}
+
+/// Removes temp files left behind by interrupted writes and downloads
+#[tauri::command]
+pub async fn cleanup_temp_files() -> Result<PruneCategory, String> {
+    crate::installations::cleanup_temp_files().await
+}
+
+/// Disk usage of game files, installation folders, caches, logs, exports and backups
+#[tauri::command]
+pub async fn get_storage_report() -> Result<StorageReport, String> {
+    crate::installations::get_storage_report().await
+}
+
+/// Reads the installation's options.txt as key/value pairs
+#[tauri::command]
+pub async fn get_game_options(
+    installation_id: String,
+) -> Result<std::collections::HashMap<String, String>, String> {
+    crate::installations::get_game_options(&installation_id).await
+}
+
+/// Changes options in the installation's options.txt, other lines stay untouched
+#[tauri::command]
+pub async fn set_game_options(
+    installation_id: String,
+    changes: std::collections::HashMap<String, String>,
+) -> Result<(), String> {
+    crate::installations::set_game_options(&installation_id, changes).await
+}
+
+/// Keys bound to more than one action in the installation's options.txt
+#[tauri::command]
+pub async fn detect_keybind_conflicts(
+    installation_id: String,
+) -> Result<Vec<KeybindConflict>, String> {
+    crate::installations::detect_keybind_conflicts(&installation_id).await
+}
+
 /// Deletes a KableInstallation by ID from kable_profiles.json and invalidates cache
 #[tauri::command]
 pub async fn delete_installation(id: &str) -> Result<(), String> {
@@ -90,11 +193,154 @@ pub async fn create_installation_from_existing(
     .await
 }
 
+/// Start copying an installation in the background, returns the token its progress events are emitted with
+#[tauri::command]
+pub fn start_create_installation_from_existing(
+    version_id: String,
+    source_installation_id: String,
+    copy_mods: bool,
+    copy_resource_packs: bool,
+    copy_shaders: bool,
+) -> String {
+    crate::launcher::start_with_progress(|progress| async move {
+        crate::installations::create_installation_from_existing_with_progress(
+            &version_id,
+            &source_installation_id,
+            copy_mods,
+            copy_resource_packs,
+            copy_shaders,
+            &progress,
+        )
+        .await
+        .map(|_| ())
+    })
+}
+
+/// Moves an installation to another Minecraft version, optionally updating its mods (backed up first)
+#[tauri::command]
+pub async fn migrate_installation_version(
+    id: String,
+    new_version_id: String,
+    update_mods: bool,
+) -> Result<VersionMigrationReport, String> {
+    crate::installations::migrate_installation_version(&id, &new_version_id, update_mods).await
+}
+
+/// Stores an installation's settings (without its content) as a named template
+#[tauri::command]
+pub async fn save_installation_template(
+    installation_id: String,
+    template_name: String,
+) -> Result<InstallationTemplate, String> {
+    crate::installations::save_installation_template(&installation_id, &template_name).await
+}
+
+/// Lists the saved installation templates
+#[tauri::command]
+pub async fn list_installation_templates() -> Result<Vec<InstallationTemplate>, String> {
+    crate::installations::list_installation_templates().await
+}
+
+/// Deletes a saved installation template by name
+#[tauri::command]
+pub async fn delete_installation_template(template_name: String) -> Result<(), String> {
+    crate::installations::delete_installation_template(&template_name).await
+}
+
+/// Creates a new KableInstallation with the settings of a saved template
+#[tauri::command]
+pub async fn create_installation_from_template(
+    version_id: String,
+    template_name: String,
+) -> Result<KableInstallation, String> {
+    crate::installations::create_installation_from_template(&version_id, &template_name).await
+}
+
+/// Creates a new KableInstallation of a custom version json, copied into versions/ with its jar
+#[tauri::command]
+pub async fn create_installation_from_manifest(
+    manifest_path: String,
+    jar_path: Option<String>,
+) -> Result<KableInstallation, String> {
+    crate::installations::create_installation_from_manifest(&manifest_path, jar_path.as_deref())
+        .await
+}
+
 #[tauri::command]
 pub async fn get_mod_info(installation: KableInstallation) -> Result<Vec<ModJarInfo>, String> {
     installation.get_mod_info()
 }
 
+/// Switch an installation between a dedicated and the global mods folder
+#[tauri::command]
+pub async fn set_mods_folder_mode(
+    installation_id: String,
+    dedicated: bool,
+    transfer: Option<crate::installations::ModsTransfer>,
+) -> Result<KableInstallation, String> {
+    crate::installations::set_mods_folder_mode(
+        &installation_id,
+        dedicated,
+        transfer.unwrap_or_default(),
+    )
+    .await
+}
+
+/// Move a dedicated mods/resourcepacks/shaders/config folder to an absolute path
+#[tauri::command]
+pub async fn relocate_installation_folder(
+    id: String,
+    which: crate::installations::FolderKind,
+    new_path: String,
+) -> Result<KableInstallation, String> {
+    crate::installations::relocate_installation_folder(&id, which, &new_path).await
+}
+
+/// Reset the JVM flags, parameters or window/quick play settings without touching content
+#[tauri::command]
+pub async fn reset_installation_settings(
+    id: String,
+    what: crate::installations::ResetScope,
+) -> Result<KableInstallation, String> {
+    crate::installations::reset_installation_settings(&id, what).await
+}
+
+/// Set a parameters_map entry, `--key` adds a game argument and other keys override a variable
+#[tauri::command]
+pub async fn set_installation_parameter(
+    id: String,
+    key: String,
+    value: String,
+) -> Result<KableInstallation, String> {
+    crate::installations::set_installation_parameter(&id, &key, &value).await
+}
+
+/// Remove a parameters_map entry
+#[tauri::command]
+pub async fn remove_installation_parameter(
+    id: String,
+    key: String,
+) -> Result<KableInstallation, String> {
+    crate::installations::remove_installation_parameter(&id, &key).await
+}
+
+/// Mod identity of any jar on disk, fails for files that aren't readable jars
+#[tauri::command]
+pub async fn read_mod_jar_info(path: String) -> Result<ModJarInfo, String> {
+    crate::installations::read_mod_jar_info(&path).await
+}
+
+/// Copy a jar into an installation's mods folder, `replace` removes a jar with the same mod id
+#[tauri::command]
+pub async fn install_local_mod(
+    installation_id: String,
+    jar_path: String,
+    replace: Option<bool>,
+) -> Result<crate::installations::LocalModInstall, String> {
+    crate::installations::install_local_mod(&installation_id, &jar_path, replace.unwrap_or(false))
+        .await
+}
+
 /// Get resource pack info for an installation
 #[tauri::command]
 pub async fn get_resourcepack_info_for_installation(
@@ -275,14 +521,55 @@ pub async fn import(path: String) -> Result<KableInstallation, String> {
     KableInstallation::import(&path).await
 }
 
+/// Import several export zips at once, failed files are reported per file
+#[tauri::command]
+pub async fn import_installations(
+    paths: Vec<String>,
+) -> Result<crate::installations::BatchImportReport, String> {
+    crate::installations::import_installations(&paths).await
+}
+
 #[tauri::command]
 pub async fn import_from_minecraft_folder(path: String) -> Result<Vec<KableInstallation>, String> {
     KableInstallation::import_from_minecraft_folder(&path).await
 }
 
+/// Start importing a .minecraft folder in the background, returns the token its progress events are emitted with
+#[tauri::command]
+pub fn start_import_from_minecraft_folder(path: String) -> String {
+    crate::launcher::start_with_progress(|progress| async move {
+        KableInstallation::import_from_minecraft_folder_with_progress(&path, &progress)
+            .await
+            .map(|_| ())
+    })
+}
+
+/// Import a Prism Launcher instance, or every instance in a Prism `instances` folder
 #[tauri::command]
-pub async fn export(installation: KableInstallation) -> Result<String, String> {
-    installation.export().await
+pub async fn import_from_prism(path: String) -> Result<Vec<KableInstallation>, String> {
+    crate::installations::import_from_prism(&path).await
+}
+
+/// Import a CurseForge modpack zip, files that can't be downloaded automatically are reported back
+#[tauri::command]
+pub async fn import_curseforge_zip(
+    path: String,
+) -> Result<crate::installations::CurseForgePackImport, String> {
+    crate::installations::import_curseforge_zip(&path).await
+}
+
+/// Import a MultiMC instance, or every instance in a MultiMC `instances` folder
+#[tauri::command]
+pub async fn import_from_multimc(path: String) -> Result<Vec<KableInstallation>, String> {
+    crate::installations::import_from_multimc(&path).await
+}
+
+#[tauri::command]
+pub async fn export(
+    installation: KableInstallation,
+    content: Option<crate::installations::ExportContent>,
+) -> Result<String, String> {
+    installation.export(content.unwrap_or_default()).await
 }
 
 #[tauri::command]
@@ -314,6 +601,25 @@ pub async fn select_installation_zip(app: tauri::AppHandle) -> Result<Option<Str
     }
 }
 
+/// Open a file picker for a CurseForge modpack zip
+#[tauri::command]
+pub async fn select_curseforge_zip(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let file_path = app
+        .dialog()
+        .file()
+        .add_filter("CurseForge Modpack", &["zip"])
+        .set_title("Import CurseForge Modpack")
+        .blocking_pick_file();
+
+    match file_path {
+        Some(path) => match path.as_path() {
+            Some(path_buf) => Ok(Some(path_buf.to_string_lossy().to_string())),
+            None => Err("Invalid file path".to_string()),
+        },
+        None => Ok(None),
+    }
+}
+
 /// Select a .minecraft folder for importing installations
 #[tauri::command]
 pub async fn select_minecraft_folder(app: tauri::AppHandle) -> Result<Option<String>, String> {
@@ -331,3 +637,21 @@ pub async fn select_minecraft_folder(app: tauri::AppHandle) -> Result<Option<Str
         None => Ok(None),
     }
 }
+
+/// Open a folder picker for a Prism/MultiMC instance (or instances) folder
+#[tauri::command]
+pub async fn select_instance_folder(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let folder_path = app
+        .dialog()
+        .file()
+        .set_title("Import Prism/MultiMC Instance")
+        .blocking_pick_folder();
+
+    match folder_path {
+        Some(path) => match path.as_path() {
+            Some(path_buf) => Ok(Some(path_buf.to_string_lossy().to_string())),
+            None => Err("Invalid folder path".to_string()),
+        },
+        None => Ok(None),
+    }
+}
@@ -1,13 +1,85 @@
 pub use crate::launcher::*;
-use crate::{CategorizedLauncherSettings, KableInstallation, LauncherAccount};
+use crate::{CategorizedLauncherSettings, KableError, KableInstallation, LauncherAccount};
 
+/// suppress_launch_behaviors skips the on_game_launch/close/crash behaviors for this one launch
 #[tauri::command]
 pub async fn launch_installation(
     installation: KableInstallation,
     settings: CategorizedLauncherSettings,
     account: LauncherAccount,
-) -> Result<LaunchResult, String> {
-    crate::launcher::launch_installation(installation, settings, account).await
+    suppress_launch_behaviors: Option<bool>,
+) -> Result<LaunchResult, KableError> {
+    Ok(crate::launcher::launch_installation_with_progress(
+        installation,
+        settings,
+        account,
+        crate::launcher::ProgressReporter::none(),
+        suppress_launch_behaviors.unwrap_or(false),
+    )
+    .await?)
+}
+
+/// Start a launch in the background, returns the token its progress events are emitted with
+#[tauri::command]
+pub fn start_launch_installation(
+    installation: KableInstallation,
+    settings: CategorizedLauncherSettings,
+    account: LauncherAccount,
+) -> String {
+    crate::launcher::start_launch_installation(installation, settings, account)
+}
+
+/// Preview the java command a launch would run, without launching (access token redacted)
+#[tauri::command]
+pub async fn preview_launch_command(
+    installation_id: String,
+) -> Result<LaunchCommandPreview, String> {
+    crate::launcher::preview_launch_command(&installation_id).await
+}
+
+/// Write the launch command as a script to run in a terminal, returns the script path
+#[tauri::command]
+pub async fn get_launch_script(installation_id: String) -> Result<String, String> {
+    crate::launcher::get_launch_script(&installation_id).await
+}
+
+/// The merged manifest a version launches with, optionally also written to dump_path
+#[tauri::command]
+pub async fn get_merged_manifest(
+    version_id: String,
+    dump_path: Option<String>,
+) -> Result<serde_json::Value, String> {
+    crate::launcher::utils::get_merged_manifest(&version_id, dump_path.as_deref()).await
+}
+
+/// Run all pre-launch checks for an installation and report errors and warnings, without launching
+#[tauri::command]
+pub async fn validate_launch(installation_id: String) -> Result<LaunchReadiness, String> {
+    crate::launcher::validate_launch(&installation_id).await
+}
+
+/// Keep only the highest LWJGL version on the classpath of an installation from now on
+#[tauri::command]
+pub async fn resolve_lwjgl_conflict(
+    installation_id: String,
+) -> Result<LwjglConflictResolution, String> {
+    crate::launcher::resolve_lwjgl_conflict(&installation_id).await
+}
+
+/// Compare the client jar of a version with the sha1 in its version json
+#[tauri::command]
+pub async fn verify_version_jar(version_id: String) -> Result<VersionJarVerification, String> {
+    crate::launcher::verify_version_jar(&version_id).await
+}
+
+/// Download the client jar of a version again when it is missing or does not match its sha1,
+/// through the proxy of `installation_id` when given
+#[tauri::command]
+pub async fn repair_version_jar(
+    version_id: String,
+    installation_id: Option<String>,
+) -> Result<VersionJarVerification, String> {
+    crate::launcher::repair_version_jar(&version_id, installation_id.as_deref()).await
 }
 
 /// Kill a Minecraft process by PID (only if tracked)
@@ -16,18 +88,45 @@ pub async fn kill_minecraft_process(process_id: u32) -> Result<(), String> {
     crate::launcher::kill_minecraft_process(process_id).await
 }
 
+/// Stop every running game of an installation, force kills instead of asking them to close
+#[tauri::command]
+pub async fn kill_installation(
+    installation_id: String,
+    force: Option<bool>,
+) -> Result<KillInstallationResult, String> {
+    crate::launcher::kill_installation(&installation_id, force.unwrap_or(true)).await
+}
+
 /// Get all running Minecraft process IDs (tracked by launcher)
 #[tauri::command]
 pub async fn get_running_minecraft_processes() -> Result<Vec<u32>, String> {
     crate::launcher::get_running_minecraft_processes().await
 }
 
+/// Get the running Minecraft processes with the installation each one belongs to
+#[tauri::command]
+pub async fn get_running_minecraft_process_details() -> Result<Vec<TrackedProcess>, String> {
+    crate::launcher::get_running_minecraft_process_details().await
+}
+
+/// Answer the `confirm-close-with-running-games` event, true closes the launcher anyway
+#[tauri::command]
+pub fn confirm_close_with_running_games(app: tauri::AppHandle, close: bool) -> Result<(), String> {
+    crate::launcher::confirm_close_with_running_games(&app, close)
+}
+
 /// Check if any Minecraft process is running (tracked by launcher)
 #[tauri::command]
 pub async fn is_minecraft_running() -> Result<bool, String> {
     crate::launcher::is_minecraft_running().await
 }
 
+/// Check if a game of this installation is running (tracked by launcher)
+#[tauri::command]
+pub async fn is_installation_running(installation_id: String) -> Result<bool, String> {
+    crate::launcher::is_installation_running(&installation_id).await
+}
+
 /// Wait for a Minecraft process to exit (tracked by launcher)
 #[tauri::command]
 pub async fn wait_for_minecraft_exit(process_id: u32) -> Result<(), String> {
@@ -37,12 +136,46 @@ pub async fn wait_for_minecraft_exit(process_id: u32) -> Result<(), String> {
         .map(|_exit_code| ())
 }
 
+/// Answer an `ask-launch-behavior`, `ask-close-behavior` or `ask-crash-behavior` event by its request id
+#[tauri::command]
+pub fn answer_launch_behavior(request_id: String, choice: String) -> Result<(), String> {
+    crate::launcher::answer_launch_behavior(&request_id, &choice)
+}
+
+/// Cancel a running long operation by the id from its `operation-heartbeat` events
+#[tauri::command]
+pub fn cancel_operation(operation_id: String) -> bool {
+    crate::launcher::operations::cancel_operation(&operation_id)
+}
+
+/// Java major version a version needs, None when its manifest isn't downloaded yet
+#[tauri::command]
+pub async fn get_required_java_version(version_id: String) -> Result<Option<u32>, String> {
+    java::get_required_java_version(&version_id).await
+}
+
 /// Auto-detect Java executable path
 #[tauri::command]
 pub fn auto_detect_java() -> Result<String, String> {
     java::auto_detect_java()
 }
 
+/// Version, vendor and arch of a Java executable, fails when it isn't a runnable Java
+#[tauri::command]
+pub async fn probe_java(path: String) -> Result<java::JavaInstallation, String> {
+    tokio::task::spawn_blocking(move || java::probe_java(&path))
+        .await
+        .map_err(|e| format!("Java probe task failed: {}", e))?
+}
+
+/// Java runtimes installed on the system, newest first
+#[tauri::command]
+pub async fn detect_system_java() -> Result<Vec<java::JavaInstallation>, String> {
+    tokio::task::spawn_blocking(java::detect_system_java)
+        .await
+        .map_err(|e| format!("Java detection task failed: {}", e))
+}
+
 /// Tauri command: Returns the path to a working Java executable, using the provided path or searching common locations.
 ///
 /// Used by the frontend to validate or auto-detect Java installations.
@@ -56,3 +189,15 @@ pub fn auto_detect_java() -> Result<String, String> {
 pub fn get_java_path(java_path: Option<String>) -> Result<String, String> {
     java::find_java_executable(java_path.as_ref())
 }
+
+/// The merged stdout/stderr of the last run of an instance
+#[tauri::command]
+pub async fn get_game_log(instance_id: String) -> Result<String, String> {
+    crate::launcher::game_log::get_game_log(&instance_id).await
+}
+
+/// Removes the game logs of an installation, the current one is kept while games run
+#[tauri::command]
+pub async fn clear_game_logs(installation_id: String) -> Result<(), String> {
+    crate::launcher::game_log::clear_game_logs(&installation_id).await
+}
@@ -1,5 +1,5 @@
 use crate::installations::kable_profiles::KableInstallation;
-use crate::{mods::*, ModJarInfo};
+use crate::{mods::*, KableError, ModJarInfo};
 
 #[tauri::command]
 pub async fn download_or_prepare_mod(
@@ -7,9 +7,14 @@ pub async fn download_or_prepare_mod(
     mod_id: String,
     version_id: Option<String>,
     installation: crate::installations::kable_profiles::KableInstallation,
-) -> Result<DownloadOrPrepareResponse, String> {
-    crate::mods::download_or_prepare_mod(provider, &mod_id, version_id.as_deref(), &installation)
-        .await
+) -> Result<DownloadOrPrepareResponse, KableError> {
+    Ok(crate::mods::download_or_prepare_mod(
+        provider,
+        &mod_id,
+        version_id.as_deref(),
+        &installation,
+    )
+    .await?)
 }
 
 #[tauri::command]
@@ -17,8 +22,25 @@ pub async fn apply_modpack_selection(
     installation: crate::installations::kable_profiles::KableInstallation,
     selection: ModpackSelection,
     context: ModpackContext,
-) -> Result<(), String> {
-    crate::mods::apply_modpack_selection(installation, selection, context).await
+) -> Result<(), KableError> {
+    Ok(crate::mods::apply_modpack_selection(
+        installation,
+        selection,
+        context,
+        &crate::launcher::ProgressReporter::none(),
+    )
+    .await?)
+}
+
+#[tauri::command]
+pub fn start_apply_modpack_selection(
+    installation: crate::installations::kable_profiles::KableInstallation,
+    selection: ModpackSelection,
+    context: ModpackContext,
+) -> String {
+    crate::launcher::start_with_progress(|progress| async move {
+        crate::mods::apply_modpack_selection(installation, selection, context, &progress).await
+    })
 }
 
 #[tauri::command]
@@ -26,6 +48,31 @@ pub async fn get_mods(provider: ProviderKind, offset: usize) -> Result<Vec<ModIn
     crate::mods::get_mods(provider, offset).await
 }
 
+#[tauri::command]
+pub async fn get_mods_page(provider: ProviderKind, offset: usize) -> Result<ModsPage, String> {
+    crate::mods::get_mods_page(provider, offset).await
+}
+
+#[tauri::command]
+pub async fn prefetch_featured_mods() -> Result<usize, String> {
+    crate::mods::prefetch_featured_mods().await
+}
+
+#[tauri::command]
+pub async fn add_mod_bookmark(provider: ProviderKind, project_id: String) -> Result<(), String> {
+    crate::mods::add_mod_bookmark(provider, &project_id).await
+}
+
+#[tauri::command]
+pub async fn remove_mod_bookmark(provider: ProviderKind, project_id: String) -> Result<(), String> {
+    crate::mods::remove_mod_bookmark(provider, &project_id).await
+}
+
+#[tauri::command]
+pub async fn get_mod_bookmarks() -> Result<Vec<ModInfoKind>, String> {
+    crate::mods::get_mod_bookmarks().await
+}
+
 #[tauri::command]
 pub async fn get_mod_metadata(
     installation: KableInstallation,
@@ -34,6 +81,23 @@ pub async fn get_mod_metadata(
     crate::mods::get_mod_metadata(&installation, &jar_filename).await
 }
 
+#[tauri::command]
+pub async fn rollback_mod(
+    installation_id: String,
+    file_name: String,
+    version_id: String,
+) -> Result<String, String> {
+    crate::mods::rollback_mod(&installation_id, &file_name, &version_id).await
+}
+
+#[tauri::command]
+pub async fn export_modlist(
+    installation_id: String,
+    format: ModlistFormat,
+) -> Result<String, String> {
+    crate::mods::export_modlist(&installation_id, format).await
+}
+
 #[tauri::command]
 pub async fn get_modpack_source_records(
     installation: KableInstallation,
@@ -84,18 +148,63 @@ pub async fn get_project_versions(
     }
 }
 
+#[tauri::command]
+pub async fn modrinth_version_by_hash(
+    sha1: String,
+) -> Result<Option<modrinth::ModrinthVersion>, String> {
+    modrinth::modrinth_version_by_hash(&sha1).await
+}
+
+#[tauri::command]
+pub async fn get_modrinth_categories(
+    project_type: Option<String>,
+) -> Result<Vec<ModrinthCategory>, String> {
+    crate::mods::get_modrinth_categories(project_type.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn get_modrinth_loaders(
+    project_type: Option<String>,
+) -> Result<Vec<ModrinthLoaderTag>, String> {
+    crate::mods::get_modrinth_loaders(project_type.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn get_modrinth_game_versions(
+    include_snapshots: Option<bool>,
+) -> Result<Vec<ModrinthGameVersionTag>, String> {
+    crate::mods::get_modrinth_game_versions(include_snapshots.unwrap_or(false)).await
+}
+
 #[tauri::command]
 pub async fn set_provider_filter(
     provider: ProviderKind,
     installation: Option<KableInstallation>,
     filter: Option<ModFilter>,
 ) {
-    crate::mods::set_provider_filter(provider, installation.as_ref(), filter);
+    crate::mods::set_provider_filter(provider, installation.as_ref(), filter.clone());
+    crate::mods::remember_provider_filter(provider, filter).await;
 }
 
 #[tauri::command]
 pub async fn set_provider_limit(provider: ProviderKind, limit: usize) {
     crate::mods::set_provider_limit(provider, limit);
+    crate::mods::remember_provider_limit(provider, limit).await;
+}
+
+#[tauri::command]
+pub async fn get_provider_filter(provider: ProviderKind) -> Option<ModFilter> {
+    crate::mods::get_provider_filter(provider)
+}
+
+#[tauri::command]
+pub async fn get_provider_limit(provider: ProviderKind) -> usize {
+    crate::mods::get_provider_limit(provider)
+}
+
+#[tauri::command]
+pub async fn get_last_provider() -> Result<Option<ProviderKind>, String> {
+    crate::mods::get_last_provider().await
 }
 
 #[tauri::command]
@@ -112,3 +221,10 @@ pub async fn purge_stale_provider_cache(provider: ProviderKind) {
 pub async fn get_extended_mod_info(mod_jar_info: ModJarInfo) -> Result<ExtendedModInfo, String> {
     crate::mods::get_extended_mod_info(mod_jar_info).await
 }
+
+#[tauri::command]
+pub async fn get_extended_mod_info_batch(
+    jars: Vec<ModJarInfo>,
+) -> Result<Vec<Option<ExtendedModInfo>>, String> {
+    crate::mods::get_extended_mod_info_batch(jars).await
+}
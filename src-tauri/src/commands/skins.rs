@@ -103,6 +103,14 @@ pub async fn get_skin_url_by_uuid(uuid: String) -> Result<String, String> {
     Err("No skin URL found in profile".to_string())
 }
 
+/// Look a player up by uuid or name for their current name, skin and cape
+#[tauri::command]
+pub async fn resolve_player(
+    uuid_or_name: String,
+) -> Result<crate::skins::types::ResolvedPlayer, crate::KableError> {
+    crate::skins::resolve_player(&uuid_or_name).await
+}
+
 /// Get the current skin information from Mojang
 #[tauri::command]
 pub async fn get_current_skin_info() -> Result<CurrentSkin, String> {
@@ -9,6 +9,12 @@ pub async fn list_symlinks() -> Result<Vec<SymlinkInfo>, String> {
     crate::symlink_manager::list_all_symlinks().await
 }
 
+/// Try whether symlinks can be created, false on Windows without Developer Mode or admin rights
+#[tauri::command]
+pub async fn can_create_symlinks() -> Result<bool, String> {
+    crate::symlink_manager::can_create_symlinks().await
+}
+
 /// Create a custom symlink
 #[tauri::command]
 pub async fn create_custom_symlink(
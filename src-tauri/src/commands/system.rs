@@ -70,3 +70,9 @@ pub async fn open_path(path: String) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Checks the minecraft dir, Java, the network and symlink support for the onboarding screen
+#[command]
+pub async fn run_first_run_checks() -> Result<Vec<crate::setup::CheckResult>, String> {
+    Ok(crate::setup::run_first_run_checks().await)
+}
@@ -3,11 +3,24 @@ use discord_rich_presence::{
     DiscordIpc, DiscordIpcClient,
 };
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::Emitter;
+
+use crate::settings::DiscordPresenceTemplates;
 
 const DISCORD_APP_ID: &str = "1432139549592649738";
 
+/// Discord rejects presence texts shorter or longer than this (in bytes)
+const DISCORD_TEXT_MIN_LEN: usize = 2;
+pub const DISCORD_TEXT_MAX_LEN: usize = 128;
+
+/// Emitted with a `DiscordStatus` whenever the connection to Discord is made or lost
+pub const DISCORD_STATUS_EVENT: &str = "discord-status-changed";
+
 /// Priority levels for different activities
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ActivityPriority {
@@ -44,6 +57,29 @@ impl Default for PresenceState {
     }
 }
 
+/// First retry after a failed connection, doubled after every failure up to the max
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_secs(5);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(300);
+
+/// Set while the reconnect task runs, there is at most one
+static RECONNECT_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Whether Rich Presence is enabled and actually connected to a running Discord
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscordStatus {
+    pub enabled: bool,
+    pub connected: bool,
+    /// What Discord is showing, None while not connected
+    pub current_activity: Option<DiscordActivity>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscordActivity {
+    pub details: String,
+    pub state: String,
+    pub playing: bool,
+}
+
 /// Global Discord RPC manager
 pub struct DiscordRpcManager {
     client: Option<DiscordIpcClient>,
@@ -51,6 +87,15 @@ pub struct DiscordRpcManager {
     enabled: bool,
 }
 
+/// Connects to the local Discord client, blocks until Discord answers or the attempt fails
+fn connect_client() -> Result<DiscordIpcClient, String> {
+    let mut client = DiscordIpcClient::new(DISCORD_APP_ID);
+    client
+        .connect()
+        .map_err(|e| format!("Failed to connect to Discord: {}", e))?;
+    Ok(client)
+}
+
 static DISCORD_MANAGER: Lazy<Mutex<DiscordRpcManager>> = Lazy::new(|| {
     Mutex::new(DiscordRpcManager {
         client: None,
@@ -70,14 +115,14 @@ impl DiscordRpcManager {
             return Ok(());
         }
 
-        let mut client = DiscordIpcClient::new(DISCORD_APP_ID);
-
-        client
-            .connect()
-            .map_err(|e| format!("Failed to connect to Discord: {}", e))?;
+        self.install_client(connect_client()?)
+    }
 
+    /// Uses a freshly connected client and shows the current presence on it
+    fn install_client(&mut self, client: DiscordIpcClient) -> Result<(), String> {
         self.client = Some(client);
         crate::logging::Logger::info_global("Discord Rich Presence connected", None);
+        self.emit_status();
 
         // Set initial presence
         self.update_presence()?;
@@ -124,13 +169,38 @@ impl DiscordRpcManager {
             activity = activity.timestamps(Timestamps::new().start(timestamp));
         }
 
-        client
-            .set_activity(activity)
-            .map_err(|e| format!("Failed to set Discord activity: {}", e))?;
+        if let Err(e) = client.set_activity(activity) {
+            // Usually Discord was closed, drop the dead connection so the status reflects it
+            self.client = None;
+            crate::logging::Logger::warn_global("Discord Rich Presence connection lost", None);
+            self.emit_status();
+            return Err(format!("Failed to set Discord activity: {}", e));
+        }
 
         Ok(())
     }
 
+    fn status(&self) -> DiscordStatus {
+        let connected = self.client.is_some();
+        DiscordStatus {
+            enabled: self.enabled,
+            connected,
+            current_activity: connected.then(|| DiscordActivity {
+                details: self.current_state.details.clone(),
+                state: self.current_state.state.clone(),
+                playing: self.current_state.priority == ActivityPriority::Playing,
+            }),
+        }
+    }
+
+    fn emit_status(&self) {
+        if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
+            if let Some(app_handle) = handle_guard.as_ref() {
+                let _ = app_handle.emit(DISCORD_STATUS_EVENT, self.status());
+            }
+        }
+    }
+
     /// Set a new state if its priority is higher or equal to current
     fn set_state(&mut self, new_state: PresenceState) -> Result<(), String> {
         if !self.enabled {
@@ -163,6 +233,7 @@ impl DiscordRpcManager {
                 .map_err(|e| format!("Failed to close Discord connection: {}", e))?;
             crate::logging::Logger::info_global("Discord Rich Presence disconnected", None);
         }
+        self.emit_status();
         Ok(())
     }
 }
@@ -177,6 +248,70 @@ pub fn initialize() -> Result<(), String> {
     manager.initialize()
 }
 
+/// Starts the background task that reconnects to Discord when it isn't running yet or was
+/// closed, with backoff between attempts. The task stops once Rich Presence is disabled.
+pub fn start_reconnect_loop() {
+    if RECONNECT_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tauri::async_runtime::spawn(async {
+        let mut delay = RECONNECT_INITIAL_DELAY;
+        loop {
+            tokio::time::sleep(delay).await;
+            {
+                let manager = DISCORD_MANAGER.lock().unwrap_or_else(|e| e.into_inner());
+                if !manager.enabled {
+                    break;
+                }
+                if manager.client.is_some() {
+                    delay = RECONNECT_INITIAL_DELAY;
+                    continue;
+                }
+            }
+            // Connecting blocks, the manager stays usable by presence updates meanwhile
+            let connected = tauri::async_runtime::spawn_blocking(connect_client)
+                .await
+                .map_err(|e| format!("Discord connect task failed: {}", e))
+                .and_then(|connected| connected);
+            let mut manager = DISCORD_MANAGER.lock().unwrap_or_else(|e| e.into_inner());
+            if !manager.enabled {
+                break;
+            }
+            if manager.client.is_some() {
+                // Connected elsewhere while this attempt ran, the extra client is dropped
+                delay = RECONNECT_INITIAL_DELAY;
+                continue;
+            }
+            match connected.and_then(|client| manager.install_client(client)) {
+                Ok(()) => {
+                    crate::logging::Logger::info_global("Discord Rich Presence reconnected", None);
+                    delay = RECONNECT_INITIAL_DELAY;
+                }
+                Err(e) => {
+                    delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+                    crate::logging::Logger::debug_global(
+                        &format!(
+                            "Discord not reachable, retrying in {}s: {}",
+                            delay.as_secs(),
+                            e
+                        ),
+                        None,
+                    );
+                }
+            }
+        }
+        RECONNECT_RUNNING.store(false, Ordering::SeqCst);
+    });
+}
+
+/// Whether Rich Presence is enabled and connected, and what it currently shows
+pub fn get_status() -> Result<DiscordStatus, String> {
+    let manager = DISCORD_MANAGER
+        .lock()
+        .map_err(|e| format!("Failed to lock Discord manager: {}", e))?;
+    Ok(manager.status())
+}
+
 /// Enable or disable Discord Rich Presence
 pub fn set_enabled(enabled: bool) -> Result<(), String> {
     let mut manager = DISCORD_MANAGER
@@ -186,6 +321,7 @@ pub fn set_enabled(enabled: bool) -> Result<(), String> {
     manager.enabled = enabled;
 
     if enabled {
+        start_reconnect_loop();
         manager.initialize()?;
         manager.update_presence()?;
     } else {
@@ -200,6 +336,7 @@ pub fn set_playing(
     installation_name: &str,
     version_id: &str,
     mod_loader: Option<&str>,
+    templates: &DiscordPresenceTemplates,
 ) -> Result<(), String> {
     let timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -207,8 +344,17 @@ pub fn set_playing(
         .as_secs() as i64;
 
     let loader_text = mod_loader.unwrap_or("Vanilla");
-    let details = format!("Playing Minecraft {}", version_id);
-    let state = format!("{} • {}", installation_name, loader_text);
+    let variables = HashMap::from([
+        ("installation".to_string(), installation_name.to_string()),
+        ("version".to_string(), version_id.to_string()),
+        ("loader".to_string(), loader_text.to_string()),
+    ]);
+    let details = render_template(
+        &templates.playing_details,
+        &variables,
+        &format!("Playing Minecraft {}", version_id),
+    );
+    let state = render_template(&templates.playing_state, &variables, installation_name);
 
     let (small_image, small_text) = match mod_loader {
         Some("fabric") => (Some("fabric".to_string()), Some("Fabric".to_string())),
@@ -236,9 +382,8 @@ pub fn set_playing(
 }
 
 /// Update presence for browsing a specific section
-pub fn set_browsing(section: &str) -> Result<(), String> {
-    let details = "In Launcher".to_string();
-    let state = match section {
+pub fn set_browsing(section: &str, templates: &DiscordPresenceTemplates) -> Result<(), String> {
+    let label = match section {
         "mods" => "Browsing Mods",
         "shaders" => "Browsing Shaders",
         "resourcepacks" | "resource-packs" => "Browsing Resource Packs",
@@ -249,9 +394,12 @@ pub fn set_browsing(section: &str) -> Result<(), String> {
         "profile" => "Viewing Profile",
         _ => "In Launcher",
     };
+    let variables = HashMap::from([("section".to_string(), label.to_string())]);
+    let details = render_template(&templates.browsing_details, &variables, "In Launcher");
+    let state = render_template(&templates.browsing_state, &variables, label);
 
     let new_state = PresenceState {
-        state: state.to_string(),
+        state,
         details,
         priority: ActivityPriority::Browsing,
         large_image: Some("kable_logo".to_string()),
@@ -267,6 +415,43 @@ pub fn set_browsing(section: &str) -> Result<(), String> {
     manager.set_state(new_state)
 }
 
+// `{name}` without the `$` of launch arguments
+static SHORT_PLACEHOLDER_RE: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r"(^|[^$])\{(\w+)\}").unwrap());
+
+/// Fills in a presence template, variables are written as `{name}` or `${name}`.
+/// Uses `fallback` when the result is too short for Discord and cuts it at a char boundary
+/// when it is too long.
+fn render_template(template: &str, variables: &HashMap<String, String>, fallback: &str) -> String {
+    // Unknown `{name}`s are kept as text, braces are common in presence texts
+    let template = SHORT_PLACEHOLDER_RE.replace_all(template, |caps: &regex::Captures| {
+        if variables.contains_key(&caps[2]) {
+            format!("{}${{{}}}", &caps[1], &caps[2])
+        } else {
+            caps[0].to_string()
+        }
+    });
+    let rendered = crate::launcher::utils::substitute_variables(&template, variables);
+    let rendered = rendered.trim();
+    let text = if rendered.len() < DISCORD_TEXT_MIN_LEN {
+        fallback
+    } else {
+        rendered
+    };
+    truncate_for_discord(text)
+}
+
+fn truncate_for_discord(text: &str) -> String {
+    if text.len() <= DISCORD_TEXT_MAX_LEN {
+        return text.to_string();
+    }
+    let mut end = DISCORD_TEXT_MAX_LEN - '…'.len_utf8();
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", text[..end].trim_end())
+}
+
 /// Clear playing status (reverts to last browsing state or idle)
 pub fn clear_playing() -> Result<(), String> {
     let new_state = PresenceState {
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+/// What went wrong, so the frontend can react to it (e.g. offer a retry on Network)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    Network,
+    NotFound,
+    Io,
+    Auth,
+    Validation,
+    Cancelled,
+    Unknown,
+}
+
+/// Error returned by commands that were moved off `Result<T, String>`.
+/// Serializes to `{ code, message, details }`, `Display` is the message the command used to return.
+/// The code is set where the error happens, errors that reach a command as a `String` are `Unknown`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KableError {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(default)]
+    pub details: Option<String>,
+}
+
+impl KableError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        KableError {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn network(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Network, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub fn io(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Io, message)
+    }
+
+    pub fn auth(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Auth, message)
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Validation, message)
+    }
+
+    pub fn cancelled(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Cancelled, message)
+    }
+
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+}
+
+impl std::fmt::Display for KableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for KableError {}
+
+/// Errors from code that still returns `String` are `Unknown`, the message isn't guessed at
+impl From<String> for KableError {
+    fn from(message: String) -> Self {
+        KableError::new(ErrorCode::Unknown, message)
+    }
+}
+
+impl From<&str> for KableError {
+    fn from(message: &str) -> Self {
+        KableError::from(message.to_string())
+    }
+}
+
+impl From<std::io::Error> for KableError {
+    fn from(e: std::io::Error) -> Self {
+        let code = match e.kind() {
+            std::io::ErrorKind::NotFound => ErrorCode::NotFound,
+            _ => ErrorCode::Io,
+        };
+        KableError::new(code, e.to_string())
+    }
+}
+
+impl From<reqwest::Error> for KableError {
+    fn from(e: reqwest::Error) -> Self {
+        let code = match e.status().map(|s| s.as_u16()) {
+            Some(401) | Some(403) => ErrorCode::Auth,
+            Some(404) => ErrorCode::NotFound,
+            _ => ErrorCode::Network,
+        };
+        KableError::new(code, e.to_string())
+    }
+}
+
+/// Lets functions that still return `String` errors use `?` on `KableError` results
+impl From<KableError> for String {
+    fn from(e: KableError) -> Self {
+        e.message
+    }
+}
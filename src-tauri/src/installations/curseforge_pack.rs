@@ -0,0 +1,333 @@
+use super::{get_versions, kable_profiles, KableInstallation};
+use crate::logging::Logger;
+use crate::mods::curseforge;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tokio::task;
+
+// CurseForge class ids of the project types a modpack can contain
+const CLASS_RESOURCEPACKS: u32 = 12;
+const CLASS_SHADERPACKS: u32 = 6552;
+
+/// manifest.json of a CurseForge modpack zip
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeManifest {
+    minecraft: CurseForgeManifestMinecraft,
+    name: Option<String>,
+    version: Option<String>,
+    #[serde(default)]
+    files: Vec<CurseForgeManifestFile>,
+    overrides: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeManifestMinecraft {
+    version: String,
+    #[serde(default)]
+    mod_loaders: Vec<CurseForgeManifestLoader>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CurseForgeManifestLoader {
+    /// e.g. "forge-47.2.0" or "fabric-0.15.7"
+    id: String,
+    #[serde(default)]
+    primary: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CurseForgeManifestFile {
+    #[serde(rename = "projectID")]
+    project_id: u32,
+    #[serde(rename = "fileID")]
+    file_id: u32,
+}
+
+/// A pack file the author doesn't allow third-party launchers to download,
+/// the user has to get it from the project page and drop it in themselves
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurseForgeManualDownload {
+    pub project_id: u32,
+    pub file_id: u32,
+    pub name: String,
+    pub file_name: String,
+    /// Project page of the exact file
+    pub url: String,
+    /// Folder of the installation the file belongs in
+    pub target_dir: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurseForgePackImport {
+    pub installation: KableInstallation,
+    /// File names that were downloaded
+    pub downloaded: Vec<String>,
+    pub manual_downloads: Vec<CurseForgeManualDownload>,
+    /// Files that could not be downloaded, with the reason
+    pub failed: Vec<String>,
+}
+
+/// Imports a CurseForge modpack zip as a new installation.
+/// The overrides folder is copied into the installation's folders and every file in the
+/// manifest is downloaded through the CurseForge API (needs CURSEFORGE_API_KEY).
+/// Files that can't be distributed are returned in `manual_downloads` instead of skipped.
+pub async fn import_curseforge_zip(path: &str) -> Result<CurseForgePackImport, String> {
+    let zip_path = PathBuf::from(path);
+    let manifest = {
+        let zip_path = zip_path.clone();
+        task::spawn_blocking(move || read_manifest(&zip_path))
+            .await
+            .map_err(|e| format!("Import task join error: {}", e))??
+    };
+
+    let version_id = kable_version_id(&manifest)?;
+    if get_versions().await.get_version(&version_id).is_none() {
+        Logger::warn_global(
+            &format!(
+                "CurseForge pack uses version '{}' which is not in the version list yet",
+                version_id
+            ),
+            None,
+        );
+    }
+
+    let mut name = manifest
+        .name
+        .clone()
+        .unwrap_or_else(|| "CurseForge Modpack".to_string());
+    if let Some(version) = &manifest.version {
+        name = format!("{} {}", name, version);
+    }
+    let mut installation = KableInstallation {
+        name,
+        version_id,
+        ..Default::default()
+    };
+    installation.dedicated_mods_folder = Some(format!("mods/{}", installation.id));
+
+    // Overrides are extracted to a temp dir first so they can be copied like a game directory
+    let kable_dir = crate::get_minecraft_kable_dir()?;
+    let staging_dir = crate::get_temp_dir(&installation.id, "curseforge-overrides")?;
+    let overrides = manifest
+        .overrides
+        .clone()
+        .unwrap_or_else(|| "overrides".to_string());
+    let copied = {
+        let zip_path = zip_path.clone();
+        let staging_dir = staging_dir.clone();
+        task::spawn_blocking(move || {
+            extract_overrides(&zip_path, &overrides, &staging_dir)?;
+            installation.copy_game_folders_from(&staging_dir, &kable_dir)?;
+            // Create the dedicated mods folder up front, otherwise find_mods_dir would fall back
+            // to .minecraft/mods for packs without mods in their overrides
+            crate::ensure_folder_sync(&kable_dir.join("mods").join(&installation.id))?;
+            Ok::<KableInstallation, String>(installation)
+        })
+        .await
+    };
+    // Also when extracting or copying failed half-way
+    let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+    installation = copied.map_err(|e| format!("Import task join error: {}", e))??;
+
+    let mut existing = kable_profiles::read_kable_profiles_async().await?;
+    existing.push(installation.clone());
+    kable_profiles::write_kable_profiles_async(&existing).await?;
+
+    let mut result = CurseForgePackImport {
+        installation,
+        downloaded: Vec::new(),
+        manual_downloads: Vec::new(),
+        failed: Vec::new(),
+    };
+    // The installation is saved by now, so failures end up in `failed` instead of an error
+    download_pack_files(&manifest.files, &mut result).await;
+
+    Logger::info_global(
+        &format!(
+            "Imported CurseForge pack '{}': {} downloaded, {} manual, {} failed",
+            result.installation.name,
+            result.downloaded.len(),
+            result.manual_downloads.len(),
+            result.failed.len()
+        ),
+        Some(&result.installation.id),
+    );
+    Ok(result)
+}
+
+fn read_manifest(zip_path: &Path) -> Result<CurseForgeManifest, String> {
+    let file = File::open(zip_path).map_err(|e| format!("Failed to open modpack zip: {}", e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to read modpack zip: {}", e))?;
+    let entry = archive
+        .by_name("manifest.json")
+        .map_err(|_| "Not a CurseForge modpack: manifest.json not found".to_string())?;
+    serde_json::from_reader(entry).map_err(|e| format!("Failed to parse manifest.json: {}", e))
+}
+
+/// Extracts everything below `overrides/` in the zip into `out_dir`
+fn extract_overrides(zip_path: &Path, overrides: &str, out_dir: &Path) -> Result<(), String> {
+    let file = File::open(zip_path).map_err(|e| format!("Failed to open modpack zip: {}", e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to read modpack zip: {}", e))?;
+
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read modpack zip entry: {}", e))?;
+        // enclosed_name rejects absolute paths and `..` components
+        let Some(path) = entry.enclosed_name() else {
+            continue;
+        };
+        let Ok(relative) = path.strip_prefix(overrides) else {
+            continue;
+        };
+        let dest = out_dir.join(relative);
+        if entry.is_dir() {
+            crate::ensure_folder_sync(&dest)?;
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            crate::ensure_folder_sync(parent)?;
+        }
+        let mut out =
+            File::create(&dest).map_err(|e| format!("Failed to create override file: {}", e))?;
+        std::io::copy(&mut entry, &mut out)
+            .map_err(|e| format!("Failed to extract override file: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Maps the primary mod loader of the manifest to a Kable version id
+fn kable_version_id(manifest: &CurseForgeManifest) -> Result<String, String> {
+    let minecraft = &manifest.minecraft.version;
+    let loader = manifest
+        .minecraft
+        .mod_loaders
+        .iter()
+        .find(|l| l.primary)
+        .or_else(|| manifest.minecraft.mod_loaders.first());
+    let Some(loader) = loader else {
+        return Ok(minecraft.clone());
+    };
+
+    let (kind, version) = loader
+        .id
+        .split_once('-')
+        .ok_or_else(|| format!("Unknown mod loader '{}' in manifest.json", loader.id))?;
+    match kind {
+        "forge" => Ok(format!("{}-forge-{}", minecraft, version)),
+        "neoforge" => Ok(format!("neoforge-{}", version)),
+        "fabric" => Ok(format!("fabric-loader-{}-{}", version, minecraft)),
+        "quilt" => Ok(format!("quilt-loader-{}-{}", version, minecraft)),
+        _ => Err(format!(
+            "Unsupported mod loader '{}' in manifest.json",
+            loader.id
+        )),
+    }
+}
+
+/// Downloads the manifest files into the installation's folders, a file that can't be looked up
+/// or downloaded is added to `result.failed`
+async fn download_pack_files(files: &[CurseForgeManifestFile], result: &mut CurseForgePackImport) {
+    if let Err(e) = try_download_pack_files(files, result).await {
+        Logger::warn_global(
+            &format!("Failed to download the files of the CurseForge pack: {}", e),
+            Some(&result.installation.id),
+        );
+        for manifest_file in files {
+            result.failed.push(format!(
+                "File {} of project {}: {}",
+                manifest_file.file_id, manifest_file.project_id, e
+            ));
+        }
+    }
+}
+
+/// Errors when nothing could be downloaded, files that fail on their own are added to
+/// `result.failed` and the rest is still downloaded
+async fn try_download_pack_files(
+    files: &[CurseForgeManifestFile],
+    result: &mut CurseForgePackImport,
+) -> Result<(), String> {
+    if files.is_empty() {
+        return Ok(());
+    }
+    let installation = &result.installation;
+    let mods_dir = installation.find_mods_dir()?;
+    let resourcepacks_dir = installation.find_resourcepacks_dir()?;
+    let shaderpacks_dir = installation.find_shaderpacks_dir()?;
+
+    let file_ids: Vec<u32> = files.iter().map(|f| f.file_id).collect();
+    let project_ids: Vec<u32> = files.iter().map(|f| f.project_id).collect();
+    let cf_files = curseforge::get_files_by_ids(&file_ids).await?;
+    let projects: HashMap<u32, curseforge::CurseForgeInfo> =
+        curseforge::get_mods_by_ids(&project_ids)
+            .await?
+            .into_iter()
+            .map(|p| (p.id, p))
+            .collect();
+    let cf_files: HashMap<u32, curseforge::CurseForgeFile> =
+        cf_files.into_iter().map(|f| (f.id, f)).collect();
+
+    for manifest_file in files {
+        let Some(file) = cf_files.get(&manifest_file.file_id) else {
+            result.failed.push(format!(
+                "File {} of project {}: not found on CurseForge",
+                manifest_file.file_id, manifest_file.project_id
+            ));
+            continue;
+        };
+        let project = projects.get(&manifest_file.project_id);
+        let (target_dir, target_name) = match project.and_then(|p| p.class_id) {
+            Some(CLASS_RESOURCEPACKS) => (&resourcepacks_dir, "resourcepacks"),
+            Some(CLASS_SHADERPACKS) => (&shaderpacks_dir, "shaderpacks"),
+            _ => (&mods_dir, "mods"),
+        };
+
+        let distributable = project
+            .and_then(|p| p.allow_mod_distribution)
+            .unwrap_or(true);
+        let download_url = file.download_url.as_deref().filter(|_| distributable);
+        let Some(download_url) = download_url else {
+            let project_url = project
+                .and_then(|p| p.links.website_url.clone())
+                .unwrap_or_else(|| {
+                    format!(
+                        "https://www.curseforge.com/projects/{}",
+                        manifest_file.project_id
+                    )
+                });
+            result.manual_downloads.push(CurseForgeManualDownload {
+                project_id: manifest_file.project_id,
+                file_id: file.id,
+                name: project
+                    .map(|p| p.name.clone())
+                    .unwrap_or_else(|| file.display_name.clone()),
+                file_name: file.file_name.clone(),
+                url: format!("{}/files/{}", project_url, file.id),
+                target_dir: target_dir.to_string_lossy().to_string(),
+            });
+            Logger::warn_global(
+                &format!(
+                    "{} can't be downloaded automatically, download it from {}/files/{}",
+                    file.file_name, project_url, file.id
+                ),
+                Some(&installation.id),
+            );
+            continue;
+        };
+
+        match curseforge::download_mod_file(download_url, &target_dir.join(&file.file_name)).await {
+            Ok(()) => result.downloaded.push(file.file_name.clone()),
+            Err(e) => result
+                .failed
+                .push(format!("{} ({}): {}", file.file_name, target_name, e)),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use super::kable_profiles::KableInstallation;
+use std::path::Path;
+
+/// Creates an installation of a hand-made version json (custom clients, old modpack formats).
+/// The json, and the jar when given, are copied into `versions/<id>/`. A version it inherits
+/// from has to be installed already or be a Minecraft version that can be downloaded, and the
+/// merged manifest has to load before the installation is created.
+pub async fn create_installation_from_manifest(
+    manifest_path: &str,
+    jar_path: Option<&str>,
+) -> Result<KableInstallation, String> {
+    let content = tokio::fs::read_to_string(manifest_path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", manifest_path, e))?;
+    let manifest: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid version json {}: {}", manifest_path, e))?;
+    let version_id = manifest
+        .get("id")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .ok_or("Version json has no id")?
+        .to_string();
+    if version_id.contains(['/', '\\']) || version_id.starts_with('.') {
+        return Err(format!("Invalid version id '{}'", version_id));
+    }
+    let inherits_from = manifest
+        .get("inheritsFrom")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    if inherits_from.is_none() && manifest.get("mainClass").is_none() {
+        return Err("Version json has neither a mainClass nor inheritsFrom".to_string());
+    }
+    if let Some(jar) = jar_path {
+        if !Path::new(jar).is_file() {
+            return Err(format!("Jar not found: {}", jar));
+        }
+    }
+
+    let minecraft_dir = crate::get_default_minecraft_dir()?;
+    let minecraft_dir_str = minecraft_dir.to_string_lossy().to_string();
+    let version_dir = minecraft_dir.join("versions").join(&version_id);
+    let json_path = version_dir.join(format!("{}.json", version_id));
+    if json_path.exists() {
+        return Err(format!("Version '{}' is already installed", version_id));
+    }
+
+    // The parent has to resolve before anything is copied
+    if let Some(parent) = &inherits_from {
+        let parent_json = minecraft_dir
+            .join("versions")
+            .join(parent)
+            .join(format!("{}.json", parent));
+        if !parent_json.is_file() {
+            crate::launcher::utils::ensure_version_manifest_and_jar(
+                parent,
+                &minecraft_dir_str,
+                &reqwest::Client::new(),
+            )
+            .await
+            .map_err(|e| {
+                format!(
+                    "inheritsFrom '{}' is not installed and could not be downloaded: {}",
+                    parent, e
+                )
+            })?;
+        }
+    }
+
+    crate::ensure_folder(&version_dir).await?;
+    let copied = async {
+        crate::write_file_atomic_async(&json_path, content.as_bytes()).await?;
+        if let Some(jar) = jar_path {
+            tokio::fs::copy(jar, version_dir.join(format!("{}.jar", version_id)))
+                .await
+                .map_err(|e| format!("Failed to copy {}: {}", jar, e))?;
+        }
+        let merged =
+            crate::launcher::utils::load_and_merge_manifest(&minecraft_dir_str, &version_id)
+                .await?;
+        if merged.get("mainClass").is_none() {
+            return Err(format!(
+                "Version '{}' has no mainClass after merging with its parents",
+                version_id
+            ));
+        }
+        Ok(())
+    }
+    .await;
+    if let Err(e) = copied {
+        let _ = tokio::fs::remove_dir_all(&version_dir).await;
+        return Err(e);
+    }
+
+    // The next version list picks the new version up from disk
+    *super::VERSIONS_CACHE.write().await = None;
+    let installation = super::add_installation_for_version(&version_id).await?;
+    crate::logging::info(&format!(
+        "Created installation '{}' from custom version json {}",
+        installation.name, manifest_path
+    ));
+    Ok(installation)
+}
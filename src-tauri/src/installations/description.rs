@@ -0,0 +1,36 @@
+use super::get_installation;
+
+/// Renders the description of an installation to HTML that is safe to inject into the page.
+/// Markdown descriptions are rendered and then sanitized (scripts, event handlers and
+/// `javascript:` links are dropped), plain descriptions are escaped with line breaks kept.
+/// Returns an empty string when the installation has no description.
+pub async fn render_installation_description(id: &str) -> Result<String, String> {
+    let installation = get_installation(id)
+        .await?
+        .ok_or_else(|| format!("Installation with id '{}' not found", id))?;
+    let Some(description) = installation.description.as_deref() else {
+        return Ok(String::new());
+    };
+
+    match installation.description_format_kind()? {
+        "markdown" => Ok(render_markdown(description)),
+        _ => Ok(render_plain(description)),
+    }
+}
+
+fn render_markdown(markdown: &str) -> String {
+    let options = pulldown_cmark::Options::ENABLE_TABLES
+        | pulldown_cmark::Options::ENABLE_STRIKETHROUGH
+        | pulldown_cmark::Options::ENABLE_TASKLISTS;
+    let parser = pulldown_cmark::Parser::new_ext(markdown, options);
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+    ammonia::clean(&html)
+}
+
+fn render_plain(text: &str) -> String {
+    text.lines()
+        .map(ammonia::clean_text)
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
@@ -0,0 +1,180 @@
+use super::kable_profiles::{KableInstallation, ModJarInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Fields that tell installations apart rather than configure them, or that have their own diff
+const IGNORED_FIELDS: &[&str] = &[
+    "id",
+    "name",
+    "created",
+    "last_used",
+    "total_time_played_ms",
+    "times_launched",
+    "favorite",
+    "java_args",
+    "dedicated_mods_folder",
+    "dedicated_resource_pack_folder",
+    "dedicated_shaders_folder",
+    "dedicated_config_folder",
+];
+
+/// Names only in `a` (removed) or only in `b` (added)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// A mod both installations have, in a different version or enabled state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModChange {
+    /// The mod's name from its jar, or the file name when the jar has none
+    pub id: String,
+    pub a: ModJarInfo,
+    pub b: ModJarInfo,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModsDiff {
+    pub added: Vec<ModJarInfo>,
+    pub removed: Vec<ModJarInfo>,
+    pub changed: Vec<ModChange>,
+}
+
+/// An installation setting with a different value, as it is stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingChange {
+    pub field: String,
+    pub a: serde_json::Value,
+    pub b: serde_json::Value,
+}
+
+/// What `b` has that `a` doesn't and the other way around, "added" always means only in `b`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstallationDiff {
+    pub mods: ModsDiff,
+    pub resourcepacks: ListDiff,
+    pub shaders: ListDiff,
+    pub java_args: ListDiff,
+    pub settings: Vec<SettingChange>,
+}
+
+/// Compares the content and settings of two installations, e.g. a dev and a release copy of a pack
+pub async fn diff_installations(a_id: &str, b_id: &str) -> Result<InstallationDiff, String> {
+    let find = |id: &str| {
+        let id = id.to_string();
+        async move {
+            super::get_installation(&id)
+                .await?
+                .ok_or_else(|| format!("Installation with id '{}' not found", id))
+        }
+    };
+    let (a, b) = (find(a_id).await?, find(b_id).await?);
+
+    // Reading jars is blocking work
+    tokio::task::spawn_blocking(move || {
+        Ok(InstallationDiff {
+            mods: diff_mods(&installation_mods(&a), &installation_mods(&b)),
+            resourcepacks: diff_names(resourcepack_names(&a), resourcepack_names(&b)),
+            shaders: diff_names(shader_names(&a), shader_names(&b)),
+            java_args: diff_names(a.java_args.clone(), b.java_args.clone()),
+            settings: diff_settings(&a, &b)?,
+        })
+    })
+    .await
+    .map_err(|e| format!("Installation diff task failed: {}", e))?
+}
+
+/// An installation without a mods folder has no mods
+fn installation_mods(installation: &KableInstallation) -> Vec<ModJarInfo> {
+    installation.get_mod_info().unwrap_or_default()
+}
+
+fn resourcepack_names(installation: &KableInstallation) -> Vec<String> {
+    installation
+        .get_resourcepack_info()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| p.file_name)
+        .collect()
+}
+
+fn shader_names(installation: &KableInstallation) -> Vec<String> {
+    installation
+        .get_shaderpack_info()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| p.file_name)
+        .collect()
+}
+
+fn mod_id(info: &ModJarInfo) -> String {
+    info.mod_name
+        .as_deref()
+        .filter(|n| !n.is_empty())
+        .unwrap_or(&info.file_name)
+        .to_lowercase()
+}
+
+fn diff_mods(a: &[ModJarInfo], b: &[ModJarInfo]) -> ModsDiff {
+    let by_id = |mods: &[ModJarInfo]| -> BTreeMap<String, ModJarInfo> {
+        mods.iter().map(|m| (mod_id(m), m.clone())).collect()
+    };
+    let (a, b) = (by_id(a), by_id(b));
+    let mut diff = ModsDiff::default();
+    for (id, a_mod) in &a {
+        match b.get(id) {
+            None => diff.removed.push(a_mod.clone()),
+            Some(b_mod) => {
+                if a_mod.mod_version != b_mod.mod_version || a_mod.disabled != b_mod.disabled {
+                    diff.changed.push(ModChange {
+                        id: id.clone(),
+                        a: a_mod.clone(),
+                        b: b_mod.clone(),
+                    });
+                }
+            }
+        }
+    }
+    diff.added = b
+        .into_iter()
+        .filter(|(id, _)| !a.contains_key(id))
+        .map(|(_, m)| m)
+        .collect();
+    diff
+}
+
+fn diff_names(a: Vec<String>, b: Vec<String>) -> ListDiff {
+    let (a, b): (BTreeSet<String>, BTreeSet<String>) =
+        (a.into_iter().collect(), b.into_iter().collect());
+    ListDiff {
+        added: b.difference(&a).cloned().collect(),
+        removed: a.difference(&b).cloned().collect(),
+    }
+}
+
+fn diff_settings(
+    a: &KableInstallation,
+    b: &KableInstallation,
+) -> Result<Vec<SettingChange>, String> {
+    let to_map = |installation: &KableInstallation| match serde_json::to_value(installation) {
+        Ok(serde_json::Value::Object(map)) => Ok(map),
+        Ok(_) => Err("Installation did not serialize to an object".to_string()),
+        Err(e) => Err(format!("Failed to serialize installation: {}", e)),
+    };
+    let (a, b) = (to_map(a)?, to_map(b)?);
+    let fields: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+    Ok(fields
+        .into_iter()
+        .filter(|field| !IGNORED_FIELDS.contains(&field.as_str()))
+        .filter_map(|field| {
+            let a_value = a.get(field).cloned().unwrap_or_default();
+            let b_value = b.get(field).cloned().unwrap_or_default();
+            (a_value != b_value).then(|| SettingChange {
+                field: field.clone(),
+                a: a_value,
+                b: b_value,
+            })
+        })
+        .collect())
+}
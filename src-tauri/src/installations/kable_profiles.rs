@@ -7,12 +7,14 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use std::{
     fs,
     io::{Read, Write},
 };
+use tauri::Emitter;
 use tokio::fs as async_fs;
 use tokio::task;
 use toml::Value as TomlValue;
@@ -60,6 +62,64 @@ pub struct KableInstallation {
     /// List of resource packs to merge (others remain individual)
     #[serde(default)]
     pub merged_packs: Vec<String>,
+    /// User-defined tags for grouping and searching installations
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Server to join directly on launch as "host:port" (passed as --server/--port)
+    #[serde(default)]
+    pub connect_server: Option<String>,
+    /// Force natives for this architecture ('x86' | 'x86_64' | 'arm64') instead of the host's,
+    /// e.g. x86_64 Java running under Rosetta on Apple Silicon
+    #[serde(default)]
+    pub native_arch_override: Option<String>,
+    /// Drop LWJGL jars that don't match the highest LWJGL version on the classpath at launch
+    #[serde(default)]
+    pub resolve_lwjgl_conflicts: bool,
+    /// How `description` is rendered: 'plain' (default) | 'markdown'
+    #[serde(default)]
+    pub description_format: Option<String>,
+    /// Use own assets and natives folders (kable/assets/<id>, kable/natives/<id>) instead of
+    /// the shared .minecraft ones, costs disk space but nothing is shared with other installations
+    #[serde(default)]
+    pub isolated_game_files: bool,
+    /// Proxy for http:// traffic of the game and the game file downloads of its launches
+    /// (client jar, libraries, assets). Mod browsing and mod downloads don't go through it.
+    #[serde(default)]
+    pub http_proxy: Option<String>,
+    /// Proxy for https:// traffic, covers the same as `http_proxy`
+    #[serde(default)]
+    pub https_proxy: Option<String>,
+    /// Extra jars or folders appended to the computed classpath
+    #[serde(default)]
+    pub extra_classpath: Vec<String>,
+    /// Java agent jars passed as `-javaagent:`, an entry may carry agent options as `path=options`
+    #[serde(default)]
+    pub java_agents: Vec<String>,
+    /// Overrides `settings.general.on_game_launch` for this installation when set
+    #[serde(default)]
+    pub on_game_launch: Option<String>,
+    /// Overrides `settings.general.on_game_close` for this installation when set
+    #[serde(default)]
+    pub on_game_close: Option<String>,
+    /// Overrides `settings.general.on_game_crash` for this installation when set
+    #[serde(default)]
+    pub on_game_crash: Option<String>,
+    /// `local_id` of the account to launch with instead of the active one, e.g. for an alt
+    #[serde(default)]
+    pub preferred_account_id: Option<String>,
+    /// Java executable for this installation, overrides `settings.general.java_path` when set
+    #[serde(default)]
+    pub java_path: Option<String>,
+    /// `gameDir` of the launcher profile this installation was imported from
+    #[serde(default)]
+    pub game_directory: Option<String>,
+    /// Uses .minecraft/mods instead of a dedicated mods folder, also for modded versions
+    #[serde(default)]
+    pub use_global_mods: bool,
+    /// Launch with `--demo`, for accounts that don't own the game. Online demo play needs an
+    /// account that Mojang lets into the demo, otherwise the game still refuses to start a world.
+    #[serde(default)]
+    pub demo_mode: bool,
 }
 
 impl Default for KableInstallation {
@@ -93,14 +153,37 @@ impl Default for KableInstallation {
             enable_pack_merging: false,
             pack_order: Vec::new(),
             merged_packs: Vec::new(),
+            tags: Vec::new(),
+            connect_server: None,
+            native_arch_override: None,
+            resolve_lwjgl_conflicts: false,
+            description_format: None,
+            isolated_game_files: false,
+            http_proxy: None,
+            https_proxy: None,
+            extra_classpath: Vec::new(),
+            java_agents: Vec::new(),
+            on_game_launch: None,
+            on_game_close: None,
+            on_game_crash: None,
+            preferred_account_id: None,
+            java_path: None,
+            game_directory: None,
+            use_global_mods: false,
+            demo_mode: false,
         }
     }
 }
 
 impl From<LauncherProfile> for KableInstallation {
     fn from(profile: LauncherProfile) -> Self {
-        let installation_id = uuid::Uuid::new_v4().to_string();
+        Self::from_launcher_profile(profile, uuid::Uuid::new_v4().to_string())
+    }
+}
 
+impl KableInstallation {
+    /// Converts a launcher profile into an installation with a pre-assigned id
+    pub fn from_launcher_profile(profile: LauncherProfile, installation_id: String) -> Self {
         KableInstallation {
             id: installation_id.clone(),
             name: profile.name,
@@ -142,10 +225,46 @@ impl From<LauncherProfile> for KableInstallation {
             enable_pack_merging: false,
             pack_order: Vec::new(),
             merged_packs: Vec::new(),
+            tags: Vec::new(),
+            connect_server: None,
+            native_arch_override: None,
+            resolve_lwjgl_conflicts: false,
+            description_format: None,
+            isolated_game_files: false,
+            http_proxy: None,
+            https_proxy: None,
+            extra_classpath: Vec::new(),
+            java_agents: Vec::new(),
+            on_game_launch: None,
+            on_game_close: None,
+            on_game_crash: None,
+            preferred_account_id: None,
+            java_path: profile.java_dir.filter(|p| !p.trim().is_empty()),
+            game_directory: profile.game_dir.filter(|p| !p.trim().is_empty()),
+            use_global_mods: false,
+            demo_mode: false,
         }
     }
 }
 
+/// Number of top level files and folders `copy_game_folders_from` copies from a game directory
+pub fn game_folder_entry_count(game_dir: &Path) -> u64 {
+    let count = |folder: &str, jars_only: bool| {
+        std::fs::read_dir(game_dir.join(folder))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|e| !jars_only || e.path().extension().is_some_and(|ext| ext == "jar"))
+                    .count() as u64
+            })
+            .unwrap_or(0)
+    };
+    count("mods", true)
+        + count("resourcepacks", false)
+        + count("shaderpacks", false)
+        + count("config", false)
+}
+
 #[log_result]
 pub fn read_kable_profiles() -> Result<Vec<KableInstallation>, String> {
     // Synchronous version for compatibility
@@ -296,16 +415,586 @@ pub async fn write_kable_profiles_async(profiles: &[KableInstallation]) -> Resul
     Ok(())
 }
 
+/// Folder in export zips (and kable/<folder>/<id> after import) holding bundled
+/// `extra_classpath` jars and `java_agents`
+const LAUNCH_EXTRAS_DIR: &str = "launch_extras";
+
+/// Queues an existing jar for bundling and returns its entry as written to the export,
+/// folders and missing files keep their original path
+fn bundle_launch_extra(path: &str, options: &str, bundled: &mut Vec<(String, PathBuf)>) -> String {
+    let original = if options.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}={}", path, options)
+    };
+    let source = PathBuf::from(path.trim());
+    let Some(file_name) = source
+        .is_file()
+        .then(|| source.file_name().and_then(|n| n.to_str()))
+        .flatten()
+    else {
+        return original;
+    };
+    let name = format!("{}/{}_{}", LAUNCH_EXTRAS_DIR, bundled.len(), file_name);
+    let entry = if options.is_empty() {
+        name.clone()
+    } else {
+        format!("{}={}", name, options)
+    };
+    bundled.push((name, source));
+    entry
+}
+
+/// Entry name of a file in a folder zip. Names use '/' on every platform so subfolders like
+/// `disabled/` survive an import on another OS, file names such as `.jar.disabled` are kept as-is.
+fn zip_entry_name(rel: &Path) -> String {
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Where an entry of an imported folder zip is written, None when the name could escape
+/// `dest_dir`: `..`, absolute and drive letter paths, with either separator
+fn safe_entry_path(name: &str, dest_dir: &Path) -> Option<PathBuf> {
+    // Older exports made on Windows used '\\' in names
+    let name = name.replace('\\', "/");
+    if name.starts_with('/') {
+        return None;
+    }
+    let mut out_path = dest_dir.to_path_buf();
+    for component in name.split('/').filter(|c| !c.is_empty() && *c != ".") {
+        // "C:" drive letters, and alternate data streams on Windows
+        if component == ".." || component.contains(':') {
+            return None;
+        }
+        out_path.push(component);
+    }
+    (out_path != dest_dir).then_some(out_path)
+}
+
+/// Whether `path` stays inside `root` once symlinks are resolved. Only the deepest existing
+/// ancestor can be resolved, the rest doesn't exist yet and is made of plain names.
+fn resolves_inside(path: &Path, root: &Path) -> Result<bool, String> {
+    let root = fs::canonicalize(root)
+        .map_err(|e| format!("Failed to resolve {}: {}", root.display(), e))?;
+    // symlink_metadata so a dangling link counts as existing, and then fails to resolve
+    let Some(existing) = path.ancestors().find(|p| fs::symlink_metadata(p).is_ok()) else {
+        return Ok(false);
+    };
+    Ok(fs::canonicalize(existing).is_ok_and(|resolved| resolved.starts_with(&root)))
+}
+
+/// Extracts one entry of an imported folder zip into `dest_dir`. Entries whose path would
+/// end up outside of it, also through a symlinked folder, are skipped with a warning before
+/// anything is created.
+fn safe_extract<R: Read>(
+    entry: &mut zip::read::ZipFile<'_, R>,
+    dest_dir: &Path,
+) -> Result<(), String> {
+    let Some(out_path) = safe_entry_path(entry.name(), dest_dir) else {
+        Logger::warn_global(
+            &format!("Skipped unsafe zip entry '{}'", entry.name()),
+            None,
+        );
+        return Ok(());
+    };
+    // A symlinked file at the destination would be written through as well
+    let is_symlink = fs::symlink_metadata(&out_path).is_ok_and(|m| m.file_type().is_symlink());
+    if is_symlink || !resolves_inside(&out_path, dest_dir)? {
+        Logger::warn_global(
+            &format!(
+                "Skipped zip entry '{}' outside of the destination",
+                entry.name()
+            ),
+            None,
+        );
+        return Ok(());
+    }
+    let dir = if entry.is_dir() {
+        out_path.as_path()
+    } else {
+        out_path.parent().unwrap_or(dest_dir)
+    };
+    crate::ensure_folder_sync(dir)
+        .map_err(|e| format!("Failed to create dir during extract: {}", e))?;
+    if entry.is_dir() {
+        return Ok(());
+    }
+    let mut outfile = fs::File::create(&out_path)
+        .map_err(|e| format!("Failed to create file during extract: {}", e))?;
+    std::io::copy(entry, &mut outfile)
+        .map_err(|e| format!("Failed to write extracted file: {}", e))?;
+    Ok(())
+}
+
+/// Emitted while an export is written, at most every `EXPORT_PROGRESS_INTERVAL`
+pub const EXPORT_PROGRESS_EVENT: &str = "export-progress";
+const EXPORT_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportProgress {
+    pub installation_id: String,
+    pub bytes_written: u64,
+    /// Size of the files going into the export, zip headers and kable_export.json not included
+    pub total_bytes: u64,
+    /// Entry being written, files of a folder are shown as e.g. "mods.zip/sodium.jar"
+    pub current_file: String,
+}
+
+/// Copies files into an export and reports how much of it is written
+struct ExportProgressTracker {
+    progress: ExportProgress,
+    last_emit: Option<Instant>,
+}
+
+impl ExportProgressTracker {
+    fn new(installation_id: &str, total_bytes: u64) -> Self {
+        Self {
+            progress: ExportProgress {
+                installation_id: installation_id.to_string(),
+                bytes_written: 0,
+                total_bytes,
+                current_file: String::new(),
+            },
+            last_emit: None,
+        }
+    }
+
+    fn emit(&mut self, force: bool) {
+        if !force
+            && self
+                .last_emit
+                .is_some_and(|last| last.elapsed() < EXPORT_PROGRESS_INTERVAL)
+        {
+            return;
+        }
+        self.last_emit = Some(Instant::now());
+        if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
+            if let Some(app_handle) = handle_guard.as_ref() {
+                let _ = app_handle.emit(EXPORT_PROGRESS_EVENT, &self.progress);
+            }
+        }
+    }
+
+    fn copy(&mut self, source: &Path, name: &str, out: &mut impl Write) -> Result<(), String> {
+        self.progress.current_file = name.to_string();
+        self.emit(false);
+        let mut file = fs::File::open(source)
+            .map_err(|e| format!("Failed to open {}: {}", source.display(), e))?;
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let read = file
+                .read(&mut buffer)
+                .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+            if read == 0 {
+                return Ok(());
+            }
+            out.write_all(&buffer[..read])
+                .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+            self.progress.bytes_written += read as u64;
+            self.emit(false);
+        }
+    }
+}
+
+/// The file itself, or every file in the folder
+fn export_files(source: &Path) -> Vec<PathBuf> {
+    if source.is_file() {
+        return vec![source.to_path_buf()];
+    }
+    walkdir::WalkDir::new(source)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .collect()
+}
+
+/// Adds a dedicated folder as a nested zip entry, a file (an already zipped folder) is copied
+/// as-is. The nested zip is streamed straight into the export instead of a temp archive.
+fn add_folder_to_export(
+    zip: &mut zip::ZipWriter<fs::File>,
+    entry: &str,
+    source: &Path,
+    files: &[PathBuf],
+    options: &zip::write::FullFileOptions,
+    progress: &mut ExportProgressTracker,
+) -> Result<(), String> {
+    if !source.exists() {
+        return Ok(());
+    }
+    zip.start_file(entry, options.clone())
+        .map_err(|e| format!("Failed to write {}: {}", entry, e))?;
+    if source.is_file() {
+        return progress.copy(source, entry, zip);
+    }
+    let mut nested = zip::ZipWriter::new_stream(&mut *zip);
+    for file in files {
+        let Ok(rel) = file.strip_prefix(source) else {
+            continue;
+        };
+        let name = zip_entry_name(rel);
+        nested
+            .start_file(name.as_str(), options.clone())
+            .map_err(|e| format!("Failed to add {} to {}: {}", name, entry, e))?;
+        progress.copy(file, &format!("{}/{}", entry, name), &mut nested)?;
+    }
+    nested
+        .finish()
+        .map_err(|e| format!("Failed to finish {}: {}", entry, e))?;
+    Ok(())
+}
+
+/// Embedded folder archives of an export zip
+const EXPORT_NESTED_ZIPS: &[&str] = &["mods.zip", "resource_packs.zip", "shaders.zip"];
+
+/// Modrinth mods of a `ModsAsRefs` export, downloaded again on import
+const EXPORT_MOD_REFS: &str = "mod_refs.json";
+
+/// What an export bundles besides kable_export.json
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportContent {
+    /// Mods, resource packs, shaders, launch extras and the icon
+    #[default]
+    Full,
+    /// Only kable_export.json, the importer adds the content themselves
+    MetadataOnly,
+    /// Like `Full`, but mods downloaded from Modrinth are stored as project/version ids
+    ModsAsRefs,
+}
+
+/// A mod of a `ModsAsRefs` export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedModRef {
+    pub file_name: String,
+    pub project_id: String,
+    pub version_id: String,
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+/// Mods in the folder (and its disabled/) that Kable knows a Modrinth version of, jars
+/// without one are still bundled
+async fn export_mod_refs(mods_dir: &Path) -> Result<Vec<ExportedModRef>, String> {
+    let provenance = crate::mods::load_mod_provenance(mods_dir).await?;
+    let mut refs = Vec::new();
+    for (file_name, record) in provenance {
+        let Some(version_id) = record.version_id else {
+            continue;
+        };
+        if record.provider != crate::mods::ProviderKind::Modrinth {
+            continue;
+        }
+        // Legacy metadata can outlive its jar, those mods are gone
+        let disabled = if mods_dir.join(&file_name).is_file() {
+            false
+        } else if mods_dir.join("disabled").join(&file_name).is_file() {
+            true
+        } else {
+            continue;
+        };
+        refs.push(ExportedModRef {
+            file_name,
+            project_id: record.project_id,
+            version_id,
+            disabled,
+        });
+    }
+    Ok(refs)
+}
+
+/// Downloads the mods of a `ModsAsRefs` export into the imported installation. A mod that
+/// can't be downloaded is logged and skipped, the installation is already imported by then.
+async fn import_mod_refs(
+    installation: &KableInstallation,
+    refs: Vec<ExportedModRef>,
+) -> Result<(), String> {
+    let mods_dir = installation.find_mods_dir()?;
+    // Every mod is reported as missing below when Modrinth can't be reached
+    let versions =
+        crate::mods::get_versions_by_ids(refs.iter().map(|r| r.version_id.clone()).collect())
+            .await
+            .unwrap_or_else(|e| {
+                Logger::warn_global(
+                    &format!("Failed to look up the exported mod versions: {}", e),
+                    Some(&installation.id),
+                );
+                Vec::new()
+            });
+    let mut missing = Vec::new();
+    for mod_ref in refs {
+        let file = versions
+            .iter()
+            .find(|v| v.id == mod_ref.version_id)
+            .and_then(|v| {
+                v.files
+                    .iter()
+                    .find(|f| f.filename == mod_ref.file_name)
+                    .or_else(|| v.files.iter().find(|f| f.primary))
+                    .or(v.files.first())
+                    .map(|f| (v, f))
+            });
+        let Some((version, file)) = file else {
+            missing.push(mod_ref.file_name);
+            continue;
+        };
+        let dir = if mod_ref.disabled {
+            mods_dir.join("disabled")
+        } else {
+            mods_dir.clone()
+        };
+        if let Err(e) =
+            crate::mods::download_mod_file(&file.url, &dir.join(&mod_ref.file_name), &file.hashes)
+                .await
+        {
+            Logger::warn_global(
+                &format!("Failed to download {}: {}", mod_ref.file_name, e),
+                Some(&installation.id),
+            );
+            missing.push(mod_ref.file_name);
+            continue;
+        }
+        // The jar is in place, without a record it just can't be updated through Modrinth
+        if let Err(e) = crate::mods::record_mod_provenance(
+            &mods_dir,
+            &mod_ref.file_name,
+            crate::mods::ModProvenance {
+                provider: crate::mods::ProviderKind::Modrinth,
+                project_id: mod_ref.project_id,
+                version_id: Some(version.id.clone()),
+                version_number: Some(version.version_number.clone()),
+                download_url: Some(file.url.clone()),
+                downloaded_at: chrono::Utc::now().to_rfc3339(),
+            },
+        )
+        .await
+        {
+            Logger::warn_global(
+                &format!(
+                    "Failed to record where {} came from: {}",
+                    mod_ref.file_name, e
+                ),
+                Some(&installation.id),
+            );
+        }
+    }
+    if !missing.is_empty() {
+        Logger::warn_global(
+            &format!(
+                "Could not download {} mod(s) of the import: {}",
+                missing.len(),
+                missing.join(", ")
+            ),
+            Some(&installation.id),
+        );
+    }
+    Ok(())
+}
+
+/// Reopens a finished export: kable_export.json has to parse and the embedded folder zips
+/// have to be readable archives, so a partial or corrupt export is never shared
+fn verify_export(path: &Path) -> Result<(), String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to reopen export: {}", e))?;
+    let mut zip = ZipArchive::new(file).map_err(|e| format!("Export is not a valid zip: {}", e))?;
+    {
+        let mut json = String::new();
+        zip.by_name("kable_export.json")
+            .map_err(|e| format!("kable_export.json is missing: {}", e))?
+            .read_to_string(&mut json)
+            .map_err(|e| format!("Failed to read kable_export.json: {}", e))?;
+        serde_json::from_str::<KableInstallation>(&json)
+            .map_err(|e| format!("kable_export.json does not parse: {}", e))?;
+    }
+    if let Ok(entry) = zip.by_name(EXPORT_MOD_REFS) {
+        serde_json::from_reader::<_, Vec<ExportedModRef>>(entry)
+            .map_err(|e| format!("{} does not parse: {}", EXPORT_MOD_REFS, e))?;
+    }
+    for name in EXPORT_NESTED_ZIPS {
+        let Ok(mut entry) = zip.by_name(name) else {
+            continue;
+        };
+        // ZipArchive needs to seek, so the entry is copied out first like import does
+        let tmp = path.with_file_name(format!(
+            "{}.{}.check.tmp",
+            path.file_name().unwrap_or_default().to_string_lossy(),
+            name
+        ));
+        let checked = (|| {
+            let mut tmp_file = fs::File::create(&tmp)
+                .map_err(|e| format!("Failed to create temp file for {}: {}", name, e))?;
+            std::io::copy(&mut entry, &mut tmp_file)
+                .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+            let tmp_file =
+                fs::File::open(&tmp).map_err(|e| format!("Failed to open {}: {}", name, e))?;
+            let mut inner = ZipArchive::new(tmp_file)
+                .map_err(|e| format!("{} is not a valid zip: {}", name, e))?;
+            for i in 0..inner.len() {
+                inner
+                    .by_index(i)
+                    .map_err(|e| format!("{} has a broken entry: {}", name, e))?;
+            }
+            Ok::<(), String>(())
+        })();
+        let _ = fs::remove_file(&tmp);
+        checked?;
+    }
+    Ok(())
+}
+
+/// Folder in .minecraft/config while dedicated configs are overlaid, holds the manifest and
+/// the global configs they replaced
+const CONFIG_OVERLAY_DIR: &str = ".kable_overlay";
+const CONFIG_OVERLAY_MANIFEST: &str = "overlay.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ConfigOverlay {
+    /// Dedicated config folder the files came from
+    dedicated: String,
+    /// Overlaid files, relative with '/'
+    files: Vec<String>,
+    /// Global configs before the overlay, files the game creates besides these belong to the
+    /// installation. None in manifests of older versions.
+    #[serde(default)]
+    global_files: Option<Vec<String>>,
+}
+
+/// Files in .minecraft/config, relative with '/', the overlay folder left out
+fn global_config_files(config_dir: &Path) -> Vec<String> {
+    walkdir::WalkDir::new(config_dir)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != CONFIG_OVERLAY_DIR)
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| Some(zip_entry_name(e.path().strip_prefix(config_dir).ok()?)))
+        .collect()
+}
+
+/// Copies the dedicated configs over the global ones in .minecraft/config. Global configs the
+/// dedicated folder doesn't have stay in place and are shared, the replaced ones are kept in
+/// the overlay folder until `restore_config_overlay`.
+fn overlay_config_folder(config_dir: &Path, dedicated: &Path) -> Result<(), String> {
+    if config_dir.is_symlink() {
+        return Err(format!(
+            "{} is still linked to a dedicated folder",
+            config_dir.display()
+        ));
+    }
+    let overlay_dir = config_dir.join(CONFIG_OVERLAY_DIR);
+    // Left behind by a game that didn't exit cleanly
+    if overlay_dir.is_dir() {
+        restore_config_overlay(config_dir)?;
+    }
+    crate::ensure_folder_sync(&overlay_dir)?;
+
+    let mut overlay = ConfigOverlay {
+        dedicated: dedicated.to_string_lossy().to_string(),
+        files: Vec::new(),
+        global_files: Some(global_config_files(config_dir)),
+    };
+    for entry in walkdir::WalkDir::new(dedicated)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let Ok(rel) = entry.path().strip_prefix(dedicated) else {
+            continue;
+        };
+        let target = config_dir.join(rel);
+        if target.is_file() {
+            move_config_file(&target, &overlay_dir.join(rel))?;
+        }
+        if let Some(parent) = target.parent() {
+            crate::ensure_folder_sync(parent)?;
+        }
+        fs::copy(entry.path(), &target)
+            .map_err(|e| format!("Failed to overlay {}: {}", rel.display(), e))?;
+        overlay.files.push(zip_entry_name(rel));
+    }
+    let json = serde_json::to_string_pretty(&overlay)
+        .map_err(|e| format!("Failed to serialize config overlay: {}", e))?;
+    crate::write_file_atomic_sync(&overlay_dir.join(CONFIG_OVERLAY_MANIFEST), json.as_bytes())
+}
+
+/// Moves the overlaid configs and the ones the game created back to the dedicated folder, so
+/// what the game changed stays with the installation, and puts the global configs they
+/// replaced back
+fn restore_config_overlay(config_dir: &Path) -> Result<(), String> {
+    let overlay_dir = config_dir.join(CONFIG_OVERLAY_DIR);
+    let manifest = fs::read_to_string(overlay_dir.join(CONFIG_OVERLAY_MANIFEST))
+        .ok()
+        .and_then(|json| serde_json::from_str::<ConfigOverlay>(&json).ok());
+    if let Some(overlay) = manifest {
+        let dedicated = PathBuf::from(&overlay.dedicated);
+        if let Some(global_files) = &overlay.global_files {
+            for rel in global_config_files(config_dir) {
+                if !global_files.contains(&rel) && !overlay.files.contains(&rel) {
+                    move_config_file(&config_dir.join(&rel), &dedicated.join(&rel))?;
+                }
+            }
+        }
+        for rel in &overlay.files {
+            let current = config_dir.join(rel);
+            if current.is_file() {
+                move_config_file(&current, &dedicated.join(rel))?;
+            }
+            let replaced = overlay_dir.join(rel);
+            if replaced.is_file() {
+                move_config_file(&replaced, &current)?;
+            }
+        }
+    }
+    fs::remove_dir_all(&overlay_dir)
+        .map_err(|e| format!("Failed to remove {}: {}", overlay_dir.display(), e))
+}
+
+fn move_config_file(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        crate::ensure_folder_sync(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    // Across drives, e.g. an absolute dedicated folder elsewhere
+    fs::copy(from, to)
+        .and_then(|_| fs::remove_file(from))
+        .map(|_| ())
+        .map_err(|e| {
+            format!(
+                "Failed to move {} to {}: {}",
+                from.display(),
+                to.display(),
+                e
+            )
+        })
+}
+
 impl KableInstallation {
     /// Exports this KableInstallation as a bundled zip file containing a kable_export.json with the data
-    /// and if applicable, the resource pack and shaders folder.
+    /// and, depending on `content`, the resource pack, shaders and mods folders.
     /// Returns the path to the exported file.
-    pub async fn export(&self) -> Result<String, String> {
+    pub async fn export(&self, content: ExportContent) -> Result<String, String> {
         let self_owned = self.clone();
         Logger::debug_global(
-            &format!("Starting export for installation id={}", self_owned.id),
+            &format!(
+                "Starting export for installation id={} ({:?})",
+                self_owned.id, content
+            ),
             None,
         );
+        // find_mods_dir also handles legacy "{id}" folders and absolute paths
+        let mods_source = match self_owned.dedicated_mods_folder {
+            Some(_) if !self_owned.use_global_mods => Some(self_owned.find_mods_dir()?),
+            Some(_) => self_owned.get_dedicated_mods_folder_path(),
+            None => None,
+        };
+        let mod_refs = match (content, mods_source.as_deref()) {
+            (ExportContent::ModsAsRefs, Some(mods_dir)) if !self_owned.use_global_mods => {
+                export_mod_refs(mods_dir).await?
+            }
+            _ => Vec::new(),
+        };
         let res = task::spawn_blocking(move || {
             let kable_dir = crate::get_minecraft_kable_dir()?;
             let path = kable_dir.join("exports");
@@ -370,6 +1059,74 @@ impl KableInstallation {
                 }
             }
 
+            // Bundle extra classpath jars and java agents, their absolute paths only exist on this machine
+            let mut launch_extras: Vec<(String, PathBuf)> = Vec::new();
+            if content != ExportContent::MetadataOnly {
+                export_install.extra_classpath = self_owned
+                    .extra_classpath
+                    .iter()
+                    .map(|entry| bundle_launch_extra(entry, "", &mut launch_extras))
+                    .collect();
+                export_install.java_agents = self_owned
+                    .java_agents
+                    .iter()
+                    .map(|entry| {
+                        let path = KableInstallation::java_agent_path(entry);
+                        let options = entry.split_once('=').map_or("", |(_, options)| options);
+                        bundle_launch_extra(path, options, &mut launch_extras)
+                    })
+                    .collect();
+            }
+
+            // Dedicated resource pack, shaders and mods folders (or files) go in as nested zips
+            let resolve = |folder: Option<&str>| {
+                let folder = PathBuf::from(folder?);
+                Some(if folder.is_absolute() {
+                    folder
+                } else {
+                    kable_dir.join(folder)
+                })
+            };
+            let folders: Vec<(&str, PathBuf, Vec<PathBuf>)> = [
+                (
+                    "resource_packs.zip",
+                    resolve(self_owned.dedicated_resource_pack_folder.as_deref()),
+                ),
+                (
+                    "shaders.zip",
+                    resolve(self_owned.dedicated_shaders_folder.as_deref()),
+                ),
+                ("mods.zip", mods_source),
+            ]
+            .into_iter()
+            .filter(|_| content != ExportContent::MetadataOnly)
+            .filter_map(|(entry, source)| {
+                let source = source?;
+                let mut files = export_files(&source);
+                if entry == "mods.zip" {
+                    // Referenced mods are downloaded again on import instead
+                    files.retain(|file| {
+                        let Ok(rel) = file.strip_prefix(&source) else {
+                            return true;
+                        };
+                        let name = zip_entry_name(rel);
+                        !mod_refs.iter().any(|r| {
+                            name == r.file_name || name == format!("disabled/{}", r.file_name)
+                        })
+                    });
+                }
+                Some((entry, source, files))
+            })
+            .collect();
+            let total_bytes = folders
+                .iter()
+                .flat_map(|(_, _, files)| files)
+                .chain(launch_extras.iter().map(|(_, path)| path))
+                .filter_map(|path| fs::metadata(path).ok())
+                .map(|metadata| metadata.len())
+                .sum();
+            let mut progress = ExportProgressTracker::new(&self_owned.id, total_bytes);
+
             // Use placeholder for ID in export
             export_install.id = "{{INSTALLATION_ID}}".to_string();
 
@@ -381,8 +1138,21 @@ impl KableInstallation {
             zip.write_all(json.as_bytes())
                 .map_err(|e| format!("Failed to write KableInstallation data: {}", e))?;
 
+            if !mod_refs.is_empty() {
+                zip.start_file(EXPORT_MOD_REFS, options.clone())
+                    .map_err(|e| format!("Failed to write {}: {}", EXPORT_MOD_REFS, e))?;
+                let json = serde_json::to_string_pretty(&mod_refs)
+                    .map_err(|e| format!("Failed to serialize mod references: {}", e))?;
+                zip.write_all(json.as_bytes())
+                    .map_err(|e| format!("Failed to write {}: {}", EXPORT_MOD_REFS, e))?;
+            }
+
             // Export the custom icon if it exists (as raw data to preserve it)
-            if let Some(ref icon_spec) = self_owned.icon {
+            if let Some(ref icon_spec) = self_owned
+                .icon
+                .as_ref()
+                .filter(|_| content != ExportContent::MetadataOnly)
+            {
                 // Try to extract icon bytes and save to icon.dat in the export
                 let icon_bytes: Option<Vec<u8>> = (|| -> Option<Vec<u8>> {
                     // data: URI
@@ -438,179 +1208,21 @@ impl KableInstallation {
                 }
             }
 
-            // If there is a dedicated resource pack folder or file, add it to the zip
-            if let Some(ref resource_pack) = self_owned.dedicated_resource_pack_folder {
-                // if resource_pack is absolute, use as-is; otherwise relative to kable_dir
-                let rp_path = PathBuf::from(resource_pack);
-                let resource_pack_path = if rp_path.is_absolute() {
-                    rp_path
-                } else {
-                    kable_dir.join(resource_pack)
-                };
-                if resource_pack_path.exists() {
-                    // If it's a file (zip), copy it directly
-                    if resource_pack_path.is_file() {
-                        zip.start_file("resource_packs.zip", options.clone())
-                            .map_err(|e| format!("Failed to write resource pack: {}", e))?;
-                        let mut resource_pack_file = fs::File::open(&resource_pack_path)
-                            .map_err(|e| format!("Failed to open resource pack: {}", e))?;
-                        std::io::copy(&mut resource_pack_file, &mut zip)
-                            .map_err(|e| format!("Failed to copy resource pack: {}", e))?;
-                    } else if resource_pack_path.is_dir() {
-                        // Create a temporary zip of the directory and copy it into the main zip as resource_packs.zip
-                        let mut tmp = std::env::temp_dir();
-                        tmp.push(format!("resource_packs_{}.zip", export_install.id));
-                        {
-                            let tmp_file = fs::File::create(&tmp).map_err(|e| {
-                                format!("Failed to create temp resource zip: {}", e)
-                            })?;
-                            let mut tmp_zip = zip::ZipWriter::new(tmp_file);
-                            let walk = walkdir::WalkDir::new(&resource_pack_path);
-                            for entry in walk.into_iter().filter_map(|e| e.ok()) {
-                                let path = entry.path();
-                                if path.is_file() {
-                                    let rel = path.strip_prefix(&resource_pack_path).unwrap();
-                                    let name = format!("{}", rel.to_string_lossy());
-                                    tmp_zip.start_file(name, options.clone()).map_err(|e| {
-                                        format!("Failed to add file to tmp resource zip: {}", e)
-                                    })?;
-                                    let mut f = fs::File::open(path).map_err(|e| {
-                                        format!("Failed to open file for tmp zip: {}", e)
-                                    })?;
-                                    std::io::copy(&mut f, &mut tmp_zip).map_err(|e| {
-                                        format!("Failed to copy to tmp resource zip: {}", e)
-                                    })?;
-                                }
-                            }
-                            tmp_zip
-                                .finish()
-                                .map_err(|e| format!("Failed to finish tmp resource zip: {}", e))?;
-                        }
-                        // Copy tmp into main zip
-                        let mut tmp_file = fs::File::open(&tmp)
-                            .map_err(|e| format!("Failed to open tmp resource zip: {}", e))?;
-                        zip.start_file("resource_packs.zip", options.clone())
-                            .map_err(|e| format!("Failed to write resource pack: {}", e))?;
-                        std::io::copy(&mut tmp_file, &mut zip)
-                            .map_err(|e| format!("Failed to copy resource pack: {}", e))?;
-                        // Ignore failure to remove temp file
-                        let _ = fs::remove_file(&tmp);
-                    }
-                }
+            for (entry, source, files) in &folders {
+                add_folder_to_export(&mut zip, entry, source, files, &options, &mut progress)?;
             }
-            // If there is a dedicated shaders folder, add it to the zip
-            if let Some(ref shaders_folder) = self_owned.dedicated_shaders_folder {
-                let sf_path = PathBuf::from(shaders_folder);
-                let shaders_path = if sf_path.is_absolute() {
-                    sf_path
-                } else {
-                    kable_dir.join(shaders_folder)
-                };
-                if shaders_path.exists() {
-                    if shaders_path.is_file() {
-                        zip.start_file("shaders.zip", options.clone())
-                            .map_err(|e| format!("Failed to write shaders folder: {}", e))?;
-                        let mut shaders_file = fs::File::open(&shaders_path)
-                            .map_err(|e| format!("Failed to open shaders folder: {}", e))?;
-                        std::io::copy(&mut shaders_file, &mut zip)
-                            .map_err(|e| format!("Failed to copy shaders folder: {}", e))?;
-                    } else if shaders_path.is_dir() {
-                        // Zip dir to tmp and copy
-                        let mut tmp = std::env::temp_dir();
-                        tmp.push(format!("shaders_{}.zip", export_install.id));
-                        {
-                            let tmp_file = fs::File::create(&tmp)
-                                .map_err(|e| format!("Failed to create tmp shaders zip: {}", e))?;
-                            let mut tmp_zip = zip::ZipWriter::new(tmp_file);
-                            let walk = walkdir::WalkDir::new(&shaders_path);
-                            for entry in walk.into_iter().filter_map(|e| e.ok()) {
-                                let path = entry.path();
-                                if path.is_file() {
-                                    let rel = path.strip_prefix(&shaders_path).unwrap();
-                                    let name = format!("{}", rel.to_string_lossy());
-                                    tmp_zip.start_file(name, options.clone()).map_err(|e| {
-                                        format!("Failed to add file to tmp shaders zip: {}", e)
-                                    })?;
-                                    let mut f = fs::File::open(path).map_err(|e| {
-                                        format!("Failed to open file for tmp shaders zip: {}", e)
-                                    })?;
-                                    std::io::copy(&mut f, &mut tmp_zip).map_err(|e| {
-                                        format!("Failed to copy to tmp shaders zip: {}", e)
-                                    })?;
-                                }
-                            }
-                            tmp_zip
-                                .finish()
-                                .map_err(|e| format!("Failed to finish tmp shaders zip: {}", e))?;
-                        }
-                        let mut tmp_file = fs::File::open(&tmp)
-                            .map_err(|e| format!("Failed to open tmp shaders zip: {}", e))?;
-                        zip.start_file("shaders.zip", options.clone())
-                            .map_err(|e| format!("Failed to write shaders folder: {}", e))?;
-                        std::io::copy(&mut tmp_file, &mut zip)
-                            .map_err(|e| format!("Failed to copy shaders folder: {}", e))?;
-                        let _ = fs::remove_file(&tmp);
-                    }
-                }
-            }
-
-            // If there is a dedicated mods folder, add it to the zip
-            if let Some(ref mods_folder) = self_owned.dedicated_mods_folder {
-                let mf_path = PathBuf::from(mods_folder);
-                let mods_path = if mf_path.is_absolute() {
-                    mf_path
-                } else {
-                    kable_dir.join(mods_folder)
-                };
-                if mods_path.exists() {
-                    if mods_path.is_file() {
-                        zip.start_file("mods.zip", options.clone())
-                            .map_err(|e| format!("Failed to write mods: {}", e))?;
-                        let mut mods_file = fs::File::open(&mods_path)
-                            .map_err(|e| format!("Failed to open mods file: {}", e))?;
-                        std::io::copy(&mut mods_file, &mut zip)
-                            .map_err(|e| format!("Failed to copy mods file: {}", e))?;
-                    } else if mods_path.is_dir() {
-                        // Zip dir to tmp and copy
-                        let mut tmp = std::env::temp_dir();
-                        tmp.push(format!("mods_{}.zip", export_install.id));
-                        {
-                            let tmp_file = fs::File::create(&tmp)
-                                .map_err(|e| format!("Failed to create tmp mods zip: {}", e))?;
-                            let mut tmp_zip = zip::ZipWriter::new(tmp_file);
-                            let walk = walkdir::WalkDir::new(&mods_path);
-                            for entry in walk.into_iter().filter_map(|e| e.ok()) {
-                                let path = entry.path();
-                                if path.is_file() {
-                                    let rel = path.strip_prefix(&mods_path).unwrap();
-                                    let name = format!("{}", rel.to_string_lossy());
-                                    tmp_zip.start_file(name, options.clone()).map_err(|e| {
-                                        format!("Failed to add file to tmp mods zip: {}", e)
-                                    })?;
-                                    let mut f = fs::File::open(path).map_err(|e| {
-                                        format!("Failed to open file for tmp mods zip: {}", e)
-                                    })?;
-                                    std::io::copy(&mut f, &mut tmp_zip).map_err(|e| {
-                                        format!("Failed to copy to tmp mods zip: {}", e)
-                                    })?;
-                                }
-                            }
-                            tmp_zip
-                                .finish()
-                                .map_err(|e| format!("Failed to finish tmp mods zip: {}", e))?;
-                        }
-                        let mut tmp_file = fs::File::open(&tmp)
-                            .map_err(|e| format!("Failed to open tmp mods zip: {}", e))?;
-                        zip.start_file("mods.zip", options.clone())
-                            .map_err(|e| format!("Failed to write mods: {}", e))?;
-                        std::io::copy(&mut tmp_file, &mut zip)
-                            .map_err(|e| format!("Failed to copy mods: {}", e))?;
-                        let _ = fs::remove_file(&tmp);
-                    }
-                }
+            for (name, path) in &launch_extras {
+                zip.start_file(name.as_str(), options.clone())
+                    .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+                progress.copy(path, name, &mut zip)?;
             }
+            progress.emit(true);
             zip.finish()
                 .map_err(|e| format!("Failed to finish zip file: {}", e))?;
+            if let Err(e) = verify_export(&tmp_path) {
+                let _ = fs::remove_file(&tmp_path);
+                return Err(format!("Export verification failed: {}", e));
+            }
             // Atomically move tmp into final location
             std::fs::rename(&tmp_path, &export_path)
                 .map_err(|e| format!("Failed to move export into place: {}", e))?;
@@ -625,6 +1237,8 @@ impl KableInstallation {
     }
 
     /// This import does the opposite of export by extracting the KableInstallation data from a zip file and putting it in the right places.
+    /// Mods of a `ModsAsRefs` export are downloaded from Modrinth afterwards, a `MetadataOnly`
+    /// export just has no folders to extract.
     pub async fn import(path: &str) -> Result<KableInstallation, String> {
         let path_owned = path.to_string();
         Logger::debug_global(&format!("Starting import from {}", path_owned), None);
@@ -655,6 +1269,12 @@ impl KableInstallation {
                 installation.id = new_id.clone();
             }
 
+            let mod_refs: Vec<ExportedModRef> = match zip.by_name(EXPORT_MOD_REFS) {
+                Ok(entry) => serde_json::from_reader(entry)
+                    .map_err(|e| format!("Failed to parse {}: {}", EXPORT_MOD_REFS, e))?,
+                Err(_) => Vec::new(),
+            };
+
             // Extract and restore the custom icon if it exists
             if let Ok(mut icon_file) = zip.by_name("icon.dat") {
                 let mut icon_data = Vec::new();
@@ -724,26 +1344,7 @@ impl KableInstallation {
                     let mut entry = inner_zip
                         .by_index(i)
                         .map_err(|e| format!("Failed to access inner zip entry: {}", e))?;
-                    let name = entry.name();
-                    // Security: skip dangerous paths
-                    if name.contains("..") || name.starts_with('/') {
-                        continue;
-                    }
-                    let out_path = dest_dir.join(name);
-                    if entry.is_dir() {
-                        crate::ensure_folder_sync(&out_path)
-                            .map_err(|e| format!("Failed to create dir during extract: {}", e))?;
-                    } else {
-                        if let Some(p) = out_path.parent() {
-                            crate::ensure_folder_sync(p).map_err(|e| {
-                                format!("Failed to create parent dir during extract: {}", e)
-                            })?;
-                        }
-                        let mut outfile = fs::File::create(&out_path)
-                            .map_err(|e| format!("Failed to create file during extract: {}", e))?;
-                        std::io::copy(&mut entry, &mut outfile)
-                            .map_err(|e| format!("Failed to write extracted file: {}", e))?;
-                    }
+                    safe_extract(&mut entry, &dest_dir)?;
                 }
                 let _ = fs::remove_file(&tmp);
             }
@@ -781,25 +1382,7 @@ impl KableInstallation {
                     let mut entry = inner_zip
                         .by_index(i)
                         .map_err(|e| format!("Failed to access inner zip entry: {}", e))?;
-                    let name = entry.name();
-                    if name.contains("..") || name.starts_with('/') {
-                        continue;
-                    }
-                    let out_path = dest_dir.join(name);
-                    if entry.is_dir() {
-                        crate::ensure_folder_sync(&out_path)
-                            .map_err(|e| format!("Failed to create dir during extract: {}", e))?;
-                    } else {
-                        if let Some(p) = out_path.parent() {
-                            crate::ensure_folder_sync(p).map_err(|e| {
-                                format!("Failed to create parent dir during extract: {}", e)
-                            })?;
-                        }
-                        let mut outfile = fs::File::create(&out_path)
-                            .map_err(|e| format!("Failed to create file during extract: {}", e))?;
-                        std::io::copy(&mut entry, &mut outfile)
-                            .map_err(|e| format!("Failed to write extracted file: {}", e))?;
-                    }
+                    safe_extract(&mut entry, &dest_dir)?;
                 }
                 let _ = fs::remove_file(&tmp);
             }
@@ -837,29 +1420,45 @@ impl KableInstallation {
                     let mut entry = inner_zip
                         .by_index(i)
                         .map_err(|e| format!("Failed to access inner zip entry: {}", e))?;
-                    let name = entry.name();
-                    if name.contains("..") || name.starts_with('/') {
-                        continue;
-                    }
-                    let out_path = dest_dir.join(name);
-                    if entry.is_dir() {
-                        crate::ensure_folder_sync(&out_path)
-                            .map_err(|e| format!("Failed to create dir during extract: {}", e))?;
-                    } else {
-                        if let Some(p) = out_path.parent() {
-                            crate::ensure_folder_sync(p).map_err(|e| {
-                                format!("Failed to create parent dir during extract: {}", e)
-                            })?;
-                        }
-                        let mut outfile = fs::File::create(&out_path)
-                            .map_err(|e| format!("Failed to create file during extract: {}", e))?;
-                        std::io::copy(&mut entry, &mut outfile)
-                            .map_err(|e| format!("Failed to write extracted file: {}", e))?;
-                    }
+                    safe_extract(&mut entry, &dest_dir)?;
                 }
                 let _ = fs::remove_file(&tmp);
             }
 
+            // Extract bundled extra classpath jars and java agents to kable/launch_extras/<id>
+            let extras_dir = kable_dir.join(LAUNCH_EXTRAS_DIR).join(&new_id);
+            let mut extract_extra = |entry: &str| -> Result<String, String> {
+                let path = KableInstallation::java_agent_path(entry);
+                let Some(name) = path
+                    .strip_prefix(LAUNCH_EXTRAS_DIR)
+                    .and_then(|n| n.strip_prefix('/'))
+                    .filter(|n| !n.contains("..") && !n.contains('/'))
+                else {
+                    return Ok(entry.to_string());
+                };
+                let Ok(mut file) = zip.by_name(path) else {
+                    return Ok(entry.to_string());
+                };
+                crate::ensure_folder_sync(&extras_dir)
+                    .map_err(|e| format!("Failed to create launch extras directory: {}", e))?;
+                let out_path = extras_dir.join(name);
+                let mut outfile = fs::File::create(&out_path)
+                    .map_err(|e| format!("Failed to create {}: {}", out_path.display(), e))?;
+                std::io::copy(&mut file, &mut outfile)
+                    .map_err(|e| format!("Failed to write {}: {}", out_path.display(), e))?;
+                Ok(entry.replacen(path, &out_path.to_string_lossy(), 1))
+            };
+            installation.extra_classpath = installation
+                .extra_classpath
+                .iter()
+                .map(|entry| extract_extra(entry))
+                .collect::<Result<_, _>>()?;
+            installation.java_agents = installation
+                .java_agents
+                .iter()
+                .map(|entry| extract_extra(entry))
+                .collect::<Result<_, _>>()?;
+
             // Persist the imported installation into kable_profiles.json so the frontend
             // and other parts of the app can immediately see the new installation.
             // Prefix the name so users can easily identify imported items.
@@ -881,10 +1480,17 @@ impl KableInstallation {
                 return Err(format!("Failed to persist imported installation: {}", e));
             }
 
-            Ok::<KableInstallation, String>(installation)
+            Ok::<_, String>((installation, mod_refs))
         })
         .await
         .map_err(|e| format!("Import task join error: {}", e))?;
+        let res = match res {
+            Ok((installation, mod_refs)) if !mod_refs.is_empty() => {
+                import_mod_refs(&installation, mod_refs).await?;
+                Ok(installation)
+            }
+            other => other.map(|(installation, _)| installation),
+        };
         if let Ok(ref inst) = res {
             Logger::debug_global(
                 &format!("Import completed: id={} name={}", inst.id, inst.name),
@@ -899,8 +1505,23 @@ impl KableInstallation {
     /// by copying mods, resourcepacks, and shaderpacks to dedicated folders.
     pub async fn import_from_minecraft_folder(
         minecraft_folder: &str,
+    ) -> Result<Vec<KableInstallation>, String> {
+        Self::import_from_minecraft_folder_with_progress(
+            minecraft_folder,
+            &crate::launcher::ProgressReporter::none(),
+        )
+        .await
+    }
+
+    /// Same as `import_from_minecraft_folder`, reporting every copied file or folder as the
+    /// "import_files" stage. The copy runs as a "minecraft_import" operation so it can be
+    /// cancelled, a cancelled import removes what it copied and adds no installations.
+    pub async fn import_from_minecraft_folder_with_progress(
+        minecraft_folder: &str,
+        progress: &crate::launcher::ProgressReporter,
     ) -> Result<Vec<KableInstallation>, String> {
         use crate::logging::Logger;
+        use std::sync::atomic::{AtomicU64, Ordering};
 
         let minecraft_path = PathBuf::from(minecraft_folder);
         Logger::debug_global(
@@ -911,6 +1532,8 @@ impl KableInstallation {
             None,
         );
 
+        let operation = crate::launcher::operations::Operation::start("minecraft_import", None);
+        let progress = progress.clone();
         let res = task::spawn_blocking(move || {
             // Verify it's a valid .minecraft folder
             let launcher_profiles_path = minecraft_path.join("launcher_profiles.json");
@@ -924,133 +1547,61 @@ impl KableInstallation {
             let data = std::fs::read_to_string(&launcher_profiles_path)
                 .map_err(|e| format!("Failed to read launcher_profiles.json: {}", e))?;
 
-            let json: serde_json::Value = serde_json::from_str(&data)
-                .map_err(|e| format!("Failed to parse launcher_profiles.json: {}", e))?;
-
-            let profiles = json
-                .get("profiles")
-                .and_then(|p| p.as_object())
-                .ok_or("No 'profiles' object found in launcher_profiles.json")?;
-
-            let mut new_installations = Vec::new();
+            let mut new_installations: Vec<KableInstallation> =
+                crate::profiles::parse_launcher_profiles_json(&data)?
+                    .into_iter()
+                    .map(KableInstallation::from)
+                    .collect();
             let kable_dir = crate::get_minecraft_kable_dir()?;
 
-            for profile_value in profiles.values() {
-                let profile: LauncherProfile = serde_json::from_value(profile_value.clone())
-                    .map_err(|e| format!("Failed to parse a profile: {}", e))?;
-
-                // Create a new Kable installation from this profile
-                let mut installation = KableInstallation::from(profile.clone());
-
-                // Copy mods folder if it exists
-                let source_mods = minecraft_path.join("mods");
-                if source_mods.exists() && source_mods.is_dir() {
-                    let dest_mods = kable_dir.join("mods").join(&installation.id);
-                    crate::ensure_folder_sync(&dest_mods)
-                        .map_err(|e| format!("Failed to create mods folder: {}", e))?;
-
-                    // Copy all mod files
-                    if let Ok(entries) = std::fs::read_dir(&source_mods) {
-                        for entry in entries.flatten() {
-                            let path = entry.path();
-                            if path.extension().map(|e| e == "jar").unwrap_or(false) {
-                                let file_name = path.file_name().unwrap();
-                                let dest_file = dest_mods.join(file_name);
-                                let _ = std::fs::copy(&path, &dest_file);
-                            }
-                        }
-                    }
-
-                    installation.dedicated_mods_folder = Some(format!("mods/{}", installation.id));
-                }
-
-                // Copy resourcepacks folder if it exists
-                let source_resourcepacks = minecraft_path.join("resourcepacks");
-                if source_resourcepacks.exists() && source_resourcepacks.is_dir() {
-                    let dest_resourcepacks = kable_dir.join("resourcepacks").join(&installation.id);
-                    crate::ensure_folder_sync(&dest_resourcepacks)
-                        .map_err(|e| format!("Failed to create resourcepacks folder: {}", e))?;
-
-                    // Copy all resourcepack files
-                    if let Ok(entries) = std::fs::read_dir(&source_resourcepacks) {
-                        for entry in entries.flatten() {
-                            let path = entry.path();
-                            let file_name = path.file_name().unwrap();
-                            let dest_file = dest_resourcepacks.join(file_name);
-
-                            if path.is_file() {
-                                let _ = std::fs::copy(&path, &dest_file);
-                            } else if path.is_dir() {
-                                let _ = crate::copy_dir_recursive_sync(&path, &dest_file);
-                            }
-                        }
-                    }
-
-                    installation.dedicated_resource_pack_folder =
-                        Some(format!("resourcepacks/{}", installation.id));
-                }
-
-                // Copy shaderpacks folder if it exists
-                let source_shaderpacks = minecraft_path.join("shaderpacks");
-                if source_shaderpacks.exists() && source_shaderpacks.is_dir() {
-                    let dest_shaderpacks = kable_dir.join("shaderpacks").join(&installation.id);
-                    crate::ensure_folder_sync(&dest_shaderpacks)
-                        .map_err(|e| format!("Failed to create shaderpacks folder: {}", e))?;
-
-                    // Copy all shader files
-                    if let Ok(entries) = std::fs::read_dir(&source_shaderpacks) {
-                        for entry in entries.flatten() {
-                            let path = entry.path();
-                            let file_name = path.file_name().unwrap();
-                            let dest_file = dest_shaderpacks.join(file_name);
-
-                            if path.is_file() {
-                                let _ = std::fs::copy(&path, &dest_file);
-                            } else if path.is_dir() {
-                                let _ = crate::copy_dir_recursive_sync(&path, &dest_file);
-                            }
-                        }
-                    }
-
-                    installation.dedicated_shaders_folder =
-                        Some(format!("shaderpacks/{}", installation.id));
-                }
-
-                // Copy config folder if it exists
-                let source_config = minecraft_path.join("config");
-                if source_config.exists() && source_config.is_dir() {
-                    let dest_config = kable_dir.join("config").join(&installation.id);
-                    crate::ensure_folder_sync(&dest_config)
-                        .map_err(|e| format!("Failed to create config directory: {}", e))?;
-
-                    // Copy all config files and directories
-                    if let Ok(entries) = std::fs::read_dir(&source_config) {
-                        for entry in entries.flatten() {
-                            let path = entry.path();
-                            let file_name = path.file_name().unwrap();
-                            let dest = dest_config.join(file_name);
-
-                            if path.is_file() {
-                                std::fs::copy(&path, &dest)
-                                    .map_err(|e| format!("Failed to copy config file: {}", e))?;
-                            } else if path.is_dir() {
-                                // Recursively copy directory
-                                crate::copy_dir_recursive_sync(&path, &dest).map_err(|e| {
-                                    format!("Failed to copy config directory: {}", e)
-                                })?;
-                            }
-                        }
+            // Profiles with their own gameDir keep their mods there instead of in .minecraft
+            let game_dir_of = |installation: &KableInstallation| {
+                installation
+                    .game_directory
+                    .as_ref()
+                    .map(PathBuf::from)
+                    .filter(|dir| dir.is_dir())
+                    .unwrap_or_else(|| minecraft_path.clone())
+            };
+            let total: u64 = new_installations
+                .iter()
+                .map(|installation| game_folder_entry_count(&game_dir_of(installation)))
+                .sum();
+            let copied = AtomicU64::new(0);
+            progress.report("import_files", 0, Some(total), None);
+
+            // Each installation copies into its own dedicated folders, so they can run side by side
+            let copy_result = new_installations
+                .par_iter_mut()
+                .try_for_each(|installation| {
+                    let name = installation.name.clone();
+                    let game_dir = game_dir_of(installation);
+                    installation.copy_game_folders_with(&game_dir, &kable_dir, &|path| {
+                        operation.check_cancelled()?;
+                        let current = copied.fetch_add(1, Ordering::Relaxed) + 1;
+                        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                        progress.report(
+                            "import_files",
+                            current,
+                            Some(total),
+                            Some(&format!("{}: {}", name, file_name)),
+                        );
+                        Ok(())
+                    })?;
+                    Logger::debug_global(
+                        &format!("Created installation from profile: {}", installation.name),
+                        None,
+                    );
+                    Ok::<(), String>(())
+                });
+            if let Err(e) = copy_result {
+                for installation in &new_installations {
+                    for folder in ["mods", "resourcepacks", "shaderpacks", "config"] {
+                        let _ =
+                            std::fs::remove_dir_all(kable_dir.join(folder).join(&installation.id));
                     }
-
-                    installation.dedicated_config_folder =
-                        Some(format!("config/{}", installation.id));
                 }
-
-                Logger::debug_global(
-                    &format!("Created installation from profile: {}", installation.name),
-                    None,
-                );
-                new_installations.push(installation);
+                return Err(e);
             }
 
             // Add all new installations to kable_profiles.json
@@ -1078,6 +1629,130 @@ impl KableInstallation {
         res
     }
 
+    /// Copies mods (jars only), resourcepacks, shaderpacks and config from a game directory
+    /// into this installation's dedicated folders in the kable directory
+    pub fn copy_game_folders_from(
+        &mut self,
+        game_dir: &Path,
+        kable_dir: &Path,
+    ) -> Result<(), String> {
+        self.copy_game_folders_with(game_dir, kable_dir, &|_| Ok(()))
+    }
+
+    /// Same as `copy_game_folders_from`, calling `on_copied` after every top level file or folder.
+    /// An error from `on_copied` stops the copy.
+    pub fn copy_game_folders_with(
+        &mut self,
+        game_dir: &Path,
+        kable_dir: &Path,
+        on_copied: &(dyn Fn(&Path) -> Result<(), String> + Sync),
+    ) -> Result<(), String> {
+        // Copy mods folder if it exists
+        let source_mods = game_dir.join("mods");
+        if source_mods.exists() && source_mods.is_dir() {
+            let dest_mods = kable_dir.join("mods").join(&self.id);
+            crate::ensure_folder_sync(&dest_mods)
+                .map_err(|e| format!("Failed to create mods folder: {}", e))?;
+
+            // Copy all mod files
+            if let Ok(entries) = std::fs::read_dir(&source_mods) {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    if path.extension().map(|e| e == "jar").unwrap_or(false) {
+                        let file_name = path.file_name().unwrap();
+                        let dest_file = dest_mods.join(file_name);
+                        let _ = std::fs::copy(&path, &dest_file);
+                        on_copied(&path)?;
+                    }
+                }
+            }
+
+            self.dedicated_mods_folder = Some(format!("mods/{}", self.id));
+        }
+
+        // Copy resourcepacks folder if it exists
+        let source_resourcepacks = game_dir.join("resourcepacks");
+        if source_resourcepacks.exists() && source_resourcepacks.is_dir() {
+            let dest_resourcepacks = kable_dir.join("resourcepacks").join(&self.id);
+            crate::ensure_folder_sync(&dest_resourcepacks)
+                .map_err(|e| format!("Failed to create resourcepacks folder: {}", e))?;
+
+            // Copy all resourcepack files
+            if let Ok(entries) = std::fs::read_dir(&source_resourcepacks) {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    let file_name = path.file_name().unwrap();
+                    let dest_file = dest_resourcepacks.join(file_name);
+
+                    if path.is_file() {
+                        let _ = std::fs::copy(&path, &dest_file);
+                    } else if path.is_dir() {
+                        let _ = crate::copy_dir_recursive_sync(&path, &dest_file);
+                    }
+                    on_copied(&path)?;
+                }
+            }
+
+            self.dedicated_resource_pack_folder = Some(format!("resourcepacks/{}", self.id));
+        }
+
+        // Copy shaderpacks folder if it exists
+        let source_shaderpacks = game_dir.join("shaderpacks");
+        if source_shaderpacks.exists() && source_shaderpacks.is_dir() {
+            let dest_shaderpacks = kable_dir.join("shaderpacks").join(&self.id);
+            crate::ensure_folder_sync(&dest_shaderpacks)
+                .map_err(|e| format!("Failed to create shaderpacks folder: {}", e))?;
+
+            // Copy all shader files
+            if let Ok(entries) = std::fs::read_dir(&source_shaderpacks) {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    let file_name = path.file_name().unwrap();
+                    let dest_file = dest_shaderpacks.join(file_name);
+
+                    if path.is_file() {
+                        let _ = std::fs::copy(&path, &dest_file);
+                    } else if path.is_dir() {
+                        let _ = crate::copy_dir_recursive_sync(&path, &dest_file);
+                    }
+                    on_copied(&path)?;
+                }
+            }
+
+            self.dedicated_shaders_folder = Some(format!("shaderpacks/{}", self.id));
+        }
+
+        // Copy config folder if it exists
+        let source_config = game_dir.join("config");
+        if source_config.exists() && source_config.is_dir() {
+            let dest_config = kable_dir.join("config").join(&self.id);
+            crate::ensure_folder_sync(&dest_config)
+                .map_err(|e| format!("Failed to create config directory: {}", e))?;
+
+            // Copy all config files and directories
+            if let Ok(entries) = std::fs::read_dir(&source_config) {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    let file_name = path.file_name().unwrap();
+                    let dest = dest_config.join(file_name);
+
+                    if path.is_file() {
+                        std::fs::copy(&path, &dest)
+                            .map_err(|e| format!("Failed to copy config file: {}", e))?;
+                    } else if path.is_dir() {
+                        // Recursively copy directory
+                        crate::copy_dir_recursive_sync(&path, &dest)
+                            .map_err(|e| format!("Failed to copy config directory: {}", e))?;
+                    }
+                    on_copied(&path)?;
+                }
+            }
+
+            self.dedicated_config_folder = Some(format!("config/{}", self.id));
+        }
+        Ok(())
+    }
+
     /// Setup config folder for this installation before launch.
     /// This function:
     /// 1. Backs up global configs to .minecraft/config/kable_global if not already done
@@ -1142,6 +1817,30 @@ impl KableInstallation {
             return Ok(());
         }
 
+        let overlay = crate::settings::load_settings()
+            .await
+            .is_ok_and(|s| s.content.config_folder_mode == "overlay");
+        if overlay {
+            Logger::debug_global(
+                &format!(
+                    "Overlaying dedicated configs of {} on the global configs",
+                    self.name
+                ),
+                None,
+            );
+            return overlay_config_folder(&global_config_dir, &dedicated_config_path);
+        }
+
+        // Check before moving the global configs away, they'd be gone without a link to replace them.
+        // A copy isn't an option, mods write to their configs while the game runs.
+        let strategy = crate::symlink_manager::link_strategy(true).await?;
+        if strategy == crate::symlink_manager::LinkStrategy::Copy {
+            return Err(format!(
+                "A dedicated config folder needs a symlink or junction. {}",
+                crate::symlink_manager::symlink_unavailable_message()
+            ));
+        }
+
         Logger::debug_global(
             &format!(
                 "Setting up dedicated config folder for installation: {}",
@@ -1187,8 +1886,14 @@ impl KableInstallation {
             crate::remove_symlink_if_exists(&global_config_dir).await?;
         }
 
-        // Step 3: Create symlink from dedicated config to .minecraft/config
-        crate::create_directory_symlink(&dedicated_config_path, &global_config_dir).await?;
+        // Step 3: Link the dedicated config to .minecraft/config
+        crate::symlink_manager::create_link(
+            &dedicated_config_path,
+            &global_config_dir,
+            true,
+            strategy,
+        )
+        .await?;
 
         Logger::debug_global(
             &format!(
@@ -1213,6 +1918,11 @@ impl KableInstallation {
 
         Logger::debug_global("Restoring global configs", None);
 
+        if !global_config_dir.is_symlink() && global_config_dir.join(CONFIG_OVERLAY_DIR).is_dir() {
+            restore_config_overlay(&global_config_dir)?;
+            Logger::debug_global("Removed the dedicated config overlay", None);
+        }
+
         // Remove symlink if it exists
         if global_config_dir.exists() && global_config_dir.is_symlink() {
             crate::remove_symlink_if_exists(&global_config_dir).await?;
@@ -1360,38 +2070,7 @@ impl KableInstallation {
     /// directory could be found.
     pub fn find_mods_dir(&self) -> Result<PathBuf, String> {
         use crate::logging::Logger;
-        let legacy_default_dir = crate::get_default_minecraft_dir()
-            .ok()
-            .map(|dir| dir.join("mods"));
-        let preferred_kable_dir = crate::get_minecraft_kable_dir()
-            .ok()
-            .map(|dir| dir.join("mods").join(&self.id));
-
-        let mods_dirs = [
-            self.get_dedicated_mods_folder_path(),
-            self.get_mods_folder_from_version_manifest(),
-            preferred_kable_dir,
-            legacy_default_dir,
-        ];
-
-        let existing = mods_dirs.iter().find_map(|dir_opt| {
-            dir_opt.as_ref().and_then(|dir| {
-                if dir.exists() {
-                    Some(dir.clone())
-                } else {
-                    None
-                }
-            })
-        });
-
-        let selected = if let Some(existing) = existing {
-            existing
-        } else {
-            // No candidate existed yet, so create the first meaningful one.
-            mods_dirs.into_iter().flatten().next().ok_or_else(|| {
-                "No mods directory candidate available for installation".to_string()
-            })?
-        };
+        let selected = self.resolve_mods_dir()?;
 
         std::fs::create_dir_all(&selected).map_err(|e| {
             format!(
@@ -1414,6 +2093,47 @@ impl KableInstallation {
         Ok(selected)
     }
 
+    /// The mods folder `find_mods_dir` uses, without creating it
+    pub fn resolve_mods_dir(&self) -> Result<PathBuf, String> {
+        let legacy_default_dir = crate::get_default_minecraft_dir()
+            .ok()
+            .map(|dir| dir.join("mods"));
+        let preferred_kable_dir = crate::get_minecraft_kable_dir()
+            .ok()
+            .map(|dir| dir.join("mods").join(&self.id));
+
+        let mods_dirs = if self.use_global_mods {
+            [legacy_default_dir, None, None, None]
+        } else {
+            [
+                self.get_dedicated_mods_folder_path(),
+                self.get_mods_folder_from_version_manifest(),
+                preferred_kable_dir,
+                legacy_default_dir,
+            ]
+        };
+
+        let existing = mods_dirs.iter().find_map(|dir_opt| {
+            dir_opt.as_ref().and_then(|dir| {
+                if dir.exists() {
+                    Some(dir.clone())
+                } else {
+                    None
+                }
+            })
+        });
+
+        if let Some(existing) = existing {
+            return Ok(existing);
+        }
+        // No candidate exists yet, the first meaningful one is created
+        mods_dirs
+            .into_iter()
+            .flatten()
+            .next()
+            .ok_or_else(|| "No mods directory candidate available for installation".to_string())
+    }
+
     /// Move the given mod JAR into the installation's disabled/ subfolder.
     pub fn disable_mod(&self, file_name: &str) -> Result<(), String> {
         use crate::logging::Logger;
@@ -2168,6 +2888,174 @@ impl KableInstallation {
         Ok(result)
     }
 
+    /// Parses `connect_server` into host and port, the port defaults to 25565 when omitted.
+    /// Returns Ok(None) when no server is configured.
+    pub fn connect_server_address(&self) -> Result<Option<(String, u16)>, String> {
+        let Some(address) = self
+            .connect_server
+            .as_deref()
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+        else {
+            return Ok(None);
+        };
+
+        // Bracketed IPv6 addresses, e.g. [::1]:25565
+        let (host, port) = if let Some(rest) = address.strip_prefix('[') {
+            let (host, after) = rest
+                .split_once(']')
+                .ok_or_else(|| format!("Invalid server address '{}': missing ']'", address))?;
+            (host, after.strip_prefix(':'))
+        } else {
+            match address.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (address, None),
+            }
+        };
+
+        if host.is_empty() || host.contains(char::is_whitespace) {
+            return Err(format!(
+                "Invalid server address '{}', expected host:port",
+                address
+            ));
+        }
+        let port = match port {
+            Some(port) => port
+                .parse::<u16>()
+                .ok()
+                .filter(|p| *p != 0)
+                .ok_or_else(|| format!("Invalid port in server address '{}'", address))?,
+            None => 25565,
+        };
+        Ok(Some((host.to_string(), port)))
+    }
+
+    /// Validates `native_arch_override` and normalizes it to a natives arch tag.
+    /// Returns Ok(None) when no override is set.
+    pub fn native_arch_override_tag(&self) -> Result<Option<&'static str>, String> {
+        match self
+            .native_arch_override
+            .as_deref()
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+        {
+            Some(arch) => crate::launcher::utils::normalize_arch_tag(arch)
+                .map(Some)
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown native architecture '{}', expected x86, x86_64 or arm64",
+                        arch
+                    )
+                }),
+            None => Ok(None),
+        }
+    }
+
+    /// Validates `description_format`, None (or empty) means plain
+    pub fn description_format_kind(&self) -> Result<&str, String> {
+        match self
+            .description_format
+            .as_deref()
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+        {
+            None | Some("plain") => Ok("plain"),
+            Some("markdown") => Ok("markdown"),
+            Some(other) => Err(format!(
+                "Unknown description format '{}', expected plain or markdown",
+                other
+            )),
+        }
+    }
+
+    /// Validates `http_proxy` and `https_proxy`, returns (http, https) with blank values as None
+    pub fn proxy_urls(&self) -> Result<(Option<url::Url>, Option<url::Url>), String> {
+        fn parse(field: &str, value: &Option<String>) -> Result<Option<url::Url>, String> {
+            let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) else {
+                return Ok(None);
+            };
+            let url = url::Url::parse(value)
+                .map_err(|e| format!("Invalid {} '{}': {}", field, value, e))?;
+            if !matches!(url.scheme(), "http" | "https") {
+                return Err(format!(
+                    "Invalid {} '{}': only http:// and https:// proxies are supported",
+                    field, value
+                ));
+            }
+            if url.host_str().is_none_or(str::is_empty) {
+                return Err(format!("Invalid {} '{}': missing host", field, value));
+            }
+            Ok(Some(url))
+        }
+        Ok((
+            parse("http_proxy", &self.http_proxy)?,
+            parse("https_proxy", &self.https_proxy)?,
+        ))
+    }
+
+    /// Validates the on_game_launch/close/crash overrides against the values the global settings accept
+    pub fn validate_behavior_overrides(&self) -> Result<(), String> {
+        let checks: [(&str, &Option<String>, &[&str]); 3] = [
+            (
+                "on_game_launch",
+                &self.on_game_launch,
+                &["keep_open", "exit", "open_logs", "minimize", "ask"],
+            ),
+            (
+                "on_game_close",
+                &self.on_game_close,
+                &["open_logs", "open_home", "exit", "minimize", "ask"],
+            ),
+            (
+                "on_game_crash",
+                &self.on_game_crash,
+                &[
+                    "restart",
+                    "open_logs",
+                    "open_home",
+                    "exit",
+                    "minimize",
+                    "ask",
+                ],
+            ),
+        ];
+        for (field, value, allowed) in checks {
+            let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) else {
+                continue;
+            };
+            if !allowed.contains(&value) {
+                return Err(format!(
+                    "Unknown {} '{}', expected one of {}",
+                    field,
+                    value,
+                    allowed.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Path part of a `java_agents` entry, without the agent options
+    pub fn java_agent_path(entry: &str) -> &str {
+        entry.split_once('=').map_or(entry, |(path, _)| path).trim()
+    }
+
+    /// Warnings for `extra_classpath` entries and `java_agents` whose file doesn't exist
+    pub fn missing_launch_extras(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for entry in &self.extra_classpath {
+            if !PathBuf::from(entry.trim()).exists() {
+                warnings.push(format!("Extra classpath entry not found: {}", entry));
+            }
+        }
+        for entry in &self.java_agents {
+            if !PathBuf::from(Self::java_agent_path(entry)).is_file() {
+                warnings.push(format!("Java agent not found: {}", entry));
+            }
+        }
+        warnings
+    }
+
     /// See if the version_id contains a known mod loader identifier, and return it.
     pub fn get_loader_type(&self) -> Option<&str> {
         match self.version_id.as_str() {
@@ -2824,3 +3712,283 @@ pub struct ShaderPackInfo {
     /// true when the pack was found in the installation's disabled/ subfolder
     pub disabled: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("kable-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// A zip with the given (name, content) entries, names ending in '/' are folders
+    fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in entries {
+            let options = zip::write::FullFileOptions::default();
+            if name.ends_with('/') {
+                zip.add_directory(*name, options).unwrap();
+            } else {
+                zip.start_file(*name, options).unwrap();
+                zip.write_all(content.as_bytes()).unwrap();
+            }
+        }
+        zip.finish().unwrap();
+    }
+
+    fn extract_all(zip_path: &Path, dest_dir: &Path) {
+        let mut archive = zip::ZipArchive::new(File::open(zip_path).unwrap()).unwrap();
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).unwrap();
+            safe_extract(&mut entry, dest_dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn safe_entry_path_accepts_nested_names_with_either_separator() {
+        let dest = Path::new("/kable/mods/abc");
+        assert_eq!(
+            safe_entry_path("sodium.jar", dest),
+            Some(dest.join("sodium.jar"))
+        );
+        assert_eq!(
+            safe_entry_path("disabled/lithium.jar.disabled", dest),
+            Some(dest.join("disabled").join("lithium.jar.disabled"))
+        );
+        assert_eq!(
+            safe_entry_path("disabled\\lithium.jar", dest),
+            Some(dest.join("disabled").join("lithium.jar"))
+        );
+        assert_eq!(
+            safe_entry_path("./config//a.toml", dest),
+            Some(dest.join("config").join("a.toml"))
+        );
+        // Dots inside a name are not a parent reference
+        assert_eq!(
+            safe_entry_path("..hidden.jar", dest),
+            Some(dest.join("..hidden.jar"))
+        );
+    }
+
+    #[test]
+    fn safe_entry_path_rejects_malicious_names() {
+        let dest = Path::new("/kable/mods/abc");
+        for name in [
+            "../escape.jar",
+            "disabled/../../escape.jar",
+            "..\\escape.jar",
+            "disabled\\..\\..\\escape.jar",
+            "/etc/passwd",
+            "\\server\\share\\escape.jar",
+            "C:\\Windows\\escape.jar",
+            "C:/Windows/escape.jar",
+            "C:escape.jar",
+            "sodium.jar:stream",
+            "",
+            ".",
+        ] {
+            assert_eq!(safe_entry_path(name, dest), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn safe_extract_skips_malicious_entries() {
+        let dir = temp_dir();
+        let dest = dir.join("dest");
+        fs::create_dir_all(&dest).unwrap();
+        let zip_path = dir.join("mods.zip");
+        write_zip(
+            &zip_path,
+            &[
+                ("sodium.jar", "sodium"),
+                ("../escape.jar", "escape"),
+                ("..\\escape-backslash.jar", "escape"),
+                ("C:\\escape-drive.jar", "escape"),
+            ],
+        );
+        extract_all(&zip_path, &dest);
+
+        let mut names: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        let extracted = fs::read_dir(&dest).unwrap().count();
+        let sodium = fs::read_to_string(dest.join("sodium.jar")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(names, vec!["dest".to_string(), "mods.zip".to_string()]);
+        assert_eq!(extracted, 1);
+        assert_eq!(sodium, "sodium");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn safe_extract_does_not_follow_symlinked_folders_out_of_the_destination() {
+        let dir = temp_dir();
+        let dest = dir.join("dest");
+        let outside = dir.join("outside");
+        fs::create_dir_all(&dest).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, dest.join("link")).unwrap();
+        let zip_path = dir.join("mods.zip");
+        write_zip(
+            &zip_path,
+            &[
+                ("link/nested/escape.jar", "escape"),
+                ("link/escape.jar", "escape"),
+            ],
+        );
+        extract_all(&zip_path, &dest);
+
+        let outside_entries = fs::read_dir(&outside).unwrap().count();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(outside_entries, 0);
+    }
+
+    #[test]
+    fn mods_folder_round_trips_through_an_export() {
+        let dir = temp_dir();
+        let mods = dir.join("mods");
+        fs::create_dir_all(mods.join("disabled")).unwrap();
+        let files = [
+            "sodium.jar",
+            "lithium.jar.disabled",
+            "disabled/iris.jar",
+            "disabled/old.jar.disabled",
+        ];
+        for file in files {
+            fs::write(mods.join(file), file).unwrap();
+        }
+
+        let export_path = dir.join("export.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&export_path).unwrap());
+        let options = zip::write::FullFileOptions::default();
+        let mut progress = ExportProgressTracker::new("test", 0);
+        let sources = export_files(&mods);
+        add_folder_to_export(
+            &mut zip,
+            "mods.zip",
+            &mods,
+            &sources,
+            &options,
+            &mut progress,
+        )
+        .unwrap();
+        zip.finish().unwrap();
+
+        let mut export = ZipArchive::new(File::open(&export_path).unwrap()).unwrap();
+        let mut nested = Vec::new();
+        export
+            .by_name("mods.zip")
+            .unwrap()
+            .read_to_end(&mut nested)
+            .unwrap();
+        let mut archive = ZipArchive::new(std::io::Cursor::new(nested)).unwrap();
+        let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        names.sort();
+        let dest = dir.join("imported");
+        fs::create_dir_all(&dest).unwrap();
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).unwrap();
+            safe_extract(&mut entry, &dest).unwrap();
+        }
+        let contents: Vec<String> = files
+            .iter()
+            .map(|file| fs::read_to_string(dest.join(file)).unwrap_or_default())
+            .collect();
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut expected: Vec<String> = files.iter().map(|f| f.to_string()).collect();
+        expected.sort();
+        assert_eq!(names, expected);
+        assert_eq!(contents, files);
+    }
+
+    #[test]
+    fn config_overlay_restore_moves_new_configs_to_the_dedicated_folder() {
+        let root = temp_dir();
+        let config = root.join("config");
+        let dedicated = root.join("dedicated");
+        fs::create_dir_all(&config).unwrap();
+        fs::create_dir_all(&dedicated).unwrap();
+        fs::write(config.join("shared.toml"), "global").unwrap();
+        fs::write(config.join("sodium.json"), "global").unwrap();
+        fs::write(dedicated.join("sodium.json"), "dedicated").unwrap();
+
+        overlay_config_folder(&config, &dedicated).unwrap();
+        assert_eq!(
+            fs::read_to_string(config.join("sodium.json")).unwrap(),
+            "dedicated"
+        );
+        fs::create_dir_all(config.join("iris")).unwrap();
+        fs::write(config.join("iris").join("iris.properties"), "new").unwrap();
+        restore_config_overlay(&config).unwrap();
+
+        let read = |path: PathBuf| fs::read_to_string(path).ok();
+        let global = (
+            read(config.join("shared.toml")),
+            read(config.join("sodium.json")),
+            read(config.join("iris").join("iris.properties")),
+        );
+        let installation = (
+            read(dedicated.join("sodium.json")),
+            read(dedicated.join("iris").join("iris.properties")),
+        );
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(
+            global,
+            (Some("global".to_string()), Some("global".to_string()), None)
+        );
+        assert_eq!(
+            installation,
+            (Some("dedicated".to_string()), Some("new".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn mod_refs_only_cover_modrinth_versions() {
+        let mods = temp_dir();
+        fs::create_dir_all(mods.join("disabled")).unwrap();
+        for file in ["sodium.jar", "disabled/iris.jar", "jei.jar", "local.jar"] {
+            fs::write(mods.join(file), file).unwrap();
+        }
+        let record =
+            |provider, project_id: &str, version_id: Option<&str>| crate::mods::ModProvenance {
+                provider,
+                project_id: project_id.to_string(),
+                version_id: version_id.map(str::to_string),
+                version_number: None,
+                download_url: None,
+                downloaded_at: chrono::Utc::now().to_rfc3339(),
+            };
+        use crate::mods::ProviderKind::{CurseForge, Modrinth};
+        for (file, provenance) in [
+            ("sodium.jar", record(Modrinth, "AANobbMI", Some("v1"))),
+            ("iris.jar", record(Modrinth, "YL57xq9U", Some("v2"))),
+            ("jei.jar", record(CurseForge, "238222", Some("4712866"))),
+            ("local.jar", record(Modrinth, "unknown", None)),
+            ("deleted.jar", record(Modrinth, "P7dR8mSH", Some("v3"))),
+        ] {
+            crate::mods::record_mod_provenance(&mods, file, provenance)
+                .await
+                .unwrap();
+        }
+        let mut refs = export_mod_refs(&mods).await.unwrap();
+        let _ = fs::remove_dir_all(&mods);
+
+        refs.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+        let refs: Vec<(&str, &str, bool)> = refs
+            .iter()
+            .map(|r| (r.file_name.as_str(), r.version_id.as_str(), r.disabled))
+            .collect();
+        assert_eq!(
+            refs,
+            vec![("iris.jar", "v2", true), ("sodium.jar", "v1", false)]
+        );
+    }
+}
@@ -1,11 +1,33 @@
+pub mod curseforge_pack;
+pub mod custom_version;
+pub mod description;
+pub mod diff;
 pub mod kable_profiles;
 pub mod mrpack;
+pub mod options;
+pub mod prism;
 pub mod profiles;
+pub mod prune;
+pub mod search;
+pub mod stats;
+pub mod storage;
+pub mod templates;
 pub mod versions;
 
+pub use self::curseforge_pack::*;
+pub use self::custom_version::*;
+pub use self::description::*;
+pub use self::diff::*;
 pub use self::kable_profiles::*;
 pub use self::mrpack::*;
+pub use self::options::*;
+pub use self::prism::*;
 pub use self::profiles::*;
+pub use self::prune::*;
+pub use self::search::*;
+pub use self::stats::*;
+pub use self::storage::*;
+pub use self::templates::*;
 pub use self::versions::*;
 use once_cell::sync::Lazy;
 use std::fs::File;
@@ -31,7 +53,7 @@ async fn ensure_dedicated_mods_folder(
         Some(v) => v.loader != LoaderKind::Vanilla,
         None => false,
     };
-    if is_modded && installation.dedicated_mods_folder.is_none() {
+    if is_modded && installation.dedicated_mods_folder.is_none() && !installation.use_global_mods {
         // Set mods_folder to relative path format: "mods/{id}"
         installation.dedicated_mods_folder = Some(format!("mods/{}", installation.id));
         // Create .minecraft/.kable/mods/<id> if not exists
@@ -76,26 +98,26 @@ async fn build_installations_async() -> Result<Vec<KableInstallation>, String> {
 
     match profiles::read_launcher_profiles_async().await {
         Ok(launcher_profiles) => {
-            // Use a tuple of (name, last_version_id, created) for deduplication, all as String
-            let kable_keys: std::collections::HashSet<(String, String, String)> = installations
-                .iter()
-                .map(|i| (i.name.clone(), i.version_id.clone(), i.created.clone()))
-                .collect();
+            let mut profile_map = profiles::read_launcher_profile_map_async()
+                .await
+                .unwrap_or_else(|e| {
+                    crate::logging::Logger::warn_global(
+                        &format!("Failed to read launcher profile map, rebuilding it: {}", e),
+                        None,
+                    );
+                    profiles::LauncherProfileMap::new()
+                });
+            let map_len = profile_map.len();
 
             // IMPORTANT: Filter BEFORE converting to avoid expensive get_mods_folder_from_version_manifest() calls
-            let mut new_converted: Vec<KableInstallation> = launcher_profiles
+            let unconverted = profiles::select_unconverted_profiles(
+                launcher_profiles,
+                &installations,
+                &mut profile_map,
+            );
+            let mut new_converted: Vec<KableInstallation> = unconverted
                 .into_iter()
-                .filter(|lp| {
-                    let key = (
-                        lp.name.clone(),
-                        lp.last_version_id.clone(),
-                        lp.created
-                            .clone()
-                            .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
-                    );
-                    !kable_keys.contains(&key)
-                })
-                .map(|lp| lp.into())
+                .map(|(lp, id)| KableInstallation::from_launcher_profile(lp, id))
                 .collect();
 
             if !new_converted.is_empty() {
@@ -120,6 +142,16 @@ async fn build_installations_async() -> Result<Vec<KableInstallation>, String> {
                     }
                 }
             }
+
+            // Written after kable_profiles.json so a failed write can't map to missing installations
+            if profile_map.len() != map_len {
+                if let Err(e) = profiles::write_launcher_profile_map_async(&profile_map).await {
+                    crate::logging::Logger::warn_global(
+                        &format!("Failed to write launcher profile map: {}", e),
+                        None,
+                    );
+                }
+            }
         }
         Err(e) => {
             crate::logging::Logger::warn_global(
@@ -162,6 +194,29 @@ pub struct InstallationsComplete {
     pub total_count: usize,
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct InstallationsCacheInvalidated {
+    /// 'created' | 'modified' | 'deleted' | 'imported'
+    pub reason: String,
+    pub installation_id: String,
+}
+
+/// Tells the frontend the installations cache was replaced after a create/modify/delete,
+/// so it can refetch the whole list instead of patching single entries
+fn emit_cache_invalidated(reason: &str, installation_id: &str) {
+    if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
+        if let Some(app_handle) = handle_guard.as_ref() {
+            let _ = app_handle.emit(
+                "installations-cache-invalidated",
+                InstallationsCacheInvalidated {
+                    reason: reason.to_string(),
+                    installation_id: installation_id.to_string(),
+                },
+            );
+        }
+    }
+}
+
 // !NOTE: Public API:
 
 static VERSIONS_CACHE: Lazy<Arc<RwLock<Option<Versions>>>> =
@@ -272,6 +327,36 @@ pub async fn get_installation(id: &str) -> Result<Option<KableInstallation>, Str
     let installations = get_installations().await?;
     Ok(installations.into_iter().find(|i| i.id == id))
 }
+
+/// Finds an installation by name, ignoring case. An exact-case match wins when several
+/// installations only differ in case, otherwise several matches are an error listing them.
+pub async fn get_installation_by_name(name: &str) -> Result<Option<KableInstallation>, String> {
+    let name = name.trim();
+    let lowercase = name.to_lowercase();
+    let mut matches: Vec<KableInstallation> = get_installations()
+        .await?
+        .into_iter()
+        .filter(|i| i.name.trim().to_lowercase() == lowercase)
+        .collect();
+    if matches.len() > 1 {
+        let exact: Vec<usize> = (0..matches.len())
+            .filter(|&idx| matches[idx].name.trim() == name)
+            .collect();
+        if let [idx] = exact[..] {
+            return Ok(Some(matches.swap_remove(idx)));
+        }
+        let candidates: Vec<String> = matches
+            .iter()
+            .map(|i| format!("'{}' ({})", i.name, i.id))
+            .collect();
+        return Err(format!(
+            "Several installations are named '{}': {}",
+            name,
+            candidates.join(", ")
+        ));
+    }
+    Ok(matches.pop())
+}
 /// Deletes a KableInstallation by ID from kable_profiles.json and invalidates cache
 pub async fn delete_installation(id: &str) -> Result<(), String> {
     let mut installations = kable_profiles::read_kable_profiles_async().await?;
@@ -301,7 +386,28 @@ pub async fn delete_installation(id: &str) -> Result<(), String> {
     }
 
     // Also remove from launcher_profiles.json if it exists there (to prevent re-import)
-    if let Some((name, version_id, created, _, _, _, _)) = &deletion_data {
+    let mut profile_map = profiles::read_launcher_profile_map_async()
+        .await
+        .unwrap_or_default();
+    let mapped_key = profile_map
+        .iter()
+        .find(|(_, installation_id)| installation_id.as_str() == id)
+        .map(|(key, _)| key.clone());
+    if let Some(key) = mapped_key {
+        if let Err(e) = profiles::remove_launcher_profile_by_key(&key).await {
+            crate::logging::Logger::warn_global(
+                &format!("Failed to remove from launcher_profiles.json: {}", e),
+                None,
+            );
+        }
+        profile_map.remove(&key);
+        if let Err(e) = profiles::write_launcher_profile_map_async(&profile_map).await {
+            crate::logging::Logger::warn_global(
+                &format!("Failed to write launcher profile map: {}", e),
+                None,
+            );
+        }
+    } else if let Some((name, version_id, created, _, _, _, _)) = &deletion_data {
         if let Err(e) = profiles::remove_launcher_profile_by_match(name, version_id, created).await
         {
             crate::logging::Logger::warn_global(
@@ -380,6 +486,7 @@ pub async fn delete_installation(id: &str) -> Result<(), String> {
         let mut cache_write = INSTALLATIONS_CACHE.write().await;
         *cache_write = Some(installations.clone());
     }
+    emit_cache_invalidated("deleted", id);
     match &result {
         Ok(_) => crate::logging::info(&format!("Installation '{}' deleted successfully.", id)),
         Err(e) => crate::logging::error(&format!("Failed to delete installation '{}': {}", id, e)),
@@ -392,6 +499,11 @@ pub async fn modify_installation(
     id: &str,
     mut new_installation: KableInstallation,
 ) -> Result<(), String> {
+    new_installation.connect_server_address()?;
+    new_installation.native_arch_override_tag()?;
+    new_installation.description_format_kind()?;
+    new_installation.proxy_urls()?;
+    new_installation.validate_behavior_overrides()?;
     let mut installations = kable_profiles::read_kable_profiles_async().await?;
     let index = installations.iter().position(|i| i.id == id);
     if let Some(index) = index {
@@ -403,6 +515,7 @@ pub async fn modify_installation(
             let mut cache_write = INSTALLATIONS_CACHE.write().await;
             *cache_write = Some(installations.clone());
         }
+        emit_cache_invalidated("modified", id);
         match &result {
             Ok(_) => {
                 crate::logging::info(&format!("Installation '{}' modified successfully.", id));
@@ -436,6 +549,16 @@ pub async fn modify_installation(
 
 /// Creates a new KableInstallation with the given version_id, using default settings for other fields and invalidates cache
 pub async fn create_installation(version_id: &str) -> Result<KableInstallation, String> {
+    let versions = get_versions().await;
+    let version_data = versions
+        .get_version(version_id)
+        .cloned()
+        .ok_or_else(|| format!("No version found for id: {}", version_id))?;
+    add_installation_for_version(&version_data.version_id).await
+}
+
+/// Adds an installation of a version that is known to exist, named after the version
+async fn add_installation_for_version(version_id: &str) -> Result<KableInstallation, String> {
     let mut installations = kable_profiles::read_kable_profiles_async().await?;
     // Generate a default name (e.g., based on version_id and count)
     let base_name = version_id.to_string();
@@ -445,18 +568,13 @@ pub async fn create_installation(version_id: &str) -> Result<KableInstallation,
         name = format!("{}-{}", base_name, count);
         count += 1;
     }
-    let versions = get_versions().await;
-    let version_data = versions
-        .get_version(version_id)
-        .cloned()
-        .ok_or_else(|| format!("No version found for id: {}", version_id))?;
     crate::logging::info(&format!(
         "Creating new installation: name='{}', version_id='{}'",
-        name, version_data.version_id
+        name, version_id
     ));
     let mut new_installation = KableInstallation {
         name,
-        version_id: version_data.version_id.clone(),
+        version_id: version_id.to_string(),
         ..Default::default()
     };
     // Ensure dedicated mods folder if needed
@@ -467,6 +585,7 @@ pub async fn create_installation(version_id: &str) -> Result<KableInstallation,
         let mut cache_write = INSTALLATIONS_CACHE.write().await;
         *cache_write = Some(installations.clone());
     }
+    emit_cache_invalidated("created", &new_installation.id);
     match &result {
         Ok(_) => crate::logging::info(&format!(
             "Installation '{}' created successfully.",
@@ -481,6 +600,84 @@ pub async fn create_installation(version_id: &str) -> Result<KableInstallation,
     Ok(new_installation)
 }
 
+/// Emitted before each file of `import_installations` is imported
+pub const IMPORT_PROGRESS_EVENT: &str = "import-progress";
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ImportProgress {
+    /// Files imported so far, failed ones included
+    pub current: usize,
+    pub total: usize,
+    /// File that is imported next
+    pub path: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ImportFileResult {
+    pub path: String,
+    /// Id of the created installation, None when the import failed
+    pub installation_id: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct BatchImportReport {
+    /// One result per path, in the order they were given
+    pub results: Vec<ImportFileResult>,
+    pub installations: Vec<KableInstallation>,
+}
+
+/// Imports several export zips one after another, a file that fails is reported and the
+/// rest is still imported. The installations cache is rebuilt once at the end.
+pub async fn import_installations(paths: &[String]) -> Result<BatchImportReport, String> {
+    let mut report = BatchImportReport::default();
+    for (current, path) in paths.iter().enumerate() {
+        if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
+            if let Some(app_handle) = handle_guard.as_ref() {
+                let _ = app_handle.emit(
+                    IMPORT_PROGRESS_EVENT,
+                    ImportProgress {
+                        current,
+                        total: paths.len(),
+                        path: path.clone(),
+                    },
+                );
+            }
+        }
+        match KableInstallation::import(path).await {
+            Ok(installation) => {
+                report.results.push(ImportFileResult {
+                    path: path.clone(),
+                    installation_id: Some(installation.id.clone()),
+                    error: None,
+                });
+                report.installations.push(installation);
+            }
+            Err(e) => {
+                crate::logging::Logger::warn_global(
+                    &format!("Failed to import {}: {}", path, e),
+                    None,
+                );
+                report.results.push(ImportFileResult {
+                    path: path.clone(),
+                    installation_id: None,
+                    error: Some(e),
+                });
+            }
+        }
+    }
+    if !report.installations.is_empty() {
+        get_installations_force().await?;
+        emit_cache_invalidated("imported", &report.installations[0].id);
+    }
+    crate::logging::info(&format!(
+        "Imported {} of {} installation(s)",
+        report.installations.len(),
+        paths.len()
+    ));
+    Ok(report)
+}
+
 /// Creates a new KableInstallation by copying from an existing one
 /// Optionally copies mods (with version updates), resource packs, and shaders
 pub async fn create_installation_from_existing(
@@ -489,6 +686,26 @@ pub async fn create_installation_from_existing(
     copy_mods: bool,
     copy_resource_packs: bool,
     copy_shaders: bool,
+) -> Result<KableInstallation, String> {
+    create_installation_from_existing_with_progress(
+        version_id,
+        source_installation_id,
+        copy_mods,
+        copy_resource_packs,
+        copy_shaders,
+        &crate::launcher::ProgressReporter::none(),
+    )
+    .await
+}
+
+/// `create_installation_from_existing` reporting the mod updates to `progress`
+pub async fn create_installation_from_existing_with_progress(
+    version_id: &str,
+    source_installation_id: &str,
+    copy_mods: bool,
+    copy_resource_packs: bool,
+    copy_shaders: bool,
+    progress: &crate::launcher::ProgressReporter,
 ) -> Result<KableInstallation, String> {
     // First create the base installation
     let new_installation = create_installation(version_id).await?;
@@ -509,7 +726,18 @@ pub async fn create_installation_from_existing(
 
     // Copy mods if requested
     if copy_mods {
-        copy_and_update_mods(&source_installation, &new_installation).await?;
+        let report =
+            copy_and_update_mods(&source_installation, &new_installation, progress).await?;
+        if !report.incompatible.is_empty() {
+            crate::logging::Logger::warn_global(
+                &format!(
+                    "Disabled {} mods without a compatible version: {}",
+                    report.incompatible.len(),
+                    report.incompatible.join(", ")
+                ),
+                Some(&new_installation.id),
+            );
+        }
     }
 
     // Copy resource packs if requested
@@ -525,14 +753,537 @@ pub async fn create_installation_from_existing(
     Ok(new_installation)
 }
 
+/// What happens to the jars in .minecraft/mods when an installation switches to a dedicated folder
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModsTransfer {
+    /// Leave them, the dedicated folder keeps what it has
+    #[default]
+    None,
+    Copy,
+    /// Copy them and remove them from .minecraft/mods
+    Move,
+}
+
+/// Switches an installation between a dedicated mods folder and the global .minecraft/mods.
+/// Switching to global leaves the dedicated folder on disk so switching back restores it.
+pub async fn set_mods_folder_mode(
+    installation_id: &str,
+    dedicated: bool,
+    transfer: ModsTransfer,
+) -> Result<KableInstallation, String> {
+    let mut installation = get_installation(installation_id)
+        .await?
+        .ok_or_else(|| format!("Installation with id '{}' not found", installation_id))?;
+
+    if !dedicated {
+        // dedicated_mods_folder is kept, switching back uses the same (possibly relocated) folder
+        installation.use_global_mods = true;
+        modify_installation(&installation.id, installation.clone()).await?;
+        crate::logging::info(&format!(
+            "Installation '{}' now uses the global mods folder",
+            installation.name
+        ));
+        return Ok(installation);
+    }
+
+    installation.use_global_mods = false;
+    let folder = installation
+        .dedicated_mods_folder
+        .get_or_insert_with(|| format!("mods/{}", installation.id));
+    // find_mods_dir falls back to .minecraft/mods while the dedicated folder doesn't exist yet
+    let mods_dir = storage::dedicated_path(&crate::get_minecraft_kable_dir()?, "mods", folder);
+    crate::ensure_folder(&mods_dir)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", mods_dir.display(), e))?;
+    let global_dir = crate::get_default_minecraft_dir()?.join("mods");
+    if transfer != ModsTransfer::None && global_dir.is_dir() && global_dir != mods_dir {
+        let provenance = crate::mods::load_mod_provenance(&global_dir).await?;
+        let mut moved = Vec::new();
+        let mut entries = async_fs::read_dir(&global_dir)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", global_dir.display(), e))?;
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if !path.is_file() || path.extension().is_none_or(|ext| ext != "jar") {
+                continue;
+            }
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            async_fs::copy(&path, mods_dir.join(&file_name))
+                .await
+                .map_err(|e| format!("Failed to copy {}: {}", file_name, e))?;
+            if let Some(record) = provenance.get(&file_name) {
+                crate::mods::record_mod_provenance(&mods_dir, &file_name, record.clone()).await?;
+            }
+            if transfer == ModsTransfer::Move {
+                async_fs::remove_file(&path)
+                    .await
+                    .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+                moved.push(file_name);
+            }
+        }
+        if !moved.is_empty() {
+            crate::mods::forget_mod_provenance(&global_dir, &moved).await?;
+        }
+    }
+    modify_installation(&installation.id, installation.clone()).await?;
+    crate::logging::info(&format!(
+        "Installation '{}' now uses the dedicated mods folder {}",
+        installation.name,
+        mods_dir.display()
+    ));
+    Ok(installation)
+}
+
+/// Dedicated folder of an installation that `relocate_installation_folder` moves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FolderKind {
+    Mods,
+    Resourcepacks,
+    Shaders,
+    Config,
+}
+
+impl FolderKind {
+    /// Folder in the kable dir that relative dedicated folders are stored under
+    fn category(self) -> &'static str {
+        match self {
+            FolderKind::Mods => "mods",
+            FolderKind::Resourcepacks => "resourcepacks",
+            FolderKind::Shaders => "shaderpacks",
+            FolderKind::Config => "config",
+        }
+    }
+
+    fn folder(self, installation: &mut KableInstallation) -> &mut Option<String> {
+        match self {
+            FolderKind::Mods => &mut installation.dedicated_mods_folder,
+            FolderKind::Resourcepacks => &mut installation.dedicated_resource_pack_folder,
+            FolderKind::Shaders => &mut installation.dedicated_shaders_folder,
+            FolderKind::Config => &mut installation.dedicated_config_folder,
+        }
+    }
+}
+
+/// Moves a dedicated folder of an installation to an absolute path, e.g. on another drive, and
+/// stores the new path. The destination has to be missing or empty. Links in .minecraft that
+/// point into the old folder are made again for the new one.
+pub async fn relocate_installation_folder(
+    installation_id: &str,
+    which: FolderKind,
+    new_path: &str,
+) -> Result<KableInstallation, String> {
+    let destination = PathBuf::from(new_path);
+    if !destination.is_absolute() {
+        return Err(format!("'{}' is not an absolute path", new_path));
+    }
+    if crate::launcher::is_installation_running(installation_id).await? {
+        return Err("Close the game before moving its folders".to_string());
+    }
+    let mut installation = get_installation(installation_id)
+        .await?
+        .ok_or_else(|| format!("Installation with id '{}' not found", installation_id))?;
+    if which == FolderKind::Mods && installation.use_global_mods {
+        return Err(format!(
+            "Installation '{}' uses the global mods folder",
+            installation.name
+        ));
+    }
+    let kable_dir = crate::get_minecraft_kable_dir()?;
+    let source = which
+        .folder(&mut installation)
+        .as_deref()
+        .filter(|folder| !folder.is_empty())
+        .map(|folder| storage::dedicated_path(&kable_dir, which.category(), folder))
+        .ok_or_else(|| {
+            format!(
+                "Installation '{}' has no dedicated {} folder",
+                installation.name,
+                which.category()
+            )
+        })?;
+    if source == destination {
+        return Ok(installation);
+    }
+    if destination.starts_with(&source) {
+        return Err("The new location can't be inside the current folder".to_string());
+    }
+    if destination.exists() {
+        let mut entries = async_fs::read_dir(&destination)
+            .await
+            .map_err(|e| format!("{} is not an empty folder: {}", destination.display(), e))?;
+        if entries.next_entry().await.ok().flatten().is_some() {
+            return Err(format!("{} is not empty", destination.display()));
+        }
+    }
+
+    // Fail before moving anything when the destination can't be written to
+    async_fs::create_dir_all(&destination)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", destination.display(), e))?;
+    let probe = destination.join(".kable_write_test");
+    async_fs::write(&probe, b"")
+        .await
+        .map_err(|e| format!("{} is not writable: {}", destination.display(), e))?;
+    let _ = async_fs::remove_file(&probe).await;
+
+    // Links still pointing into the folder would dangle once it is moved
+    let minecraft_dir = crate::get_default_minecraft_dir()?;
+    let relink_managed = crate::symlink_manager::list_managed_symlinks()
+        .await?
+        .iter()
+        .any(|link| std::path::Path::new(&link.source).starts_with(&source));
+    let config_link = minecraft_dir.join("config");
+    let relink_config = async_fs::read_link(&config_link)
+        .await
+        .is_ok_and(|target| target.starts_with(&source));
+    if relink_config {
+        crate::remove_symlink_if_exists(&config_link).await?;
+    }
+
+    if source.exists() {
+        // Renaming onto the empty destination fails on Windows, so it is removed first
+        let _ = async_fs::remove_dir(&destination).await;
+        if async_fs::rename(&source, &destination).await.is_err() {
+            // Another drive, copy and remove the original once everything is copied
+            crate::copy_dir_recursive(&source, &destination).await?;
+            async_fs::remove_dir_all(&source)
+                .await
+                .map_err(|e| format!("Failed to remove {}: {}", source.display(), e))?;
+        }
+    }
+
+    *which.folder(&mut installation) = Some(destination.to_string_lossy().to_string());
+    modify_installation(&installation.id, installation.clone()).await?;
+    crate::logging::info(&format!(
+        "Moved the {} folder of '{}' from {} to {}",
+        which.category(),
+        installation.name,
+        source.display(),
+        destination.display()
+    ));
+
+    if relink_managed {
+        crate::symlink_manager::SymlinkManager::new(minecraft_dir)
+            .setup_for_installation(&installation.id)
+            .await
+            .map_err(|e| format!("Moved the folder, but failed to link it again: {}", e))?;
+    }
+    if relink_config {
+        installation.setup_config_folder().await.map_err(|e| {
+            format!(
+                "Moved the folder, but failed to link the configs again: {}",
+                e
+            )
+        })?;
+    }
+    Ok(installation)
+}
+
+/// What `reset_installation_settings` puts back to its default
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResetScope {
+    /// The default JVM flags of a new installation
+    JavaArgs,
+    /// Clear parameters_map
+    Parameters,
+    /// Window size and fullscreen overrides, the server to join and quick play arguments
+    WindowAndQuickPlay,
+    All,
+}
+
+/// parameters_map keys of the window and of joining a world or server
+const WINDOW_AND_QUICK_PLAY_PARAMETERS: &[&str] = &[
+    "resolution_width",
+    "resolution_height",
+    "--width",
+    "--height",
+    "--fullscreen",
+    "--server",
+    "--port",
+];
+
+/// Resets launch settings of an installation that may have been broken by hand.
+/// Name, version and content (mods, packs, configs, worlds) are left alone.
+pub async fn reset_installation_settings(
+    id: &str,
+    scope: ResetScope,
+) -> Result<KableInstallation, String> {
+    let mut installation = get_installation(id)
+        .await?
+        .ok_or_else(|| format!("Installation with id '{}' not found", id))?;
+    let all = scope == ResetScope::All;
+    if all || scope == ResetScope::JavaArgs {
+        installation.java_args = KableInstallation::default().java_args;
+    }
+    if all || scope == ResetScope::Parameters {
+        installation.parameters_map.clear();
+    }
+    if all || scope == ResetScope::WindowAndQuickPlay {
+        installation.connect_server = None;
+        installation.parameters_map.retain(|key, _| {
+            !WINDOW_AND_QUICK_PLAY_PARAMETERS.contains(&key.as_str())
+                && !key.starts_with("--quickPlay")
+        });
+    }
+    modify_installation(&installation.id, installation.clone()).await?;
+    crate::logging::info(&format!(
+        "Reset {:?} of installation '{}'",
+        scope, installation.name
+    ));
+    Ok(installation)
+}
+
+/// Launch variables Kable fills in itself, overriding them breaks the launch
+const PROTECTED_VARIABLES: &[&str] = &[
+    "classpath",
+    "classpath_separator",
+    "game_directory",
+    "natives_directory",
+    "library_directory",
+    "assets_root",
+    "assets_index_name",
+    "version_name",
+];
+
+/// Game arguments carrying the same values as the protected variables
+const PROTECTED_GAME_ARGS: &[&str] = &[
+    "--username",
+    "--uuid",
+    "--accessToken",
+    "--userType",
+    "--xuid",
+    "--clientId",
+    "--gameDir",
+    "--assetsDir",
+    "--assetIndex",
+    "--version",
+];
+
+/// Whether overriding the launch variable breaks the launch or the login, `auth_*` included
+pub fn is_protected_variable(key: &str) -> bool {
+    PROTECTED_VARIABLES.contains(&key) || key.starts_with("auth_")
+}
+
+/// Keys starting with `--` are extra game arguments, others override a launch variable
+fn validate_parameter_key(key: &str) -> Result<(), String> {
+    if key.is_empty() || key.contains(char::is_whitespace) {
+        return Err(format!("Invalid parameter name '{}'", key));
+    }
+    if key.starts_with("--") {
+        if key.len() == 2 {
+            return Err("Game argument '--' has no name".to_string());
+        }
+        if PROTECTED_GAME_ARGS.contains(&key) {
+            return Err(format!(
+                "{} is set by the launcher and can't be overridden",
+                key
+            ));
+        }
+        return Ok(());
+    }
+    if key.starts_with('-') {
+        return Err(format!(
+            "'{}' looks like a JVM flag, add it to the Java arguments instead",
+            key
+        ));
+    }
+    if is_protected_variable(key) {
+        return Err(format!(
+            "${{{}}} is set by the launcher and can't be overridden",
+            key
+        ));
+    }
+    Ok(())
+}
+
+/// Sets one entry of parameters_map: `--key` adds a game argument, other keys override the
+/// launch variable of that name
+pub async fn set_installation_parameter(
+    id: &str,
+    key: &str,
+    value: &str,
+) -> Result<KableInstallation, String> {
+    let key = key.trim();
+    validate_parameter_key(key)?;
+    let mut installation = get_installation(id)
+        .await?
+        .ok_or_else(|| format!("Installation with id '{}' not found", id))?;
+    installation
+        .parameters_map
+        .insert(key.to_string(), value.to_string());
+    modify_installation(&installation.id, installation.clone()).await?;
+    Ok(installation)
+}
+
+pub async fn remove_installation_parameter(
+    id: &str,
+    key: &str,
+) -> Result<KableInstallation, String> {
+    let mut installation = get_installation(id)
+        .await?
+        .ok_or_else(|| format!("Installation with id '{}' not found", id))?;
+    if installation.parameters_map.remove(key).is_none() {
+        return Err(format!(
+            "Installation '{}' has no parameter '{}'",
+            installation.name, key
+        ));
+    }
+    modify_installation(&installation.id, installation.clone()).await?;
+    Ok(installation)
+}
+
+/// Jars of a mods folder update, by file name in the source folder
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ModUpdateReport {
+    /// Replaced by a version for the target Minecraft version and loader
+    pub updated: Vec<String>,
+    /// No compatible version found, copied as they were into the disabled folder
+    pub incompatible: Vec<String>,
+}
+
+/// Outcome of `migrate_installation_version`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct VersionMigrationReport {
+    pub installation: KableInstallation,
+    /// Where the mods folder was backed up before updating, None when mods weren't updated
+    pub backup_dir: Option<String>,
+    pub mods: ModUpdateReport,
+}
+
+/// Moves an installation to another Minecraft version in place.
+/// With `update_mods` its mods folder is backed up to `mods-backups` in the kable dir and every
+/// jar is replaced by a version for the new Minecraft version and loader where there is one.
+pub async fn migrate_installation_version(
+    id: &str,
+    new_version_id: &str,
+    update_mods: bool,
+) -> Result<VersionMigrationReport, String> {
+    let mut installation = get_installation(id)
+        .await?
+        .ok_or_else(|| format!("No Kable installation found with id: {}", id))?;
+    let version_data = get_versions()
+        .await
+        .get_version(new_version_id)
+        .cloned()
+        .ok_or_else(|| format!("No version found for id: {}", new_version_id))?;
+    // A shared mods folder belongs to other installations too
+    if update_mods && installation.dedicated_mods_folder.is_none() {
+        return Err(format!(
+            "Installation '{}' has no dedicated mods folder, its mods can't be updated",
+            installation.name
+        ));
+    }
+
+    let old_version_id = std::mem::replace(&mut installation.version_id, version_data.version_id);
+    let _ = ensure_dedicated_mods_folder(&mut installation).await?;
+
+    let mut backup_dir = None;
+    let mut mods = ModUpdateReport::default();
+    if update_mods {
+        let mods_dir = installation.find_mods_dir()?;
+        let backup = crate::get_minecraft_kable_dir()?
+            .join("mods-backups")
+            .join(format!(
+                "{}-{}",
+                installation.id,
+                chrono::Utc::now().format("%Y%m%d-%H%M%S")
+            ));
+        crate::copy_dir_recursive(&mods_dir, &backup).await?;
+        crate::logging::info(&format!(
+            "Backed up mods of '{}' to {}",
+            installation.name,
+            backup.display()
+        ));
+
+        // The jars are put back from the backup, updated where possible
+        let updated = async {
+            remove_top_level_mods(&mods_dir).await?;
+            update_mods_into(
+                &backup,
+                &mods_dir,
+                &installation.version_id,
+                &crate::launcher::ProgressReporter::none(),
+            )
+            .await
+        }
+        .await;
+        mods = match updated {
+            Ok(mods) => mods,
+            Err(e) => {
+                // The installation wasn't saved yet, so only the folder has to be put back
+                let restored = async {
+                    remove_top_level_mods(&mods_dir).await?;
+                    crate::copy_dir_recursive(&backup, &mods_dir).await
+                }
+                .await;
+                return Err(match restored {
+                    Ok(()) => format!(
+                        "Failed to update mods, they were restored from the backup at {}: {}",
+                        backup.display(),
+                        e
+                    ),
+                    Err(restore_error) => format!(
+                        "Failed to update mods: {}. Restoring them failed too ({}), the original mods are in {}",
+                        e,
+                        restore_error,
+                        backup.display()
+                    ),
+                });
+            }
+        };
+        backup_dir = Some(backup.to_string_lossy().to_string());
+    }
+
+    modify_installation(&installation.id, installation.clone()).await?;
+    crate::logging::info(&format!(
+        "Migrated '{}' from {} to {} ({} mods updated, {} incompatible)",
+        installation.name,
+        old_version_id,
+        installation.version_id,
+        mods.updated.len(),
+        mods.incompatible.len()
+    ));
+    Ok(VersionMigrationReport {
+        installation,
+        backup_dir,
+        mods,
+    })
+}
+
+/// Removes the jars directly in a mods folder and their provenance entries, disabled/ is left alone
+async fn remove_top_level_mods(mods_dir: &std::path::Path) -> Result<(), String> {
+    let mut entries = async_fs::read_dir(mods_dir)
+        .await
+        .map_err(|e| format!("Failed to read mods directory: {}", e))?;
+    let mut removed = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| format!("Failed to read directory entry: {}", e))?
+    {
+        let path = entry.path();
+        if matches!(path.extension(), Some(ext) if ext == "jar") {
+            async_fs::remove_file(&path)
+                .await
+                .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+            removed.push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+    // A disabled jar with the same file name shares the entry
+    removed.retain(|name| !mods_dir.join("disabled").join(name).exists());
+    crate::mods::forget_mod_provenance(mods_dir, &removed).await
+}
+
 /// Copy and update mods from source to target installation
 /// Uses Modrinth API to find compatible versions for the target MC version and loader
 async fn copy_and_update_mods(
     source: &KableInstallation,
     target: &KableInstallation,
-) -> Result<(), String> {
-    use crate::mods::modrinth;
-
+    progress: &crate::launcher::ProgressReporter,
+) -> Result<ModUpdateReport, String> {
     crate::logging::info(&format!(
         "Copying and updating mods from '{}' to '{}'",
         source.name, target.name
@@ -541,399 +1292,450 @@ async fn copy_and_update_mods(
     // Reuse centralized mods directory resolution on KableInstallation.
     let source_mods_dir = source.find_mods_dir()?;
     let target_mods_dir = target.find_mods_dir()?;
+    update_mods_into(
+        &source_mods_dir,
+        &target_mods_dir,
+        &target.version_id,
+        progress,
+    )
+    .await
+}
+
+/// How many mods `update_mods_into` resolves or downloads at once
+const MOD_UPDATE_CONCURRENCY: usize = 8;
+
+/// A jar of the source folder and the version that replaces it, None to copy it as it is
+struct ModUpdatePlan {
+    path: PathBuf,
+    file_name: String,
+    provenance: Option<crate::mods::ModProvenance>,
+    update: Option<(String, crate::mods::modrinth::ModrinthVersion)>,
+}
+
+/// Puts a version of every jar in `source_mods_dir` that fits `target_version_id` into
+/// `target_mods_dir`, jars without one go to its disabled folder unchanged.
+/// Versions are looked up and downloaded concurrently, reported as the "mods" and "mod_downloads" stages.
+async fn update_mods_into(
+    source_mods_dir: &std::path::Path,
+    target_mods_dir: &std::path::Path,
+    target_version_id: &str,
+    progress: &crate::launcher::ProgressReporter,
+) -> Result<ModUpdateReport, String> {
+    let mut report = ModUpdateReport::default();
 
     // Read all mods from source
-    let source_mod_files = async_fs::read_dir(&source_mods_dir)
+    let mut read_dir = async_fs::read_dir(source_mods_dir)
         .await
         .map_err(|e| format!("Failed to read source mods directory: {}", e))?;
-
-    let mut entries = vec![];
-    let mut read_dir = source_mod_files;
+    let mut jars = vec![];
     while let Some(entry) = read_dir
         .next_entry()
         .await
         .map_err(|e| format!("Failed to read directory entry: {}", e))?
     {
-        entries.push(entry);
+        let path = entry.path();
+        // Skip if not a .jar file
+        if !matches!(path.extension(), Some(ext) if ext == "jar") {
+            continue;
+        }
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| "Invalid file name".to_string())?
+            .to_string();
+        jars.push((path, file_name));
     }
 
+    // Where each source jar came from, so mods don't need to be matched by name
+    let source_provenance = crate::mods::load_mod_provenance(source_mods_dir)
+        .await
+        .unwrap_or_default();
+
     // Extract loader and game version from target installation
-    let target_loader = extract_loader_from_version_id(&target.version_id);
-    let target_game_version = extract_game_version_from_version_id(&target.version_id);
+    let target_loader = extract_loader_from_version_id(target_version_id);
+    let target_game_version = extract_game_version_from_version_id(target_version_id);
 
     crate::logging::info(&format!(
         "Target: loader={:?}, game_version={:?}",
         target_loader, target_game_version
     ));
 
-    // Process each mod file
-    for entry in entries {
-        let path = entry.path();
-
-        // Skip if not a .jar file
-        if !matches!(path.extension(), Some(ext) if ext == "jar") {
-            continue;
-        }
-
-        let file_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .ok_or_else(|| "Invalid file name".to_string())?;
-
-        crate::logging::info(&format!("Processing mod: {}", file_name));
-
-        // PRIORITY 1: Check for kable_metadata.json file
-        let metadata_file = source_mods_dir.join(format!("{}.kable_metadata.json", file_name));
-        let project_id_from_metadata = if metadata_file.exists() {
-            match async_fs::read_to_string(&metadata_file).await {
-                Ok(content) => match serde_json::from_str::<serde_json::Value>(&content) {
-                    Ok(metadata) => {
-                        let project_id = metadata
-                            .get("project_id")
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string());
-                        if let Some(ref id) = project_id {
-                            crate::logging::info(&format!(
-                                "Found metadata for {}: project_id = {}",
-                                file_name, id
-                            ));
-                        }
-                        project_id
-                    }
-                    Err(e) => {
-                        crate::logging::Logger::warn_global(
-                            &format!("Failed to parse metadata for {}: {}", file_name, e),
-                            None,
-                        );
-                        None
-                    }
-                },
-                Err(_) => None,
+    // Resolve the compatible version of every jar
+    let total = jars.len() as u64;
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(MOD_UPDATE_CONCURRENCY));
+    let mut resolving = tokio::task::JoinSet::new();
+    for (path, file_name) in jars {
+        let provenance = source_provenance.get(&file_name).cloned();
+        let (semaphore, loader, game_version) = (
+            semaphore.clone(),
+            target_loader.clone(),
+            target_game_version.clone(),
+        );
+        resolving.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let update = resolve_mod_update(
+                &path,
+                &file_name,
+                provenance.as_ref(),
+                loader.as_deref(),
+                game_version.as_deref(),
+            )
+            .await;
+            ModUpdatePlan {
+                path,
+                file_name,
+                provenance,
+                update,
             }
-        } else {
-            None
-        };
+        });
+    }
+    let mut plans = Vec::new();
+    progress.report("mods", 0, Some(total), Some("Finding compatible versions"));
+    while let Some(joined) = resolving.join_next().await {
+        let plan = joined.map_err(|e| format!("Mod resolve task failed: {}", e))?;
+        plans.push(plan);
+        progress.report(
+            "mods",
+            plans.len() as u64,
+            Some(total),
+            plans.last().map(|p| p.file_name.as_str()),
+        );
+    }
 
-        // PRIORITY 2: Try to get mod info from JAR if no metadata
-        let mod_info = if project_id_from_metadata.is_none() {
-            match get_mod_info_single(&path).await {
-                Ok(Some(info)) => Some(info),
-                Ok(None) => {
-                    // Can't read mod info, move to disabled folder
-                    let disabled_dir = target_mods_dir.join("disabled");
-                    crate::ensure_folder_sync(&disabled_dir)
-                        .map_err(|e| format!("Failed to create disabled directory: {}", e))?;
-                    let target_path = disabled_dir.join(file_name);
-                    crate::logging::Logger::warn_global(
-                        &format!(
-                            "Could not read mod info from {}, moving to disabled folder",
-                            file_name
-                        ),
-                        None,
-                    );
-                    async_fs::copy(&path, &target_path).await.map_err(|e| {
-                        format!("Failed to copy mod file to disabled folder: {}", e)
-                    })?;
-                    // Copy metadata file if it exists
-                    if metadata_file.exists() {
-                        let metadata_target =
-                            disabled_dir.join(format!("{}.kable_metadata.json", file_name));
-                        let _ = async_fs::copy(&metadata_file, &metadata_target).await;
-                    }
-                    continue;
-                }
-                Err(e) => {
-                    // Error reading mod, move to disabled folder
-                    let disabled_dir = target_mods_dir.join("disabled");
-                    crate::ensure_folder_sync(&disabled_dir)
-                        .map_err(|e| format!("Failed to create disabled directory: {}", e))?;
-                    let target_path = disabled_dir.join(file_name);
-                    crate::logging::Logger::warn_global(
-                        &format!(
-                            "Error reading mod {}: {}, moving to disabled folder",
-                            file_name, e
-                        ),
-                        None,
-                    );
-                    async_fs::copy(&path, &target_path).await.map_err(|e| {
-                        format!("Failed to copy mod file to disabled folder: {}", e)
-                    })?;
-                    // Copy metadata file if it exists
-                    if metadata_file.exists() {
-                        let metadata_target =
-                            disabled_dir.join(format!("{}.kable_metadata.json", file_name));
-                        let _ = async_fs::copy(&metadata_file, &metadata_target).await;
-                    }
-                    continue;
+    // Download the compatible versions
+    let (to_download, mut to_copy): (Vec<_>, Vec<_>) =
+        plans.into_iter().partition(|p| p.update.is_some());
+    let download_total = to_download.len() as u64;
+    let mut downloading = tokio::task::JoinSet::new();
+    for plan in to_download {
+        let semaphore = semaphore.clone();
+        let target_mods_dir = target_mods_dir.to_path_buf();
+        downloading.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = download_mod_update(&plan, &target_mods_dir).await;
+            (plan, result)
+        });
+    }
+    let mut downloaded = 0;
+    progress.report("mod_downloads", 0, Some(download_total), None);
+    while let Some(joined) = downloading.join_next().await {
+        let (plan, result) = joined.map_err(|e| format!("Mod download task failed: {}", e))?;
+        downloaded += 1;
+        progress.report(
+            "mod_downloads",
+            downloaded,
+            Some(download_total),
+            Some(plan.file_name.as_str()),
+        );
+        match result {
+            Ok((saved_as, url)) => {
+                // Save metadata to track this mod properly, one at a time as they share a file
+                if let Some((project_id, version)) = &plan.update {
+                    crate::mods::modrinth::save_mod_metadata(
+                        target_mods_dir,
+                        &saved_as,
+                        project_id,
+                        &version.version_number,
+                        &version.id,
+                        Some(&url),
+                    )
+                    .await?;
                 }
-            }
-        } else {
-            None
-        };
-
-        // Determine project_id (from metadata or search)
-        let project_id = if let Some(id) = project_id_from_metadata {
-            Some(id)
-        } else if let Some(ref info) = mod_info {
-            // PRIORITY 3: Search Modrinth by internal name and VERIFY with JAR filename
-            if let Some(ref mod_name) = info.mod_name {
                 crate::logging::info(&format!(
-                    "No metadata found, searching Modrinth for '{}' (internal name: {})",
-                    file_name, mod_name
+                    "Successfully downloaded: {} (with metadata)",
+                    saved_as
                 ));
-
-                let search_url = format!(
-                    "https://api.modrinth.com/v2/search?query={}&limit=10",
-                    urlencoding::encode(mod_name)
+                report.updated.push(plan.file_name);
+            }
+            Err(e) => {
+                crate::logging::Logger::warn_global(
+                    &format!("Failed to download an update for {}: {}", plan.file_name, e),
+                    None,
                 );
+                to_copy.push(plan);
+            }
+        }
+    }
 
-                let search_result: Result<serde_json::Value, String> = async {
-                    let resp = reqwest::get(&search_url)
-                        .await
-                        .map_err(|e| format!("Modrinth search failed: {}", e))?;
-                    resp.json()
-                        .await
-                        .map_err(|e| format!("Modrinth search parse failed: {}", e))
-                }
-                .await;
+    // Fallback: copy the original file to the disabled folder
+    // (likely outdated/incompatible with the new version)
+    if !to_copy.is_empty() {
+        let disabled_dir = target_mods_dir.join("disabled");
+        crate::ensure_folder_sync(&disabled_dir)
+            .map_err(|e| format!("Failed to create disabled directory: {}", e))?;
+        for plan in to_copy {
+            crate::logging::Logger::warn_global(
+                &format!(
+                    "No compatible update found for '{}', moving to disabled folder (may be outdated/incompatible)",
+                    plan.file_name
+                ),
+                None,
+            );
+            async_fs::copy(&plan.path, disabled_dir.join(&plan.file_name))
+                .await
+                .map_err(|e| format!("Failed to copy mod file to disabled folder: {}", e))?;
+            // Carry the provenance over so the user can update it later
+            carry_mod_provenance(target_mods_dir, &plan.file_name, plan.provenance.as_ref()).await;
+            report.incompatible.push(plan.file_name);
+        }
+    }
 
-                match search_result {
-                    Ok(json) => {
-                        let hits = json.get("hits").and_then(|h| h.as_array());
-
-                        let verified_project_id = if let Some(hits) = hits {
-                            crate::logging::info(&format!(
-                                "Search returned {} results for '{}'",
-                                hits.len(),
-                                mod_name
-                            ));
-
-                            // NEW: Verify each search result by checking if it has a version with matching JAR filename
-                            let mut found_id = None;
-                            for hit in hits {
-                                let candidate_id = hit
-                                    .get("project_id")
-                                    .and_then(|id| id.as_str())
-                                    .map(|s| s.to_string());
-
-                                if let Some(ref candidate_id) = candidate_id {
-                                    let slug =
-                                        hit.get("slug").and_then(|s| s.as_str()).unwrap_or("");
-                                    let title =
-                                        hit.get("title").and_then(|s| s.as_str()).unwrap_or("");
-
-                                    crate::logging::info(&format!(
-                                        "Checking candidate: {} (slug: {}, title: {})",
-                                        candidate_id, slug, title
-                                    ));
-
-                                    // Fetch ALL versions for this candidate (no filters, to check all possible filenames)
-                                    match modrinth::get_project_versions_filtered(
-                                        candidate_id,
-                                        None, // No loader filter
-                                        None, // No game version filter
-                                    )
-                                    .await
-                                    {
-                                        Ok(all_versions) => {
-                                            // Check if any version has a file matching our current JAR filename
-                                            let has_matching_filename =
-                                                all_versions.iter().any(|version| {
-                                                    version.files.iter().any(|file| {
-                                                        file.filename
-                                                            .eq_ignore_ascii_case(file_name)
-                                                    })
-                                                });
-
-                                            if has_matching_filename {
-                                                crate::logging::info(&format!(
-                                                    "✓ VERIFIED: Project '{}' has a version with filename '{}'",
-                                                    candidate_id, file_name
-                                                ));
-
-                                                // This is the correct project!
-                                                found_id = Some(candidate_id.clone());
-                                                break;
-                                            } else {
-                                                crate::logging::info(&format!(
-                                                    "✗ Project '{}' does NOT have filename '{}', trying next result",
-                                                    candidate_id, file_name
-                                                ));
-                                            }
-                                        }
-                                        Err(e) => {
-                                            crate::logging::Logger::warn_global(
-                                                &format!("Failed to fetch versions for candidate '{}': {}", candidate_id, e),
-                                                None,
-                                            );
-                                        }
-                                    }
-                                }
-                            }
-
-                            if found_id.is_none() {
-                                crate::logging::Logger::warn_global(
-                                    &format!("No search results matched JAR filename '{}' for query '{}'", file_name, mod_name),
-                                    None,
-                                );
-                            }
-
-                            found_id
-                        } else {
-                            None
-                        };
-
-                        verified_project_id
-                    }
-                    Err(e) => {
-                        crate::logging::Logger::warn_global(
-                            &format!("Failed to search Modrinth for '{}': {}", mod_name, e),
-                            None,
-                        );
-                        None
-                    }
-                }
-            } else {
+    report.updated.sort();
+    report.incompatible.sort();
+    crate::logging::info("Finished copying and updating mods");
+    Ok(report)
+}
+
+/// Downloads the update of a plan, returns the file it was saved as and where it came from
+async fn download_mod_update(
+    plan: &ModUpdatePlan,
+    target_mods_dir: &std::path::Path,
+) -> Result<(String, String), String> {
+    use crate::mods::modrinth;
+
+    let Some((_, version)) = &plan.update else {
+        return Err("No update to download".to_string());
+    };
+    let primary_file = version
+        .files
+        .iter()
+        .find(|f| f.primary)
+        .or_else(|| version.files.first())
+        .ok_or_else(|| "No files found in version".to_string())?;
+    let target_path = target_mods_dir.join(&primary_file.filename);
+
+    crate::logging::info(&format!(
+        "Downloading {} from {}",
+        primary_file.filename, primary_file.url
+    ));
+    modrinth::download_mod_file(&primary_file.url, &target_path, &primary_file.hashes).await?;
+    Ok((primary_file.filename.clone(), primary_file.url.clone()))
+}
+
+/// The Modrinth project of a jar and its best version for the target loader and game version.
+/// None when the jar can't be identified or has no compatible version.
+async fn resolve_mod_update(
+    path: &std::path::Path,
+    file_name: &str,
+    provenance: Option<&crate::mods::ModProvenance>,
+    target_loader: Option<&str>,
+    target_game_version: Option<&str>,
+) -> Option<(String, crate::mods::modrinth::ModrinthVersion)> {
+    use crate::mods::modrinth;
+
+    crate::logging::info(&format!("Processing mod: {}", file_name));
+
+    // PRIORITY 1: Check the recorded provenance (only Modrinth ids can be looked up here)
+    let project_id = match provenance.filter(|p| p.provider == crate::mods::ProviderKind::Modrinth)
+    {
+        Some(p) => {
+            crate::logging::info(&format!(
+                "Found provenance for {}: project_id = {}",
+                file_name, p.project_id
+            ));
+            p.project_id.clone()
+        }
+        // PRIORITY 2: Try to get mod info from JAR if no metadata,
+        // then search Modrinth for it
+        None => match get_mod_info_single(path).await {
+            Ok(Some(info)) => {
+                search_verified_project_id(file_name, info.mod_name.as_deref()).await?
+            }
+            Ok(None) => {
                 crate::logging::Logger::warn_global(
-                    &format!("No mod name found in {} JAR", file_name),
+                    &format!(
+                        "Could not read mod info from {}, moving to disabled folder",
+                        file_name
+                    ),
                     None,
                 );
-                None
+                return None;
             }
-        } else {
-            None
-        };
+            Err(e) => {
+                crate::logging::Logger::warn_global(
+                    &format!(
+                        "Error reading mod {}: {}, moving to disabled folder",
+                        file_name, e
+                    ),
+                    None,
+                );
+                return None;
+            }
+        },
+    };
 
-        // If we have a project_id, try to download compatible version
-        if let Some(project_id) = project_id {
+    crate::logging::info(&format!(
+        "Fetching compatible versions for project '{}' (loader: {:?}, game_version: {:?})",
+        project_id, target_loader, target_game_version
+    ));
+    match modrinth::get_project_versions_filtered(
+        &project_id,
+        target_loader.map(|l| vec![l.to_string()]),
+        target_game_version.map(|v| vec![v.to_string()]),
+    )
+    .await
+    {
+        Ok(versions) if !versions.is_empty() => {
             crate::logging::info(&format!(
-                "Fetching compatible versions for project '{}' (loader: {:?}, game_version: {:?})",
-                project_id, target_loader, target_game_version
+                "Found {} compatible versions for project '{}'",
+                versions.len(),
+                project_id
             ));
-
-            match modrinth::get_project_versions_filtered(
-                &project_id,
-                target_loader.clone().map(|l| vec![l]),
-                target_game_version.clone().map(|v| vec![v]),
-            )
-            .await
-            {
-                Ok(versions) if !versions.is_empty() => {
+            // Find the best version
+            match modrinth::find_best_version(&versions, target_loader, target_game_version) {
+                Some(version) => {
                     crate::logging::info(&format!(
-                        "Found {} compatible versions for project '{}'",
-                        versions.len(),
-                        project_id
+                        "Selected best version: {} ({})",
+                        version.version_number, version.id
                     ));
-
-                    // Find the best version
-                    let best_version = modrinth::find_best_version(
-                        &versions,
-                        target_loader.as_deref(),
-                        target_game_version.as_deref(),
-                    );
-
-                    if let Some(version) = best_version {
-                        crate::logging::info(&format!(
-                            "Selected best version: {} ({})",
-                            version.version_number, version.id
-                        ));
-
-                        // Download the compatible version
-                        let primary_file = version
-                            .files
-                            .iter()
-                            .find(|f| f.primary)
-                            .or_else(|| version.files.first())
-                            .ok_or_else(|| "No files found in version".to_string())?;
-
-                        let target_path = target_mods_dir.join(&primary_file.filename);
-
-                        crate::logging::info(&format!(
-                            "Downloading {} from {}",
-                            primary_file.filename, primary_file.url
-                        ));
-
-                        modrinth::download_mod_file(&primary_file.url, &target_path).await?;
-
-                        // Save metadata to track this mod properly
-                        modrinth::save_mod_metadata(
-                            &target_mods_dir,
-                            &primary_file.filename,
-                            &project_id,
-                            &version.version_number,
-                            &version.id,
-                        )
-                        .await?;
-
-                        crate::logging::info(&format!(
-                            "Successfully downloaded: {} (with metadata)",
-                            primary_file.filename
-                        ));
-                        continue;
-                    } else {
-                        crate::logging::Logger::warn_global(
-                            &format!(
-                                "Could not select best version for project '{}' from {} candidates",
-                                project_id,
-                                versions.len()
-                            ),
-                            None,
-                        );
-                    }
-                }
-                Ok(_) => {
-                    crate::logging::Logger::warn_global(
-                        &format!("No compatible versions found for project '{}' (loader: {:?}, game_version: {:?})", 
-                            project_id, target_loader, target_game_version),
-                        None,
-                    );
+                    Some((project_id, version))
                 }
-                Err(e) => {
+                None => {
                     crate::logging::Logger::warn_global(
                         &format!(
-                            "Failed to fetch versions for project '{}': {}",
-                            project_id, e
+                            "Could not select best version for project '{}' from {} candidates",
+                            project_id,
+                            versions.len()
                         ),
                         None,
                     );
+                    None
                 }
             }
-        } else {
+        }
+        Ok(_) => {
+            crate::logging::Logger::warn_global(
+                &format!(
+                    "No compatible versions found for project '{}' (loader: {:?}, game_version: {:?})",
+                    project_id, target_loader, target_game_version
+                ),
+                None,
+            );
+            None
+        }
+        Err(e) => {
             crate::logging::Logger::warn_global(
                 &format!(
-                    "No project ID found for {}, cannot check for updates",
-                    file_name
+                    "Failed to fetch versions for project '{}': {}",
+                    project_id, e
                 ),
                 None,
             );
+            None
         }
+    }
+}
 
-        // Fallback: copy the original file to the disabled folder
-        // (likely outdated/incompatible with the new version)
-        let disabled_dir = target_mods_dir.join("disabled");
-        crate::ensure_folder_sync(&disabled_dir)
-            .map_err(|e| format!("Failed to create disabled directory: {}", e))?;
+/// PRIORITY 3: Search Modrinth by internal name and VERIFY with JAR filename
+async fn search_verified_project_id(file_name: &str, mod_name: Option<&str>) -> Option<String> {
+    use crate::mods::modrinth;
 
-        let target_path = disabled_dir.join(file_name);
+    let Some(mod_name) = mod_name else {
         crate::logging::Logger::warn_global(
-            &format!(
-                "No compatible update found for '{}', moving to disabled folder (may be outdated/incompatible)",
-                file_name
-            ),
+            &format!("No mod name found in {} JAR", file_name),
             None,
         );
-        async_fs::copy(&path, &target_path)
+        return None;
+    };
+    crate::logging::info(&format!(
+        "No metadata found, searching Modrinth for '{}' (internal name: {})",
+        file_name, mod_name
+    ));
+
+    let search_url = format!(
+        "https://api.modrinth.com/v2/search?query={}&limit=10",
+        urlencoding::encode(mod_name)
+    );
+    let search_result: Result<serde_json::Value, String> = async {
+        let resp = reqwest::get(&search_url)
             .await
-            .map_err(|e| format!("Failed to copy mod file to disabled folder: {}", e))?;
-        // Copy metadata file if it exists so user can update it later
-        if metadata_file.exists() {
-            let metadata_target = disabled_dir.join(format!("{}.kable_metadata.json", file_name));
-            let _ = async_fs::copy(&metadata_file, &metadata_target).await;
+            .map_err(|e| format!("Modrinth search failed: {}", e))?;
+        resp.json()
+            .await
+            .map_err(|e| format!("Modrinth search parse failed: {}", e))
+    }
+    .await;
+    let json = match search_result {
+        Ok(json) => json,
+        Err(e) => {
+            crate::logging::Logger::warn_global(
+                &format!("Failed to search Modrinth for '{}': {}", mod_name, e),
+                None,
+            );
+            return None;
+        }
+    };
+    let hits = json.get("hits").and_then(|h| h.as_array())?;
+    crate::logging::info(&format!(
+        "Search returned {} results for '{}'",
+        hits.len(),
+        mod_name
+    ));
+
+    // Verify each search result by checking if it has a version with matching JAR filename
+    for hit in hits {
+        let Some(candidate_id) = hit.get("project_id").and_then(|id| id.as_str()) else {
+            continue;
+        };
+        let slug = hit.get("slug").and_then(|s| s.as_str()).unwrap_or("");
+        let title = hit.get("title").and_then(|s| s.as_str()).unwrap_or("");
+        crate::logging::info(&format!(
+            "Checking candidate: {} (slug: {}, title: {})",
+            candidate_id, slug, title
+        ));
+
+        // Fetch ALL versions for this candidate (no filters, to check all possible filenames)
+        match modrinth::get_project_versions_filtered(candidate_id, None, None).await {
+            Ok(all_versions) => {
+                let has_matching_filename = all_versions.iter().any(|version| {
+                    version
+                        .files
+                        .iter()
+                        .any(|file| file.filename.eq_ignore_ascii_case(file_name))
+                });
+                if has_matching_filename {
+                    crate::logging::info(&format!(
+                        "✓ VERIFIED: Project '{}' has a version with filename '{}'",
+                        candidate_id, file_name
+                    ));
+                    return Some(candidate_id.to_string());
+                }
+                crate::logging::info(&format!(
+                    "✗ Project '{}' does NOT have filename '{}', trying next result",
+                    candidate_id, file_name
+                ));
+            }
+            Err(e) => {
+                crate::logging::Logger::warn_global(
+                    &format!(
+                        "Failed to fetch versions for candidate '{}': {}",
+                        candidate_id, e
+                    ),
+                    None,
+                );
+            }
         }
     }
 
-    crate::logging::info("Finished copying and updating mods");
-    Ok(())
+    crate::logging::Logger::warn_global(
+        &format!(
+            "No search results matched JAR filename '{}' for query '{}'",
+            file_name, mod_name
+        ),
+        None,
+    );
+    None
+}
+
+async fn carry_mod_provenance(
+    target_mods_dir: &std::path::Path,
+    file_name: &str,
+    provenance: Option<&crate::mods::ModProvenance>,
+) {
+    if let Some(provenance) = provenance {
+        let _ = crate::mods::record_mod_provenance(target_mods_dir, file_name, provenance.clone())
+            .await;
+    }
 }
 
 /// Copy resource packs between installations
@@ -1080,7 +1882,7 @@ async fn get_shaders_directory(installation: &KableInstallation) -> Result<PathB
 }
 
 /// Extract loader type from version_id
-fn extract_loader_from_version_id(version_id: &str) -> Option<String> {
+pub(crate) fn extract_loader_from_version_id(version_id: &str) -> Option<String> {
     let version_lower = version_id.to_lowercase();
 
     if version_lower.contains("fabric") {
@@ -1115,6 +1917,149 @@ fn extract_game_version_from_version_id(version_id: &str) -> Option<String> {
     None
 }
 
+/// Result of installing a jar the user picked
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LocalModInstall {
+    pub info: ModJarInfo,
+    /// Set when Modrinth knows the file, the mod then updates like a downloaded one
+    pub provenance: Option<crate::mods::ModProvenance>,
+    /// Jars with the same mod id that the new jar replaced
+    pub replaced: Vec<String>,
+}
+
+/// Copies a jar into the mods folder of an installation. Its sha1 is looked up on Modrinth to
+/// record provenance. A jar with the same mod id already in the folder is an error unless
+/// `replace` is set, then that jar is removed.
+pub async fn install_local_mod(
+    installation_id: &str,
+    jar_path: &str,
+    replace: bool,
+) -> Result<LocalModInstall, String> {
+    use sha1::{Digest, Sha1};
+
+    let info = read_mod_jar_info(jar_path).await?;
+    let mut installation = get_installation(installation_id)
+        .await?
+        .ok_or_else(|| format!("Installation with id '{}' not found", installation_id))?;
+    if ensure_dedicated_mods_folder(&mut installation).await? {
+        modify_installation(&installation.id, installation.clone()).await?;
+    }
+    let mods_dir = installation.find_mods_dir()?;
+
+    let duplicates: Vec<ModJarInfo> = match &info.mod_name {
+        Some(mod_id) => installation
+            .get_mod_info()?
+            .into_iter()
+            .filter(|existing| existing.mod_name.as_ref() == Some(mod_id))
+            .collect(),
+        None => Vec::new(),
+    };
+    if !duplicates.is_empty() && !replace {
+        return Err(format!(
+            "{} is already installed as {}, replace it to install this jar",
+            info.mod_name.as_deref().unwrap_or(&info.file_name),
+            duplicates
+                .iter()
+                .map(|d| d.file_name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    let mut replaced = Vec::new();
+    for duplicate in duplicates {
+        let dir = if duplicate.disabled {
+            mods_dir.join("disabled")
+        } else {
+            mods_dir.clone()
+        };
+        async_fs::remove_file(dir.join(&duplicate.file_name))
+            .await
+            .map_err(|e| format!("Failed to remove {}: {}", duplicate.file_name, e))?;
+        replaced.push(duplicate.file_name);
+    }
+    if !replaced.is_empty() {
+        crate::mods::forget_mod_provenance(&mods_dir, &replaced).await?;
+    }
+
+    let bytes = async_fs::read(jar_path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", jar_path, e))?;
+    crate::write_file_atomic_async(&mods_dir.join(&info.file_name), &bytes).await?;
+
+    let sha1 = hex::encode(Sha1::digest(&bytes));
+    let provenance = match crate::mods::modrinth::modrinth_version_by_hash(&sha1).await {
+        Ok(Some(version)) if !version.project_id.is_empty() => {
+            let provenance = crate::mods::ModProvenance {
+                provider: crate::mods::ProviderKind::Modrinth,
+                project_id: version.project_id.clone(),
+                version_id: Some(version.id.clone()),
+                version_number: Some(version.version_number.clone()),
+                download_url: version
+                    .files
+                    .iter()
+                    .find(|f| f.hashes.get("sha1") == Some(&sha1))
+                    .map(|f| f.url.clone()),
+                downloaded_at: chrono::Utc::now().to_rfc3339(),
+            };
+            crate::mods::record_mod_provenance(&mods_dir, &info.file_name, provenance.clone())
+                .await?;
+            Some(provenance)
+        }
+        Ok(_) => None,
+        Err(e) => {
+            crate::logging::Logger::warn_global(
+                &format!("Could not look up {} on Modrinth: {}", info.file_name, e),
+                Some(installation_id),
+            );
+            None
+        }
+    };
+
+    crate::logging::info(&format!(
+        "Installed local mod {} into '{}'{}",
+        info.file_name,
+        installation.name,
+        if provenance.is_some() {
+            " (found on Modrinth)"
+        } else {
+            ""
+        }
+    ));
+    Ok(LocalModInstall {
+        info,
+        provenance,
+        replaced,
+    })
+}
+
+/// Identity of any jar on disk, e.g. one the user dropped in before installing it.
+/// Jars without fabric.mod.json, quilt.mod.json or mods.toml only have `file_name` set.
+pub async fn read_mod_jar_info(path: &str) -> Result<ModJarInfo, String> {
+    let path = std::path::Path::new(path);
+    if !path.is_file() {
+        return Err(format!("{} not found", path.display()));
+    }
+    if !path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"))
+    {
+        return Err(format!("{} is not a .jar file", path.display()));
+    }
+    let info = get_mod_info_single(path)
+        .await
+        .map_err(|e| format!("{} is not a valid mod jar: {}", path.display(), e))?;
+    Ok(info.unwrap_or_else(|| ModJarInfo {
+        file_name: path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        mod_name: None,
+        mod_version: None,
+        loader: None,
+        disabled: false,
+    }))
+}
+
 /// Get mod info from a single jar file
 async fn get_mod_info_single(path: &std::path::Path) -> Result<Option<ModJarInfo>, String> {
     let path_clone = path.to_path_buf();
@@ -1217,3 +2162,30 @@ async fn get_mod_info_single(path: &std::path::Path) -> Result<Option<ModJarInfo
     .await
     .map_err(|e| format!("Task join error: {}", e))?
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn failed_imports_are_reported_per_file_in_order() {
+        let dir = std::env::temp_dir().join(format!("kable-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let not_a_zip = dir.join("not-a-zip.zip");
+        std::fs::write(&not_a_zip, "plain text").unwrap();
+        let paths = vec![
+            dir.join("missing.zip").to_string_lossy().to_string(),
+            not_a_zip.to_string_lossy().to_string(),
+        ];
+        let report = import_installations(&paths).await.unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let reported: Vec<&str> = report.results.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(reported, paths);
+        assert!(report
+            .results
+            .iter()
+            .all(|r| r.installation_id.is_none() && r.error.is_some()));
+        assert!(report.installations.is_empty());
+    }
+}
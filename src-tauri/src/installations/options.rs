@@ -0,0 +1,151 @@
+use super::get_installation;
+use super::kable_profiles::KableInstallation;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+const OPTIONS_FILE: &str = "options.txt";
+const UNBOUND_KEY: &str = "key.keyboard.unknown";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeybindConflict {
+    /// The bound key, e.g. "key.keyboard.r", with the modifier when the loader stores one
+    pub key: String,
+    /// Every action bound to it, e.g. ["key.jump", "key.somemod.dash"]
+    pub actions: Vec<String>,
+}
+
+/// Reads the installation's `options.txt` as key/value pairs.
+/// Returns an empty map when the game hasn't created the file yet.
+pub async fn get_game_options(installation_id: &str) -> Result<HashMap<String, String>, String> {
+    let path = options_path(&find_installation(installation_id).await?)?;
+    let content = read_options(&path).await?;
+    Ok(content
+        .lines()
+        .filter_map(parse_line)
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect())
+}
+
+/// Applies `changes` to the installation's `options.txt`, keeping every other line and the
+/// order as they are. Keys that don't exist yet are appended, the file is created if needed.
+pub async fn set_game_options(
+    installation_id: &str,
+    changes: HashMap<String, String>,
+) -> Result<(), String> {
+    for (key, value) in &changes {
+        if key.trim().is_empty() || key.contains([':', '\n', '\r']) {
+            return Err(format!("Invalid option key '{}'", key));
+        }
+        if value.contains(['\n', '\r']) {
+            return Err(format!("Invalid value for option '{}': line breaks", key));
+        }
+    }
+
+    let installation = find_installation(installation_id).await?;
+    let path = options_path(&installation)?;
+    let content = read_options(&path).await?;
+
+    let mut remaining = changes;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|line| match parse_line(line) {
+            Some((key, _)) => match remaining.remove(key) {
+                Some(value) => format!("{}:{}", key, value),
+                None => line.to_string(),
+            },
+            None => line.to_string(),
+        })
+        .collect();
+    // HashMap order is random, keep appended keys stable
+    let mut new_keys: Vec<(String, String)> = remaining.into_iter().collect();
+    new_keys.sort();
+    lines.extend(
+        new_keys
+            .into_iter()
+            .map(|(key, value)| format!("{}:{}", key, value)),
+    );
+
+    let mut output = lines.join("\n");
+    output.push('\n');
+    if let Some(parent) = path.parent() {
+        crate::ensure_folder(parent).await?;
+    }
+    crate::write_file_atomic_async(&path, output.as_bytes()).await?;
+    crate::logging::Logger::debug_global(
+        &format!("Updated {}", path.display()),
+        Some(&installation.id),
+    );
+    Ok(())
+}
+
+/// Keys that are bound to more than one action in the installation's `options.txt`.
+/// Unbound actions are ignored, an installation without options.txt has no conflicts.
+pub async fn detect_keybind_conflicts(
+    installation_id: &str,
+) -> Result<Vec<KeybindConflict>, String> {
+    let options = get_game_options(installation_id).await?;
+
+    let mut actions_by_key: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (option, binding) in &options {
+        let Some(action) = option.strip_prefix("key_") else {
+            continue;
+        };
+        // Forge and NeoForge store the modifier after the key ("key.keyboard.r:SHIFT"),
+        // the default NONE is the same binding as no modifier at all
+        let binding = binding.trim().trim_end_matches(":NONE");
+        if binding.is_empty() || binding == UNBOUND_KEY {
+            continue;
+        }
+        actions_by_key
+            .entry(binding.to_string())
+            .or_default()
+            .push(action.to_string());
+    }
+
+    Ok(actions_by_key
+        .into_iter()
+        .filter(|(_, actions)| actions.len() > 1)
+        .map(|(key, mut actions)| {
+            actions.sort();
+            KeybindConflict { key, actions }
+        })
+        .collect())
+}
+
+async fn find_installation(installation_id: &str) -> Result<KableInstallation, String> {
+    get_installation(installation_id)
+        .await?
+        .ok_or_else(|| format!("Installation with id '{}' not found", installation_id))
+}
+
+/// `options.txt` lives in the dedicated config folder when the installation has one,
+/// otherwise the game uses the shared one in .minecraft
+fn options_path(installation: &KableInstallation) -> Result<PathBuf, String> {
+    match installation.dedicated_config_folder.as_deref() {
+        Some(folder) if !folder.is_empty() => {
+            let folder_path = PathBuf::from(folder);
+            let config_dir = if folder_path.is_absolute() {
+                folder_path
+            } else {
+                crate::get_minecraft_kable_dir()?.join(folder)
+            };
+            Ok(config_dir.join(OPTIONS_FILE))
+        }
+        _ => Ok(crate::get_default_minecraft_dir()?.join(OPTIONS_FILE)),
+    }
+}
+
+async fn read_options(path: &std::path::Path) -> Result<String, String> {
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// Splits an options line at its first ':', values may contain more colons (e.g. key bindings)
+fn parse_line(line: &str) -> Option<(&str, &str)> {
+    line.split_once(':').filter(|(key, _)| !key.is_empty())
+}
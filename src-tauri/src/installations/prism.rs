@@ -0,0 +1,241 @@
+use super::{get_versions, kable_profiles, KableInstallation};
+use crate::logging::Logger;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::task;
+
+/// Minecraft version and loader of a Prism/MultiMC instance, read from mmc-pack.json
+#[derive(Debug, Clone, Default)]
+struct InstanceComponents {
+    minecraft: Option<String>,
+    /// (loader, loader version), loader is 'fabric' | 'quilt' | 'forge' | 'neoforge'
+    loader: Option<(&'static str, String)>,
+}
+
+/// Imports Prism Launcher instances as Kable installations.
+/// `instance_dir` can be a single instance folder (containing instance.cfg) or the
+/// `instances` folder itself, in which case every instance in it is imported.
+/// The instance's mods, resourcepacks, shaderpacks and config are copied, never moved.
+pub async fn import_from_prism(instance_dir: &str) -> Result<Vec<KableInstallation>, String> {
+    import_mmc_instances(instance_dir, "Prism").await
+}
+
+/// Imports MultiMC instances, they use the same instance format as Prism
+pub async fn import_from_multimc(instance_dir: &str) -> Result<Vec<KableInstallation>, String> {
+    import_mmc_instances(instance_dir, "MultiMC").await
+}
+
+async fn import_mmc_instances(
+    instance_dir: &str,
+    launcher: &str,
+) -> Result<Vec<KableInstallation>, String> {
+    let root = PathBuf::from(instance_dir);
+    let single = root.join("instance.cfg").is_file();
+    let instance_dirs: Vec<PathBuf> = if single {
+        vec![root.clone()]
+    } else {
+        std::fs::read_dir(&root)
+            .map_err(|e| format!("Failed to read {}: {}", root.display(), e))?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.join("instance.cfg").is_file())
+            .collect()
+    };
+    if instance_dirs.is_empty() {
+        return Err(format!(
+            "No {} instance found in {} (instance.cfg missing)",
+            launcher,
+            root.display()
+        ));
+    }
+
+    let versions = get_versions().await;
+    let kable_dir = crate::get_minecraft_kable_dir()?;
+    let launcher_name = launcher.to_string();
+
+    let imported = task::spawn_blocking(move || {
+        let mut imported = Vec::new();
+        for dir in instance_dirs {
+            match installation_from_instance(&dir, &kable_dir) {
+                Ok(installation) => {
+                    if versions.get_version(&installation.version_id).is_none() {
+                        Logger::warn_global(
+                            &format!(
+                                "Imported {} instance '{}' uses version '{}' which is not in the version list yet",
+                                launcher_name, installation.name, installation.version_id
+                            ),
+                            None,
+                        );
+                    }
+                    imported.push(installation);
+                }
+                // One broken instance shouldn't stop importing the whole instances folder
+                Err(e) if !single => Logger::warn_global(
+                    &format!(
+                        "Skipped {} instance {}: {}",
+                        launcher_name,
+                        dir.display(),
+                        e
+                    ),
+                    None,
+                ),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok::<Vec<KableInstallation>, String>(imported)
+    })
+    .await
+    .map_err(|e| format!("Import task join error: {}", e))??;
+
+    if !imported.is_empty() {
+        let mut existing = kable_profiles::read_kable_profiles_async().await?;
+        existing.extend(imported.clone());
+        kable_profiles::write_kable_profiles_async(&existing).await?;
+    }
+
+    Logger::info_global(
+        &format!(
+            "Imported {} installation(s) from {} instances in {}",
+            imported.len(),
+            launcher,
+            instance_dir
+        ),
+        None,
+    );
+    Ok(imported)
+}
+
+fn installation_from_instance(dir: &Path, kable_dir: &Path) -> Result<KableInstallation, String> {
+    let cfg_data = std::fs::read_to_string(dir.join("instance.cfg"))
+        .map_err(|e| format!("Failed to read instance.cfg: {}", e))?;
+    let cfg = parse_instance_cfg(&cfg_data);
+
+    let pack_path = dir.join("mmc-pack.json");
+    let mut components = if pack_path.is_file() {
+        let data = std::fs::read_to_string(&pack_path)
+            .map_err(|e| format!("Failed to read mmc-pack.json: {}", e))?;
+        parse_mmc_pack(&data)?
+    } else {
+        InstanceComponents::default()
+    };
+    // Legacy MultiMC instances keep the version in instance.cfg
+    if components.minecraft.is_none() {
+        components.minecraft = cfg.get("IntendedVersion").cloned();
+    }
+    let minecraft = components
+        .minecraft
+        .ok_or("Could not determine the Minecraft version of the instance")?;
+
+    let version_id = match &components.loader {
+        None => minecraft.clone(),
+        Some(("fabric", v)) => format!("fabric-loader-{}-{}", v, minecraft),
+        Some(("quilt", v)) => format!("quilt-loader-{}-{}", v, minecraft),
+        Some(("forge", v)) => format!("{}-forge-{}", minecraft, v),
+        Some((_, v)) => format!("neoforge-{}", v),
+    };
+
+    let name = cfg
+        .get("name")
+        .cloned()
+        .filter(|n| !n.is_empty())
+        .or_else(|| dir.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| version_id.clone());
+
+    let mut installation = KableInstallation {
+        name,
+        version_id,
+        ..Default::default()
+    };
+    if components.loader.is_some() {
+        installation.dedicated_mods_folder = Some(format!("mods/{}", installation.id));
+    }
+    apply_java_settings(&mut installation, &cfg);
+
+    // Prism uses `.minecraft`, older MultiMC versions `minecraft`
+    let game_dir = [".minecraft", "minecraft"]
+        .iter()
+        .map(|d| dir.join(d))
+        .find(|d| d.is_dir());
+    if let Some(game_dir) = game_dir {
+        installation.copy_game_folders_from(&game_dir, kable_dir)?;
+    }
+
+    Logger::debug_global(
+        &format!(
+            "Created installation '{}' ({}) from instance {}",
+            installation.name,
+            installation.version_id,
+            dir.display()
+        ),
+        None,
+    );
+    Ok(installation)
+}
+
+/// instance.cfg is a flat INI file, values may be quoted
+fn parse_instance_cfg(data: &str) -> HashMap<String, String> {
+    data.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('[') && !l.starts_with('#'))
+        .filter_map(|l| l.split_once('='))
+        .map(|(k, v)| {
+            let v = v.trim();
+            let v = v
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(v);
+            (k.trim().to_string(), v.to_string())
+        })
+        .collect()
+}
+
+fn parse_mmc_pack(data: &str) -> Result<InstanceComponents, String> {
+    let json: serde_json::Value =
+        serde_json::from_str(data).map_err(|e| format!("Failed to parse mmc-pack.json: {}", e))?;
+    let mut components = InstanceComponents::default();
+
+    for component in json
+        .get("components")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let Some(uid) = component.get("uid").and_then(|u| u.as_str()) else {
+            continue;
+        };
+        let Some(version) = component
+            .get("version")
+            .or_else(|| component.get("cachedVersion"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        match uid {
+            "net.minecraft" => components.minecraft = Some(version),
+            "net.fabricmc.fabric-loader" => components.loader = Some(("fabric", version)),
+            "org.quiltmc.quilt-loader" => components.loader = Some(("quilt", version)),
+            "net.minecraftforge" => components.loader = Some(("forge", version)),
+            "net.neoforged" => components.loader = Some(("neoforge", version)),
+            _ => {}
+        }
+    }
+    Ok(components)
+}
+
+/// Takes over the instance's JVM arguments and max memory when it overrides the global ones
+fn apply_java_settings(installation: &mut KableInstallation, cfg: &HashMap<String, String>) {
+    let enabled = |key: &str| cfg.get(key).map(|v| v == "true").unwrap_or(false);
+
+    if enabled("OverrideJavaArgs") {
+        if let Some(args) = cfg.get("JvmArgs").filter(|a| !a.trim().is_empty()) {
+            installation.java_args = args.split_whitespace().map(String::from).collect();
+        }
+    }
+    if enabled("OverrideMemory") {
+        if let Some(max_mb) = cfg.get("MaxMemAlloc").and_then(|m| m.parse::<u32>().ok()) {
+            installation.java_args.retain(|a| !a.starts_with("-Xmx"));
+            installation.java_args.insert(0, format!("-Xmx{}M", max_mb));
+        }
+    }
+}
@@ -3,20 +3,50 @@ use std::fs;
 use tokio::fs as async_fs;
 use tokio::task;
 
+/// A profile from launcher_profiles.json. Old launcher versions and third-party launchers leave
+/// out most fields (no `type`, `created` or `lastVersionId`, profiles keyed by their name),
+/// so everything but the key has a default.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LauncherProfile {
+    #[serde(default)]
     pub created: Option<String>,
+    #[serde(default)]
     pub game_dir: Option<String>,
+    #[serde(default)]
     pub icon: Option<String>,
+    #[serde(default)]
     pub java_args: Option<String>,
+    /// Path of the Java executable, only set when the profile doesn't use the bundled one
+    #[serde(default, alias = "javaPath")]
+    pub java_dir: Option<String>,
+    #[serde(default)]
     pub last_used: Option<String>,
+    /// Profiles without a version always launched the latest release
+    #[serde(default = "default_last_version_id")]
     pub last_version_id: String,
+    /// Falls back to the key of the profile, old launchers keyed profiles by name
+    #[serde(default)]
     pub name: String,
-    #[serde(rename = "type")]
+    #[serde(rename = "type", default = "default_profile_type")]
     pub profile_type: String,
+    /// Key of this profile in the `profiles` object of launcher_profiles.json
+    #[serde(skip)]
+    pub key: String,
 }
 
+fn default_last_version_id() -> String {
+    "latest-release".to_string()
+}
+
+fn default_profile_type() -> String {
+    "custom".to_string()
+}
+
+/// Launcher profile key -> id of the Kable installation it was converted into.
+/// Persisted so profiles are only converted once, also when they have no `created` timestamp.
+pub type LauncherProfileMap = std::collections::HashMap<String, String>;
+
 pub fn read_launcher_profiles() -> Result<Vec<LauncherProfile>, String> {
     // Synchronous version for compatibility
     let mc_dir = crate::get_default_minecraft_dir()?;
@@ -26,7 +56,8 @@ pub fn read_launcher_profiles() -> Result<Vec<LauncherProfile>, String> {
     parse_launcher_profiles_json(&data)
 }
 
-fn parse_launcher_profiles_json(data: &str) -> Result<Vec<LauncherProfile>, String> {
+/// Parses the `profiles` of a launcher_profiles.json, profiles that still can't be read are skipped
+pub fn parse_launcher_profiles_json(data: &str) -> Result<Vec<LauncherProfile>, String> {
     let json: serde_json::Value = serde_json::from_str(data)
         .map_err(|e| format!("Failed to parse launcher_profiles.json: {}", e))?;
     let profiles = json
@@ -34,9 +65,24 @@ fn parse_launcher_profiles_json(data: &str) -> Result<Vec<LauncherProfile>, Stri
         .and_then(|p| p.as_object())
         .ok_or("No 'profiles' object found in launcher_profiles.json")?;
     let mut result = Vec::new();
-    for profile_value in profiles.values() {
-        let profile: LauncherProfile = serde_json::from_value(profile_value.clone())
-            .map_err(|e| format!("Failed to parse a profile: {}", e))?;
+    for (key, profile_value) in profiles {
+        let mut profile: LauncherProfile = match serde_json::from_value(profile_value.clone()) {
+            Ok(profile) => profile,
+            Err(e) => {
+                crate::logging::Logger::warn_global(
+                    &format!("Skipping launcher profile '{}': {}", key, e),
+                    None,
+                );
+                continue;
+            }
+        };
+        profile.key = key.clone();
+        if profile.name.trim().is_empty() {
+            profile.name = key.clone();
+        }
+        if profile.last_version_id.trim().is_empty() {
+            profile.last_version_id = default_last_version_id();
+        }
         result.push(profile);
     }
     Ok(result)
@@ -54,6 +100,99 @@ pub async fn read_launcher_profiles_async() -> Result<Vec<LauncherProfile>, Stri
         .unwrap()
 }
 
+/// Reads the persisted launcher profile key mapping, empty when it doesn't exist yet
+pub async fn read_launcher_profile_map_async() -> Result<LauncherProfileMap, String> {
+    let path = crate::get_minecraft_kable_dir()?.join("launcher_profile_map.json");
+    if !path.exists() {
+        return Ok(LauncherProfileMap::new());
+    }
+    let data = async_fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read launcher_profile_map.json: {}", e))?;
+    serde_json::from_str(&data)
+        .map_err(|e| format!("Failed to parse launcher_profile_map.json: {}", e))
+}
+
+pub async fn write_launcher_profile_map_async(map: &LauncherProfileMap) -> Result<(), String> {
+    let path = crate::get_minecraft_kable_dir()?.join("launcher_profile_map.json");
+    let json = serde_json::to_string_pretty(map)
+        .map_err(|e| format!("Failed to serialize launcher profile map: {}", e))?;
+    crate::ensure_parent_dir_exists_async(&path).await?;
+    crate::write_file_atomic_async(&path, json.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write launcher_profile_map.json: {}", e))
+}
+
+/// Splits launcher profiles into the ones that still need to be converted and records the
+/// mapping for them. Profiles converted before the mapping existed are matched to their Kable
+/// installation by name and version (plus `created` when the profile has one) and adopted.
+/// `map` is updated in place, new mappings point to the id the converted installation must get.
+pub fn select_unconverted_profiles(
+    launcher_profiles: Vec<LauncherProfile>,
+    installations: &[super::KableInstallation],
+    map: &mut LauncherProfileMap,
+) -> Vec<(LauncherProfile, String)> {
+    let mut claimed: std::collections::HashSet<String> = map.values().cloned().collect();
+    let mut unconverted = Vec::new();
+
+    for profile in launcher_profiles {
+        if map.contains_key(&profile.key) {
+            continue;
+        }
+        let existing = installations.iter().find(|i| {
+            !claimed.contains(&i.id)
+                && i.name == profile.name
+                && i.version_id == profile.last_version_id
+                && profile.created.as_ref().is_none_or(|c| *c == i.created)
+        });
+        let id = match existing {
+            Some(installation) => installation.id.clone(),
+            None => {
+                let id = uuid::Uuid::new_v4().to_string();
+                unconverted.push((profile.clone(), id.clone()));
+                id
+            }
+        };
+        claimed.insert(id.clone());
+        map.insert(profile.key, id);
+    }
+    unconverted
+}
+
+/// Removes the launcher profile stored under `key` in launcher_profiles.json
+pub async fn remove_launcher_profile_by_key(key: &str) -> Result<(), String> {
+    let mc_dir = crate::get_default_minecraft_dir()?;
+    let path = mc_dir.join("launcher_profiles.json");
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let data = async_fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read launcher_profiles.json: {}", e))?;
+    let mut json: serde_json::Value = serde_json::from_str(&data)
+        .map_err(|e| format!("Failed to parse launcher_profiles.json: {}", e))?;
+    let removed = json
+        .get_mut("profiles")
+        .and_then(|p| p.as_object_mut())
+        .and_then(|profiles| profiles.remove(key))
+        .is_some();
+    if !removed {
+        return Ok(());
+    }
+
+    let json_str = serde_json::to_string_pretty(&json)
+        .map_err(|e| format!("Failed to serialize launcher profiles: {}", e))?;
+    crate::write_file_atomic_async(&path, json_str.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write launcher_profiles.json: {}", e))?;
+    crate::logging::Logger::debug_global(
+        &format!("Removed profile '{}' from launcher_profiles.json", key),
+        None,
+    );
+    Ok(())
+}
+
 /// Removes a profile from launcher_profiles.json that matches the given criteria
 /// This is used during deletion to prevent re-importing official launcher profiles
 pub async fn remove_launcher_profile_by_match(
@@ -130,3 +269,162 @@ pub async fn remove_launcher_profile_by_match(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::installations::KableInstallation;
+
+    fn profile(key: &str, name: &str, created: Option<&str>) -> LauncherProfile {
+        LauncherProfile {
+            created: created.map(str::to_string),
+            game_dir: None,
+            icon: None,
+            java_args: None,
+            java_dir: None,
+            last_used: None,
+            last_version_id: "1.20.4".to_string(),
+            name: name.to_string(),
+            profile_type: default_profile_type(),
+            key: key.to_string(),
+        }
+    }
+
+    fn installation(id: &str, name: &str, created: &str) -> KableInstallation {
+        KableInstallation {
+            id: id.to_string(),
+            name: name.to_string(),
+            version_id: "1.20.4".to_string(),
+            created: created.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn profile_without_created_is_converted_once() {
+        let profiles = vec![profile("a1", "Forge", None)];
+        let mut map = LauncherProfileMap::new();
+
+        let first = select_unconverted_profiles(profiles.clone(), &[], &mut map);
+        assert_eq!(first.len(), 1);
+        let (_, id) = &first[0];
+        // Converting stamps the installation with the time of conversion
+        let installations = vec![installation(id, "Forge", "2024-05-02T19:44:03Z")];
+
+        // The next startup reads the same launcher profile and the persisted map
+        let second = select_unconverted_profiles(profiles, &installations, &mut map);
+        assert!(second.is_empty());
+        assert_eq!(map.get("a1"), Some(id));
+    }
+
+    #[test]
+    fn installations_converted_before_the_map_are_adopted() {
+        let installations = vec![installation("existing", "Forge", "2024-05-02T19:44:03Z")];
+        let mut map = LauncherProfileMap::new();
+
+        let unconverted = select_unconverted_profiles(
+            vec![profile("a1", "Forge", None)],
+            &installations,
+            &mut map,
+        );
+        assert!(unconverted.is_empty());
+        assert_eq!(map.get("a1").map(String::as_str), Some("existing"));
+    }
+
+    #[test]
+    fn same_named_profiles_get_their_own_installation() {
+        let installations = vec![installation("existing", "Forge", "2024-05-02T19:44:03Z")];
+        let mut map = LauncherProfileMap::new();
+
+        let unconverted = select_unconverted_profiles(
+            vec![profile("a1", "Forge", None), profile("b2", "Forge", None)],
+            &installations,
+            &mut map,
+        );
+        assert_eq!(unconverted.len(), 1);
+        assert_eq!(map.len(), 2);
+        assert_ne!(map["a1"], map["b2"]);
+    }
+
+    fn by_key<'a>(profiles: &'a [LauncherProfile], key: &str) -> &'a LauncherProfile {
+        profiles
+            .iter()
+            .find(|p| p.key == key)
+            .unwrap_or_else(|| panic!("no profile '{}'", key))
+    }
+
+    #[test]
+    fn parses_current_launcher_profiles() {
+        let profiles =
+            parse_launcher_profiles_json(include_str!("../../tests/launcher_profiles-2.0.json"))
+                .unwrap();
+        assert_eq!(profiles.len(), 2);
+
+        let latest = by_key(&profiles, "b1f5c9f4a2d7e3c8a6b0d4e2f1a3c5b7");
+        // An empty name falls back to the key
+        assert_eq!(latest.name, "b1f5c9f4a2d7e3c8a6b0d4e2f1a3c5b7");
+        assert_eq!(latest.profile_type, "latest-release");
+        assert!(latest.game_dir.is_none());
+
+        let fabric = by_key(&profiles, "4e1a9c2b8d6f0e3a7c5b9d1f2e4a6c8b");
+        assert_eq!(fabric.name, "Fabric 1.20.4");
+        assert_eq!(fabric.last_version_id, "fabric-loader-0.15.7-1.20.4");
+        assert_eq!(
+            fabric.game_dir.as_deref(),
+            Some("/home/steve/.minecraft/profiles/fabric")
+        );
+        assert_eq!(
+            fabric.java_dir.as_deref(),
+            Some("/usr/lib/jvm/java-21-openjdk/bin/java")
+        );
+    }
+
+    #[test]
+    fn parses_legacy_launcher_profiles() {
+        let profiles =
+            parse_launcher_profiles_json(include_str!("../../tests/launcher_profiles-legacy.json"))
+                .unwrap();
+        assert_eq!(profiles.len(), 2);
+
+        let forge = by_key(&profiles, "Forge");
+        assert_eq!(forge.last_version_id, "1.7.10-Forge10.13.4.1614-1.7.10");
+        assert_eq!(forge.profile_type, "custom");
+        assert!(forge.created.is_none());
+        assert_eq!(
+            forge.game_dir.as_deref(),
+            Some("C:\\Users\\steve\\AppData\\Roaming\\.minecraft\\forge")
+        );
+        assert_eq!(
+            forge.java_dir.as_deref(),
+            Some("C:\\Program Files\\Java\\jre1.8.0_51\\bin\\javaw.exe")
+        );
+
+        // A profile with only a name launches the latest release
+        let steve = by_key(&profiles, "steve");
+        assert_eq!(steve.last_version_id, "latest-release");
+        assert!(steve.java_dir.is_none());
+    }
+
+    #[test]
+    fn parses_third_party_launcher_profiles() {
+        let profiles = parse_launcher_profiles_json(include_str!(
+            "../../tests/launcher_profiles-third-party.json"
+        ))
+        .unwrap();
+        // "Broken" is not an object and is skipped
+        assert_eq!(profiles.len(), 1);
+
+        let modpack = by_key(&profiles, "Modpack");
+        assert_eq!(modpack.name, "Modpack");
+        assert_eq!(modpack.last_version_id, "1.12.2-forge-14.23.5.2860");
+        // javaPath is read as javaDir
+        assert_eq!(
+            modpack.java_dir.as_deref(),
+            Some("/opt/java/jdk8u402/bin/java")
+        );
+        assert_eq!(
+            modpack.game_dir.as_deref(),
+            Some("/home/steve/.technic/modpacks/modpack")
+        );
+    }
+}
@@ -0,0 +1,545 @@
+use super::get_installations;
+use crate::logging::Logger;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::task;
+
+// Written by the Forge/NeoForge installers (patched client, mappings) and never listed in a
+// version's libraries, so they are kept even when nothing seems to reference them
+const INSTALLER_LIBRARY_PREFIXES: &[&str] =
+    &["net/minecraft/", "net/minecraftforge/", "net/neoforged/"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PruneCategory {
+    /// Paths that are (or would be) removed
+    pub paths: Vec<String>,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PruneReport {
+    pub dry_run: bool,
+    /// Version folders in versions/
+    pub versions: PruneCategory,
+    /// Jars in libraries/
+    pub libraries: PruneCategory,
+    /// Asset indexes and objects in assets/
+    pub assets: PruneCategory,
+    /// Isolated assets/natives folders of installations that no longer exist
+    pub isolated: PruneCategory,
+    pub reclaimable_bytes: u64,
+    /// Why a category was left alone, e.g. a manifest that could not be read
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionUsage {
+    pub version_id: String,
+    /// Installations of this version or of a version inheriting from it
+    pub installation_count: usize,
+}
+
+/// Versions installations use, loader versions like fabric-loader-0.16.0-1.21 included along
+/// with the versions they inherit from (read from their version json on disk). Most used first.
+pub async fn get_versions_in_use() -> Result<Vec<VersionUsage>, String> {
+    let minecraft_dir = crate::get_default_minecraft_dir()?;
+    let installations = get_installations().await?;
+    task::spawn_blocking(move || {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for installation in &installations {
+            let version_id = installation.version_id.trim().to_string();
+            for id in inherits_chain(&minecraft_dir, &version_id)
+                .into_iter()
+                .chain(std::iter::once(version_id))
+            {
+                *counts.entry(id).or_default() += 1;
+            }
+        }
+        let mut usage: Vec<VersionUsage> = counts
+            .into_iter()
+            .map(|(version_id, installation_count)| VersionUsage {
+                version_id,
+                installation_count,
+            })
+            .collect();
+        usage.sort_by(|a, b| {
+            b.installation_count
+                .cmp(&a.installation_count)
+                .then_with(|| a.version_id.cmp(&b.version_id))
+        });
+        usage
+    })
+    .await
+    .map_err(|e| format!("Versions in use task join error: {}", e))
+}
+
+/// Finds versions, libraries and assets in the Minecraft directory that no installation uses
+/// and, unless `dry_run`, deletes them. Libraries and assets are shared between versions,
+/// so they are only removed when no kept version references them, and a category is skipped
+/// entirely when the versions that are kept can't be fully read.
+pub async fn prune_unused_game_files(dry_run: bool) -> Result<PruneReport, String> {
+    if !dry_run && crate::launcher::is_minecraft_running().await? {
+        return Err("Close all running games before removing unused game files".to_string());
+    }
+
+    let minecraft_dir = crate::get_default_minecraft_dir()?;
+    let minecraft_dir_str = minecraft_dir.to_string_lossy().to_string();
+    let installations = get_installations().await?;
+    let mut report = PruneReport {
+        dry_run,
+        ..Default::default()
+    };
+
+    // 1. Versions used by an installation, placeholders like latest-release resolved
+    let mut kept_versions: HashSet<String> = HashSet::new();
+    let mut keep_all_vanilla = false;
+    for installation in &installations {
+        let version_id = installation.version_id.trim();
+        if version_id.starts_with("latest") {
+            match crate::launcher::utils::load_and_merge_manifest(&minecraft_dir_str, version_id)
+                .await
+            {
+                Ok(manifest) => {
+                    if let Some(id) = manifest.get("id").and_then(|v| v.as_str()) {
+                        kept_versions.insert(id.to_string());
+                    }
+                }
+                Err(e) => {
+                    keep_all_vanilla = true;
+                    report.skipped.push(format!(
+                        "Could not resolve '{}' ({}), keeping all vanilla versions, libraries and assets",
+                        version_id, e
+                    ));
+                }
+            }
+        } else {
+            kept_versions.insert(version_id.to_string());
+        }
+    }
+    // Parents of loader versions (inheritsFrom) are needed too
+    for version_id in kept_versions.clone() {
+        kept_versions.extend(inherits_chain(&minecraft_dir, &version_id));
+    }
+
+    // 2. Libraries and asset indexes of every kept version that is installed
+    let mut kept_libraries: HashSet<String> = HashSet::new();
+    let mut kept_indexes: HashSet<String> = HashSet::new();
+    // Kept vanilla versions that weren't resolved have unknown libraries and assets
+    let mut manifests_complete = !keep_all_vanilla;
+    for version_id in &kept_versions {
+        if !version_json(&minecraft_dir, version_id).is_file() {
+            continue;
+        }
+        match crate::launcher::utils::load_and_merge_manifest(&minecraft_dir_str, version_id).await
+        {
+            Ok(manifest) => {
+                kept_libraries.extend(library_paths(&manifest));
+                if let Some(index) = manifest.get("assets").and_then(|v| v.as_str()) {
+                    kept_indexes.insert(index.to_string());
+                }
+            }
+            Err(e) => {
+                manifests_complete = false;
+                report.skipped.push(format!(
+                    "Could not read the manifest of '{}' ({}), keeping all libraries and assets",
+                    version_id, e
+                ));
+            }
+        }
+    }
+
+    let installation_ids: HashSet<String> = installations.iter().map(|i| i.id.clone()).collect();
+    let kable_dir = crate::get_minecraft_kable_dir()?;
+
+    let report = task::spawn_blocking(move || {
+        collect_unused_versions(
+            &minecraft_dir,
+            &kept_versions,
+            keep_all_vanilla,
+            &mut report,
+        );
+        if manifests_complete {
+            collect_unused_libraries(&minecraft_dir, &kept_libraries, &mut report);
+            collect_unused_assets(&minecraft_dir, &kept_indexes, &mut report);
+        }
+        collect_orphaned_isolated_dirs(&kable_dir, &installation_ids, &mut report);
+
+        report.reclaimable_bytes = report.versions.bytes
+            + report.libraries.bytes
+            + report.assets.bytes
+            + report.isolated.bytes;
+        if !dry_run {
+            delete_paths(&report);
+            remove_empty_dirs(&minecraft_dir.join("libraries"));
+        }
+        report
+    })
+    .await
+    .map_err(|e| format!("Prune task join error: {}", e))?;
+
+    Logger::info_global(
+        &format!(
+            "{} {} versions, {} libraries, {} asset files and {} isolated folders ({} MB)",
+            if dry_run { "Found unused:" } else { "Removed" },
+            report.versions.paths.len(),
+            report.libraries.paths.len(),
+            report.assets.paths.len(),
+            report.isolated.paths.len(),
+            report.reclaimable_bytes / (1024 * 1024)
+        ),
+        None,
+    );
+    Ok(report)
+}
+
+/// Temp files older than this can't belong to a write that is still running
+const TEMP_FILE_MIN_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Removes `.tmp` files left behind by writes and downloads that were interrupted (crash,
+/// killed process) from the kable dirs and the versions, libraries and assets folders
+pub async fn cleanup_temp_files() -> Result<PruneCategory, String> {
+    let minecraft_dir = crate::get_default_minecraft_dir()?;
+    // The Minecraft dir itself only holds launcher files, its subfolders are walked below
+    let mut roots = vec![(minecraft_dir.clone(), false)];
+    for folder in ["versions", "libraries", "assets"] {
+        roots.push((minecraft_dir.join(folder), true));
+    }
+    roots.push((crate::get_minecraft_kable_dir()?, true));
+    if let Ok(launcher_dir) = crate::get_kable_launcher_dir() {
+        roots.push((launcher_dir, true));
+    }
+
+    let removed = task::spawn_blocking(move || {
+        let mut removed = PruneCategory::default();
+        let mut seen: HashSet<PathBuf> = HashSet::new();
+        for (root, recursive) in roots {
+            let walker =
+                walkdir::WalkDir::new(&root).max_depth(if recursive { usize::MAX } else { 1 });
+            for entry in walker
+                .into_iter()
+                .flatten()
+                .filter(|e| e.file_type().is_file())
+            {
+                let path = entry.path();
+                if path.extension().is_none_or(|e| e != "tmp") || !seen.insert(path.to_path_buf()) {
+                    continue;
+                }
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                let old_enough = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| modified.elapsed().ok())
+                    .is_some_and(|age| age >= TEMP_FILE_MIN_AGE);
+                if !old_enough {
+                    continue;
+                }
+                match std::fs::remove_file(path) {
+                    Ok(()) => {
+                        removed.bytes += metadata.len();
+                        removed.paths.push(path.to_string_lossy().to_string());
+                    }
+                    Err(e) => Logger::warn_global(
+                        &format!("Failed to remove temp file {}: {}", path.display(), e),
+                        None,
+                    ),
+                }
+            }
+        }
+        removed
+    })
+    .await
+    .map_err(|e| format!("Temp file cleanup task join error: {}", e))?;
+
+    if !removed.paths.is_empty() {
+        Logger::info_global(
+            &format!(
+                "Removed {} leftover temp files ({} KB)",
+                removed.paths.len(),
+                removed.bytes / 1024
+            ),
+            None,
+        );
+    }
+    Ok(removed)
+}
+
+fn version_json(minecraft_dir: &Path, version_id: &str) -> PathBuf {
+    minecraft_dir
+        .join("versions")
+        .join(version_id)
+        .join(format!("{}.json", version_id))
+}
+
+fn read_version_json(minecraft_dir: &Path, version_id: &str) -> Option<serde_json::Value> {
+    let data = std::fs::read_to_string(version_json(minecraft_dir, version_id)).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+/// All versions `version_id` inherits from, nearest parent first
+fn inherits_chain(minecraft_dir: &Path, version_id: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut current = version_id.to_string();
+    while let Some(parent) = read_version_json(minecraft_dir, &current)
+        .and_then(|json| json.get("inheritsFrom")?.as_str().map(str::to_string))
+    {
+        if chain.contains(&parent) || parent == version_id {
+            break;
+        }
+        chain.push(parent.clone());
+        current = parent;
+    }
+    chain
+}
+
+/// Relative paths (forward slashes) of every library file a manifest references
+fn library_paths(manifest: &serde_json::Value) -> Vec<String> {
+    let mut paths = Vec::new();
+    let Some(libraries) = manifest.get("libraries").and_then(|v| v.as_array()) else {
+        return paths;
+    };
+    for library in libraries {
+        if let Some(downloads) = library.get("downloads") {
+            if let Some(path) = downloads.pointer("/artifact/path").and_then(|v| v.as_str()) {
+                paths.push(path.to_string());
+            }
+            if let Some(classifiers) = downloads.get("classifiers").and_then(|v| v.as_object()) {
+                paths.extend(
+                    classifiers
+                        .values()
+                        .filter_map(|c| c.get("path").and_then(|v| v.as_str()))
+                        .map(str::to_string),
+                );
+            }
+        }
+        // Libraries without downloads (Fabric, Quilt) are resolved from their maven name
+        if let Some(name) = library.get("name").and_then(|v| v.as_str()) {
+            if let Some(path) = maven_path(name) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+/// "group:artifact:version[:classifier][@ext]" to its path in a maven repository
+fn maven_path(name: &str) -> Option<String> {
+    let (coords, ext) = name.split_once('@').unwrap_or((name, "jar"));
+    let parts: Vec<&str> = coords.split(':').collect();
+    if parts.len() < 3 {
+        return None;
+    }
+    let (group, artifact, version) = (parts[0].replace('.', "/"), parts[1], parts[2]);
+    let file = match parts.get(3) {
+        Some(classifier) => format!("{}-{}-{}.{}", artifact, version, classifier, ext),
+        None => format!("{}-{}.{}", artifact, version, ext),
+    };
+    Some(format!("{}/{}/{}/{}", group, artifact, version, file))
+}
+
+fn collect_unused_versions(
+    minecraft_dir: &Path,
+    kept_versions: &HashSet<String>,
+    keep_all_vanilla: bool,
+    report: &mut PruneReport,
+) {
+    let Ok(entries) = std::fs::read_dir(minecraft_dir.join("versions")) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let version_id = entry.file_name().to_string_lossy().to_string();
+        if kept_versions.contains(&version_id) {
+            continue;
+        }
+        let json = read_version_json(minecraft_dir, &version_id);
+        // Folders without a readable version json aren't ours to judge
+        let Some(json) = json else {
+            continue;
+        };
+        if keep_all_vanilla && json.get("inheritsFrom").is_none() {
+            continue;
+        }
+        report.versions.bytes += dir_size(&path);
+        report
+            .versions
+            .paths
+            .push(path.to_string_lossy().to_string());
+    }
+}
+
+fn collect_unused_libraries(
+    minecraft_dir: &Path,
+    kept_libraries: &HashSet<String>,
+    report: &mut PruneReport,
+) {
+    let libraries_dir = minecraft_dir.join("libraries");
+    // Other files in the folder of a referenced library (sources, installer outputs) are kept too
+    let kept_dirs: HashSet<&str> = kept_libraries
+        .iter()
+        .filter_map(|p| p.rsplit_once('/').map(|(dir, _)| dir))
+        .collect();
+
+    for entry in walkdir::WalkDir::new(&libraries_dir)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+    {
+        let Ok(relative) = entry.path().strip_prefix(&libraries_dir) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        let dir = relative.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+        if kept_libraries.contains(&relative)
+            || kept_dirs.contains(dir)
+            || INSTALLER_LIBRARY_PREFIXES
+                .iter()
+                .any(|prefix| relative.starts_with(prefix))
+        {
+            continue;
+        }
+        report.libraries.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        report
+            .libraries
+            .paths
+            .push(entry.path().to_string_lossy().to_string());
+    }
+}
+
+fn collect_unused_assets(
+    minecraft_dir: &Path,
+    kept_indexes: &HashSet<String>,
+    report: &mut PruneReport,
+) {
+    let assets_dir = minecraft_dir.join("assets");
+    let indexes_dir = assets_dir.join("indexes");
+
+    let mut kept_objects: HashSet<String> = HashSet::new();
+    for index in kept_indexes {
+        let index_path = indexes_dir.join(format!("{}.json", index));
+        let parsed = std::fs::read_to_string(&index_path)
+            .ok()
+            .and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok());
+        let Some(parsed) = parsed else {
+            // Without the index we can't tell which objects it needs
+            report.skipped.push(format!(
+                "Asset index '{}' is missing, keeping all assets",
+                index
+            ));
+            return;
+        };
+        if let Some(objects) = parsed.get("objects").and_then(|v| v.as_object()) {
+            kept_objects.extend(
+                objects
+                    .values()
+                    .filter_map(|o| o.get("hash").and_then(|h| h.as_str()))
+                    .map(str::to_string),
+            );
+        }
+    }
+
+    if let Ok(entries) = std::fs::read_dir(&indexes_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(index) = path
+                .file_stem()
+                .filter(|_| path.extension().is_some_and(|e| e == "json"))
+            else {
+                continue;
+            };
+            if !kept_indexes.contains(index.to_string_lossy().as_ref()) {
+                report.assets.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+                report.assets.paths.push(path.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    for entry in walkdir::WalkDir::new(assets_dir.join("objects"))
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+    {
+        let hash = entry.file_name().to_string_lossy();
+        if kept_objects.contains(hash.as_ref()) {
+            continue;
+        }
+        report.assets.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        report
+            .assets
+            .paths
+            .push(entry.path().to_string_lossy().to_string());
+    }
+}
+
+fn collect_orphaned_isolated_dirs(
+    kable_dir: &Path,
+    installation_ids: &HashSet<String>,
+    report: &mut PruneReport,
+) {
+    for folder in ["assets", "natives"] {
+        let Ok(entries) = std::fs::read_dir(kable_dir.join(folder)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let id = entry.file_name().to_string_lossy().to_string();
+            if path.is_dir() && !installation_ids.contains(&id) {
+                report.isolated.bytes += dir_size(&path);
+                report
+                    .isolated
+                    .paths
+                    .push(path.to_string_lossy().to_string());
+            }
+        }
+    }
+}
+
+pub(super) fn dir_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+fn delete_paths(report: &PruneReport) {
+    let categories = [
+        &report.versions,
+        &report.libraries,
+        &report.assets,
+        &report.isolated,
+    ];
+    for path in categories.iter().flat_map(|c| c.paths.iter()) {
+        let path = Path::new(path);
+        let result = if path.is_dir() {
+            std::fs::remove_dir_all(path)
+        } else {
+            std::fs::remove_file(path)
+        };
+        if let Err(e) = result {
+            Logger::warn_global(&format!("Failed to remove {}: {}", path.display(), e), None);
+        }
+    }
+}
+
+/// Removes folders that became empty after deleting libraries, bottom-up
+fn remove_empty_dirs(root: &Path) {
+    for entry in walkdir::WalkDir::new(root)
+        .contents_first(true)
+        .min_depth(1)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_dir())
+    {
+        // Fails for non-empty folders, which is what we want
+        let _ = std::fs::remove_dir(entry.path());
+    }
+}
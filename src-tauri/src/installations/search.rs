@@ -0,0 +1,216 @@
+use super::{get_installations, KableInstallation};
+use serde::{Deserialize, Serialize};
+
+/// Half-open `[start, end)` range of character indices in a field that matched the query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Matched ranges for a single searchable field of an installation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallationFieldMatch {
+    /// 'name' | 'version_id' | 'description' | 'tag'
+    pub field: String,
+    /// Index into `installation.tags` when `field` is 'tag'
+    pub tag_index: Option<usize>,
+    pub ranges: Vec<MatchRange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallationSearchResult {
+    pub installation: KableInstallation,
+    pub score: u32,
+    pub matches: Vec<InstallationFieldMatch>,
+}
+
+// Field weights, higher means a match in this field ranks the installation higher
+const NAME_WEIGHT: u32 = 4;
+const TAG_WEIGHT: u32 = 3;
+const VERSION_WEIGHT: u32 = 2;
+const DESCRIPTION_WEIGHT: u32 = 1;
+
+/// Fuzzy-searches the cached installations by name, version_id, description and tags.
+/// Every whitespace-separated term of the query has to match at least one field.
+/// Results are ordered by score; ties keep the cache order (most recently used first).
+/// An empty query returns all installations with a score of 0.
+pub async fn search_installations(query: &str) -> Result<Vec<InstallationSearchResult>, String> {
+    let installations = get_installations().await?;
+
+    let terms: Vec<Vec<char>> = query.split_whitespace().map(lowercase_chars).collect();
+    if terms.is_empty() {
+        return Ok(installations
+            .into_iter()
+            .map(|installation| InstallationSearchResult {
+                installation,
+                score: 0,
+                matches: Vec::new(),
+            })
+            .collect());
+    }
+
+    let mut results: Vec<InstallationSearchResult> = installations
+        .into_iter()
+        .filter_map(|installation| score_installation(installation, &terms))
+        .collect();
+
+    // sort_by_key is stable, so equal scores stay in last_used order
+    results.sort_by_key(|r| std::cmp::Reverse(r.score));
+
+    crate::logging::debug(&format!(
+        "Installation search '{}' matched {} installations",
+        query,
+        results.len()
+    ));
+    Ok(results)
+}
+
+fn score_installation(
+    installation: KableInstallation,
+    terms: &[Vec<char>],
+) -> Option<InstallationSearchResult> {
+    let mut fields: Vec<(&str, Option<usize>, &str, u32)> = vec![
+        ("name", None, installation.name.as_str(), NAME_WEIGHT),
+        (
+            "version_id",
+            None,
+            installation.version_id.as_str(),
+            VERSION_WEIGHT,
+        ),
+    ];
+    if let Some(description) = installation.description.as_deref() {
+        fields.push(("description", None, description, DESCRIPTION_WEIGHT));
+    }
+    for (index, tag) in installation.tags.iter().enumerate() {
+        fields.push(("tag", Some(index), tag.as_str(), TAG_WEIGHT));
+    }
+
+    let mut field_ranges: Vec<Vec<MatchRange>> = vec![Vec::new(); fields.len()];
+    let mut score = 0;
+
+    for term in terms {
+        let mut best = 0;
+        for (i, (_, _, text, weight)) in fields.iter().enumerate() {
+            if let Some((term_score, ranges)) = fuzzy_match(text, term) {
+                best = best.max(term_score * weight);
+                field_ranges[i].extend(ranges);
+            }
+        }
+        if best == 0 {
+            return None;
+        }
+        score += best;
+    }
+
+    let matches = fields
+        .iter()
+        .zip(field_ranges)
+        .filter(|(_, ranges)| !ranges.is_empty())
+        .map(
+            |((field, tag_index, _, _), ranges)| InstallationFieldMatch {
+                field: field.to_string(),
+                tag_index: *tag_index,
+                ranges: merge_ranges(ranges),
+            },
+        )
+        .collect();
+
+    Some(InstallationSearchResult {
+        installation,
+        score,
+        matches,
+    })
+}
+
+/// Lowercases per character so indices stay aligned with the original string
+fn lowercase_chars(s: &str) -> Vec<char> {
+    s.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+fn is_word_start(text: &[char], index: usize) -> bool {
+    index == 0 || !text[index - 1].is_alphanumeric()
+}
+
+/// Matches a single lowercase term against `text`.
+/// Contiguous substring matches always outrank scattered subsequence matches.
+fn fuzzy_match(text: &str, term: &[char]) -> Option<(u32, Vec<MatchRange>)> {
+    let text = lowercase_chars(text);
+    if term.is_empty() || term.len() > text.len() {
+        return None;
+    }
+
+    // Contiguous substring, prefer the first one that starts at a word boundary
+    let mut substring_start = None;
+    for start in 0..=(text.len() - term.len()) {
+        if text[start..start + term.len()] == *term {
+            if is_word_start(&text, start) {
+                substring_start = Some(start);
+                break;
+            }
+            if substring_start.is_none() {
+                substring_start = Some(start);
+            }
+        }
+    }
+    if let Some(start) = substring_start {
+        let mut score = 100;
+        if term.len() == text.len() {
+            score += 100;
+        } else if start == 0 {
+            score += 50;
+        } else if is_word_start(&text, start) {
+            score += 25;
+        }
+        let range = MatchRange {
+            start,
+            end: start + term.len(),
+        };
+        return Some((score, vec![range]));
+    }
+
+    // Scattered subsequence, every term character has to appear in order
+    let mut positions = Vec::with_capacity(term.len());
+    let mut next = 0;
+    for c in term {
+        let found = text[next..].iter().position(|t| t == c)?;
+        positions.push(next + found);
+        next += found + 1;
+    }
+
+    let mut score = 10;
+    for (i, &pos) in positions.iter().enumerate() {
+        if i > 0 && positions[i - 1] + 1 == pos {
+            score += 5;
+        }
+        if is_word_start(&text, pos) {
+            score += 3;
+        }
+    }
+    // Keep scattered matches strictly below any substring match
+    let score = score.min(99);
+
+    let ranges = positions
+        .into_iter()
+        .map(|pos| MatchRange {
+            start: pos,
+            end: pos + 1,
+        })
+        .collect();
+    Some((score, merge_ranges(ranges)))
+}
+
+/// Sorts ranges and merges the ones that overlap or touch
+fn merge_ranges(mut ranges: Vec<MatchRange>) -> Vec<MatchRange> {
+    ranges.sort_by_key(|r| r.start);
+    let mut merged: Vec<MatchRange> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
@@ -0,0 +1,209 @@
+use super::{get_installations, get_versions, KableInstallation, LoaderKind};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoaderPlaytimeStats {
+    pub loader: LoaderKind,
+    pub installation_count: usize,
+    pub total_time_played_ms: u64,
+    pub times_launched: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MostPlayedInstallation {
+    pub id: String,
+    pub name: String,
+    pub version_id: String,
+    pub total_time_played_ms: u64,
+    pub times_launched: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaytimeStats {
+    pub installation_count: usize,
+    pub total_time_played_ms: u64,
+    pub total_launches: u64,
+    /// Only loaders that have at least one installation, most played first
+    pub per_loader: Vec<LoaderPlaytimeStats>,
+    /// None when nothing has been played yet
+    pub most_played: Option<MostPlayedInstallation>,
+}
+
+/// Aggregates playtime and launch counts over all cached installations
+pub async fn get_playtime_stats() -> Result<PlaytimeStats, String> {
+    let installations = get_installations().await?;
+    let versions = get_versions().await;
+
+    let mut per_loader: Vec<LoaderPlaytimeStats> = Vec::new();
+    let mut total_time_played_ms: u64 = 0;
+    let mut total_launches: u64 = 0;
+    let mut most_played: Option<&KableInstallation> = None;
+
+    for installation in &installations {
+        total_time_played_ms += installation.total_time_played_ms;
+        total_launches += installation.times_launched as u64;
+
+        let loader = versions
+            .get_version(&installation.version_id)
+            .map(|v| v.loader)
+            .unwrap_or_else(|| {
+                // Version ids that are not in the versions list, e.g. removed or custom versions
+                match super::extract_loader_from_version_id(&installation.version_id).as_deref() {
+                    Some("fabric") => LoaderKind::Fabric,
+                    Some("neoforge") => LoaderKind::NeoForge,
+                    Some("forge") => LoaderKind::Forge,
+                    Some("quilt") => LoaderKind::Quilt,
+                    _ => LoaderKind::Vanilla,
+                }
+            });
+        match per_loader.iter_mut().find(|s| s.loader == loader) {
+            Some(stats) => {
+                stats.installation_count += 1;
+                stats.total_time_played_ms += installation.total_time_played_ms;
+                stats.times_launched += installation.times_launched as u64;
+            }
+            None => per_loader.push(LoaderPlaytimeStats {
+                loader,
+                installation_count: 1,
+                total_time_played_ms: installation.total_time_played_ms,
+                times_launched: installation.times_launched as u64,
+            }),
+        }
+
+        if installation.total_time_played_ms > 0
+            && most_played
+                .is_none_or(|best| installation.total_time_played_ms > best.total_time_played_ms)
+        {
+            most_played = Some(installation);
+        }
+    }
+
+    per_loader.sort_by_key(|s| std::cmp::Reverse(s.total_time_played_ms));
+
+    Ok(PlaytimeStats {
+        installation_count: installations.len(),
+        total_time_played_ms,
+        total_launches,
+        per_loader,
+        most_played: most_played.map(|i| MostPlayedInstallation {
+            id: i.id.clone(),
+            name: i.name.clone(),
+            version_id: i.version_id.clone(),
+            total_time_played_ms: i.total_time_played_ms,
+            times_launched: i.times_launched,
+        }),
+    })
+}
+
+/// What is in the mods, resourcepacks and shaderpacks folders of one installation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstallationContentCounts {
+    /// Enabled and disabled mods
+    pub mods: usize,
+    pub enabled_mods: usize,
+    /// Resource packs including disabled ones
+    pub resourcepacks: usize,
+    /// Shader packs including disabled ones
+    pub shaders: usize,
+    /// Folders that are the global .minecraft one ('mods' | 'resourcepacks' | 'shaderpacks'),
+    /// their counts include content of every installation without a dedicated folder
+    pub shared_folders: Vec<String>,
+}
+
+/// Counts the content of an installation's folders, reading each folder once on a blocking task
+pub async fn get_installation_content_counts(
+    installation_id: &str,
+) -> Result<InstallationContentCounts, String> {
+    let installation = super::get_installation(installation_id)
+        .await?
+        .ok_or_else(|| format!("Installation with id '{}' not found", installation_id))?;
+    tokio::task::spawn_blocking(move || count_content(&installation))
+        .await
+        .map_err(|e| format!("Content count task failed: {}", e))?
+}
+
+fn count_content(installation: &KableInstallation) -> Result<InstallationContentCounts, String> {
+    let minecraft_dir = crate::get_default_minecraft_dir()?;
+    let mut counts = InstallationContentCounts::default();
+
+    // No mods folder at all yet just means no mods, and a stats query doesn't create it
+    if let Ok(mods_dir) = installation.resolve_mods_dir() {
+        let mut disabled = count_entries(&mods_dir.join("disabled"), is_mod_jar);
+        counts.enabled_mods = count_entries(&mods_dir, |path, name| {
+            if name.ends_with(".jar.disabled") && path.is_file() {
+                disabled += 1;
+            }
+            is_mod_jar(path, name)
+        });
+        counts.mods = counts.enabled_mods + disabled;
+        if mods_dir == minecraft_dir.join("mods") {
+            counts.shared_folders.push("mods".to_string());
+        }
+    }
+
+    let packs_dir = installation.find_resourcepacks_dir()?;
+    let merged_dir = packs_dir.join("merged");
+    let individual_dir = packs_dir.join("individual");
+    let pack_dirs = if merged_dir.exists() || individual_dir.exists() {
+        vec![
+            merged_dir.join("disabled"),
+            individual_dir.join("disabled"),
+            merged_dir,
+            individual_dir,
+        ]
+    } else {
+        vec![packs_dir.join("disabled"), packs_dir.clone()]
+    };
+    counts.resourcepacks = pack_dirs
+        .iter()
+        .map(|dir| count_entries(dir, is_resourcepack))
+        .sum();
+    if packs_dir == minecraft_dir.join("resourcepacks") {
+        counts.shared_folders.push("resourcepacks".to_string());
+    }
+
+    let shaders_dir = installation.find_shaderpacks_dir()?;
+    counts.shaders = count_entries(&shaders_dir, is_shaderpack)
+        + count_entries(&shaders_dir.join("disabled"), is_shaderpack);
+    if shaders_dir == minecraft_dir.join("shaderpacks") {
+        counts.shared_folders.push("shaderpacks".to_string());
+    }
+
+    Ok(counts)
+}
+
+/// Entries of `dir` accepted by `accept(path, file_name)`, hidden entries are skipped.
+/// A missing folder has nothing in it.
+fn count_entries(dir: &Path, mut accept: impl FnMut(&Path, &str) -> bool) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            !name.starts_with('.') && accept(&entry.path(), &name)
+        })
+        .count()
+}
+
+fn is_mod_jar(path: &Path, name: &str) -> bool {
+    name.ends_with(".jar") && path.is_file()
+}
+
+fn is_resourcepack(path: &Path, name: &str) -> bool {
+    if name == "kable-merged.zip" || name == "disabled" {
+        return false;
+    }
+    if path.is_dir() {
+        path.join("pack.mcmeta").exists()
+    } else {
+        name.ends_with(".zip")
+    }
+}
+
+fn is_shaderpack(path: &Path, name: &str) -> bool {
+    name != "disabled" && (path.is_dir() || name.ends_with(".zip"))
+}
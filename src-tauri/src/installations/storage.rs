@@ -0,0 +1,179 @@
+use super::get_installations;
+use super::kable_profiles::KableInstallation;
+use super::prune::dir_size;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::task;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageCategory {
+    /// Folders and files that were measured, only those that exist
+    pub paths: Vec<String>,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstallationStorage {
+    pub installation_id: String,
+    pub name: String,
+    /// Dedicated mods/resourcepacks/shaderpacks/config folders, isolated assets/natives and game logs
+    pub paths: Vec<String>,
+    pub bytes: u64,
+}
+
+/// Disk usage of everything the launcher stores, for the maintenance overview
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageReport {
+    /// When the report was computed (RFC 3339), it is slow to compute so the UI may cache it
+    pub generated_at: String,
+    pub versions: StorageCategory,
+    pub libraries: StorageCategory,
+    pub assets: StorageCategory,
+    pub installations: Vec<InstallationStorage>,
+    /// Provider, tag and hash caches, cached manifests and modpack temp folders
+    pub caches: StorageCategory,
+    /// Launcher logs, the game logs are part of `installations`
+    pub logs: StorageCategory,
+    pub exports: StorageCategory,
+    pub world_backups: StorageCategory,
+    pub total_bytes: u64,
+}
+
+/// Measures the versions, libraries and assets in .minecraft and what Kable keeps in its own
+/// directory. Every category is measured in its own blocking task so they run side by side.
+pub async fn get_storage_report() -> Result<StorageReport, String> {
+    let minecraft_dir = crate::get_default_minecraft_dir()?;
+    let kable_dir = crate::get_minecraft_kable_dir()?;
+    let installations = get_installations().await?;
+
+    let measure_paths = |paths: Vec<PathBuf>| task::spawn_blocking(move || measure(&paths));
+    let versions = measure_paths(vec![minecraft_dir.join("versions")]);
+    let libraries = measure_paths(vec![minecraft_dir.join("libraries")]);
+    let assets = measure_paths(vec![minecraft_dir.join("assets")]);
+    // Game logs are counted with their installation
+    let logs = measure_paths(vec![
+        kable_dir.join("logs").join("launcher"),
+        kable_dir.join("logs").join("installations"),
+    ]);
+    let exports = measure_paths(vec![kable_dir.join("exports")]);
+    let world_backups = measure_paths(vec![kable_dir.join("world-backups")]);
+    let caches = {
+        let kable_dir = kable_dir.clone();
+        task::spawn_blocking(move || measure(&cache_paths(&kable_dir)))
+    };
+    let per_installation: Vec<_> = installations
+        .into_iter()
+        .map(|installation| {
+            let kable_dir = kable_dir.clone();
+            task::spawn_blocking(move || {
+                let measured = measure(&installation_paths(&kable_dir, &installation));
+                InstallationStorage {
+                    installation_id: installation.id,
+                    name: installation.name,
+                    paths: measured.paths,
+                    bytes: measured.bytes,
+                }
+            })
+        })
+        .collect();
+
+    let join_error = |e: task::JoinError| format!("Storage report task join error: {}", e);
+    let mut report = StorageReport {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        versions: versions.await.map_err(join_error)?,
+        libraries: libraries.await.map_err(join_error)?,
+        assets: assets.await.map_err(join_error)?,
+        caches: caches.await.map_err(join_error)?,
+        logs: logs.await.map_err(join_error)?,
+        exports: exports.await.map_err(join_error)?,
+        world_backups: world_backups.await.map_err(join_error)?,
+        ..Default::default()
+    };
+    for handle in per_installation {
+        report.installations.push(handle.await.map_err(join_error)?);
+    }
+    report
+        .installations
+        .sort_by_key(|i| std::cmp::Reverse(i.bytes));
+
+    report.total_bytes = [
+        &report.versions,
+        &report.libraries,
+        &report.assets,
+        &report.caches,
+        &report.logs,
+        &report.exports,
+        &report.world_backups,
+    ]
+    .iter()
+    .map(|c| c.bytes)
+    .sum::<u64>()
+        + report.installations.iter().map(|i| i.bytes).sum::<u64>();
+    Ok(report)
+}
+
+fn measure(paths: &[PathBuf]) -> StorageCategory {
+    let mut category = StorageCategory::default();
+    for path in paths {
+        let bytes = match std::fs::metadata(path) {
+            Ok(metadata) if metadata.is_dir() => dir_size(path),
+            Ok(metadata) => metadata.len(),
+            Err(_) => continue,
+        };
+        category.bytes += bytes;
+        category.paths.push(path.to_string_lossy().to_string());
+    }
+    category
+}
+
+/// The `*_cache.json` files and cache folders in the kable dir
+fn cache_paths(kable_dir: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![kable_dir.join("manifests"), kable_dir.join("tmp")];
+    if let Ok(entries) = std::fs::read_dir(kable_dir) {
+        paths.extend(entries.flatten().map(|e| e.path()).filter(|p| {
+            p.file_name()
+                .is_some_and(|n| n.to_string_lossy().ends_with("_cache.json"))
+        }));
+    }
+    paths
+}
+
+fn installation_paths(kable_dir: &Path, installation: &KableInstallation) -> Vec<PathBuf> {
+    let mods = if installation.use_global_mods {
+        None
+    } else {
+        installation.dedicated_mods_folder.as_deref()
+    };
+    let mut paths: Vec<PathBuf> = [
+        ("mods", mods),
+        (
+            "resourcepacks",
+            installation.dedicated_resource_pack_folder.as_deref(),
+        ),
+        (
+            "shaderpacks",
+            installation.dedicated_shaders_folder.as_deref(),
+        ),
+        ("config", installation.dedicated_config_folder.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(category, folder)| Some(dedicated_path(kable_dir, category, folder?)))
+    .collect();
+    paths.push(kable_dir.join("assets").join(&installation.id));
+    paths.push(kable_dir.join("natives").join(&installation.id));
+    paths.push(kable_dir.join("logs").join(&installation.id));
+    paths
+}
+
+/// Relative dedicated folders are stored as "{category}/{id}" or just "{id}" and live in the kable dir
+pub(crate) fn dedicated_path(kable_dir: &Path, category: &str, folder: &str) -> PathBuf {
+    let path = PathBuf::from(folder);
+    if path.is_absolute() {
+        return path;
+    }
+    let normalized = folder.replace('\\', "/");
+    let cleaned = normalized
+        .strip_prefix(&format!("{}/", category))
+        .unwrap_or(&normalized);
+    kable_dir.join(category).join(cleaned)
+}
@@ -0,0 +1,179 @@
+use super::kable_profiles::KableInstallation;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Named installation templates, stored in the kable dir
+pub const INSTALLATION_TEMPLATES_FILE: &str = "installation_templates.json";
+
+/// The settings of an installation without its content (mods, packs, dedicated folders)
+/// or identity (name, version, playtime), used to set up new installations the same way
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstallationTemplate {
+    pub name: String,
+    pub created: String,
+    #[serde(default)]
+    pub java_args: Vec<String>,
+    /// Launch parameters such as the window size
+    #[serde(default)]
+    pub parameters_map: HashMap<String, String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub connect_server: Option<String>,
+    #[serde(default)]
+    pub native_arch_override: Option<String>,
+    #[serde(default)]
+    pub resolve_lwjgl_conflicts: bool,
+    #[serde(default)]
+    pub isolated_game_files: bool,
+    #[serde(default)]
+    pub enable_pack_merging: bool,
+    #[serde(default)]
+    pub http_proxy: Option<String>,
+    #[serde(default)]
+    pub https_proxy: Option<String>,
+    #[serde(default)]
+    pub extra_classpath: Vec<String>,
+    #[serde(default)]
+    pub java_agents: Vec<String>,
+    #[serde(default)]
+    pub on_game_launch: Option<String>,
+    #[serde(default)]
+    pub on_game_close: Option<String>,
+    #[serde(default)]
+    pub on_game_crash: Option<String>,
+}
+
+impl InstallationTemplate {
+    fn from_installation(name: &str, installation: &KableInstallation) -> Self {
+        InstallationTemplate {
+            name: name.to_string(),
+            created: chrono::Utc::now().to_rfc3339(),
+            java_args: installation.java_args.clone(),
+            parameters_map: installation.parameters_map.clone(),
+            tags: installation.tags.clone(),
+            connect_server: installation.connect_server.clone(),
+            native_arch_override: installation.native_arch_override.clone(),
+            resolve_lwjgl_conflicts: installation.resolve_lwjgl_conflicts,
+            isolated_game_files: installation.isolated_game_files,
+            enable_pack_merging: installation.enable_pack_merging,
+            http_proxy: installation.http_proxy.clone(),
+            https_proxy: installation.https_proxy.clone(),
+            extra_classpath: installation.extra_classpath.clone(),
+            java_agents: installation.java_agents.clone(),
+            on_game_launch: installation.on_game_launch.clone(),
+            on_game_close: installation.on_game_close.clone(),
+            on_game_crash: installation.on_game_crash.clone(),
+        }
+    }
+
+    fn apply_to(&self, installation: &mut KableInstallation) {
+        installation.java_args = self.java_args.clone();
+        installation.parameters_map = self.parameters_map.clone();
+        installation.tags = self.tags.clone();
+        installation.connect_server = self.connect_server.clone();
+        installation.native_arch_override = self.native_arch_override.clone();
+        installation.resolve_lwjgl_conflicts = self.resolve_lwjgl_conflicts;
+        installation.isolated_game_files = self.isolated_game_files;
+        installation.enable_pack_merging = self.enable_pack_merging;
+        installation.http_proxy = self.http_proxy.clone();
+        installation.https_proxy = self.https_proxy.clone();
+        installation.extra_classpath = self.extra_classpath.clone();
+        installation.java_agents = self.java_agents.clone();
+        installation.on_game_launch = self.on_game_launch.clone();
+        installation.on_game_close = self.on_game_close.clone();
+        installation.on_game_crash = self.on_game_crash.clone();
+    }
+}
+
+/// Stores the settings of an installation as a template, replacing a template with the same name
+pub async fn save_installation_template(
+    installation_id: &str,
+    template_name: &str,
+) -> Result<InstallationTemplate, String> {
+    let template_name = template_name.trim();
+    if template_name.is_empty() {
+        return Err("Template name is empty".to_string());
+    }
+    let installation = super::get_installation(installation_id)
+        .await?
+        .ok_or_else(|| format!("Installation with id '{}' not found", installation_id))?;
+
+    let template = InstallationTemplate::from_installation(template_name, &installation);
+    let mut templates = read_templates().await?;
+    templates.retain(|t| t.name != template_name);
+    templates.push(template.clone());
+    write_templates(&templates).await?;
+
+    crate::logging::info(&format!(
+        "Saved installation '{}' as template '{}'",
+        installation.name, template_name
+    ));
+    Ok(template)
+}
+
+/// All templates, sorted by name
+pub async fn list_installation_templates() -> Result<Vec<InstallationTemplate>, String> {
+    let mut templates = read_templates().await?;
+    templates.sort_by_key(|t| t.name.to_lowercase());
+    Ok(templates)
+}
+
+pub async fn delete_installation_template(template_name: &str) -> Result<(), String> {
+    let mut templates = read_templates().await?;
+    let before = templates.len();
+    templates.retain(|t| t.name != template_name);
+    if templates.len() == before {
+        return Err(format!("Template '{}' not found", template_name));
+    }
+    write_templates(&templates).await
+}
+
+/// Creates a new installation of `version_id` with the settings of a template
+pub async fn create_installation_from_template(
+    version_id: &str,
+    template_name: &str,
+) -> Result<KableInstallation, String> {
+    let template = read_templates()
+        .await?
+        .into_iter()
+        .find(|t| t.name == template_name)
+        .ok_or_else(|| format!("Template '{}' not found", template_name))?;
+
+    let mut installation = super::create_installation(version_id).await?;
+    template.apply_to(&mut installation);
+    super::modify_installation(&installation.id, installation.clone()).await?;
+
+    crate::logging::info(&format!(
+        "Applied template '{}' to new installation '{}'",
+        template_name, installation.name
+    ));
+    Ok(installation)
+}
+
+fn templates_path() -> Result<PathBuf, String> {
+    Ok(crate::get_minecraft_kable_dir()?.join(INSTALLATION_TEMPLATES_FILE))
+}
+
+async fn read_templates() -> Result<Vec<InstallationTemplate>, String> {
+    let path = templates_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", INSTALLATION_TEMPLATES_FILE, e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", INSTALLATION_TEMPLATES_FILE, e))
+}
+
+async fn write_templates(templates: &[InstallationTemplate]) -> Result<(), String> {
+    let path = templates_path()?;
+    if let Some(parent) = path.parent() {
+        crate::ensure_folder(parent).await?;
+    }
+    let content = serde_json::to_string_pretty(templates)
+        .map_err(|e| format!("Failed to serialize {}: {}", INSTALLATION_TEMPLATES_FILE, e))?;
+    crate::write_file_atomic_async(&path, content.as_bytes()).await
+}
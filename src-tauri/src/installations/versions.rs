@@ -1,6 +1,7 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use strum::{EnumIter, IntoEnumIterator};
@@ -154,6 +155,25 @@ pub struct VersionData {
     pub display_name: String,
     pub is_stable: bool,
     pub extra: serde_json::Value,
+    /// Loaders with builds for this game version, only filled in for vanilla versions
+    #[serde(default)]
+    pub supports_loaders: Vec<LoaderKind>,
+    #[serde(default)]
+    pub source: VersionSource,
+    /// Its version json is in .minecraft/versions, so it launches without downloading it first
+    #[serde(default)]
+    pub installed: bool,
+}
+
+/// Where a version entry came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionSource {
+    /// The version lists of Mojang and the loaders
+    #[default]
+    Remote,
+    /// Only known from a version json on disk, e.g. when offline or for custom versions
+    Local,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -323,6 +343,9 @@ impl Loader for VanillaLoader {
                         display_name: id.to_string(),
                         is_stable,
                         extra: v.clone(),
+                        supports_loaders: Vec::new(),
+                        source: VersionSource::Remote,
+                        installed: false,
                     }
                 })
                 .collect();
@@ -391,6 +414,9 @@ impl Loader for FabricLoader {
                             display_name: format!("Fabric {} for MC {}", fabricv, mcv.version_id),
                             is_stable: stable,
                             extra,
+                            supports_loaders: Vec::new(),
+                            source: VersionSource::Remote,
+                            installed: false,
                         });
                     }
                 }
@@ -466,6 +492,9 @@ impl Loader for IrisFabricLoader {
                             ),
                             is_stable: stable,
                             extra,
+                            supports_loaders: Vec::new(),
+                            source: VersionSource::Remote,
+                            installed: false,
                         });
                     }
                 }
@@ -521,6 +550,9 @@ impl Loader for ForgeLoader {
                             display_name,
                             is_stable: true,
                             extra: json!({ "forge_version": forge_version_display, "minecraft_version": mc_version }),
+                            supports_loaders: Vec::new(),
+                            source: VersionSource::Remote,
+                            installed: false,
                         });
                     }
                 }
@@ -586,6 +618,9 @@ impl Loader for NeoForgeLoader {
                         "minecraft_version": mc_version,
                         "is_snapshot": is_snapshot
                     }),
+                    supports_loaders: Vec::new(),
+                    source: VersionSource::Remote,
+                    installed: false,
                 });
             }
             Ok(out)
@@ -631,6 +666,9 @@ impl Loader for QuiltLoader {
                             display_name: format!("Quilt {} for MC {}", quilt_ver, mcv.version_id),
                             is_stable: stable,
                             extra: json!({ "quilt_version": quilt_ver, "minecraft_version": mcv.version_id, "stable": stable }),
+                            supports_loaders: Vec::new(),
+                            source: VersionSource::Remote,
+                            installed: false,
                         });
                     }
                 }
@@ -665,10 +703,19 @@ pub async fn build_versions(force_refresh: bool) -> Versions {
 
     // First, get vanilla versions (needed for some loaders)
     let vanilla_loader = VanillaLoader;
-    let vanilla_versions = vanilla_loader
+    let mut vanilla_versions = vanilla_loader
         .get_versions(None, force_refresh)
         .await
         .unwrap_or_default();
+    let support = loader_support(force_refresh).await;
+    for version in &mut vanilla_versions {
+        version.supports_loaders = support
+            .iter()
+            .filter(|(_, game_versions)| game_versions.contains(&version.version_id))
+            .map(|(kind, _)| *kind)
+            .collect();
+        version.supports_loaders.sort_by_key(|kind| *kind as u8);
+    }
     Logger::debug_global(
         &format!("Loaded {} vanilla versions", vanilla_versions.len()),
         None,
@@ -730,6 +777,49 @@ pub async fn build_versions(force_refresh: bool) -> Versions {
         }
     }
 
+    // Versions on disk, so installed versions show up also when a list couldn't be fetched
+    let local_versions = get_local_versions().await;
+    let mut local_only = Vec::new();
+    for local in local_versions {
+        match versions
+            .0
+            .iter_mut()
+            .find(|v| v.version_id == local.version_id)
+        {
+            Some(remote) => remote.installed = true,
+            None => local_only.push(local),
+        }
+    }
+    if !local_only.is_empty() {
+        Logger::debug_global(
+            &format!("Loaded {} versions only found on disk", local_only.len()),
+            None,
+        );
+        versions.extend(local_only.clone());
+        if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
+            if let Some(app_handle) = handle_guard.as_ref() {
+                for kind in LoaderKind::iter() {
+                    let chunk: Vec<VersionData> = local_only
+                        .iter()
+                        .filter(|v| v.loader == kind)
+                        .cloned()
+                        .collect();
+                    if chunk.is_empty() {
+                        continue;
+                    }
+                    let _ = app_handle.emit(
+                        "versions-chunk-loaded",
+                        VersionsChunk {
+                            loader: kind,
+                            versions: chunk,
+                            is_complete: false,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
     // Emit completion event
     if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
         if let Some(app_handle) = handle_guard.as_ref() {
@@ -746,6 +836,165 @@ pub async fn build_versions(force_refresh: bool) -> Versions {
     versions
 }
 
+/// Versions with a json in .minecraft/versions, found without touching the network.
+/// Loader versions are recognized by their id, their game version is the one they inherit from.
+pub async fn get_local_versions() -> Versions {
+    let Ok(minecraft_dir) = crate::get_default_minecraft_dir() else {
+        return Versions::default();
+    };
+    tokio::task::spawn_blocking(move || scan_local_versions(&minecraft_dir.join("versions")))
+        .await
+        .unwrap_or_default()
+}
+
+fn scan_local_versions(versions_dir: &Path) -> Versions {
+    let Ok(entries) = std::fs::read_dir(versions_dir) else {
+        return Versions::default();
+    };
+    let mut out = Vec::new();
+    for entry in entries.flatten() {
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+        let json_path = entry.path().join(format!("{}.json", dir_name));
+        let Some(json) = std::fs::read_to_string(&json_path)
+            .ok()
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        else {
+            continue;
+        };
+        let version_id = json["id"].as_str().unwrap_or(&dir_name).to_string();
+        let inherits_from = json["inheritsFrom"].as_str().map(str::to_string);
+        let loader = local_loader_kind(&version_id, inherits_from.is_some());
+        let minecraft_version = inherits_from.clone().unwrap_or_else(|| version_id.clone());
+        let version_type = json["type"].as_str().unwrap_or("release");
+        out.push(VersionData {
+            display_name: if loader == LoaderKind::Vanilla {
+                version_id.clone()
+            } else {
+                format!("{} for MC {}", version_id, minecraft_version)
+            },
+            version_id,
+            loader,
+            is_stable: version_type == "release",
+            extra: json!({
+                "minecraft_version": minecraft_version,
+                "type": version_type,
+                "releaseTime": json["releaseTime"],
+            }),
+            supports_loaders: Vec::new(),
+            source: VersionSource::Local,
+            installed: true,
+        });
+    }
+    out.sort_by(|a, b| b.version_id.cmp(&a.version_id));
+    Versions(out)
+}
+
+/// Loader of a version json on disk, by the id patterns the loader installers use
+fn local_loader_kind(version_id: &str, inherits: bool) -> LoaderKind {
+    let id = version_id.to_lowercase();
+    if id.starts_with("iris-fabric-loader-") {
+        LoaderKind::IrisFabric
+    } else if id.starts_with("fabric-loader-") {
+        LoaderKind::Fabric
+    } else if id.starts_with("quilt-loader-") {
+        LoaderKind::Quilt
+    } else if id.starts_with("neoforge-") || id.contains("-neoforge-") {
+        LoaderKind::NeoForge
+    } else if id.contains("forge") {
+        LoaderKind::Forge
+    } else if inherits && id.contains("fabric") {
+        LoaderKind::Fabric
+    } else {
+        LoaderKind::Vanilla
+    }
+}
+
+/// Game versions each loader has builds for
+type LoaderSupport = HashMap<LoaderKind, HashSet<String>>;
+
+/// Fetched once per session
+static LOADER_SUPPORT: once_cell::sync::Lazy<tokio::sync::Mutex<Option<LoaderSupport>>> =
+    once_cell::sync::Lazy::new(|| tokio::sync::Mutex::new(None));
+
+/// The game versions every loader except vanilla supports, from the loaders' meta APIs.
+/// A loader whose meta can't be fetched supports nothing, so it is greyed out rather than offered.
+pub async fn loader_support(force_refresh: bool) -> HashMap<LoaderKind, HashSet<String>> {
+    let mut cached = LOADER_SUPPORT.lock().await;
+    if let (Some(support), false) = (cached.as_ref(), force_refresh) {
+        return support.clone();
+    }
+
+    let game_list = |data: serde_json::Value| -> HashSet<String> {
+        data.as_array()
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v["version"].as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut support = HashMap::new();
+    let fetch = |url: &'static str, cache_filename: &'static str| async move {
+        fetch_with_cache(url, cache_filename, force_refresh)
+            .await
+            .map_err(|e| {
+                crate::logging::Logger::warn_global(
+                    &format!("Failed to load loader support from {}: {}", url, e),
+                    None,
+                );
+            })
+            .ok()
+    };
+
+    let fabric = fetch(
+        "https://meta.fabricmc.net/v2/versions/game",
+        "fabric-game.json",
+    )
+    .await
+    .map(game_list)
+    .unwrap_or_default();
+    // Iris installs on top of Fabric
+    support.insert(LoaderKind::IrisFabric, fabric.clone());
+    support.insert(LoaderKind::Fabric, fabric);
+    let quilt = fetch(
+        "https://meta.quiltmc.org/v3/versions/game",
+        "quilt-game.json",
+    )
+    .await
+    .map(game_list)
+    .unwrap_or_default();
+    support.insert(LoaderKind::Quilt, quilt);
+    // Forge's metadata is keyed by game version
+    let forge = fetch(
+        "https://files.minecraftforge.net/net/minecraftforge/forge/maven-metadata.json",
+        LoaderKind::Forge.cache_filename(),
+    )
+    .await
+    .and_then(|m| m.as_object().map(|o| o.keys().cloned().collect()))
+    .unwrap_or_default();
+    support.insert(LoaderKind::Forge, forge);
+    let neoforge = fetch(
+        "https://maven.neoforged.net/api/maven/versions/releases/net%2Fneoforged%2Fneoforge",
+        LoaderKind::NeoForge.cache_filename(),
+    )
+    .await
+    .map(|m| {
+        m["versions"]
+            .as_array()
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().and_then(neoforge_game_version))
+                    .collect()
+            })
+            .unwrap_or_default()
+    })
+    .unwrap_or_default();
+    support.insert(LoaderKind::NeoForge, neoforge);
+
+    *cached = Some(support.clone());
+    support
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VersionsChunk {
     pub loader: LoaderKind,
@@ -757,3 +1006,173 @@ pub struct VersionsChunk {
 pub struct VersionsComplete {
     pub total_count: usize,
 }
+
+/// Whether a loader build can be used with a game version, with the reason when it can't
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoaderCompatibility {
+    pub compatible: bool,
+    pub reason: Option<String>,
+}
+
+impl LoaderCompatibility {
+    fn compatible() -> Self {
+        LoaderCompatibility {
+            compatible: true,
+            reason: None,
+        }
+    }
+
+    fn incompatible(reason: String) -> Self {
+        LoaderCompatibility {
+            compatible: false,
+            reason: Some(reason),
+        }
+    }
+}
+
+/// Checks a loader build against a game version using the loader's meta API.
+/// The builds per game version are cached in the manifests folder like the version lists.
+pub async fn is_loader_compatible(
+    loader: LoaderKind,
+    loader_version: &str,
+    game_version: &str,
+) -> Result<LoaderCompatibility, String> {
+    let loader_version = loader_version.trim();
+    let game_version = game_version.trim();
+    if game_version.is_empty() {
+        return Err("Game version is empty".to_string());
+    }
+
+    match loader {
+        LoaderKind::Vanilla => Ok(LoaderCompatibility::compatible()),
+        LoaderKind::Fabric | LoaderKind::IrisFabric | LoaderKind::Quilt => {
+            let (name, url) = if loader == LoaderKind::Quilt {
+                (
+                    "Quilt",
+                    format!(
+                        "https://meta.quiltmc.org/v3/versions/loader/{}",
+                        urlencoding::encode(game_version)
+                    ),
+                )
+            } else {
+                (
+                    "Fabric",
+                    format!(
+                        "https://meta.fabricmc.net/v2/versions/loader/{}",
+                        urlencoding::encode(game_version)
+                    ),
+                )
+            };
+            let cache_filename = format!(
+                "{}-loaders-{}.json",
+                name.to_lowercase(),
+                cache_safe_name(game_version)
+            );
+            let builds = fetch_with_cache(&url, &cache_filename, false)
+                .await
+                .map_err(|e| e.to_string())?;
+            // Each entry is { loader: { version }, intermediary/hashed, launcherMeta }
+            let versions: Vec<&str> = builds
+                .as_array()
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|b| b["loader"]["version"].as_str())
+                        .collect()
+                })
+                .unwrap_or_default();
+            if versions.is_empty() {
+                Ok(LoaderCompatibility::incompatible(format!(
+                    "{} doesn't support Minecraft {}",
+                    name, game_version
+                )))
+            } else if !versions.contains(&loader_version) {
+                Ok(LoaderCompatibility::incompatible(format!(
+                    "{} loader {} doesn't support Minecraft {}",
+                    name, loader_version, game_version
+                )))
+            } else {
+                Ok(LoaderCompatibility::compatible())
+            }
+        }
+        LoaderKind::Forge => {
+            let url =
+                "https://files.minecraftforge.net/net/minecraftforge/forge/maven-metadata.json";
+            let metadata = fetch_with_cache(url, loader.cache_filename(), false)
+                .await
+                .map_err(|e| e.to_string())?;
+            // Builds are listed per game version as "{mc_version}-{forge_version}"
+            let Some(builds) = metadata[game_version].as_array() else {
+                return Ok(LoaderCompatibility::incompatible(format!(
+                    "Forge doesn't support Minecraft {}",
+                    game_version
+                )));
+            };
+            let full_version = if loader_version.starts_with(&format!("{}-", game_version)) {
+                loader_version.to_string()
+            } else {
+                format!("{}-{}", game_version, loader_version)
+            };
+            if builds.iter().any(|b| b.as_str() == Some(&full_version)) {
+                Ok(LoaderCompatibility::compatible())
+            } else {
+                Ok(LoaderCompatibility::incompatible(format!(
+                    "Forge {} doesn't support Minecraft {}",
+                    loader_version, game_version
+                )))
+            }
+        }
+        LoaderKind::NeoForge => {
+            let url = "https://maven.neoforged.net/api/maven/versions/releases/net%2Fneoforged%2Fneoforge";
+            let metadata = fetch_with_cache(url, loader.cache_filename(), false)
+                .await
+                .map_err(|e| e.to_string())?;
+            let known = metadata["versions"]
+                .as_array()
+                .is_some_and(|a| a.iter().any(|v| v.as_str() == Some(loader_version)));
+            if !known {
+                return Ok(LoaderCompatibility::incompatible(format!(
+                    "NeoForge {} doesn't exist",
+                    loader_version
+                )));
+            }
+            match neoforge_game_version(loader_version) {
+                Some(supported) if supported == game_version => {
+                    Ok(LoaderCompatibility::compatible())
+                }
+                Some(supported) => Ok(LoaderCompatibility::incompatible(format!(
+                    "NeoForge {} is for Minecraft {}, not {}",
+                    loader_version, supported, game_version
+                ))),
+                None => Ok(LoaderCompatibility::incompatible(format!(
+                    "Can't tell which Minecraft version NeoForge {} is for",
+                    loader_version
+                ))),
+            }
+        }
+    }
+}
+
+/// NeoForge versions start with the game version without the leading "1.":
+/// 20.4.237 is for 1.20.4 and 21.0.167 for 1.21
+fn neoforge_game_version(neoforge_version: &str) -> Option<String> {
+    let mut parts = neoforge_version.split('.');
+    let major: u32 = parts.next()?.parse().ok()?;
+    let minor: u32 = parts.next()?.parse().ok()?;
+    Some(if minor == 0 {
+        format!("1.{}", major)
+    } else {
+        format!("1.{}.{}", major, minor)
+    })
+}
+
+fn cache_safe_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,82 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::Emitter;
+use tokio::sync::oneshot;
+
+/// How long an "ask" dialog waits for the user before falling back to doing nothing
+const ASK_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Choice used when the user doesn't answer in time or there is no window to ask in
+pub const NO_BEHAVIOR: &str = "keep_open";
+
+/// Behavior questions waiting for an answer from the frontend, by request id
+static PENDING_ANSWERS: Lazy<Mutex<HashMap<String, oneshot::Sender<String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Emits `event` with a request id and the options, then waits for `answer_launch_behavior`.
+/// Returns `NO_BEHAVIOR` when the answer doesn't come within the timeout.
+pub async fn ask_behavior(
+    app_handle: Option<&tauri::AppHandle>,
+    event: &str,
+    options: &[&str],
+    exit_code: Option<i32>,
+) -> String {
+    let Some(app) = app_handle else {
+        return NO_BEHAVIOR.to_string();
+    };
+
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let (sender, receiver) = oneshot::channel();
+    PENDING_ANSWERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(request_id.clone(), sender);
+
+    let mut payload = serde_json::json!({ "request_id": request_id, "options": options });
+    if let Some(exit_code) = exit_code {
+        payload["exit_code"] = serde_json::json!(exit_code);
+    }
+    if let Err(e) = app.emit(event, payload) {
+        crate::logging::Logger::warn_global(&format!("Failed to emit {}: {}", event, e), None);
+    }
+
+    let choice = match tokio::time::timeout(ASK_TIMEOUT, receiver).await {
+        Ok(Ok(choice)) if choice == NO_BEHAVIOR || options.contains(&choice.as_str()) => choice,
+        Ok(Ok(choice)) => {
+            crate::logging::Logger::warn_global(
+                &format!("Ignoring unknown answer '{}' to {}", choice, event),
+                None,
+            );
+            NO_BEHAVIOR.to_string()
+        }
+        _ => {
+            crate::logging::Logger::info_global(
+                &format!(
+                    "No answer to {} in time, keeping the launcher as it is",
+                    event
+                ),
+                None,
+            );
+            NO_BEHAVIOR.to_string()
+        }
+    };
+    PENDING_ANSWERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&request_id);
+    choice
+}
+
+/// Delivers the user's choice for an ask event, fails when the question already timed out
+pub fn answer_launch_behavior(request_id: &str, choice: &str) -> Result<(), String> {
+    let sender = PENDING_ANSWERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(request_id)
+        .ok_or_else(|| format!("No pending behavior question with id {}", request_id))?;
+    sender
+        .send(choice.to_string())
+        .map_err(|_| format!("Behavior question {} is no longer waiting", request_id))
+}
@@ -1,7 +1,6 @@
-use super::{LaunchContext, LaunchResult, Launchable};
+use super::{LaunchCommand, LaunchContext, LaunchResult, Launchable};
 use crate::launcher::utils::build_variable_map;
 use async_trait::async_trait;
-use reqwest::Client;
 /// Loads and merges a Fabric manifest, recursively resolving `inheritsFrom` and merging libraries and arguments.
 /// Returns the fully merged manifest as serde_json::Value.
 use serde::{Deserialize, Serialize};
@@ -201,6 +200,8 @@ pub struct FabricLaunchable;
 impl Launchable for FabricLaunchable {
     // TODO: Implement proper prepare logic... This is untested and may need adjustments
     async fn prepare(&self, context: &LaunchContext) -> Result<(), String> {
+        let _operation =
+            super::operations::Operation::start("fabric_install", Some(&context.installation.id));
         // 1. Check if manifest and jar already exist; if so, skip installer
         let version_id = &context.installation.version_id;
         let versions_dir = PathBuf::from(&context.minecraft_dir).join("versions");
@@ -229,9 +230,12 @@ impl Launchable for FabricLaunchable {
         // Variables needed for library checking and Iris mods (if applicable)
         let profile_json: String;
         let mc_version: String;
-        let client = Client::new();
+        let client = context.http_client()?;
 
         if need_fabric_files {
+            context
+                .progress
+                .stage("loader_install", &format!("Installing {}", version_id));
             // 2. Get version metadata from version.extra
             use crate::installations::get_version;
             let version_data = get_version(version_id.clone()).await;
@@ -408,6 +412,7 @@ impl Launchable for FabricLaunchable {
             let resolved_parent = crate::launcher::utils::ensure_version_manifest_and_jar(
                 parent_version,
                 &context.minecraft_dir,
+                &client,
             )
             .await
             .map_err(|e| format!("Failed to ensure parent Minecraft version: {e}"))?;
@@ -481,15 +486,22 @@ impl Launchable for FabricLaunchable {
             .map_err(|e| format!("Failed to parse profile JSON for library check: {e}"))?;
 
         let libraries_path = PathBuf::from(&context.minecraft_dir).join("libraries");
-        crate::launcher::utils::ensure_libraries(&manifest_for_libs, &libraries_path)
-            .await
-            .map_err(|e| format!("Failed to ensure Fabric libraries: {e}"))?;
+        crate::launcher::utils::ensure_libraries(
+            &manifest_for_libs,
+            &libraries_path,
+            &client,
+            &context.progress,
+        )
+        .await
+        .map_err(|e| format!("Failed to ensure Fabric libraries: {e}"))?;
 
         crate::logging::Logger::debug_global(
             "All Fabric libraries downloaded successfully",
             Some(&context.installation.id),
         );
 
+        crate::launcher::utils::ensure_isolated_assets(context, version_id).await?;
+
         // 9. For IrisFabric, download and install Iris+Sodium mods
         if version_id.contains("iris-fabric") {
             crate::logging::Logger::debug_global(
@@ -576,8 +588,7 @@ impl Launchable for FabricLaunchable {
         Ok(())
     }
 
-    async fn launch(&self, context: &LaunchContext) -> Result<LaunchResult, String> {
-        println!("FABRIC::launch() -> {}", context.installation.name);
+    async fn build_command(&self, context: &LaunchContext) -> Result<LaunchCommand, String> {
         // 1. Load and merge Fabric manifest as struct, then convert to generic manifest
         let version_id = &context.installation.version_id;
         let fabric_manifest_struct =
@@ -614,48 +625,9 @@ impl Launchable for FabricLaunchable {
             &version_jar_path,
             Some(&context.installation.id),
         );
-
-        // Clear natives folder to prevent version conflicts from previous launches
-        let natives_dir = PathBuf::from(&context.minecraft_dir).join("natives");
-        if natives_dir.exists() {
-            if let Err(e) = std::fs::remove_dir_all(&natives_dir) {
-                crate::logging::Logger::warn_global(
-                    &format!("Failed to clear natives directory (will continue): {}", e),
-                    Some(&context.installation.id),
-                );
-            } else {
-                crate::logging::Logger::debug_global(
-                    "Cleared natives directory to prevent version conflicts",
-                    Some(&context.installation.id),
-                );
-            }
-        }
-        // Recreate empty natives directory
-        if let Err(e) = crate::ensure_folder_sync(&natives_dir) {
-            crate::logging::Logger::warn_global(
-                &format!("Failed to recreate natives directory: {}", e),
-                Some(&context.installation.id),
-            );
-        }
-
-        // Extract native libraries from the manifest
-        if let Some(libs_array) = manifest.get("libraries").and_then(|v| v.as_array()) {
-            let libraries: Vec<crate::launcher::utils::Library> = libs_array
-                .iter()
-                .filter_map(|v| serde_json::from_value(v.clone()).ok())
-                .collect();
-            if let Err(e) = crate::launcher::utils::extract_natives(
-                &libraries,
-                &libraries_path,
-                &natives_dir,
-                Some(&context.installation.id),
-            ) {
-                crate::logging::Logger::warn_global(
-                    &format!("Failed to extract natives: {}", e),
-                    Some(&context.installation.id),
-                );
-            }
-        }
+        // Opt-in: keep a single LWJGL version on the classpath
+        let classpath = crate::launcher::utils::apply_lwjgl_conflict_fix(context, classpath);
+        let classpath = crate::launcher::utils::apply_extra_classpath(context, classpath);
 
         // 3. Build variable map
         let variables = build_variable_map(
@@ -691,10 +663,9 @@ impl Launchable for FabricLaunchable {
         // 5. Remove any -Dfabric.modsFolder from JVM args (we'll set it correctly below)
         cleaned_jvm_args.retain(|arg| !arg.starts_with("-Dfabric.modsFolder="));
 
-        // 6. Prepend installation-specific JVM args (Vec<String>) if present
-        if !context.installation.java_args.is_empty() {
-            cleaned_jvm_args.splice(0..0, context.installation.java_args.clone());
-        }
+        // 6. Place installation-specific JVM args before (or with the append marker after) the manifest args
+        // (normalized, conflicts are reported as warnings)
+        crate::launcher::utils::apply_installation_java_args(context, &mut cleaned_jvm_args);
 
         // 7. Add/overwrite with parameters_map (for --key style)
         for (k, v) in &context.installation.parameters_map {
@@ -721,7 +692,9 @@ impl Launchable for FabricLaunchable {
         );
 
         // 9. Game args (no mods folder override needed here, JVM property takes precedence)
-        let final_game_args_vec = game_args_vec.clone();
+        let mut final_game_args_vec = game_args_vec.clone();
+        final_game_args_vec.extend(crate::launcher::utils::connect_server_args(context));
+        crate::launcher::utils::apply_demo_mode(context, &mut final_game_args_vec);
 
         // 10. Build command: exactly like vanilla (single -cp, correct order)
         let java_path = crate::launcher::java::find_java_executable(
@@ -740,6 +713,68 @@ impl Launchable for FabricLaunchable {
         cmd.args(&final_game_args_vec);
         cmd.current_dir(&context.minecraft_dir);
 
+        Ok(LaunchCommand {
+            command: cmd,
+            manifest,
+            classpath,
+        })
+    }
+
+    async fn launch(&self, context: &LaunchContext) -> Result<LaunchResult, String> {
+        println!("FABRIC::launch() -> {}", context.installation.name);
+        // 1. Build the full command (manifest, classpath, variables, arguments)
+        let LaunchCommand {
+            command: cmd,
+            manifest,
+            ..
+        } = self.build_command(context).await?;
+
+        // 2. Prepare natives
+        let libraries_path = PathBuf::from(&context.minecraft_dir).join("libraries");
+
+        // Clear natives folder to prevent version conflicts from previous launches
+        let natives_dir = context.natives_dir();
+        if natives_dir.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&natives_dir) {
+                crate::logging::Logger::warn_global(
+                    &format!("Failed to clear natives directory (will continue): {}", e),
+                    Some(&context.installation.id),
+                );
+            } else {
+                crate::logging::Logger::debug_global(
+                    "Cleared natives directory to prevent version conflicts",
+                    Some(&context.installation.id),
+                );
+            }
+        }
+        // Recreate empty natives directory
+        if let Err(e) = crate::ensure_folder_sync(&natives_dir) {
+            crate::logging::Logger::warn_global(
+                &format!("Failed to recreate natives directory: {}", e),
+                Some(&context.installation.id),
+            );
+        }
+
+        // Extract native libraries from the manifest
+        if let Some(libs_array) = manifest.get("libraries").and_then(|v| v.as_array()) {
+            let libraries: Vec<crate::launcher::utils::Library> = libs_array
+                .iter()
+                .filter_map(|v| serde_json::from_value(v.clone()).ok())
+                .collect();
+            if let Err(e) = crate::launcher::utils::extract_natives(
+                &libraries,
+                &libraries_path,
+                &natives_dir,
+                crate::launcher::utils::native_arch_override(context),
+                Some(&context.installation.id),
+            ) {
+                crate::logging::Logger::warn_global(
+                    &format!("Failed to extract natives: {}", e),
+                    Some(&context.installation.id),
+                );
+            }
+        }
+
         // Use spawn_and_log_process utility
         let mut installation_json = serde_json::to_value(&context.installation)
             .map_err(|e| format!("Failed to serialize installation: {}", e))?;
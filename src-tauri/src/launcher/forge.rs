@@ -1,4 +1,4 @@
-use crate::launcher::launchables::{LaunchContext, LaunchResult, Launchable};
+use crate::launcher::launchables::{LaunchCommand, LaunchContext, LaunchResult, Launchable};
 use crate::launcher::utils::{
     build_classpath_from_manifest_with_instance, build_jvm_and_game_args_with_instance,
     build_variable_map, spawn_and_log_process,
@@ -29,13 +29,14 @@ pub struct ForgeLaunchable;
 
 #[async_trait]
 impl Launchable for ForgeLaunchable {
-    async fn prepare(&self, _context: &LaunchContext) -> Result<(), String> {
+    async fn prepare(&self, context: &LaunchContext) -> Result<(), String> {
         // For most Forge versions, if the manifest and jar exist, nothing to do
         // If you want to support auto-install, add logic here
-        Ok(())
+        crate::launcher::utils::ensure_isolated_assets(context, &context.installation.version_id)
+            .await
     }
 
-    async fn launch(&self, context: &LaunchContext) -> Result<LaunchResult, String> {
+    async fn build_command(&self, context: &LaunchContext) -> Result<LaunchCommand, String> {
         let version_id = &context.installation.version_id;
         let manifest = load_forge_manifest(&context.minecraft_dir, version_id)?;
 
@@ -57,48 +58,9 @@ impl Launchable for ForgeLaunchable {
             &version_jar_path,
             Some(&context.installation.id),
         );
-
-        // Clear natives folder to prevent version conflicts from previous launches
-        let natives_dir = PathBuf::from(&context.minecraft_dir).join("natives");
-        if natives_dir.exists() {
-            if let Err(e) = std::fs::remove_dir_all(&natives_dir) {
-                crate::logging::Logger::warn_global(
-                    &format!("Failed to clear natives directory (will continue): {}", e),
-                    Some(&context.installation.id),
-                );
-            } else {
-                crate::logging::Logger::debug_global(
-                    "Cleared natives directory to prevent version conflicts",
-                    Some(&context.installation.id),
-                );
-            }
-        }
-        // Recreate empty natives directory
-        if let Err(e) = crate::ensure_folder_sync(&natives_dir) {
-            crate::logging::Logger::warn_global(
-                &format!("Failed to recreate natives directory: {}", e),
-                Some(&context.installation.id),
-            );
-        }
-
-        // Extract native libraries from the manifest
-        if let Some(libs_array) = manifest.get("libraries").and_then(|v| v.as_array()) {
-            let libraries: Vec<crate::launcher::utils::Library> = libs_array
-                .iter()
-                .filter_map(|v| serde_json::from_value(v.clone()).ok())
-                .collect();
-            if let Err(e) = crate::launcher::utils::extract_natives(
-                &libraries,
-                &libraries_path,
-                &natives_dir,
-                Some(&context.installation.id),
-            ) {
-                crate::logging::Logger::warn_global(
-                    &format!("Failed to extract natives: {}", e),
-                    Some(&context.installation.id),
-                );
-            }
-        }
+        // Opt-in: keep a single LWJGL version on the classpath
+        let classpath = crate::launcher::utils::apply_lwjgl_conflict_fix(context, classpath);
+        let classpath = crate::launcher::utils::apply_extra_classpath(context, classpath);
 
         // Build variable map
         let variables = build_variable_map(
@@ -130,10 +92,9 @@ impl Launchable for ForgeLaunchable {
             cleaned_jvm_args.push(arg);
         }
 
-        // Prepend installation-specific JVM args if present
-        if !context.installation.java_args.is_empty() {
-            cleaned_jvm_args.splice(0..0, context.installation.java_args.clone());
-        }
+        // Place installation-specific JVM args before (or with the append marker after) the manifest args
+        // (normalized, conflicts are reported as warnings)
+        crate::launcher::utils::apply_installation_java_args(context, &mut cleaned_jvm_args);
 
         // Add/overwrite with parameters_map (for --key style)
         for (k, v) in &context.installation.parameters_map {
@@ -165,6 +126,8 @@ impl Launchable for ForgeLaunchable {
             final_game_args_vec.push("--gameDir".to_string());
             final_game_args_vec.push(context.minecraft_dir.clone());
         }
+        final_game_args_vec.extend(crate::launcher::utils::connect_server_args(context));
+        crate::launcher::utils::apply_demo_mode(context, &mut final_game_args_vec);
 
         // Build command: main class for Forge
         let java_path = crate::launcher::java::find_java_executable(
@@ -183,6 +146,66 @@ impl Launchable for ForgeLaunchable {
         cmd.args(&final_game_args_vec);
         cmd.current_dir(&context.minecraft_dir);
 
+        Ok(LaunchCommand {
+            command: cmd,
+            manifest,
+            classpath,
+        })
+    }
+
+    async fn launch(&self, context: &LaunchContext) -> Result<LaunchResult, String> {
+        // Build the full command (manifest, classpath, variables, arguments)
+        let LaunchCommand {
+            command: cmd,
+            manifest,
+            ..
+        } = self.build_command(context).await?;
+
+        let libraries_path = PathBuf::from(&context.minecraft_dir).join("libraries");
+
+        // Clear natives folder to prevent version conflicts from previous launches
+        let natives_dir = context.natives_dir();
+        if natives_dir.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&natives_dir) {
+                crate::logging::Logger::warn_global(
+                    &format!("Failed to clear natives directory (will continue): {}", e),
+                    Some(&context.installation.id),
+                );
+            } else {
+                crate::logging::Logger::debug_global(
+                    "Cleared natives directory to prevent version conflicts",
+                    Some(&context.installation.id),
+                );
+            }
+        }
+        // Recreate empty natives directory
+        if let Err(e) = crate::ensure_folder_sync(&natives_dir) {
+            crate::logging::Logger::warn_global(
+                &format!("Failed to recreate natives directory: {}", e),
+                Some(&context.installation.id),
+            );
+        }
+
+        // Extract native libraries from the manifest
+        if let Some(libs_array) = manifest.get("libraries").and_then(|v| v.as_array()) {
+            let libraries: Vec<crate::launcher::utils::Library> = libs_array
+                .iter()
+                .filter_map(|v| serde_json::from_value(v.clone()).ok())
+                .collect();
+            if let Err(e) = crate::launcher::utils::extract_natives(
+                &libraries,
+                &libraries_path,
+                &natives_dir,
+                crate::launcher::utils::native_arch_override(context),
+                Some(&context.installation.id),
+            ) {
+                crate::logging::Logger::warn_global(
+                    &format!("Failed to extract natives: {}", e),
+                    Some(&context.installation.id),
+                );
+            }
+        }
+
         // Use spawn_and_log_process utility
         let mut installation_json = serde_json::to_value(&context.installation)
             .map_err(|e| format!("Failed to serialize installation: {}", e))?;
@@ -0,0 +1,156 @@
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+/// The merged stdout and stderr of the last run of an instance, earlier runs are
+/// renamed to game-<time>.log when the next one starts
+pub const GAME_LOG_FILE: &str = "game.log";
+const ROTATED_PREFIX: &str = "game-";
+
+fn game_log_dir(instance_id: &str) -> Result<PathBuf, String> {
+    if instance_id.is_empty() || instance_id.contains(['/', '\\']) || instance_id.starts_with('.') {
+        return Err(format!("Invalid instance id '{}'", instance_id));
+    }
+    Ok(crate::get_minecraft_kable_dir()?
+        .join("logs")
+        .join(instance_id))
+}
+
+pub fn game_log_path(instance_id: &str) -> Result<PathBuf, String> {
+    Ok(game_log_dir(instance_id)?.join(GAME_LOG_FILE))
+}
+
+/// Writes the output of one game process to its game.log, lines are sent from the stdout and
+/// stderr readers and written in the order they arrive
+#[derive(Clone)]
+pub struct GameLogWriter {
+    sender: UnboundedSender<String>,
+}
+
+impl GameLogWriter {
+    /// Starts a new game.log for the instance, the one of the previous run is kept as
+    /// game-<time>.log. The file is closed once every clone of the writer is dropped.
+    pub async fn start(instance_id: &str) -> Result<Self, String> {
+        let path = game_log_path(instance_id)?;
+        if let Some(parent) = path.parent() {
+            crate::ensure_folder(parent).await?;
+        }
+        rotate(&path).await;
+        let file = tokio::fs::File::create(&path)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let (sender, mut receiver) = unbounded_channel::<String>();
+        tokio::spawn(async move {
+            let mut writer = tokio::io::BufWriter::new(file);
+            while let Some(line) = receiver.recv().await {
+                if writer.write_all(line.as_bytes()).await.is_err() {
+                    break;
+                }
+                // Flush once caught up, so the file is readable while the game runs
+                if receiver.is_empty() && writer.flush().await.is_err() {
+                    break;
+                }
+            }
+            let _ = writer.flush().await;
+        });
+        Ok(Self { sender })
+    }
+
+    /// `stream` is "stdout" or "stderr"
+    pub fn line(&self, stream: &str, line: &str) {
+        let timestamp = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S%.3f UTC");
+        let _ = self.sender.send(format!(
+            "[{}] [{}] {}\n",
+            timestamp,
+            stream.to_uppercase(),
+            line
+        ));
+    }
+}
+
+/// Renames the game.log of the previous run after the time it was last written to
+async fn rotate(path: &std::path::Path) {
+    let Ok(metadata) = tokio::fs::metadata(path).await else {
+        return;
+    };
+    let finished: chrono::DateTime<chrono::Utc> = metadata
+        .modified()
+        .map(Into::into)
+        .unwrap_or_else(|_| chrono::Utc::now());
+    let rotated = path.with_file_name(format!(
+        "{}{}.log",
+        ROTATED_PREFIX,
+        finished.format("%Y%m%d-%H%M%S%.3f")
+    ));
+    if let Err(e) = tokio::fs::rename(path, &rotated).await {
+        crate::logging::Logger::warn_global(
+            &format!("Failed to keep the previous game log: {}", e),
+            None,
+        );
+    }
+}
+
+/// Game logs of earlier runs, oldest first
+async fn rotated_logs(instance_id: &str) -> Result<Vec<PathBuf>, String> {
+    let dir = game_log_dir(instance_id)?;
+    let Ok(mut entries) = tokio::fs::read_dir(&dir).await else {
+        return Ok(Vec::new());
+    };
+    let mut logs = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with(ROTATED_PREFIX) && name.ends_with(".log") {
+            logs.push(entry.path());
+        }
+    }
+    // The timestamp in the name sorts chronologically
+    logs.sort();
+    Ok(logs)
+}
+
+/// Removes the oldest game logs so at most `keep` remain, the one of the last run included
+pub async fn prune_game_logs(instance_id: &str, keep: usize) -> Result<(), String> {
+    let rotated = rotated_logs(instance_id).await?;
+    let keep_rotated = keep.saturating_sub(1);
+    let excess = rotated.len().saturating_sub(keep_rotated);
+    for path in rotated.into_iter().take(excess) {
+        tokio::fs::remove_file(&path)
+            .await
+            .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Removes the game logs of an installation. While it runs the current game.log is still
+/// written to, so only the logs of earlier runs are removed then.
+pub async fn clear_game_logs(installation_id: &str) -> Result<(), String> {
+    let current = game_log_path(installation_id)?;
+    let keep_current = crate::launcher::is_installation_running(installation_id).await?;
+    for path in rotated_logs(installation_id)
+        .await?
+        .into_iter()
+        .chain((current.is_file() && !keep_current).then_some(current))
+    {
+        tokio::fs::remove_file(&path)
+            .await
+            .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+    }
+    crate::logging::info(&format!(
+        "Cleared the game logs of installation {}",
+        installation_id
+    ));
+    Ok(())
+}
+
+/// The game.log of the last (or running) process of an instance
+pub async fn get_game_log(instance_id: &str) -> Result<String, String> {
+    let path = game_log_path(instance_id)?;
+    if !path.is_file() {
+        return Err(format!("No game log found for instance '{}'", instance_id));
+    }
+    let bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    // Games may print in the platform's codepage, don't fail on it
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
@@ -1,5 +1,13 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Command;
+use std::sync::Mutex;
+
+/// version_id -> javaVersion.majorVersion, only filled for manifests that are on disk
+static REQUIRED_JAVA_CACHE: Lazy<Mutex<HashMap<String, u32>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
 
 /// Attempts to find a working Java executable, either from the provided path or common install locations.
 ///
@@ -373,6 +381,202 @@ fn find_java_linux() -> Result<String, String> {
     Err("Java not found. Please install Java 17+ or specify the Java path in settings.".to_string())
 }
 
+/// Runs `java -version` and returns the major version, None when it doesn't run or can't be parsed
+pub fn java_major_version(java_path: &str) -> Option<u32> {
+    let output = Command::new(java_path).arg("-version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let version_info = if stderr.trim().is_empty() {
+        String::from_utf8_lossy(&output.stdout)
+    } else {
+        stderr
+    };
+    Some(extract_java_version(&version_info)).filter(|major| *major > 0)
+}
+
+/// What a Java executable reports about itself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaInstallation {
+    pub path: String,
+    /// Full version, e.g. "17.0.10" or "1.8.0_402"
+    pub version: String,
+    pub major_version: u32,
+    pub vendor: Option<String>,
+    /// "x86_64", "arm64" or "x86", like the natives classifiers
+    pub arch: String,
+    pub is_64bit: bool,
+}
+
+/// Runs `java -XshowSettings:properties -version` and reads the version, vendor and arch from it.
+/// Fails when the path isn't a runnable Java.
+pub fn probe_java(java_path: &str) -> Result<JavaInstallation, String> {
+    let java_path = java_path.trim();
+    if java_path.is_empty() {
+        return Err("Java path is empty".to_string());
+    }
+    let mut command = Command::new(java_path);
+    command.args(["-XshowSettings:properties", "-version"]);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // CREATE_NO_WINDOW = 0x08000000
+        command.creation_flags(0x08000000);
+    }
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to run '{}': {}", java_path, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "'{}' exited with {} when asked for its version, is it a Java executable?",
+            java_path, output.status
+        ));
+    }
+    let mut info = String::from_utf8_lossy(&output.stderr).to_string();
+    info.push_str(&String::from_utf8_lossy(&output.stdout));
+
+    // Lines look like "    java.vendor = Eclipse Adoptium"
+    let property = |name: &str| {
+        info.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == name).then(|| value.trim().to_string())
+        })
+    };
+    let major_version = extract_java_version(&info);
+    if major_version == 0 {
+        return Err(format!(
+            "'{}' doesn't report a Java version, is it a Java executable?",
+            java_path
+        ));
+    }
+    let version = property("java.version")
+        .or_else(|| {
+            regex::Regex::new(r#"version "([^"]+)""#)
+                .ok()?
+                .captures(&info)
+                .map(|c| c[1].to_string())
+        })
+        .unwrap_or_else(|| major_version.to_string());
+    let arch = match property("os.arch").as_deref() {
+        Some("amd64") | Some("x86_64") => "x86_64",
+        Some("aarch64") | Some("arm64") => "arm64",
+        Some("x86") | Some("i386") | Some("i686") => "x86",
+        _ => crate::launcher::utils::java_arch_from_output(&info),
+    };
+    let is_64bit = match property("sun.arch.data.model").as_deref() {
+        Some(bits) => bits == "64",
+        None => arch != "x86",
+    };
+
+    Ok(JavaInstallation {
+        path: java_path.to_string(),
+        version,
+        major_version,
+        vendor: property("java.vendor"),
+        arch: arch.to_string(),
+        is_64bit,
+    })
+}
+
+/// Java executables in the usual install locations, `JAVA_HOME` and PATH, probed in parallel.
+/// Paths that resolve to the same executable are listed once, newest Java first.
+pub fn detect_system_java() -> Vec<JavaInstallation> {
+    let exe = if cfg!(target_os = "windows") {
+        "java.exe"
+    } else {
+        "java"
+    };
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if let Some(java_home) = std::env::var_os("JAVA_HOME") {
+        candidates.push(PathBuf::from(java_home).join("bin").join(exe));
+    }
+    if let Some(path) = std::env::var_os("PATH") {
+        candidates.extend(std::env::split_paths(&path).map(|dir| dir.join(exe)));
+    }
+    // Each of these holds one folder per runtime
+    for root in java_root_dirs() {
+        let Ok(entries) = std::fs::read_dir(&root) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let runtime = entry.path();
+            candidates.push(runtime.join("bin").join(exe));
+            // macOS bundles
+            candidates.push(runtime.join("Contents").join("Home").join("bin").join(exe));
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    let unique: Vec<PathBuf> = candidates
+        .into_iter()
+        .filter(|path| path.is_file())
+        .filter(|path| seen.insert(std::fs::canonicalize(path).unwrap_or_else(|_| path.clone())))
+        .collect();
+
+    let mut found: Vec<JavaInstallation> = std::thread::scope(|scope| {
+        let probes: Vec<_> = unique
+            .iter()
+            .map(|path| scope.spawn(move || probe_java(&path.to_string_lossy())))
+            .collect();
+        probes
+            .into_iter()
+            .filter_map(|probe| probe.join().ok()?.ok())
+            .collect()
+    });
+    found.sort_by_key(|j| std::cmp::Reverse(j.major_version));
+    found
+}
+
+/// Folders that contain Java runtimes side by side on this platform
+fn java_root_dirs() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        roots.push(home.join(".sdkman").join("candidates").join("java"));
+        // Downloaded by IntelliJ
+        roots.push(home.join(".jdks"));
+        #[cfg(target_os = "macos")]
+        roots.push(
+            home.join("Library")
+                .join("Java")
+                .join("JavaVirtualMachines"),
+        );
+    }
+    #[cfg(target_os = "windows")]
+    for var in ["ProgramFiles", "ProgramFiles(x86)"] {
+        if let Some(program_files) = std::env::var_os(var) {
+            let program_files = PathBuf::from(program_files);
+            for vendor in [
+                "Java",
+                "Eclipse Adoptium",
+                "Eclipse Foundation",
+                "AdoptOpenJDK",
+                "Microsoft",
+                "Zulu",
+                "Amazon Corretto",
+                "BellSoft",
+            ] {
+                roots.push(program_files.join(vendor));
+            }
+        }
+    }
+    #[cfg(target_os = "macos")]
+    roots.push(PathBuf::from("/Library/Java/JavaVirtualMachines"));
+    #[cfg(target_os = "linux")]
+    roots.extend(
+        [
+            "/usr/lib/jvm",
+            "/usr/lib64/jvm",
+            "/usr/java",
+            "/opt/java",
+            "/opt/jdk",
+        ]
+        .into_iter()
+        .map(PathBuf::from),
+    );
+    roots
+}
+
 /// Extract major Java version from version output string
 fn extract_java_version(version_str: &str) -> u32 {
     use regex::Regex;
@@ -398,3 +602,45 @@ fn extract_java_version(version_str: &str) -> u32 {
 pub fn auto_detect_java() -> Result<String, String> {
     find_java_executable(None)
 }
+
+/// Returns the Java major version a version needs (`javaVersion.majorVersion` of the merged manifest).
+/// Loader versions that aren't installed yet fall back to the manifest of their Minecraft version.
+/// Returns Ok(None) when no manifest for the version is on disk or it doesn't specify a Java version.
+pub async fn get_required_java_version(version_id: &str) -> Result<Option<u32>, String> {
+    if let Ok(cache) = REQUIRED_JAVA_CACHE.lock() {
+        if let Some(major) = cache.get(version_id) {
+            return Ok(Some(*major));
+        }
+    }
+
+    let minecraft_dir = crate::get_default_minecraft_dir()?
+        .to_string_lossy()
+        .to_string();
+    let mut major = required_java_from_manifest(&minecraft_dir, version_id).await;
+    if major.is_none() {
+        if let Some(mc_version) = crate::installations::get_minecraft_version(version_id).await {
+            if mc_version != version_id {
+                major = required_java_from_manifest(&minecraft_dir, &mc_version).await;
+            }
+        }
+    }
+
+    // Unknown results aren't cached so they are picked up once the version is downloaded
+    if let Some(major) = major {
+        if let Ok(mut cache) = REQUIRED_JAVA_CACHE.lock() {
+            cache.insert(version_id.to_string(), major);
+        }
+    }
+    Ok(major)
+}
+
+async fn required_java_from_manifest(minecraft_dir: &str, version_id: &str) -> Option<u32> {
+    let manifest = super::utils::load_and_merge_manifest(minecraft_dir, version_id)
+        .await
+        .ok()?;
+    manifest
+        .get("javaVersion")
+        .and_then(|j| j.get("majorVersion"))
+        .and_then(|m| m.as_u64())
+        .and_then(|m| u32::try_from(m).ok())
+}
@@ -7,6 +7,7 @@ use crate::settings::CategorizedLauncherSettings;
 use crate::versions::LoaderKind;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LaunchContext {
@@ -14,24 +15,72 @@ pub struct LaunchContext {
     pub settings: CategorizedLauncherSettings,
     pub account: LauncherAccount,
     pub minecraft_dir: String,
+    /// Progress of the prepare step, silent unless the launch was started with a progress token
+    #[serde(skip)]
+    pub progress: crate::launcher::progress::ProgressReporter,
     // Add more as needed (manifest, paths, etc.)
 }
 
 impl LaunchContext {
     pub fn new(
         installation: KableInstallation,
-        settings: CategorizedLauncherSettings,
+        mut settings: CategorizedLauncherSettings,
         account: LauncherAccount,
         minecraft_dir: String,
     ) -> Result<Self, String> {
+        // Loaders read the Java path from the settings, an installation's own path takes its place
+        if let Some(java_path) = installation
+            .java_path
+            .as_ref()
+            .filter(|p| !p.trim().is_empty())
+        {
+            settings.general.java_path = Some(java_path.clone());
+        }
         Ok(Self {
             installation,
             settings,
             account,
             minecraft_dir,
+            progress: Default::default(),
         })
     }
 
+    pub fn with_progress(mut self, progress: crate::launcher::progress::ProgressReporter) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Client for this installation's downloads, uses its proxy settings
+    pub fn http_client(&self) -> Result<reqwest::Client, String> {
+        crate::launcher::utils::http_client_for(&self.installation)
+    }
+
+    /// Assets root of the launch, per installation when it uses isolated game files
+    pub fn assets_dir(&self) -> PathBuf {
+        self.game_files_dir("assets")
+    }
+
+    /// Natives folder of the launch, per installation when it uses isolated game files
+    pub fn natives_dir(&self) -> PathBuf {
+        self.game_files_dir("natives")
+    }
+
+    fn game_files_dir(&self, name: &str) -> PathBuf {
+        if self.installation.isolated_game_files {
+            match crate::get_minecraft_kable_dir() {
+                Ok(kable_dir) => return kable_dir.join(name).join(&self.installation.id),
+                Err(e) => crate::logging::Logger::warn_global(
+                    &format!(
+                        "Using the shared {} folder, kable dir not found: {}",
+                        name, e
+                    ),
+                    Some(&self.installation.id),
+                ),
+            }
+        }
+        PathBuf::from(&self.minecraft_dir).join(name)
+    }
+
     pub async fn detect_loader_type(&self) -> Result<LoaderType, String> {
         let mut version_id = self.clone().installation.version_id;
         // Trim whitespace/newlines that might be present from conversion or user data
@@ -96,8 +145,17 @@ pub struct LaunchResult {
     pub command: String,
 }
 
+/// The game command as `launch` would run it, together with what it was built from
+pub struct LaunchCommand {
+    pub command: std::process::Command,
+    pub manifest: serde_json::Value,
+    pub classpath: String,
+}
+
 #[async_trait]
 pub trait Launchable: Send + Sync {
     async fn prepare(&self, context: &LaunchContext) -> Result<(), String>;
+    /// Builds the full command without downloading, extracting or spawning anything
+    async fn build_command(&self, context: &LaunchContext) -> Result<LaunchCommand, String>;
     async fn launch(&self, context: &LaunchContext) -> Result<LaunchResult, String>;
 }
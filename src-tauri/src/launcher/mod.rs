@@ -1,13 +1,24 @@
+pub mod behavior;
 pub mod fabric;
 pub mod forge;
+pub mod game_log;
 pub mod java;
 pub mod launchables;
+pub mod operations;
+pub mod preview;
+pub mod progress;
+pub mod queue;
 pub mod utils;
+pub mod validate;
 pub mod vanilla;
 
+pub use behavior::*;
 pub use fabric::*;
 pub use forge::*;
 pub use launchables::*;
+pub use preview::*;
+pub use progress::*;
+pub use validate::*;
 pub use vanilla::*;
 
 use crate::logging::Logger;
@@ -18,29 +29,65 @@ use crate::{
 use tauri::{Emitter, Manager};
 
 use once_cell::sync::OnceCell;
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use std::time::Duration;
 use tauri::async_runtime::spawn_blocking;
 
-static MINECRAFT_PIDS: OnceCell<Mutex<HashSet<u32>>> = OnceCell::new();
+static MINECRAFT_PIDS: OnceCell<Mutex<HashMap<u32, TrackedProcess>>> = OnceCell::new();
+static CLOSE_CONFIRMED: AtomicBool = AtomicBool::new(false);
 
-fn get_pid_set() -> &'static Mutex<HashSet<u32>> {
-    MINECRAFT_PIDS.get_or_init(|| Mutex::new(HashSet::new()))
+/// A game process started by the launcher
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrackedProcess {
+    pub pid: u32,
+    pub installation_id: String,
+    /// RFC 3339
+    pub started_at: String,
+    /// The command line it was started with
+    pub command: String,
+}
+
+fn get_pid_set() -> &'static Mutex<HashMap<u32, TrackedProcess>> {
+    MINECRAFT_PIDS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The installation's override of a behavior setting, or the global value when it has none
+fn effective_behavior(installation_override: &Option<String>, global: &str) -> String {
+    installation_override
+        .as_deref()
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+        .unwrap_or(global)
+        .to_string()
 }
 
 /// Handle on_game_launch settings behavior
 async fn handle_launch_settings(
     settings: &CategorizedLauncherSettings,
+    installation: &KableInstallation,
     app_handle: Option<tauri::AppHandle>,
 ) {
-    let behavior = &settings.general.on_game_launch;
+    let mut behavior = effective_behavior(
+        &installation.on_game_launch,
+        &settings.general.on_game_launch,
+    );
     Logger::info_global(
         &format!("Handling on_game_launch setting: {}", behavior),
         None,
     );
+    if behavior == "ask" {
+        Logger::info_global("Asking user what to do on game launch", None);
+        behavior = ask_behavior(
+            app_handle.as_ref(),
+            "ask-launch-behavior",
+            &["keep_open", "exit", "minimize", "open_logs"],
+            None,
+        )
+        .await;
+    }
 
     match behavior.as_str() {
         "exit" => {
@@ -49,9 +96,7 @@ async fn handle_launch_settings(
                 None,
             );
             if let Some(app) = app_handle {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.close();
-                }
+                let _ = close_main_window(&app);
             }
         }
         "minimize" => {
@@ -77,15 +122,6 @@ async fn handle_launch_settings(
                 );
             }
         }
-        "ask" => {
-            Logger::info_global("Asking user what to do on game launch", None);
-            if let Some(app) = app_handle {
-                let _ = app.emit(
-                    "ask-launch-behavior",
-                    serde_json::json!({"options": ["keep_open", "exit", "minimize", "open_logs"]}),
-                );
-            }
-        }
         "keep_open" => {
             Logger::info_global(
                 "Keeping launcher open as requested by on_game_launch setting",
@@ -105,10 +141,12 @@ async fn handle_launch_settings(
 /// Handle on_game_close settings behavior
 async fn handle_close_settings(
     settings: &CategorizedLauncherSettings,
+    installation: &KableInstallation,
     app_handle: Option<tauri::AppHandle>,
     exit_code: i32,
 ) {
-    let behavior = &settings.general.on_game_close;
+    let mut behavior =
+        effective_behavior(&installation.on_game_close, &settings.general.on_game_close);
     Logger::info_global(
         &format!(
             "Handling on_game_close setting: {} (exit code: {})",
@@ -116,6 +154,16 @@ async fn handle_close_settings(
         ),
         None,
     );
+    if behavior == "ask" {
+        Logger::info_global("Asking user what to do on game close", None);
+        behavior = ask_behavior(
+            app_handle.as_ref(),
+            "ask-close-behavior",
+            &["open_logs", "open_home", "exit", "minimize"],
+            Some(exit_code),
+        )
+        .await;
+    }
 
     match behavior.as_str() {
         "open_logs" => {
@@ -148,9 +196,7 @@ async fn handle_close_settings(
                 None,
             );
             if let Some(app) = app_handle {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.close();
-                }
+                let _ = close_main_window(&app);
             }
         }
         "minimize" => {
@@ -164,15 +210,104 @@ async fn handle_close_settings(
                 }
             }
         }
-        "ask" => {
-            Logger::info_global("Asking user what to do on game close", None);
+        NO_BEHAVIOR => {}
+        _ => {
+            Logger::warn_global(
+                &format!("Unknown on_game_close setting: {}", behavior),
+                None,
+            );
+        }
+    }
+}
+
+/// Handle on_game_crash settings behavior
+async fn handle_crash_settings(
+    settings: &CategorizedLauncherSettings,
+    installation: &KableInstallation,
+    app_handle: Option<tauri::AppHandle>,
+    exit_code: i32,
+) {
+    let mut behavior =
+        effective_behavior(&installation.on_game_crash, &settings.general.on_game_crash);
+    Logger::info_global(
+        &format!(
+            "Handling on_game_crash setting: {} (exit code: {})",
+            behavior, exit_code
+        ),
+        None,
+    );
+    if behavior == "ask" {
+        Logger::info_global("Asking user what to do after the crash", None);
+        behavior = ask_behavior(
+            app_handle.as_ref(),
+            "ask-crash-behavior",
+            &["restart", "open_logs", "open_home", "exit", "minimize"],
+            Some(exit_code),
+        )
+        .await;
+    }
+
+    match behavior.as_str() {
+        "restart" => {
+            Logger::info_global(
+                "Requesting a game restart as requested by on_game_crash setting",
+                None,
+            );
             if let Some(app) = app_handle {
-                let _ = app.emit("ask-close-behavior", serde_json::json!({"options": ["open_logs", "open_home", "exit", "minimize"], "exit_code": exit_code}));
+                let _ = app.emit(
+                    "game-restart-requested",
+                    serde_json::json!({"exit_code": exit_code}),
+                );
             }
         }
+        "open_logs" => {
+            Logger::info_global(
+                "Opening logs page as requested by on_game_crash setting",
+                None,
+            );
+            if let Some(app) = app_handle {
+                let _ = app.emit(
+                    "navigate-to-logs",
+                    serde_json::json!({"reason": "crash_setting"}),
+                );
+            }
+        }
+        "open_home" => {
+            Logger::info_global(
+                "Navigating to home page as requested by on_game_crash setting",
+                None,
+            );
+            if let Some(app) = app_handle {
+                let _ = app.emit(
+                    "navigate-to-home",
+                    serde_json::json!({"reason": "crash_setting"}),
+                );
+            }
+        }
+        "exit" => {
+            Logger::info_global(
+                "Closing launcher as requested by on_game_crash setting",
+                None,
+            );
+            if let Some(app) = app_handle {
+                let _ = close_main_window(&app);
+            }
+        }
+        "minimize" => {
+            Logger::info_global(
+                "Minimizing launcher as requested by on_game_crash setting",
+                None,
+            );
+            if let Some(app) = app_handle {
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.minimize();
+                }
+            }
+        }
+        NO_BEHAVIOR => {}
         _ => {
             Logger::warn_global(
-                &format!("Unknown on_game_close setting: {}", behavior),
+                &format!("Unknown on_game_crash setting: {}", behavior),
                 None,
             );
         }
@@ -194,11 +329,124 @@ async fn get_launchable_for_installation(
     }
 }
 
+/// Builds a launch context for an installation using the saved settings and the active account
+async fn context_for_installation(installation_id: &str) -> Result<LaunchContext, String> {
+    let installation = crate::installations::get_installation(installation_id)
+        .await?
+        .ok_or_else(|| format!("Installation not found: {}", installation_id))?;
+    let settings = crate::settings::load_settings().await?;
+    let account = crate::auth::auth_util::get_active_launcher_account()
+        .await?
+        .ok_or("No active account found")?;
+    let account = account_for_installation(&installation, account).await;
+    let minecraft_dir = get_default_minecraft_dir()?.to_string_lossy().to_string();
+    LaunchContext::new(installation, settings, account, minecraft_dir)
+}
+
+/// The installation's preferred account with a fresh token, or `active` when it has none.
+/// A preferred account that was removed or can't be refreshed also falls back to `active`.
+pub async fn account_for_installation(
+    installation: &KableInstallation,
+    active: LauncherAccount,
+) -> LauncherAccount {
+    let Some(preferred_id) = installation
+        .preferred_account_id
+        .as_deref()
+        .filter(|id| !id.is_empty() && *id != active.local_id)
+    else {
+        return active;
+    };
+    let preferred = match crate::auth::auth_util::read_launcher_accounts().await {
+        Ok(accounts) => accounts.accounts.get(preferred_id).cloned(),
+        Err(e) => {
+            Logger::warn_global(
+                &format!(
+                    "Failed to read accounts, launching with the active one: {}",
+                    e
+                ),
+                Some(&installation.id),
+            );
+            return active;
+        }
+    };
+    let Some(preferred) = preferred else {
+        Logger::warn_global(
+            &format!(
+                "Preferred account {} of {} no longer exists, launching with the active account",
+                preferred_id, installation.name
+            ),
+            Some(&installation.id),
+        );
+        return active;
+    };
+    if crate::auth::is_access_token_valid(&preferred) || preferred.encrypted_refresh_token.is_none()
+    {
+        return preferred;
+    }
+    match crate::auth::auth_util::refresh_microsoft_token(preferred.local_id.clone()).await {
+        Ok(refreshed) => refreshed,
+        Err(e) => {
+            Logger::warn_global(
+                &format!(
+                    "Failed to refresh preferred account {}, launching with the active account: {}",
+                    preferred.username, e
+                ),
+                Some(&installation.id),
+            );
+            active
+        }
+    }
+}
+
 pub async fn launch_installation(
+    installation: KableInstallation,
+    settings: CategorizedLauncherSettings,
+    account: LauncherAccount,
+) -> Result<LaunchResult, String> {
+    launch_installation_with_progress(
+        installation,
+        settings,
+        account,
+        ProgressReporter::none(),
+        false,
+    )
+    .await
+}
+
+/// Starts a launch in the background and returns its progress token right away.
+/// The prepare step reports libraries, assets and loader installs under that token.
+pub fn start_launch_installation(
+    installation: KableInstallation,
+    settings: CategorizedLauncherSettings,
+    account: LauncherAccount,
+) -> String {
+    start_with_progress(|progress| async move {
+        let result = launch_installation_with_progress(
+            installation,
+            settings,
+            account,
+            progress.clone(),
+            false,
+        )
+        .await?;
+        progress.stage(
+            "launched",
+            &format!("Minecraft started (PID {})", result.pid),
+        );
+        Ok(())
+    })
+}
+
+/// With `suppress_behaviors` the on_game_launch/close/crash behaviors are skipped for this launch,
+/// e.g. while debugging. Playtime and cleanup after the game exits still happen.
+pub async fn launch_installation_with_progress(
     mut installation: KableInstallation,
     settings: CategorizedLauncherSettings,
     account: LauncherAccount,
+    progress: ProgressReporter,
+    suppress_behaviors: bool,
 ) -> Result<LaunchResult, String> {
+    let account = account_for_installation(&installation, account).await;
     // Use installation.id for log grouping and event correlation
     let instance_id = Some(installation.id.as_str());
 
@@ -241,7 +489,7 @@ pub async fn launch_installation(
         account,
         minecraft_dir.clone(),
     ) {
-        Ok(ctx) => ctx,
+        Ok(ctx) => ctx.with_progress(progress),
         Err(e) => {
             Logger::error_global(
                 &format!("Failed to build launch context: {}", e),
@@ -251,6 +499,46 @@ pub async fn launch_installation(
         }
     };
 
+    // A second game of the same installation shares its natives, configs and links. Reserved
+    // before queueing, a launch that is still preparing has no PID to find yet.
+    let (launch_reservation, launching) = queue::reserve_launch(&installation.id);
+    let running = installation_pids(&installation.id);
+    if !running.is_empty() || launching > 0 {
+        let message = if running.is_empty() {
+            format!("{} is already being launched", installation.name)
+        } else {
+            format!(
+                "{} is already running (PID {})",
+                installation.name,
+                running
+                    .iter()
+                    .map(u32::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+        if settings.advanced.duplicate_launch == "refuse" {
+            Logger::error_global(&message, instance_id);
+            return Err(format!(
+                "{}, close it first or allow duplicate launches in the advanced settings",
+                message
+            ));
+        }
+        if settings.advanced.duplicate_launch == "warn" {
+            Logger::warn_global(
+                &format!("{}, launching another instance anyway", message),
+                instance_id,
+            );
+        }
+    }
+
+    // Wait for our turn to prepare, reserved until the game process is spawned
+    let prepare_slot = queue::acquire_prepare_slot(
+        &installation.id,
+        settings.advanced.concurrent_launch_prepares,
+    )
+    .await;
+
     // Setup dynamic symlinks for this installation before launching
     Logger::info_global(
         &format!(
@@ -291,11 +579,13 @@ pub async fn launch_installation(
         }
     };
     // Prepare (download, patch, etc.)
+    context.progress.stage("preparing", "Preparing game files");
     if let Err(e) = launchable.prepare(&context).await {
         Logger::error_global(&format!("Failed to prepare launch: {}", e), instance_id);
         return Err(format!("Failed to prepare launch: {}", e));
     }
     // Build and run the launch command
+    context.progress.stage("launching", "Starting Minecraft");
     let result = match launchable.launch(&context).await {
         Ok(res) => {
             Logger::info_global(
@@ -309,17 +599,29 @@ pub async fn launch_installation(
             return Err(format!("Failed to launch Minecraft: {}", e));
         }
     };
-    // Track the launched PID
+    // Track the launched PID before the reservation is dropped, so there is no gap to launch in
     {
         let mut pids = get_pid_set().lock().unwrap();
-        pids.insert(result.pid);
+        pids.insert(
+            result.pid,
+            TrackedProcess {
+                pid: result.pid,
+                installation_id: installation.id.clone(),
+                started_at: chrono::Utc::now().to_rfc3339(),
+                command: result.command.clone(),
+            },
+        );
     }
+    // The game runs on its own now, let the next queued launch prepare
+    drop(prepare_slot);
+    drop(launch_reservation);
 
     // Update Discord Rich Presence with playing status
     if let Err(e) = crate::discord::set_playing(
         &installation.name,
         &installation.version_id,
         installation.get_loader_type(),
+        &settings.misc.discord_presence,
     ) {
         Logger::warn_global(&format!("Failed to update Discord presence: {}", e), None);
     }
@@ -331,8 +633,20 @@ pub async fn launch_installation(
         None
     };
 
-    // Handle on_game_launch settings behavior
-    handle_launch_settings(&settings, app_handle.clone()).await;
+    // Handle on_game_launch settings behavior, in the background since "ask" waits for the user
+    if suppress_behaviors {
+        Logger::info_global(
+            "Launch behaviors are suppressed for this launch",
+            instance_id,
+        );
+    } else {
+        let settings = settings.clone();
+        let installation = installation.clone();
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            handle_launch_settings(&settings, &installation, app_handle).await;
+        });
+    }
 
     // Start monitoring the process for exit behavior
     let settings_clone = settings.clone();
@@ -351,31 +665,59 @@ pub async fn launch_installation(
         // Wait for the process to exit and get exit code
         match wait_for_minecraft_exit(pid).await {
             Ok(exit_code) => {
-                // Clean up symlinks after game closes
-                Logger::info_global("[SETTINGS TASK] Cleaning up symlinks after game exit", None);
-                let symlink_manager = crate::symlink_manager::SymlinkManager::new(PathBuf::from(
-                    &minecraft_dir_clone,
-                ));
-                if let Err(e) = symlink_manager.cleanup_all_symlinks().await {
-                    Logger::warn_global(
-                        &format!("[SETTINGS TASK] Failed to cleanup symlinks: {}", e),
+                // The links belong to whichever game was set up last, another running game
+                // may still use them
+                let still_running = running_minecraft_pids();
+                if still_running.is_empty() {
+                    Logger::info_global(
+                        "[SETTINGS TASK] Cleaning up symlinks after game exit",
+                        None,
+                    );
+                    let symlink_manager = crate::symlink_manager::SymlinkManager::new(
+                        PathBuf::from(&minecraft_dir_clone),
+                    );
+                    if let Err(e) = symlink_manager.cleanup_all_symlinks().await {
+                        Logger::warn_global(
+                            &format!("[SETTINGS TASK] Failed to cleanup symlinks: {}", e),
+                            None,
+                        );
+                    }
+
+                    // Restore global configs after game closes
+                    Logger::info_global(
+                        "[SETTINGS TASK] Restoring global configs after game exit",
+                        None,
+                    );
+                    if let Err(e) =
+                        crate::installations::kable_profiles::KableInstallation::restore_global_configs()
+                            .await
+                    {
+                        Logger::warn_global(
+                            &format!("[SETTINGS TASK] Failed to restore global configs: {}", e),
+                            None,
+                        );
+                    }
+                } else {
+                    Logger::info_global(
+                        &format!(
+                            "[SETTINGS TASK] Keeping symlinks and configs, other games are still running (PIDs {:?})",
+                            still_running
+                        ),
                         None,
                     );
                 }
 
-                // Restore global configs after game closes
-                Logger::info_global(
-                    "[SETTINGS TASK] Restoring global configs after game exit",
-                    None,
-                );
-                if let Err(e) =
-                    crate::installations::kable_profiles::KableInstallation::restore_global_configs(
-                    )
-                    .await
+                // Keep only the newest game logs of this installation
+                let game_logs_to_keep = settings_clone.logging.game_logs_to_keep.max(1) as usize;
+                if let Err(e) = crate::launcher::game_log::prune_game_logs(
+                    &installation_for_tracking.id,
+                    game_logs_to_keep,
+                )
+                .await
                 {
                     Logger::warn_global(
-                        &format!("[SETTINGS TASK] Failed to restore global configs: {}", e),
-                        None,
+                        &format!("[SETTINGS TASK] Failed to remove old game logs: {}", e),
+                        Some(&installation_for_tracking.id),
                     );
                 }
 
@@ -420,9 +762,17 @@ pub async fn launch_installation(
                 );
 
                 // Determine if it was a crash or normal exit
-                let is_crash = exit_code != 0 && exit_code != 130 && exit_code != 143; // 130 = Ctrl+C, 143 = SIGTERM
+                let is_crash = !settings_clone.general.clean_exit_codes.contains(&exit_code);
 
-                if is_crash {
+                if suppress_behaviors {
+                    Logger::info_global(
+                        &format!(
+                            "[SETTINGS TASK] Skipping close/crash settings for exit code {}, suppressed for this launch",
+                            exit_code
+                        ),
+                        None,
+                    );
+                } else if is_crash {
                     Logger::info_global(
                         &format!(
                             "[SETTINGS TASK] Handling crash settings for exit code {}",
@@ -430,6 +780,13 @@ pub async fn launch_installation(
                         ),
                         None,
                     );
+                    handle_crash_settings(
+                        &settings_clone,
+                        &installation_for_tracking,
+                        app_handle_clone,
+                        exit_code,
+                    )
+                    .await;
                 } else {
                     Logger::info_global(
                         &format!(
@@ -438,7 +795,13 @@ pub async fn launch_installation(
                         ),
                         None,
                     );
-                    handle_close_settings(&settings_clone, app_handle_clone, exit_code).await;
+                    handle_close_settings(
+                        &settings_clone,
+                        &installation_for_tracking,
+                        app_handle_clone,
+                        exit_code,
+                    )
+                    .await;
                 }
 
                 // Clear Discord Rich Presence when game exits
@@ -452,7 +815,15 @@ pub async fn launch_installation(
                     None,
                 );
                 // Handle as normal close if we can't determine exit code
-                handle_close_settings(&settings_clone, app_handle_clone, 0).await;
+                if !suppress_behaviors {
+                    handle_close_settings(
+                        &settings_clone,
+                        &installation_for_tracking,
+                        app_handle_clone,
+                        0,
+                    )
+                    .await;
+                }
 
                 // Clear Discord Rich Presence
                 if let Err(e) = crate::discord::clear_playing() {
@@ -469,36 +840,179 @@ pub async fn launch_installation(
 /// Kill a Minecraft process by PID (only if tracked)
 pub async fn kill_minecraft_process(process_id: u32) -> Result<(), String> {
     let mut pids = get_pid_set().lock().unwrap();
-    if !pids.contains(&process_id) {
+    let Some(tracked) = pids.get(&process_id).cloned() else {
         return Err(format!(
             "Process {} is not tracked by the launcher",
             process_id
         ));
+    };
+    match signal_process(process_id, true) {
+        Ok(()) => {
+            pids.remove(&process_id);
+            Logger::info_global(
+                &format!("Killed Minecraft process {}", process_id),
+                Some(&tracked.installation_id),
+            );
+            Ok(())
+        }
+        Err(e) => Err(format!(
+            "Failed to kill process {} of installation {}: {}",
+            process_id, tracked.installation_id, e
+        )),
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ProcessKillOutcome {
+    pub pid: u32,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct KillInstallationResult {
+    pub installation_id: String,
+    /// Processes that were signaled successfully
+    pub signaled: u32,
+    pub outcomes: Vec<ProcessKillOutcome>,
+}
+
+/// Stops every running game of an installation. Without `force` the games are asked to close
+/// (SIGTERM, or taskkill without /F) and leave the tracking once the exit monitor sees them go.
+pub async fn kill_installation(
+    installation_id: &str,
+    force: bool,
+) -> Result<KillInstallationResult, String> {
+    let running = installation_pids(installation_id);
+    let mut result = KillInstallationResult {
+        installation_id: installation_id.to_string(),
+        signaled: 0,
+        outcomes: Vec::new(),
+    };
+    for pid in running {
+        let outcome = signal_process(pid, force);
+        if outcome.is_ok() {
+            result.signaled += 1;
+            if force {
+                get_pid_set().lock().unwrap().remove(&pid);
+            }
+        }
+        result.outcomes.push(ProcessKillOutcome {
+            pid,
+            success: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+    Logger::info_global(
+        &format!(
+            "{} {} of {} game process(es)",
+            if force { "Killed" } else { "Asked to close" },
+            result.signaled,
+            result.outcomes.len()
+        ),
+        Some(installation_id),
+    );
+    Ok(result)
+}
+
+fn signal_process(pid: u32, force: bool) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        let pid_arg = pid.to_string();
+        let mut args = vec!["/PID", pid_arg.as_str()];
+        if force {
+            args.push("/F");
+        }
+        match std::process::Command::new("taskkill").args(&args).status() {
+            Ok(status) if status.success() => Ok(()),
+            Ok(status) => Err(format!("taskkill exited with {}", status)),
+            Err(e) => Err(format!("Failed to run taskkill: {}", e)),
+        }
     }
-    // Try to kill the process
-    match Command::new("taskkill")
-        .args(["/PID", &process_id.to_string(), "/F"])
-        .status()
+    #[cfg(unix)]
     {
-        Ok(status) if status.success() => {
-            pids.remove(&process_id);
+        let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+        if unsafe { libc::kill(pid as i32, signal) } == 0 {
             Ok(())
+        } else {
+            Err(std::io::Error::last_os_error().to_string())
         }
-        _ => Err(format!("Failed to kill process {}", process_id)),
     }
 }
 
 /// Get all running Minecraft process IDs (tracked by launcher)
 pub async fn get_running_minecraft_processes() -> Result<Vec<u32>, String> {
+    Ok(running_minecraft_pids())
+}
+
+/// Running Minecraft processes with the installation they belong to, oldest first
+pub async fn get_running_minecraft_process_details() -> Result<Vec<TrackedProcess>, String> {
+    Ok(running_minecraft_process_details())
+}
+
+pub fn running_minecraft_process_details() -> Vec<TrackedProcess> {
+    let pids = get_pid_set().lock().unwrap();
+    let mut running: Vec<TrackedProcess> = pids
+        .values()
+        .filter(|p| is_process_alive(p.pid))
+        .cloned()
+        .collect();
+    running.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    running
+}
+
+/// Tracked Minecraft processes that are still alive, for callers outside of async code
+pub fn running_minecraft_pids() -> Vec<u32> {
     let pids = get_pid_set().lock().unwrap();
     // Optionally, check if the process is still alive
     let mut running = Vec::new();
-    for &pid in pids.iter() {
+    for &pid in pids.keys() {
         if is_process_alive(pid) {
             running.push(pid);
         }
     }
-    Ok(running)
+    running
+}
+
+/// Tracked processes of one installation that are still alive
+pub fn installation_pids(installation_id: &str) -> Vec<u32> {
+    let pids = get_pid_set().lock().unwrap();
+    pids.values()
+        .filter(|p| p.installation_id == installation_id && is_process_alive(p.pid))
+        .map(|p| p.pid)
+        .collect()
+}
+
+/// Whether the installation already has a running game
+pub async fn is_installation_running(installation_id: &str) -> Result<bool, String> {
+    Ok(!installation_pids(installation_id).is_empty())
+}
+
+/// Whether the user already agreed to close the launcher while games are running
+pub fn close_confirmed() -> bool {
+    CLOSE_CONFIRMED.load(Ordering::Relaxed)
+}
+
+/// Answer to `confirm-close-with-running-games`: closes the main window when `close` is true,
+/// the running games and the symlinks they use are left alone
+pub fn confirm_close_with_running_games(app: &tauri::AppHandle, close: bool) -> Result<(), String> {
+    if !close {
+        Logger::info_global("Close cancelled, games are still running", None);
+        return Ok(());
+    }
+    close_main_window(app)
+}
+
+/// Closes the main window without asking about running games again, for a close the user
+/// already agreed to or picked through the on_game_* settings
+fn close_main_window(app: &tauri::AppHandle) -> Result<(), String> {
+    CLOSE_CONFIRMED.store(true, Ordering::Relaxed);
+    let window = app
+        .get_webview_window("main")
+        .ok_or("Main window not found")?;
+    window
+        .close()
+        .map_err(|e| format!("Failed to close the main window: {}", e))
 }
 
 fn is_process_alive(pid: u32) -> bool {
@@ -538,7 +1052,7 @@ pub async fn wait_for_minecraft_exit(process_id: u32) -> Result<i32, String> {
     let mut found = false;
     {
         let pids = get_pid_set().lock().unwrap();
-        if pids.contains(&process_id) {
+        if pids.contains_key(&process_id) {
             found = true;
         }
     }
@@ -0,0 +1,123 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Cancellation flags of the long operations that are currently running, by operation id
+static OPERATIONS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A running long operation (asset download, library download, loader install).
+/// Emits `operation-heartbeat` every few seconds until it is dropped or cancelled,
+/// and `operation-finished` when dropped.
+pub struct Operation {
+    pub id: String,
+    kind: String,
+    installation_id: Option<String>,
+    cancelled: Arc<AtomicBool>,
+    heartbeat: tokio::task::JoinHandle<()>,
+}
+
+impl Operation {
+    /// Registers the operation and starts its heartbeat. Must be called inside the tokio runtime.
+    pub fn start(kind: &str, installation_id: Option<&str>) -> Self {
+        let id = uuid::Uuid::new_v4().to_string();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        OPERATIONS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id.clone(), cancelled.clone());
+
+        let heartbeat = {
+            let id = id.clone();
+            let kind = kind.to_string();
+            let installation_id = installation_id.map(str::to_string);
+            let cancelled = cancelled.clone();
+            tokio::spawn(async move {
+                let started = Instant::now();
+                let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+                // The first tick completes immediately, the first heartbeat is after one interval
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    if cancelled.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    emit_operation_event(
+                        "operation-heartbeat",
+                        serde_json::json!({
+                            "operation_id": id,
+                            "kind": kind,
+                            "installation_id": installation_id,
+                            "elapsed_ms": started.elapsed().as_millis() as u64,
+                        }),
+                    );
+                }
+            })
+        };
+
+        Self {
+            id,
+            kind: kind.to_string(),
+            installation_id: installation_id.map(str::to_string),
+            cancelled,
+            heartbeat,
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Returns an error once the operation was cancelled, call it between steps
+    pub fn check_cancelled(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(format!("Operation '{}' was cancelled", self.kind))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        self.heartbeat.abort();
+        if let Ok(mut operations) = OPERATIONS.lock() {
+            operations.remove(&self.id);
+        }
+        emit_operation_event(
+            "operation-finished",
+            serde_json::json!({
+                "operation_id": self.id,
+                "kind": self.kind,
+                "installation_id": self.installation_id,
+                "cancelled": self.is_cancelled(),
+            }),
+        );
+    }
+}
+
+/// Requests cancellation of a running operation, its heartbeat stops right away and the
+/// operation itself aborts at its next check. Returns false if no such operation is running.
+pub fn cancel_operation(operation_id: &str) -> bool {
+    let operations = OPERATIONS.lock().unwrap_or_else(|e| e.into_inner());
+    match operations.get(operation_id) {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+fn emit_operation_event(event: &str, payload: serde_json::Value) {
+    if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
+        if let Some(app_handle) = handle_guard.as_ref() {
+            let _ = app_handle.emit(event, payload);
+        }
+    }
+}
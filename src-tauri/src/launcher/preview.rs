@@ -0,0 +1,269 @@
+use super::{context_for_installation, get_launchable_for_installation};
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+const REDACTED: &str = "<redacted>";
+
+/// Longest command line cmd.exe runs
+const CMD_LINE_LIMIT: usize = 8191;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchCommandPreview {
+    pub java_path: String,
+    /// Arguments in the order they are passed to java
+    pub args: Vec<String>,
+    /// Full command line, quoted so it can be pasted into a shell
+    pub command: String,
+    pub working_dir: String,
+}
+
+/// Builds the exact command a launch of this installation would run, without downloading,
+/// extracting natives or spawning anything. The access token is redacted.
+pub async fn preview_launch_command(installation_id: &str) -> Result<LaunchCommandPreview, String> {
+    let context = context_for_installation(installation_id).await?;
+    let launchable = get_launchable_for_installation(&context).await?;
+    let built = launchable.build_command(&context).await?;
+
+    Ok(preview_from_command(
+        &built.command,
+        &context.account.access_token,
+        &context.minecraft_dir,
+    ))
+}
+
+/// Writes the launch command of an installation as a script (launch-scripts/<id>.bat on Windows,
+/// .sh elsewhere in the kable dir) that can be run in a terminal to see the game's raw output.
+/// On Windows the arguments are written to a java @argfile (<id>.args) next to the script,
+/// unless one of them isn't ASCII.
+/// The access token is replaced by "offline", so online servers still need a launch from Kable.
+/// Returns the path of the script.
+pub async fn get_launch_script(installation_id: &str) -> Result<String, String> {
+    let context = context_for_installation(installation_id).await?;
+    let launchable = get_launchable_for_installation(&context).await?;
+    let built = launchable.build_command(&context).await?;
+    let cmd = &built.command;
+
+    let token = &context.account.access_token;
+    let arg = |a: &std::ffi::OsStr| {
+        let a = a.to_string_lossy().to_string();
+        if token.is_empty() {
+            a
+        } else {
+            a.replace(token.as_str(), "offline")
+        }
+    };
+    let program = cmd.get_program().to_string_lossy().to_string();
+    let args: Vec<String> = cmd.get_args().map(arg).collect();
+    let working_dir = cmd
+        .get_current_dir()
+        .map(|d| d.to_string_lossy().to_string())
+        .unwrap_or_else(|| context.minecraft_dir.clone());
+    let envs: Vec<(String, String)> = cmd
+        .get_envs()
+        .filter_map(|(k, v)| Some((k.to_string_lossy().to_string(), arg(v?))))
+        .collect();
+
+    let header = format!(
+        "Launch of {} as Kable runs it, generated {}",
+        context.installation.name,
+        chrono::Utc::now().to_rfc3339()
+    );
+    let dir = crate::get_minecraft_kable_dir()?.join("launch-scripts");
+    crate::ensure_folder(&dir).await?;
+    let (extension, script) = if cfg!(windows) {
+        // cd and set take the quoted text as-is, program arguments are split by the program
+        let quote_plain = |s: &str| format!("\"{}\"", s.replace('%', "%%"));
+        let quote = |s: &str| quote_cmd_arg(s).replace('%', "%%");
+        let mut script = format!("@echo off\r\nrem {}\r\n", header);
+        script.push_str("rem The access token is replaced by \"offline\"\r\n");
+        // The script is UTF-8, cmd reads it in the console code page unless told otherwise
+        let ascii_args = args.iter().all(|a| a.is_ascii());
+        let non_ascii = !ascii_args
+            || !program.is_ascii()
+            || !working_dir.is_ascii()
+            || envs.iter().any(|(k, v)| !k.is_ascii() || !v.is_ascii());
+        if non_ascii {
+            script.push_str("chcp 65001 >nul\r\n");
+        }
+        script.push_str(&format!("cd /d {}\r\n", quote_plain(&working_dir)));
+        for (k, v) in &envs {
+            script.push_str(&format!("set {}\r\n", quote_plain(&format!("{}={}", k, v))));
+        }
+        // A modded classpath easily passes the 8191 character limit of a cmd line, so the
+        // arguments go in a java @argfile next to the script. Java 8 doesn't read those, and
+        // before Java 18 they are read in the ANSI code page, so non-ASCII paths stay inline.
+        let program_for_version = program.clone();
+        let major = tokio::task::spawn_blocking(move || {
+            super::java::java_major_version(&program_for_version)
+        })
+        .await
+        .ok()
+        .flatten();
+        let line = if ascii_args && major.is_none_or(|major| major >= 9) {
+            let args_path = dir.join(format!("{}.args", context.installation.id));
+            let argfile = args
+                .iter()
+                .map(|a| quote_argfile_arg(a))
+                .collect::<Vec<_>>()
+                .join("\r\n");
+            crate::write_file_atomic_async(&args_path, argfile.as_bytes()).await?;
+            format!(
+                "{} {}",
+                quote(&program),
+                quote(&format!("@{}", args_path.to_string_lossy()))
+            )
+        } else {
+            let line = std::iter::once(&program)
+                .chain(args.iter())
+                .map(|a| quote(a))
+                .collect::<Vec<_>>()
+                .join(" ");
+            if line.len() > CMD_LINE_LIMIT {
+                script.push_str(
+                    "rem This command is longer than cmd allows, run it from PowerShell instead\r\n",
+                );
+            }
+            line
+        };
+        script.push_str(&line);
+        script.push_str("\r\npause\r\n");
+        ("bat", script)
+    } else {
+        let quote = |s: &str| format!("'{}'", s.replace('\'', "'\\''"));
+        let mut script = format!("#!/bin/sh\n# {}\n", header);
+        script.push_str("# The access token is replaced by \"offline\"\n");
+        script.push_str(&format!("cd {} || exit 1\n", quote(&working_dir)));
+        for (k, v) in &envs {
+            script.push_str(&format!("export {}={}\n", k, quote(v)));
+        }
+        let line = std::iter::once(&program)
+            .chain(args.iter())
+            .map(|a| quote(a))
+            .collect::<Vec<_>>()
+            .join(" \\\n  ");
+        script.push_str(&format!("exec {}\n", line));
+        ("sh", script)
+    };
+
+    let path = dir.join(format!("{}.{}", context.installation.id, extension));
+    crate::write_file_atomic_async(&path, script.as_bytes()).await?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+            .await
+            .map_err(|e| format!("Failed to make {} executable: {}", path.display(), e))?;
+    }
+    crate::logging::info(&format!(
+        "Wrote the launch script of {} to {}",
+        context.installation.name,
+        path.display()
+    ));
+    Ok(path.to_string_lossy().to_string())
+}
+
+fn preview_from_command(
+    cmd: &Command,
+    access_token: &str,
+    fallback_dir: &str,
+) -> LaunchCommandPreview {
+    let redact = |s: String| {
+        if access_token.is_empty() {
+            s
+        } else {
+            s.replace(access_token, REDACTED)
+        }
+    };
+
+    let java_path = cmd.get_program().to_string_lossy().to_string();
+    let args: Vec<String> = cmd
+        .get_args()
+        .map(|a| redact(a.to_string_lossy().to_string()))
+        .collect();
+    let working_dir = cmd
+        .get_current_dir()
+        .map(|d| d.to_string_lossy().to_string())
+        .unwrap_or_else(|| fallback_dir.to_string());
+
+    let command = std::iter::once(&java_path)
+        .chain(args.iter())
+        .map(|a| quote_arg(a))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    LaunchCommandPreview {
+        java_path,
+        args,
+        command,
+        working_dir,
+    }
+}
+
+fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty()
+        && !arg
+            .chars()
+            .any(|c| c.is_whitespace() || c == '"' || c == '\'')
+    {
+        return arg.to_string();
+    }
+    format!("\"{}\"", arg.replace('"', "\\\""))
+}
+
+/// Quotes an argument the way Windows programs split their command line: a quote inside is
+/// escaped with a backslash, and backslashes are only doubled in front of a quote
+fn quote_cmd_arg(arg: &str) -> String {
+    let mut quoted = String::from("\"");
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+                backslashes = 0;
+            }
+            _ => {
+                quoted.push_str(&"\\".repeat(backslashes));
+                backslashes = 0;
+            }
+        }
+        if c != '\\' {
+            quoted.push(c);
+        }
+    }
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+/// One argument of a java @argfile, where backslashes and quotes are escaped inside quotes
+fn quote_argfile_arg(arg: &str) -> String {
+    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cmd_args_escape_quotes_and_trailing_backslashes() {
+        assert_eq!(quote_cmd_arg("plain"), "\"plain\"");
+        assert_eq!(
+            quote_cmd_arg("C:\\Program Files\\Java\\bin\\java.exe"),
+            "\"C:\\Program Files\\Java\\bin\\java.exe\""
+        );
+        assert_eq!(quote_cmd_arg("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote_cmd_arg("dir\\"), "\"dir\\\\\"");
+        assert_eq!(quote_cmd_arg("a\\\"b"), "\"a\\\\\\\"b\"");
+    }
+
+    #[test]
+    fn argfile_args_escape_backslashes_and_quotes() {
+        assert_eq!(
+            quote_argfile_arg("-Djava.library.path=C:\\Users\\Jane Doe\\natives"),
+            "\"-Djava.library.path=C:\\\\Users\\\\Jane Doe\\\\natives\""
+        );
+        assert_eq!(quote_argfile_arg("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote_argfile_arg(""), "\"\"");
+    }
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use tauri::Emitter;
+
+/// The one event every progress report is emitted under, told apart by its token
+pub const PROGRESS_EVENT: &str = "progress";
+
+/// Final stages, nothing is reported for a token after one of these
+pub const STAGE_DONE: &str = "done";
+pub const STAGE_FAILED: &str = "failed";
+
+/// Emitted for every download that failed for good (after its retries), including those of
+/// background work nobody awaits, so the frontend can show failures in one place
+pub const DOWNLOAD_FAILED_EVENT: &str = "download-failed";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadFailedEvent {
+    /// File name of what was downloaded, e.g. a mod jar, library jar or asset hash
+    pub item: String,
+    pub url: String,
+    pub error: String,
+}
+
+pub fn emit_download_failed(item: &str, url: &str, error: &str) {
+    let event = DownloadFailedEvent {
+        item: item.to_string(),
+        url: url.to_string(),
+        error: error.to_string(),
+    };
+    if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
+        if let Some(app_handle) = handle_guard.as_ref() {
+            let _ = app_handle.emit(DOWNLOAD_FAILED_EVENT, event);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressEvent {
+    pub token: String,
+    /// What is being worked on, e.g. "libraries", "assets", "loader_install", "mods"
+    pub stage: String,
+    pub current: u64,
+    /// None when the amount of work isn't known
+    pub total: Option<u64>,
+    pub message: Option<String>,
+}
+
+/// Reports the progress of one long command to the frontend.
+/// A reporter without a token (the default) reports nothing, so code that isn't started
+/// through a progress token can pass one along unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressReporter {
+    token: Option<String>,
+}
+
+impl ProgressReporter {
+    /// A reporter with a new token, return `token()` to the frontend before starting the work
+    pub fn new() -> Self {
+        Self {
+            token: Some(uuid::Uuid::new_v4().to_string()),
+        }
+    }
+
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    pub fn report(&self, stage: &str, current: u64, total: Option<u64>, message: Option<&str>) {
+        let Some(token) = &self.token else {
+            return;
+        };
+        let event = ProgressEvent {
+            token: token.clone(),
+            stage: stage.to_string(),
+            current,
+            total,
+            message: message.map(str::to_string),
+        };
+        if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
+            if let Some(app_handle) = handle_guard.as_ref() {
+                let _ = app_handle.emit(PROGRESS_EVENT, event);
+            }
+        }
+    }
+
+    /// Marks the start of a stage whose amount of work isn't known
+    pub fn stage(&self, stage: &str, message: &str) {
+        self.report(stage, 0, None, Some(message));
+    }
+
+    pub fn done(&self, message: Option<&str>) {
+        self.report(STAGE_DONE, 1, Some(1), message);
+    }
+
+    pub fn failed(&self, error: &str) {
+        self.report(STAGE_FAILED, 0, None, Some(error));
+    }
+}
+
+/// Runs `work` in the background with a new reporter and returns its token right away.
+/// The last event for the token is "done", or "failed" with the error as message.
+pub fn start_with_progress<F, Fut>(work: F) -> String
+where
+    F: FnOnce(ProgressReporter) -> Fut,
+    Fut: Future<Output = Result<(), String>> + Send + 'static,
+{
+    let progress = ProgressReporter::new();
+    let token = progress.token().unwrap_or_default().to_string();
+    let future = work(progress.clone());
+    tauri::async_runtime::spawn(async move {
+        match future.await {
+            Ok(()) => progress.done(None),
+            Err(e) => progress.failed(&e),
+        }
+    });
+    token
+}
@@ -0,0 +1,134 @@
+use crate::logging::Logger;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::Emitter;
+use tokio::sync::Notify;
+
+/// Launches that are currently preparing (downloading, extracting, building the command)
+static ACTIVE_PREPARES: Mutex<usize> = Mutex::new(0);
+static PREPARE_SLOT_FREED: Lazy<Notify> = Lazy::new(Notify::new);
+
+/// Held by a launch while it prepares, the next queued launch starts when this is dropped
+pub struct PrepareSlot {
+    _private: (),
+}
+
+impl Drop for PrepareSlot {
+    fn drop(&mut self) {
+        if let Ok(mut active) = ACTIVE_PREPARES.lock() {
+            *active = active.saturating_sub(1);
+        }
+        PREPARE_SLOT_FREED.notify_waiters();
+    }
+}
+
+/// Waits until fewer than `limit` launches are preparing and takes a slot.
+/// Emits `launch-queued` when the launch has to wait and `launch-started` once it may prepare.
+/// A limit of 0 is treated as 1.
+pub async fn acquire_prepare_slot(installation_id: &str, limit: u32) -> PrepareSlot {
+    let limit = limit.max(1) as usize;
+    let mut queued = false;
+
+    loop {
+        // Register for the wake-up before checking, so a slot freed in between isn't missed
+        let freed = PREPARE_SLOT_FREED.notified();
+        {
+            let mut active = ACTIVE_PREPARES.lock().unwrap_or_else(|e| e.into_inner());
+            if *active < limit {
+                *active += 1;
+                break;
+            }
+            if !queued {
+                queued = true;
+                Logger::info_global(
+                    &format!(
+                        "Launch queued, {} launch(es) are preparing (limit {})",
+                        *active, limit
+                    ),
+                    Some(installation_id),
+                );
+                emit_queue_event(
+                    "launch-queued",
+                    serde_json::json!({
+                        "installation_id": installation_id,
+                        "active": *active,
+                        "limit": limit,
+                    }),
+                );
+            }
+        }
+        freed.await;
+    }
+
+    emit_queue_event(
+        "launch-started",
+        serde_json::json!({
+            "installation_id": installation_id,
+            "was_queued": queued,
+        }),
+    );
+    PrepareSlot { _private: () }
+}
+
+/// Launches per installation that haven't spawned their game yet, queued ones included
+static IN_FLIGHT_LAUNCHES: Lazy<Mutex<HashMap<String, usize>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Counts a launch of the installation as in flight until it is dropped
+pub struct LaunchReservation {
+    installation_id: String,
+}
+
+impl Drop for LaunchReservation {
+    fn drop(&mut self) {
+        let mut in_flight = IN_FLIGHT_LAUNCHES.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = in_flight.get_mut(&self.installation_id) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                in_flight.remove(&self.installation_id);
+            }
+        }
+    }
+}
+
+/// Reserves a launch of the installation before it waits for a prepare slot, so two launches
+/// started at once see each other. Returns how many other launches of it are already in flight.
+pub fn reserve_launch(installation_id: &str) -> (LaunchReservation, usize) {
+    let mut in_flight = IN_FLIGHT_LAUNCHES.lock().unwrap_or_else(|e| e.into_inner());
+    let count = in_flight.entry(installation_id.to_string()).or_default();
+    let others = *count;
+    *count += 1;
+    (
+        LaunchReservation {
+            installation_id: installation_id.to_string(),
+        },
+        others,
+    )
+}
+
+fn emit_queue_event(event: &str, payload: serde_json::Value) {
+    if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
+        if let Some(app_handle) = handle_guard.as_ref() {
+            let _ = app_handle.emit(event, payload);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reservations_see_each_other_until_dropped() {
+        let id = format!("test-{}", uuid::Uuid::new_v4());
+        let (first, others) = reserve_launch(&id);
+        assert_eq!(others, 0);
+        let (second, others) = reserve_launch(&id);
+        assert_eq!(others, 1);
+        drop(first);
+        drop(second);
+        let (_third, others) = reserve_launch(&id);
+        assert_eq!(others, 0);
+    }
+}
@@ -18,6 +18,20 @@ fn load_and_merge_manifest_sync(
     minecraft_dir: &str,
     version_id: &str,
     instance_id: Option<&str>,
+) -> Result<Value, String> {
+    load_manifest_chain(minecraft_dir, version_id, instance_id, &mut Vec::new())
+}
+
+/// Longest inheritsFrom chain a version may have, real ones are one or two levels deep
+pub(crate) const MAX_INHERITS_DEPTH: usize = 16;
+
+/// `chain` holds the versions already being loaded, so a version inheriting from itself
+/// (directly or through others) fails instead of recursing forever
+fn load_manifest_chain(
+    minecraft_dir: &str,
+    version_id: &str,
+    instance_id: Option<&str>,
+    chain: &mut Vec<String>,
 ) -> Result<Value, String> {
     Logger::debug_global(
         &format!("Loading manifest for version_id: {}", version_id),
@@ -68,6 +82,23 @@ fn load_and_merge_manifest_sync(
         version_id.to_string()
     };
 
+    if chain.contains(&effective_version) {
+        chain.push(effective_version);
+        return Err(format!(
+            "Version manifests inherit from each other in a loop: {}",
+            chain.join(" -> ")
+        ));
+    }
+    if chain.len() >= MAX_INHERITS_DEPTH {
+        return Err(format!(
+            "Version {} inherits through more than {} manifests: {} -> ...",
+            chain[0],
+            MAX_INHERITS_DEPTH,
+            chain.join(" -> ")
+        ));
+    }
+    chain.push(effective_version.clone());
+
     let manifest_path = PathBuf::from(minecraft_dir)
         .join("versions")
         .join(&effective_version)
@@ -104,7 +135,7 @@ fn load_and_merge_manifest_sync(
             ),
             instance_id,
         );
-        let parent = load_and_merge_manifest_sync(minecraft_dir, parent_id, instance_id)?;
+        let parent = load_manifest_chain(minecraft_dir, parent_id, instance_id, chain)?;
         manifest = merge_manifests_with_instance(parent, manifest, instance_id);
     }
     Logger::debug_global(
@@ -137,6 +168,88 @@ pub async fn load_and_merge_manifest(
     load_and_merge_manifest_with_instance(minecraft_dir, version_id, None).await
 }
 
+/// Version ids and modified times of the version jsons a merged manifest was built from
+type ManifestChainStamp = Vec<(String, std::time::SystemTime)>;
+
+/// Merged manifests by version id, with the stamp of the inheritsFrom chain they were built from
+static MERGED_MANIFEST_CACHE: once_cell::sync::Lazy<
+    std::sync::Mutex<HashMap<String, (ManifestChainStamp, Value)>>,
+> = once_cell::sync::Lazy::new(|| std::sync::Mutex::new(HashMap::new()));
+
+/// Modified times along the inheritsFrom chain of `version_id`, None when its own json is missing.
+/// The chain stops at a parent that isn't downloaded yet, so it changes once that one is.
+async fn manifest_chain_stamp(
+    minecraft_dir: &Path,
+    version_id: &str,
+) -> Option<ManifestChainStamp> {
+    let mut stamp = ManifestChainStamp::new();
+    let mut current = version_id.to_string();
+    while stamp.len() < MAX_INHERITS_DEPTH && !stamp.iter().any(|(id, _)| *id == current) {
+        let path = minecraft_dir
+            .join("versions")
+            .join(&current)
+            .join(format!("{}.json", current));
+        let Ok(modified) = async_fs::metadata(&path).await.and_then(|m| m.modified()) else {
+            break;
+        };
+        stamp.push((current.clone(), modified));
+        let parent = async_fs::read_to_string(&path)
+            .await
+            .ok()
+            .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+            .and_then(|manifest| manifest.get("inheritsFrom")?.as_str().map(str::to_string));
+        match parent {
+            Some(parent) => current = parent,
+            None => break,
+        }
+    }
+    (!stamp.is_empty()).then_some(stamp)
+}
+
+/// The fully merged manifest Kable launches `version_id` with, for inspecting launch problems.
+/// Cached until a version json of its inheritsFrom chain changes. With `dump_path` it is also
+/// written there, pretty-printed.
+pub async fn get_merged_manifest(
+    version_id: &str,
+    dump_path: Option<&str>,
+) -> Result<Value, String> {
+    let minecraft_dir = crate::get_default_minecraft_dir()?;
+    // Placeholders like latest-release have no file and are resolved every time
+    let stamp = manifest_chain_stamp(&minecraft_dir, version_id).await;
+
+    let cached = stamp.as_ref().and_then(|stamp| {
+        let cache = MERGED_MANIFEST_CACHE.lock().ok()?;
+        cache
+            .get(version_id)
+            .filter(|(cached_stamp, _)| cached_stamp == stamp)
+            .map(|(_, manifest)| manifest.clone())
+    });
+    let manifest = match cached {
+        Some(manifest) => manifest,
+        None => {
+            let manifest =
+                load_and_merge_manifest(&minecraft_dir.to_string_lossy(), version_id).await?;
+            // Stamped again, merging may have downloaded a missing parent
+            let stamp = manifest_chain_stamp(&minecraft_dir, version_id).await;
+            if let (Some(stamp), Ok(mut cache)) = (stamp, MERGED_MANIFEST_CACHE.lock()) {
+                cache.insert(version_id.to_string(), (stamp, manifest.clone()));
+            }
+            manifest
+        }
+    };
+
+    if let Some(dump_path) = dump_path {
+        let pretty = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+        crate::write_file_atomic_async(Path::new(dump_path), pretty.as_bytes()).await?;
+        Logger::info_global(
+            &format!("Wrote merged manifest of {} to {}", version_id, dump_path),
+            None,
+        );
+    }
+    Ok(manifest)
+}
+
 /// Merges two manifests (parent, child), with child values taking precedence.
 pub fn merge_manifests_with_instance(
     parent: Value,
@@ -270,6 +383,17 @@ pub fn build_classpath_from_manifest_with_instance(
     let mut entries: Vec<String> = dedup_map.into_values().map(|(path, _)| path).collect();
     entries.push(version_jar_path.to_string_lossy().to_string());
     let sep = if cfg!(windows) { ";" } else { ":" };
+    // Spaces need no quoting since args are passed to the JVM directly, but a path containing
+    // the separator itself can't be put on the classpath
+    for entry in entries.iter().filter(|e| e.contains(sep)) {
+        Logger::warn_global(
+            &format!(
+                "Classpath entry contains the path separator '{}' and will not load: {}",
+                sep, entry
+            ),
+            instance_id,
+        );
+    }
     let classpath = entries.join(sep);
     Logger::debug_global(
         &format!("Classpath built: {} entries", entries.len()),
@@ -394,7 +518,8 @@ pub struct Extract {
 /// Substitute variables in a template string with values from the provided map.
 ///
 /// Variables are in the form `${key}` and are replaced with the corresponding value from `variables`.
-/// Any unreplaced placeholders are removed.
+/// Any unreplaced placeholders are removed. Values are inserted as-is in a single pass, so
+/// paths with spaces, `$`, `{` or non-ASCII characters are never rescanned or altered.
 /// Used for argument and path processing in launcher modules.
 ///
 /// # Arguments
@@ -404,14 +529,26 @@ pub struct Extract {
 /// # Returns
 /// The string with all placeholders replaced or removed.
 pub fn substitute_variables(template: &str, variables: &HashMap<String, String>) -> String {
-    let mut result = template.to_string();
-    for (key, value) in variables {
-        let placeholder = format!("${{{}}}", key);
-        result = result.replace(&placeholder, value);
+    PLACEHOLDER_RE
+        .replace_all(template, |caps: &regex::Captures| {
+            variables.get(&caps[1]).cloned().unwrap_or_default()
+        })
+        .into_owned()
+}
+
+static PLACEHOLDER_RE: once_cell::sync::Lazy<regex::Regex> =
+    once_cell::sync::Lazy::new(|| regex::Regex::new(r"\$\{([^}]+)\}").unwrap());
+
+/// Fixes `-Dkey = value`, `-Dkey= value` and `-Dkey =value` to `-Dkey=value`. Done on the
+/// template before substitution so the spaces of a substituted path are kept.
+fn normalize_system_property(template: &str) -> String {
+    if !template.starts_with("-D") {
+        return template.to_string();
+    }
+    match template.split_once('=') {
+        Some((left, right)) => format!("{}={}", left.trim_end(), right.trim_start()),
+        None => template.to_string(),
     }
-    let re = regex::Regex::new(r"\$\{[^}]+\}").unwrap();
-    result = re.replace_all(&result, "").to_string();
-    result
 }
 
 /// Processes a list of Minecraft-style argument definitions (from version JSONs),
@@ -431,21 +568,10 @@ pub fn process_arguments(args: &[Value], variables: &HashMap<String, String>) ->
     for arg in args {
         match arg {
             Value::String(s) => {
-                let substituted = substitute_variables(s, variables);
-                // Fix -Dkey = value or -Dkey= value or -Dkey =value to -Dkey=value (no spaces)
-                if substituted.starts_with("-D") {
-                    // Try to fix only the first '=' occurrence
-                    let mut parts = substituted.splitn(2, '=');
-                    if let (Some(left), Some(right)) = (parts.next(), parts.next()) {
-                        let left = left.trim_end();
-                        let right = right.trim_start();
-                        processed.push(format!("{}={}", left, right));
-                    } else {
-                        processed.push(substituted);
-                    }
-                } else {
-                    processed.push(substituted);
-                }
+                processed.push(substitute_variables(
+                    &normalize_system_property(s),
+                    variables,
+                ));
             }
             Value::Object(obj) => {
                 if let Some(rules) = obj.get("rules") {
@@ -547,6 +673,7 @@ pub fn evaluate_os_condition(os_condition: &Value) -> Result<bool, String> {
 /// Returns true if the argument is known to cause issues or is not supported by this launcher.
 ///
 /// Used to filter out problematic JVM/game arguments (e.g., --demo, --quickPlay).
+/// `apply_demo_mode` adds `--demo` back for installations in demo mode.
 ///
 /// # Arguments
 /// * `arg` - Argument string to check.
@@ -673,23 +800,300 @@ pub fn deduplicate_libraries(libraries: Vec<Library>) -> Vec<Library> {
     library_map.into_values().collect()
 }
 
+/// Client for the game file downloads of a launch, routed through the installation's
+/// http_proxy/https_proxy when set. Mod downloads aren't tied to a launch and use a plain client.
+/// Fails on an invalid proxy rather than silently downloading without it.
+pub fn http_client_for(
+    installation: &crate::installations::kable_profiles::KableInstallation,
+) -> Result<reqwest::Client, String> {
+    let (http_proxy, https_proxy) = installation.proxy_urls()?;
+    let mut builder = reqwest::Client::builder();
+    if let Some(url) = http_proxy {
+        builder = builder.proxy(
+            reqwest::Proxy::http(url.as_str()).map_err(|e| format!("Invalid http_proxy: {}", e))?,
+        );
+    }
+    if let Some(url) = https_proxy {
+        builder = builder.proxy(
+            reqwest::Proxy::https(url.as_str())
+                .map_err(|e| format!("Invalid https_proxy: {}", e))?,
+        );
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Attempts per download, each one continues from the bytes the previous one got
+const DOWNLOAD_ATTEMPTS: usize = 3;
+
+/// Downloads `url` to `dest` through a partial file next to it. When a partial file exists
+/// (a failed attempt, or an earlier launch that was interrupted) and the server answers the
+/// Range request, the download continues from its end instead of starting over. Servers
+/// without range support send the whole file, which replaces the partial one.
+/// The finished file is checked against `expected_sha1` when given, a mismatch removes it.
+/// Two prepares of the same version write the same partial file, so only one at a time may.
+/// Without a hash the partial is unique per download and never resumed.
+pub async fn download_file_resumable(
+    client: &reqwest::Client,
+    url: &str,
+    dest: &Path,
+    expected_sha1: Option<&str>,
+) -> Result<(), String> {
+    let file_name = dest
+        .file_name()
+        .ok_or_else(|| format!("Path has no file name: {}", dest.display()))?
+        .to_string_lossy()
+        .to_string();
+    let result = match expected_sha1 {
+        Some(expected) => {
+            let lock = partial_download_lock(dest);
+            let result = async {
+                let _guard = lock.lock().await;
+                // Another prepare may have finished it while this one waited
+                if is_downloaded(dest, expected).await {
+                    return Ok(());
+                }
+                // Ends in .tmp so cleanup_temp_files removes partial files that are never resumed
+                let partial = dest.with_file_name(format!(".{}.part.tmp", file_name));
+                download_and_verify(client, url, dest, &partial, &file_name, expected_sha1).await
+            }
+            .await;
+            release_partial_download_lock(dest, lock);
+            result
+        }
+        None => {
+            let partial =
+                dest.with_file_name(format!(".{}.{}.tmp", file_name, uuid::Uuid::new_v4()));
+            let result = download_and_verify(client, url, dest, &partial, &file_name, None).await;
+            if result.is_err() {
+                let _ = async_fs::remove_file(&partial).await;
+            }
+            result
+        }
+    };
+    if let Err(e) = &result {
+        super::progress::emit_download_failed(&file_name, url, e);
+    }
+    result
+}
+
+// One lock per destination, held while its deterministic partial file is written
+static PARTIAL_DOWNLOAD_LOCKS: once_cell::sync::Lazy<
+    std::sync::Mutex<HashMap<PathBuf, std::sync::Arc<tokio::sync::Mutex<()>>>>,
+> = once_cell::sync::Lazy::new(|| std::sync::Mutex::new(HashMap::new()));
+
+fn partial_download_lock(dest: &Path) -> std::sync::Arc<tokio::sync::Mutex<()>> {
+    PARTIAL_DOWNLOAD_LOCKS
+        .lock()
+        .unwrap()
+        .entry(dest.to_path_buf())
+        .or_default()
+        .clone()
+}
+
+/// Drops the lock of a destination once no other download holds or waits for it
+fn release_partial_download_lock(dest: &Path, lock: std::sync::Arc<tokio::sync::Mutex<()>>) {
+    let mut locks = PARTIAL_DOWNLOAD_LOCKS.lock().unwrap();
+    // One reference is the map's, the other this download's
+    if std::sync::Arc::strong_count(&lock) == 2 {
+        locks.remove(dest);
+    }
+}
+
+async fn is_downloaded(dest: &Path, expected_sha1: &str) -> bool {
+    if !dest.exists() {
+        return false;
+    }
+    let dest = dest.to_path_buf();
+    tokio::task::spawn_blocking(move || sha1_file_hex(&dest))
+        .await
+        .is_ok_and(|actual| actual.is_ok_and(|actual| actual.eq_ignore_ascii_case(expected_sha1)))
+}
+
+async fn download_and_verify(
+    client: &reqwest::Client,
+    url: &str,
+    dest: &Path,
+    partial: &Path,
+    file_name: &str,
+    expected_sha1: Option<&str>,
+) -> Result<(), String> {
+    crate::ensure_parent_dir_exists_async(dest).await?;
+
+    let mut last_error = String::new();
+    for attempt in 1..=DOWNLOAD_ATTEMPTS {
+        match download_to_partial(client, url, partial).await {
+            Ok(()) => {
+                last_error.clear();
+                break;
+            }
+            Err(e) => {
+                Logger::debug_global(
+                    &format!(
+                        "Download of {} failed (attempt {}/{}): {}",
+                        url, attempt, DOWNLOAD_ATTEMPTS, e
+                    ),
+                    None,
+                );
+                last_error = e;
+            }
+        }
+    }
+    if !last_error.is_empty() {
+        return Err(last_error);
+    }
+
+    if let Some(expected) = expected_sha1 {
+        let to_hash = partial.to_path_buf();
+        let actual = tokio::task::spawn_blocking(move || sha1_file_hex(&to_hash))
+            .await
+            .map_err(|e| format!("Hash task join error: {}", e))??;
+        if !actual.eq_ignore_ascii_case(expected) {
+            let _ = async_fs::remove_file(partial).await;
+            return Err(format!(
+                "Downloaded {} sha1 mismatch ({} != {})",
+                file_name, actual, expected
+            ));
+        }
+    }
+    async_fs::rename(partial, dest)
+        .await
+        .map_err(|e| format!("Failed to move {} into place: {}", file_name, e))
+}
+
+pub(crate) fn sha1_file_hex(path: &Path) -> Result<String, String> {
+    use sha1::{Digest, Sha1};
+    let mut file =
+        fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha1::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+pub(crate) fn sha512_file_hex(path: &Path) -> Result<String, String> {
+    use sha2::{Digest, Sha512};
+    let mut file =
+        fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha512::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// One attempt of `download_file_resumable`, appending to `partial` when the server resumes
+async fn download_to_partial(
+    client: &reqwest::Client,
+    url: &str,
+    partial: &Path,
+) -> Result<(), String> {
+    use tokio::io::AsyncWriteExt;
+
+    let offset = async_fs::metadata(partial)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+    let mut request = client.get(url);
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+    }
+    let mut resp = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+
+    let status = resp.status();
+    if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        // The partial file is longer than the file on the server, start over
+        let _ = async_fs::remove_file(partial).await;
+        return Err(format!("Partial download of {} was stale", url));
+    }
+    if !status.is_success() {
+        return Err(format!("Failed to download {}: HTTP {}", url, status));
+    }
+    // Only continue when the server resumes exactly where the partial file ends
+    let resumed = offset > 0
+        && status == reqwest::StatusCode::PARTIAL_CONTENT
+        && resp
+            .headers()
+            .get(reqwest::header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|range| range.starts_with(&format!("bytes {}-", offset)));
+    if resumed {
+        Logger::debug_global(
+            &format!("Resuming download of {} at {} bytes", url, offset),
+            None,
+        );
+    }
+
+    let mut file = async_fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(partial)
+        .await
+        .map_err(|e| format!("Failed to open {}: {}", partial.display(), e))?;
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .map_err(|e| format!("Download of {} was interrupted: {}", url, e))?
+    {
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", partial.display(), e))?;
+    }
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", partial.display(), e))
+}
+
+/// Checks that a client jar has an entry point: the Minecraft main class, a bundler
+/// `META-INF/versions.list` or a manifest with a Main-Class
+fn validate_client_jar(jar_path: &Path) -> Result<(), String> {
+    use std::io::Read;
+
+    let file = std::fs::File::open(jar_path)
+        .map_err(|e| format!("Failed to open JAR for validation: {}", e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to read JAR archive: {}", e))?;
+    if archive
+        .index_for_name("net/minecraft/client/main/Main.class")
+        .is_some()
+        || archive.index_for_name("META-INF/versions.list").is_some()
+    {
+        return Ok(());
+    }
+    if let Ok(mut entry) = archive.by_name("META-INF/MANIFEST.MF") {
+        let mut manifest = String::new();
+        if entry.read_to_string(&mut manifest).is_ok()
+            && manifest.lines().any(|line| {
+                line.strip_prefix("Main-Class:")
+                    .is_some_and(|class| !class.trim().is_empty())
+            })
+        {
+            return Ok(());
+        }
+    }
+    Err("Client JAR has neither net.minecraft.client.main.Main, a bundler versions.list nor a Main-Class".to_string())
+}
+
 //  Java and JVM utilities
 /// Ensures the version manifest JSON and JAR exist for the given version_id in minecraft_dir.
 /// Downloads them from Mojang if missing.
 pub async fn ensure_version_manifest_and_jar(
     version_id: &str,
     minecraft_dir: &str,
+    client: &reqwest::Client,
 ) -> Result<String, String> {
-    use reqwest::Client;
     use std::path::PathBuf;
-    use zip::ZipArchive;
 
     // Resolve "latest-release" / "latest-snapshot" to a concrete version id
     let mut resolved_version = version_id.to_string();
     let mut maybe_version_list: Option<serde_json::Value> = None;
     if version_id == "latest-release" || version_id == "latest-snapshot" || version_id == "latest" {
         let version_list_url = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
-        let client = Client::new();
         let resp = client
             .get(version_list_url)
             .send()
@@ -725,23 +1129,6 @@ pub async fn ensure_version_manifest_and_jar(
     let manifest_path = version_subdir.join(format!("{}.json", resolved_version));
     let jar_path = version_subdir.join(format!("{}.jar", resolved_version));
 
-    // Helper to validate client jar contains the Minecraft main class
-    fn validate_client_jar(jar_path: &Path) -> Result<(), String> {
-        let file = std::fs::File::open(jar_path)
-            .map_err(|e| format!("Failed to open JAR for validation: {}", e))?;
-        let mut archive =
-            ZipArchive::new(file).map_err(|e| format!("Failed to read JAR archive: {}", e))?;
-        for i in 0..archive.len() {
-            if let Ok(entry) = archive.by_index(i) {
-                let name = entry.name();
-                if name == "net/minecraft/client/main/Main.class" {
-                    return Ok(());
-                }
-            }
-        }
-        Err("Client JAR does not contain net.minecraft.client.main.Main".to_string())
-    }
-
     // If both manifest and jar already exist for the resolved version, validate jar and skip if ok.
     if manifest_path.exists() && jar_path.exists() {
         match validate_client_jar(&jar_path) {
@@ -776,7 +1163,6 @@ pub async fn ensure_version_manifest_and_jar(
             v
         } else {
             let version_list_url = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
-            let client = Client::new();
             let resp = client
                 .get(version_list_url)
                 .send()
@@ -799,7 +1185,6 @@ pub async fn ensure_version_manifest_and_jar(
             .get("url")
             .and_then(|v| v.as_str())
             .ok_or("No url for version")?;
-        let client = Client::new();
         let resp = client
             .get(url)
             .send()
@@ -826,25 +1211,15 @@ pub async fn ensure_version_manifest_and_jar(
             .get("downloads")
             .and_then(|v| v.as_object())
             .ok_or("No downloads object")?;
-        let client = Client::new();
         if let Some(client_obj) = downloads.get("client").and_then(|v| v.as_object()) {
             let url = client_obj
                 .get("url")
                 .and_then(|v| v.as_str())
                 .ok_or("No client jar url")?;
-            let resp = client
-                .get(url)
-                .send()
-                .await
-                .map_err(|e| format!("Failed to fetch jar: {e}"))?;
-            let bytes = resp
-                .bytes()
+            let sha1 = client_obj.get("sha1").and_then(|v| v.as_str());
+            download_file_resumable(client, url, &jar_path, sha1)
                 .await
-                .map_err(|e| format!("Failed to get jar bytes: {e}"))?;
-            crate::ensure_parent_dir_exists_async(&jar_path).await?;
-            crate::write_file_atomic_async(&jar_path, &bytes)
-                .await
-                .map_err(|e| format!("Failed to write jar: {e}"))?;
+                .map_err(|e| format!("Failed to download jar: {e}"))?;
         } else {
             return Err("No client jar info in manifest".to_string());
         }
@@ -857,11 +1232,15 @@ pub async fn ensure_version_manifest_and_jar(
 pub async fn ensure_libraries(
     manifest: &serde_json::Value,
     libraries_path: &std::path::Path,
+    client: &reqwest::Client,
+    progress: &super::progress::ProgressReporter,
 ) -> Result<(), String> {
-    use reqwest::Client;
-    let client = Client::new();
+    let operation = super::operations::Operation::start("libraries", None);
     if let Some(libs) = manifest.get("libraries").and_then(|v| v.as_array()) {
-        for lib in libs {
+        let total = libs.len() as u64;
+        for (done, lib) in libs.iter().enumerate() {
+            operation.check_cancelled()?;
+            progress.report("libraries", done as u64, Some(total), None);
             if let Some(obj) = lib.as_object() {
                 // Try to get library info from downloads.artifact first
                 let downloads = obj.get("downloads").and_then(|v| v.as_object());
@@ -871,22 +1250,10 @@ pub async fn ensure_libraries(
                         let url = artifact.get("url").and_then(|v| v.as_str()).unwrap_or("");
                         let jar_path = libraries_path.join(path);
                         if !jar_path.exists() {
-                            if !jar_path.parent().unwrap().exists() {
-                                crate::ensure_parent_dir_exists_async(jar_path.parent().unwrap())
-                                    .await?;
-                            }
-                            let resp = client
-                                .get(url)
-                                .send()
-                                .await
-                                .map_err(|e| format!("Failed to fetch lib: {e}"))?;
-                            let bytes = resp
-                                .bytes()
+                            let sha1 = artifact.get("sha1").and_then(|v| v.as_str());
+                            download_file_resumable(client, url, &jar_path, sha1)
                                 .await
-                                .map_err(|e| format!("Failed to get lib bytes: {e}"))?;
-                            crate::write_file_atomic_async(&jar_path, &bytes)
-                                .await
-                                .map_err(|e| format!("Failed to write lib: {e}"))?;
+                                .map_err(|e| format!("Failed to download lib: {e}"))?;
                         }
                     }
                 } else {
@@ -935,33 +1302,10 @@ pub async fn ensure_libraries(
                                     None,
                                 );
 
-                                if !jar_path.parent().unwrap().exists() {
-                                    crate::ensure_parent_dir_exists_async(
-                                        jar_path.parent().unwrap(),
-                                    )
-                                    .await?;
-                                }
-
-                                let resp = client.get(&download_url).send().await.map_err(|e| {
-                                    format!("Failed to fetch library {}: {}", name, e)
-                                })?;
-
-                                if !resp.status().is_success() {
-                                    return Err(format!(
-                                        "Failed to download library {}: HTTP {}",
-                                        name,
-                                        resp.status()
-                                    ));
-                                }
-
-                                let bytes = resp.bytes().await.map_err(|e| {
-                                    format!("Failed to get library bytes for {}: {}", name, e)
-                                })?;
-
-                                crate::write_file_atomic_async(&jar_path, &bytes)
+                                download_file_resumable(client, &download_url, &jar_path, None)
                                     .await
                                     .map_err(|e| {
-                                        format!("Failed to write library {}: {}", name, e)
+                                        format!("Failed to download library {}: {}", name, e)
                                     })?;
 
                                 crate::logging::Logger::debug_global(
@@ -978,6 +1322,7 @@ pub async fn ensure_libraries(
                 }
             }
         }
+        progress.report("libraries", total, Some(total), None);
     }
     Ok(())
 }
@@ -989,17 +1334,136 @@ pub enum AssetMode {
     Full,
 }
 
-/// Ensures the asset index and required objects for a manifest exist in minecraft_dir.
+/// Assets an installation needs. Isolated installations get the full set since there is no
+/// shared assets folder that other launchers have already filled.
+pub fn asset_mode_for(context: &LaunchContext) -> AssetMode {
+    if context.installation.isolated_game_files {
+        AssetMode::Full
+    } else {
+        AssetMode::MinimalWithSounds
+    }
+}
+
+/// Loader installs rely on the shared assets folder, isolated installations download their own
+pub async fn ensure_isolated_assets(
+    context: &LaunchContext,
+    version_id: &str,
+) -> Result<(), String> {
+    if !context.installation.isolated_game_files {
+        return Ok(());
+    }
+    let manifest = load_and_merge_manifest_with_instance(
+        &context.minecraft_dir,
+        version_id,
+        Some(&context.installation.id),
+    )
+    .await?;
+    ensure_assets_for_manifest(
+        &context.assets_dir(),
+        &manifest,
+        AssetMode::Full,
+        Some(&context.installation.id),
+        &context.http_client()?,
+        &context.progress,
+    )
+    .await
+}
+
+/// Mojang's list of all versions, which links to each version's own manifest
+const MOJANG_VERSION_MANIFEST_URL: &str =
+    "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+
+/// The assetIndex url of the vanilla version a manifest is based on, for manifests that only
+/// name their `assets`. Uses the local vanilla manifest when it is there, otherwise Mojang's.
+async fn vanilla_asset_index_url(
+    manifest: &serde_json::Value,
+    assets_index_name: &str,
+    client: &reqwest::Client,
+    instance_id: Option<&str>,
+) -> Option<String> {
+    let asset_index_url = |version: &serde_json::Value| {
+        version
+            .get("assetIndex")
+            .filter(|index| index.get("id").and_then(|id| id.as_str()) == Some(assets_index_name))
+            .and_then(|index| index.get("url"))
+            .and_then(|url| url.as_str())
+            .map(str::to_string)
+    };
+    // The vanilla version is usually the parent, older installers only set the jar
+    let mut candidates: Vec<&str> = ["inheritsFrom", "jar", "id"]
+        .iter()
+        .filter_map(|key| manifest.get(*key).and_then(|v| v.as_str()))
+        .collect();
+    candidates.push(assets_index_name);
+    candidates.dedup();
+
+    if let Ok(minecraft_dir) = crate::get_default_minecraft_dir() {
+        for candidate in &candidates {
+            let path = minecraft_dir
+                .join("versions")
+                .join(candidate)
+                .join(format!("{}.json", candidate));
+            let Ok(text) = async_fs::read_to_string(&path).await else {
+                continue;
+            };
+            let local = serde_json::from_str::<serde_json::Value>(&text).ok();
+            if let Some(url) = local.as_ref().and_then(asset_index_url) {
+                return Some(url);
+            }
+        }
+    }
+
+    let versions: serde_json::Value = client
+        .get(MOJANG_VERSION_MANIFEST_URL)
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+    let version_url = versions
+        .get("versions")?
+        .as_array()?
+        .iter()
+        .find(|v| {
+            v.get("id")
+                .and_then(|id| id.as_str())
+                .is_some_and(|id| candidates.contains(&id))
+        })?
+        .get("url")?
+        .as_str()?
+        .to_string();
+    let vanilla: serde_json::Value = client
+        .get(&version_url)
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+    let url = asset_index_url(&vanilla)?;
+    crate::logging::Logger::debug_global(
+        &format!(
+            "Found assets index {} through the vanilla manifest {}",
+            assets_index_name, version_url
+        ),
+        instance_id,
+    );
+    Some(url)
+}
+
+/// Ensures the asset index and required objects for a manifest exist in assets_dir.
 /// Minimal mode will fetch a small curated set (panorama + icons). Full will fetch all objects referenced
 /// in the index (can be large).
 pub async fn ensure_assets_for_manifest(
-    minecraft_dir: &str,
+    assets_dir: &Path,
     manifest: &serde_json::Value,
     mode: AssetMode,
     instance_id: Option<&str>,
+    client: &reqwest::Client,
+    progress: &super::progress::ProgressReporter,
 ) -> Result<(), String> {
-    use reqwest::Client;
-    use sha1::{Digest, Sha1};
+    let operation = super::operations::Operation::start("assets", instance_id);
 
     // Determine assets index name from manifest
     let assets_index_name = match manifest.get("assets").and_then(|v| v.as_str()) {
@@ -1013,8 +1477,8 @@ pub async fn ensure_assets_for_manifest(
         }
     };
 
-    let indexes_dir = PathBuf::from(minecraft_dir).join("assets").join("indexes");
-    let objects_dir = PathBuf::from(minecraft_dir).join("assets").join("objects");
+    let indexes_dir = assets_dir.join("indexes");
+    let objects_dir = assets_dir.join("objects");
     crate::ensure_folder(&indexes_dir)
         .await
         .map_err(|e| format!("Failed to create indexes dir: {}", e))?;
@@ -1023,7 +1487,6 @@ pub async fn ensure_assets_for_manifest(
         .map_err(|e| format!("Failed to create objects dir: {}", e))?;
 
     let index_path = indexes_dir.join(format!("{}.json", assets_index_name));
-    let client = Client::new();
 
     // Fetch index JSON if missing
     if !index_path.exists() {
@@ -1031,36 +1494,38 @@ pub async fn ensure_assets_for_manifest(
         // The version manifest may include an 'assetIndex' object, but typical version manifests reference only the name in 'assets'.
         // We'll try the common URL pattern used by Mojang's manifest entries: https://launchermeta.mojang.com/v1/packages/<assetIndexUrl>
         // Fallback: try the canonical assets meta at https://launchermeta.mojang.com/mc/assets/ - but these endpoints vary.
-        // Best approach: look for an 'assetIndex' object in the version manifest (manifest may include it when downloaded from Mojang)
-        if let Some(asset_index_obj) = manifest.get("assetIndex").and_then(|v| v.as_object()) {
-            if let Some(url) = asset_index_obj.get("url").and_then(|v| v.as_str()) {
-                let resp = client
-                    .get(url)
-                    .send()
-                    .await
-                    .map_err(|e| format!("Failed to fetch assets index: {e}"))?;
-                let txt = resp
-                    .text()
-                    .await
-                    .map_err(|e| format!("Failed to read assets index text: {e}"))?;
-                crate::ensure_parent_dir_exists_async(&index_path).await?;
-                crate::write_file_atomic_async(&index_path, txt.as_bytes())
-                    .await
-                    .map_err(|e| format!("Failed to write assets index: {e}"))?;
-            } else {
-                crate::logging::Logger::debug_global(
-                    "No assetIndex.url in manifest; skipping index download",
-                    instance_id,
-                );
-                return Ok(());
+        // Best approach: look for an 'assetIndex' object in the version manifest (manifest may include it when downloaded from Mojang).
+        // Third-party installers often write only the name, then the vanilla version's manifest has it.
+        let url = match manifest
+            .get("assetIndex")
+            .and_then(|v| v.get("url"))
+            .and_then(|v| v.as_str())
+        {
+            Some(url) => Some(url.to_string()),
+            None => {
+                vanilla_asset_index_url(manifest, &assets_index_name, client, instance_id).await
             }
-        } else {
+        };
+        let Some(url) = url else {
             crate::logging::Logger::debug_global(
-                "No assetIndex object in manifest; skipping index download",
+                "No assetIndex url in manifest or its vanilla version; skipping index download",
                 instance_id,
             );
             return Ok(());
-        }
+        };
+        let resp = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch assets index: {e}"))?;
+        let txt = resp
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read assets index text: {e}"))?;
+        crate::ensure_parent_dir_exists_async(&index_path).await?;
+        crate::write_file_atomic_async(&index_path, txt.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write assets index: {e}"))?;
     }
 
     // Parse index JSON
@@ -1113,33 +1578,13 @@ pub async fn ensure_assets_for_manifest(
                 let prefix = &hash[0..2];
                 let sounds_obj_path = objects_dir.join(prefix).join(hash);
                 if !sounds_obj_path.exists() {
-                    crate::ensure_parent_dir_exists_async(&sounds_obj_path).await?;
                     let url = format!(
                         "https://resources.download.minecraft.net/{}/{}",
                         prefix, hash
                     );
-                    let resp =
-                        client.get(&url).send().await.map_err(|e| {
-                            format!("Failed to download sounds.json {}: {}", hash, e)
-                        })?;
-                    let bytes = resp
-                        .bytes()
-                        .await
-                        .map_err(|e| format!("Failed to read sounds.json bytes {}: {}", hash, e))?;
-                    // Validate sha1
-                    let mut hasher = Sha1::new();
-                    hasher.update(&bytes);
-                    let digest = hasher.finalize();
-                    let hex = hex::encode(digest);
-                    if hex != hash {
-                        return Err(format!(
-                            "Downloaded sounds.json {} sha1 mismatch ({} != {})",
-                            hash, hex, hash
-                        ));
-                    }
-                    crate::write_file_atomic_async(&sounds_obj_path, &bytes)
+                    download_file_resumable(client, &url, &sounds_obj_path, Some(hash))
                         .await
-                        .map_err(|e| format!("Failed to write sounds.json {}: {}", hash, e))?;
+                        .map_err(|e| format!("Failed to download sounds.json {}: {}", hash, e))?;
                 }
                 // Parse sounds.json to collect referenced sound files
                 let sounds_bytes = async_fs::read(&sounds_obj_path)
@@ -1202,7 +1647,10 @@ pub async fn ensure_assets_for_manifest(
     }
 
     // Download missing objects
-    for hash in required_hashes {
+    let total = required_hashes.len() as u64;
+    for (done, hash) in required_hashes.into_iter().enumerate() {
+        operation.check_cancelled()?;
+        progress.report("assets", done as u64, Some(total), None);
         if hash.len() < 2 {
             continue;
         }
@@ -1211,36 +1659,16 @@ pub async fn ensure_assets_for_manifest(
         if obj_path.exists() {
             continue;
         }
-        crate::ensure_parent_dir_exists_async(&obj_path).await?;
         let url = format!(
             "https://resources.download.minecraft.net/{}/{}",
             prefix, hash
         );
-        let resp = client
-            .get(&url)
-            .send()
+        download_file_resumable(client, &url, &obj_path, Some(&hash))
             .await
             .map_err(|e| format!("Failed to download asset {}: {}", hash, e))?;
-        let bytes = resp
-            .bytes()
-            .await
-            .map_err(|e| format!("Failed to read asset bytes {}: {}", hash, e))?;
-        // Validate sha1
-        let mut hasher = Sha1::new();
-        hasher.update(&bytes);
-        let digest = hasher.finalize();
-        let hex = hex::encode(digest);
-        if hex != hash {
-            return Err(format!(
-                "Downloaded asset {} sha1 mismatch ({} != {})",
-                hash, hex, hash
-            ));
-        }
-        crate::write_file_atomic_async(&obj_path, &bytes)
-            .await
-            .map_err(|e| format!("Failed to write asset {}: {}", hash, e))?;
         crate::logging::Logger::debug_global(&format!("Downloaded asset {}", hash), instance_id);
     }
+    progress.report("assets", total, Some(total), None);
 
     Ok(())
 }
@@ -1254,6 +1682,7 @@ pub async fn ensure_assets_for_manifest(
 /// * `libraries` - List of Library structs (from version JSONs).
 /// * `libraries_path` - Path to the root of the libraries directory.
 /// * `natives_path` - Path to the directory where natives should be extracted.
+/// * `arch_override` - Optional natives arch tag to use instead of the host architecture.
 /// * `instance_id` - Optional instance ID for logging correlation.
 ///
 /// # Returns
@@ -1262,6 +1691,7 @@ pub fn extract_natives(
     libraries: &[Library],
     libraries_path: &Path,
     natives_path: &PathBuf,
+    arch_override: Option<&str>,
     instance_id: Option<&str>,
 ) -> Result<(), String> {
     if natives_path.exists() {
@@ -1278,13 +1708,16 @@ pub fn extract_natives(
     let current_os = std::env::consts::OS;
     let current_arch = std::env::consts::ARCH; // e.g., "x86", "x86_64", "aarch64"
 
-    // Helper to map Rust arch to common manifest arch strings
-    let arch_tag = match current_arch {
-        "x86" | "i386" => "x86",
-        "x86_64" | "x64" | "amd64" => "x86_64",
-        "aarch64" | "arm64" => "arm64",
-        other => other,
-    };
+    // Map Rust arch to common manifest arch strings, unless the installation forces one
+    let arch_tag = arch_override
+        .or_else(|| normalize_arch_tag(current_arch))
+        .unwrap_or(current_arch);
+    if arch_override.is_some() {
+        Logger::debug_global(
+            &format!("Using native architecture override: {}", arch_tag),
+            instance_id,
+        );
+    }
 
     let os_tag = current_os;
 
@@ -1419,6 +1852,23 @@ pub fn extract_natives(
     Ok(())
 }
 
+/// Normalizes `java -version` output to the arch tags used by natives selection
+/// ("x86_64", "arm64" or "x86"), falls back to the host arch when the output doesn't say
+pub fn java_arch_from_output(java_info: &str) -> &'static str {
+    if java_info.contains("64-Bit") || java_info.contains("x86_64") || java_info.contains("amd64") {
+        "x86_64"
+    } else if java_info.to_lowercase().contains("arm")
+        || java_info.contains("aarch64")
+        || java_info.contains("arm64")
+    {
+        "arm64"
+    } else if java_info.contains("32-Bit") || java_info.contains("x86") {
+        "x86"
+    } else {
+        std::env::consts::ARCH
+    }
+}
+
 /// Run pre-launch compatibility checks.
 ///
 /// 1) Detect Java VM architecture (32/64/arm) and compare with native classifier architecture(s)
@@ -1466,23 +1916,7 @@ pub fn pre_launch_java_native_compat_check(
         }
     }
 
-    // Normalize to small set of arch tags used by natives selection
-    let java_arch = if java_info.contains("64-Bit")
-        || java_info.contains("x86_64")
-        || java_info.contains("amd64")
-    {
-        "x86_64"
-    } else if java_info.to_lowercase().contains("arm")
-        || java_info.contains("aarch64")
-        || java_info.contains("arm64")
-    {
-        "arm64"
-    } else if java_info.contains("32-Bit") || java_info.contains("x86") {
-        "x86"
-    } else {
-        // Fallback to host arch if we can't detect from java output
-        std::env::consts::ARCH
-    };
+    let java_arch = java_arch_from_output(&java_info);
 
     Logger::debug_global(
         &format!(
@@ -1560,13 +1994,9 @@ pub fn pre_launch_java_native_compat_check(
     Ok(())
 }
 
-/// Inspect the constructed classpath for multiple LWJGL versions and log a warning if inconsistent versions are detected.
-/// This does not block launch, but surfaces potential runtime issues.
-pub fn check_lwjgl_classpath_consistency(
-    classpath: &str,
-    instance_id: Option<&str>,
-) -> Result<(), String> {
-    use crate::logging::Logger;
+/// Collects the LWJGL versions found in the file names on the classpath.
+/// Jars whose version could not be determined are reported as "unknown".
+pub fn lwjgl_versions_on_classpath(classpath: &str) -> std::collections::HashSet<String> {
     use regex::Regex;
 
     let sep = if cfg!(windows) { ";" } else { ":" };
@@ -1590,12 +2020,85 @@ pub fn check_lwjgl_classpath_consistency(
             }
         }
     }
-    if versions.len() > 1 {
-        Logger::info_global(&format!("Multiple LWJGL versions detected on classpath: {:?}. This can cause native/JNI conflicts at runtime. Consider ensuring a single LWJGL version is present (check installed libraries).", versions), instance_id);
-    } else if versions.len() == 1 && versions.contains("unknown") {
-        Logger::debug_global("Found LWJGL artifacts on classpath but could not determine version from filenames; ensure LWJGL jars are consistent.", instance_id);
-    } else if versions.len() == 1 {
-        Logger::debug_global(
+    versions
+}
+
+/// Removes LWJGL jars that are not at the highest LWJGL version found on the classpath, so only
+/// one consistent LWJGL set remains. Jars whose version cannot be determined are kept.
+///
+/// # Returns
+/// The cleaned classpath and the removed entries (empty when there was no conflict).
+pub fn resolve_lwjgl_classpath_conflict(
+    classpath: &str,
+    instance_id: Option<&str>,
+) -> (String, Vec<String>) {
+    use regex::Regex;
+
+    let sep = if cfg!(windows) { ";" } else { ":" };
+    let ver_re = Regex::new(r"(\d+\.[0-9]+(?:\.[0-9]+)*)").unwrap();
+    let lwjgl_version = |entry: &str| -> Option<String> {
+        if !entry.to_lowercase().contains("lwjgl") {
+            return None;
+        }
+        let name = Path::new(entry).file_name()?.to_str()?;
+        Some(ver_re.captures(name)?.get(1)?.as_str().to_string())
+    };
+
+    let entries: Vec<&str> = classpath.split(sep).collect();
+    let Some(highest) = entries
+        .iter()
+        .filter_map(|e| lwjgl_version(e))
+        .max_by(|a, b| compare_versions(a, b).cmp(&0))
+    else {
+        return (classpath.to_string(), Vec::new());
+    };
+
+    let mut kept = Vec::with_capacity(entries.len());
+    let mut removed = Vec::new();
+    for entry in entries {
+        match lwjgl_version(entry) {
+            Some(version) if compare_versions(&version, &highest) != 0 => {
+                removed.push(entry.to_string())
+            }
+            _ => kept.push(entry),
+        }
+    }
+
+    for entry in &removed {
+        Logger::info_global(
+            &format!(
+                "[LWJGL] Removed {} from classpath (keeping LWJGL {})",
+                entry, highest
+            ),
+            instance_id,
+        );
+    }
+    (kept.join(sep), removed)
+}
+
+/// Applies `resolve_lwjgl_classpath_conflict` when the installation opted in to the LWJGL fix
+pub fn apply_lwjgl_conflict_fix(context: &LaunchContext, classpath: String) -> String {
+    if !context.installation.resolve_lwjgl_conflicts {
+        return classpath;
+    }
+    resolve_lwjgl_classpath_conflict(&classpath, Some(&context.installation.id)).0
+}
+
+/// Inspect the constructed classpath for multiple LWJGL versions and log a warning if inconsistent versions are detected.
+/// This does not block launch, but surfaces potential runtime issues.
+pub fn check_lwjgl_classpath_consistency(
+    classpath: &str,
+    instance_id: Option<&str>,
+) -> Result<(), String> {
+    use crate::logging::Logger;
+
+    let versions = lwjgl_versions_on_classpath(classpath);
+    if versions.len() > 1 {
+        Logger::info_global(&format!("Multiple LWJGL versions detected on classpath: {:?}. This can cause native/JNI conflicts at runtime. Consider ensuring a single LWJGL version is present (check installed libraries).", versions), instance_id);
+    } else if versions.len() == 1 && versions.contains("unknown") {
+        Logger::debug_global("Found LWJGL artifacts on classpath but could not determine version from filenames; ensure LWJGL jars are consistent.", instance_id);
+    } else if versions.len() == 1 {
+        Logger::debug_global(
             &format!(
                 "LWJGL version on classpath appears consistent: {:?}",
                 versions
@@ -1649,6 +2152,32 @@ fn extract_jar(jar_path: &PathBuf, extract_to: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// Maps an architecture name (Rust, JVM or manifest style) to the tag used to pick natives:
+/// 'x86' | 'x86_64' | 'arm64'
+pub fn normalize_arch_tag(arch: &str) -> Option<&'static str> {
+    match arch.trim().to_lowercase().as_str() {
+        "x86" | "i386" | "i686" => Some("x86"),
+        "x86_64" | "x64" | "amd64" => Some("x86_64"),
+        "aarch64" | "arm64" => Some("arm64"),
+        _ => None,
+    }
+}
+
+/// The installation's `native_arch_override` as a natives arch tag.
+/// An unknown value is logged and ignored so the host architecture is used instead.
+pub fn native_arch_override(context: &LaunchContext) -> Option<&'static str> {
+    match context.installation.native_arch_override_tag() {
+        Ok(tag) => tag,
+        Err(e) => {
+            Logger::warn_global(
+                &format!("Ignoring native_arch_override: {}", e),
+                Some(&context.installation.id),
+            );
+            None
+        }
+    }
+}
+
 //  Variable map builder
 /// Builds a map of variable substitutions for Minecraft argument templates, based on the launch context, manifest, and version info.
 ///
@@ -1739,32 +2268,429 @@ pub fn build_variable_map(
     variables.insert("game_directory".to_string(), context.minecraft_dir.clone());
     variables.insert(
         "assets_root".to_string(),
-        PathBuf::from(&context.minecraft_dir)
-            .join("assets")
-            .to_string_lossy()
-            .to_string(),
+        context.assets_dir().to_string_lossy().to_string(),
     );
     variables.insert(
         "natives_directory".to_string(),
-        PathBuf::from(&context.minecraft_dir)
-            .join("natives")
-            .to_string_lossy()
-            .to_string(),
+        context.natives_dir().to_string_lossy().to_string(),
+    );
+    // Natives architecture, `${arch}` is the bitness in legacy natives classifiers
+    let arch_tag = native_arch_override(context)
+        .or_else(|| normalize_arch_tag(std::env::consts::ARCH))
+        .unwrap_or(std::env::consts::ARCH);
+    variables.insert(
+        "arch".to_string(),
+        if arch_tag == "x86" { "32" } else { "64" }.to_string(),
     );
     // Resolution
     variables.insert("resolution_width".to_string(), "1024".to_string());
     variables.insert("resolution_height".to_string(), "768".to_string());
     // Merge/overwrite with parameters_map if provided
     if let Some(params) = parameters_map {
-        for (k, v) in params {
-            if k.starts_with("--") {
-                // Will be handled as extra args, not as variable
+        let warnings = apply_parameter_overrides(
+            &mut variables,
+            params,
+            context
+                .settings
+                .advanced
+                .allow_protected_parameter_overrides,
+        );
+        for warning in warnings {
+            Logger::warn_global(&warning, Some(&context.installation.id));
+        }
+    }
+    variables
+}
+
+/// Copies `parameters_map` into the launch variables, `--` keys are extra args and skipped.
+/// Overrides of protected variables are ignored unless `allow_protected` is set.
+///
+/// # Returns
+/// A warning for every protected variable that was overridden or ignored
+fn apply_parameter_overrides(
+    variables: &mut HashMap<String, String>,
+    params: &HashMap<String, String>,
+    allow_protected: bool,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for (k, v) in params {
+        if k.starts_with("--") {
+            // Will be handled as extra args, not as variable
+            continue;
+        }
+        if crate::installations::is_protected_variable(k) {
+            // The value is not logged, it may be a token
+            if !allow_protected {
+                warnings.push(format!(
+                    "parameters_map overrides ${{{}}}, which breaks the launch or the login, the override is ignored. Remove it from the installation or enable protected parameter overrides in the advanced settings to apply it anyway",
+                    k
+                ));
                 continue;
             }
-            variables.insert(k.clone(), v.clone());
+            warnings.push(format!(
+                "parameters_map overrides ${{{}}}, applied because protected parameter overrides are allowed",
+                k
+            ));
         }
+        variables.insert(k.clone(), v.clone());
     }
-    variables
+    warnings
+}
+
+// JVM argument sanity checks
+const GC_SELECTORS: &[&str] = &[
+    "-XX:+UseG1GC",
+    "-XX:+UseZGC",
+    "-XX:+UseShenandoahGC",
+    "-XX:+UseParallelGC",
+    "-XX:+UseParallelOldGC",
+    "-XX:+UseSerialGC",
+    "-XX:+UseConcMarkSweepGC",
+    "-XX:+UseEpsilonGC",
+];
+
+/// Parses a JVM memory size such as `4G`, `2048M` or `512k` into bytes.
+pub fn parse_jvm_memory(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let (digits, multiplier) = match value.chars().last()?.to_ascii_lowercase() {
+        'k' => (&value[..value.len() - 1], 1024),
+        'm' => (&value[..value.len() - 1], 1024 * 1024),
+        'g' => (&value[..value.len() - 1], 1024 * 1024 * 1024),
+        't' => (&value[..value.len() - 1], 1024 * 1024 * 1024 * 1024),
+        _ => (value, 1),
+    };
+    digits.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+/// True for JVM args that carry their value in the same token and can be deduplicated on their own
+fn is_self_contained_java_arg(arg: &str) -> bool {
+    arg.starts_with("-X") || (arg.starts_with("-D") && arg.contains('='))
+}
+
+/// Cleans up user-provided JVM arguments before they are passed to Java.
+///
+/// Used by the loader modules on `installation.java_args` right before building the command.
+///
+/// - Duplicate `-Xmx`/`-Xms` values: the last one wins (same as the JVM) and the others are dropped.
+/// - Multiple GC selectors (e.g. G1 and ZGC): the last one wins, since the JVM refuses to start otherwise.
+/// - G1-only tuning flags (`-XX:G1...`) are dropped when G1 is not the selected GC.
+/// - Exact duplicates of self-contained `-X...`/`-XX:...`/`-D...=...` arguments are collapsed.
+///   Flags with a separate value (`--add-opens <value>`, `-cp <value>`) are kept as they are.
+///
+/// # Returns
+/// The cleaned argument list in the original order, and human-readable warnings for everything that was changed or looks wrong.
+pub fn normalize_java_args(args: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut warnings = Vec::new();
+
+    let last_xmx = args.iter().rposition(|a| a.starts_with("-Xmx"));
+    let last_xms = args.iter().rposition(|a| a.starts_with("-Xms"));
+    let last_gc = args
+        .iter()
+        .rposition(|a| GC_SELECTORS.contains(&a.as_str()));
+
+    let xmx_count = args.iter().filter(|a| a.starts_with("-Xmx")).count();
+    if xmx_count > 1 {
+        warnings.push(format!(
+            "Found {} -Xmx arguments, only '{}' is used",
+            xmx_count,
+            args[last_xmx.unwrap_or_default()]
+        ));
+    }
+    let xms_count = args.iter().filter(|a| a.starts_with("-Xms")).count();
+    if xms_count > 1 {
+        warnings.push(format!(
+            "Found {} -Xms arguments, only '{}' is used",
+            xms_count,
+            args[last_xms.unwrap_or_default()]
+        ));
+    }
+
+    let mut gc_selectors: Vec<&str> = Vec::new();
+    for arg in args.iter().filter(|a| GC_SELECTORS.contains(&a.as_str())) {
+        if !gc_selectors.contains(&arg.as_str()) {
+            gc_selectors.push(arg);
+        }
+    }
+    let selected_gc = last_gc.map(|i| args[i].as_str());
+    if gc_selectors.len() > 1 {
+        warnings.push(format!(
+            "Conflicting garbage collectors {:?}, only '{}' is used",
+            gc_selectors,
+            selected_gc.unwrap_or_default()
+        ));
+    }
+    let uses_g1 = matches!(selected_gc, None | Some("-XX:+UseG1GC"));
+
+    let mut cleaned: Vec<String> = Vec::with_capacity(args.len());
+    let mut dropped_g1_flags = Vec::new();
+    for (i, arg) in args.iter().enumerate() {
+        let arg = arg.trim();
+        if arg.is_empty() {
+            continue;
+        }
+        if arg.starts_with("-Xmx") && Some(i) != last_xmx {
+            continue;
+        }
+        if arg.starts_with("-Xms") && Some(i) != last_xms {
+            continue;
+        }
+        if GC_SELECTORS.contains(&arg) && Some(i) != last_gc {
+            continue;
+        }
+        if !uses_g1 && arg.starts_with("-XX:G1") {
+            dropped_g1_flags.push(arg.to_string());
+            continue;
+        }
+        if is_self_contained_java_arg(arg) && cleaned.iter().any(|c| c == arg) {
+            warnings.push(format!("Removed duplicate JVM argument '{}'", arg));
+            continue;
+        }
+        cleaned.push(arg.to_string());
+    }
+    if !dropped_g1_flags.is_empty() {
+        warnings.push(format!(
+            "Removed G1-specific flags {:?} because '{}' is selected",
+            dropped_g1_flags,
+            selected_gc.unwrap_or_default()
+        ));
+    }
+
+    // Known problematic combinations that are kept but reported
+    if let (Some(xmx), Some(xms)) = (last_xmx, last_xms) {
+        if let (Some(max), Some(min)) = (
+            parse_jvm_memory(&args[xmx][4..]),
+            parse_jvm_memory(&args[xms][4..]),
+        ) {
+            if min > max {
+                warnings.push(format!(
+                    "'{}' is larger than '{}', the JVM will refuse to start",
+                    args[xms], args[xmx]
+                ));
+            }
+        }
+    }
+    if selected_gc == Some("-XX:+UseConcMarkSweepGC") {
+        warnings.push(
+            "-XX:+UseConcMarkSweepGC was removed in Java 14 and will fail on newer Java versions"
+                .to_string(),
+        );
+    }
+    if selected_gc == Some("-XX:+UseEpsilonGC")
+        && !cleaned
+            .iter()
+            .any(|a| a == "-XX:+UnlockExperimentalVMOptions")
+    {
+        warnings.push("-XX:+UseEpsilonGC requires -XX:+UnlockExperimentalVMOptions".to_string());
+    }
+
+    (cleaned, warnings)
+}
+
+/// Prefix for `java_args` entries that go after the manifest-generated JVM args.
+/// Entries without it are placed before them, which is where `-javaagent:` and module args belong.
+pub const APPEND_JAVA_ARG_MARKER: &str = "@append:";
+
+/// Strips the placement markers from user JVM args.
+/// Returns the plain args in their original order and the ones that were marked to be appended.
+pub fn split_java_arg_markers(args: &[String]) -> (Vec<String>, std::collections::HashSet<String>) {
+    let mut plain = Vec::with_capacity(args.len());
+    let mut appended = std::collections::HashSet::new();
+    for arg in args {
+        match arg.trim().strip_prefix(APPEND_JAVA_ARG_MARKER) {
+            Some(stripped) => {
+                appended.insert(stripped.trim().to_string());
+                plain.push(stripped.to_string());
+            }
+            None => plain.push(arg.clone()),
+        }
+    }
+    (plain, appended)
+}
+
+/// Normalizes the installation's `java_args` and reports any warnings.
+/// Warnings are logged and emitted as a non-fatal `java-args-warnings` event, they never block the launch.
+/// Returns the args to prepend and the args to append to the manifest JVM args.
+pub fn installation_java_args(context: &LaunchContext) -> (Vec<String>, Vec<String>) {
+    let (plain, appended) = split_java_arg_markers(&context.installation.java_args);
+    let (cleaned, warnings) = normalize_java_args(&plain);
+    if !warnings.is_empty() {
+        for warning in &warnings {
+            Logger::warn_global(
+                &format!("[JAVA ARGS] {}", warning),
+                Some(&context.installation.id),
+            );
+        }
+        if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
+            if let Some(app_handle) = handle_guard.as_ref() {
+                let _ = app_handle.emit(
+                    "java-args-warnings",
+                    serde_json::json!({
+                        "installation_id": context.installation.id,
+                        "warnings": warnings
+                    }),
+                );
+            }
+        }
+    }
+    cleaned.into_iter().partition(|arg| !appended.contains(arg))
+}
+
+/// Places the installation's `java_args` around the manifest-generated JVM args,
+/// with the `-javaagent:` args of its `java_agents` first
+pub fn apply_installation_java_args(context: &LaunchContext, jvm_args: &mut Vec<String>) {
+    let (mut prepend, append) = installation_java_args(context);
+    prepend.splice(0..0, java_agent_args(context));
+    jvm_args.splice(0..0, prepend);
+    jvm_args.extend(append);
+}
+
+/// `-javaagent:` args for the installation's `java_agents`, missing agent jars are skipped
+/// with a warning since the JVM refuses to start otherwise
+pub fn java_agent_args(context: &LaunchContext) -> Vec<String> {
+    context
+        .installation
+        .java_agents
+        .iter()
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .filter(|entry| {
+            let exists = Path::new(
+                crate::installations::kable_profiles::KableInstallation::java_agent_path(entry),
+            )
+            .is_file();
+            if !exists {
+                Logger::warn_global(
+                    &format!("[JAVA AGENTS] Skipping missing java agent: {}", entry),
+                    Some(&context.installation.id),
+                );
+            }
+            exists
+        })
+        .map(|entry| format!("-javaagent:{}", entry))
+        .collect()
+}
+
+/// Appends the installation's `extra_classpath` to the computed classpath,
+/// entries that don't exist are skipped with a warning
+pub fn apply_extra_classpath(context: &LaunchContext, mut classpath: String) -> String {
+    let sep = if cfg!(windows) { ";" } else { ":" };
+    for entry in &context.installation.extra_classpath {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            continue;
+        }
+        if !Path::new(entry).exists() {
+            Logger::warn_global(
+                &format!(
+                    "[CLASSPATH] Skipping missing extra classpath entry: {}",
+                    entry
+                ),
+                Some(&context.installation.id),
+            );
+            continue;
+        }
+        if !classpath.is_empty() {
+            classpath.push_str(sep);
+        }
+        classpath.push_str(entry);
+    }
+    classpath
+}
+
+/// Game arguments that make the client join `installation.connect_server` right after starting.
+/// An invalid address is logged and skipped instead of blocking the launch.
+pub fn connect_server_args(context: &LaunchContext) -> Vec<String> {
+    match context.installation.connect_server_address() {
+        Ok(Some((host, port))) => vec![
+            "--server".to_string(),
+            host,
+            "--port".to_string(),
+            port.to_string(),
+        ],
+        Ok(None) => Vec::new(),
+        Err(e) => {
+            Logger::warn_global(
+                &format!("Ignoring connect_server: {}", e),
+                Some(&context.installation.id),
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// `--demo` from the version json is always filtered out, it is only passed when the installation
+/// has `demo_mode` enabled
+pub fn apply_demo_mode(context: &LaunchContext, game_args: &mut Vec<String>) {
+    if !context.installation.demo_mode {
+        game_args.retain(|arg| arg != "--demo");
+    } else if !game_args.iter().any(|arg| arg == "--demo") {
+        game_args.push("--demo".to_string());
+    }
+}
+
+/// (http, https) proxies of the installation a launch is for
+type InstallationProxy = (Option<url::Url>, Option<url::Url>);
+
+fn installation_proxy(installation: &serde_json::Value) -> Result<InstallationProxy, String> {
+    let installation: crate::installations::kable_profiles::KableInstallation =
+        serde_json::from_value(installation.clone())
+            .map_err(|e| format!("Failed to read installation proxy settings: {}", e))?;
+    installation.proxy_urls()
+}
+
+/// Proxy environment variables for the game process, in both the lower and upper case
+/// spelling since tools disagree on which one they read
+fn proxy_env(proxy: &InstallationProxy) -> Vec<(String, String)> {
+    let mut env = Vec::new();
+    let (http, https) = proxy;
+    for (name, url) in [("http_proxy", http), ("https_proxy", https)] {
+        if let Some(url) = url {
+            env.push((name.to_string(), url.to_string()));
+            env.push((name.to_uppercase(), url.to_string()));
+        }
+    }
+    env
+}
+
+/// -Dhttp.proxyHost/-Dhttp.proxyPort and the https equivalents
+fn proxy_jvm_args(proxy: &InstallationProxy) -> Vec<String> {
+    let mut args = Vec::new();
+    let (http, https) = proxy;
+    for (scheme, url) in [("http", http), ("https", https)] {
+        let Some(url) = url else {
+            continue;
+        };
+        if let Some(host) = url.host_str() {
+            args.push(format!("-D{}.proxyHost={}", scheme, host));
+        }
+        if let Some(port) = url.port_or_known_default() {
+            args.push(format!("-D{}.proxyPort={}", scheme, port));
+        }
+    }
+    args
+}
+
+/// The command that is spawned for `cmd`, with the installation's proxy settings added
+fn game_process_command(
+    cmd: &Command,
+    working_dir: &str,
+    installation: &serde_json::Value,
+) -> Result<tokio::process::Command, String> {
+    let proxy = installation_proxy(installation)?;
+    let mut tokio_cmd = tokio::process::Command::new(cmd.get_program());
+    // Java ignores the proxy environment variables, so they are also passed as system
+    // properties. Those are JVM options and have to come before the main class.
+    tokio_cmd.args(proxy_jvm_args(&proxy));
+    tokio_cmd.args(cmd.get_args());
+    tokio_cmd.current_dir(working_dir);
+    for (key, value) in cmd.get_envs() {
+        match value {
+            Some(value) => tokio_cmd.env(key, value),
+            None => tokio_cmd.env_remove(key),
+        };
+    }
+    tokio_cmd.envs(proxy_env(&proxy));
+    Ok(tokio_cmd)
 }
 
 /// Spawns a process, streams stdout/stderr, and logs each line to the logger with the given instance_id.
@@ -1782,7 +2708,6 @@ pub async fn spawn_and_log_process(
     use serde_json::json;
     use std::process::Stdio;
     use tokio::io::AsyncBufReadExt;
-    use tokio::process::Command as TokioCommand;
     use tokio::sync::mpsc::unbounded_channel;
     use tokio::task;
 
@@ -1803,9 +2728,7 @@ pub async fn spawn_and_log_process(
     // game exits.
 
     // Now spawn the process
-    let mut tokio_cmd = TokioCommand::new(cmd.get_program());
-    tokio_cmd.args(cmd.get_args());
-    tokio_cmd.current_dir(working_dir);
+    let mut tokio_cmd = game_process_command(&cmd, working_dir, installation)?;
 
     // On Windows, set creation flags to hide the spawned console window
     #[cfg(target_os = "windows")]
@@ -1899,12 +2822,24 @@ pub async fn spawn_and_log_process(
     let (stdout_sender, mut stdout_receiver) = unbounded_channel::<String>();
     let (stderr_sender, mut stderr_receiver) = unbounded_channel::<String>();
     let instance_id_str = instance_id.to_string();
+    // A game that can't get a log file still runs, its output is only streamed then
+    let game_log = match super::game_log::GameLogWriter::start(instance_id).await {
+        Ok(writer) => Some(writer),
+        Err(e) => {
+            Logger::warn_global(
+                &format!("Failed to start the game log: {}", e),
+                Some(instance_id),
+            );
+            None
+        }
+    };
 
     // Stream stdout
     if let Some(stdout) = child.stdout.take() {
         let app = get_app_handle();
         let instance_id = instance_id_str.clone();
         let sender = stdout_sender.clone();
+        let game_log = game_log.clone();
         task::spawn(async move {
             let reader = BufReader::new(stdout);
             let mut lines = reader.lines();
@@ -1915,6 +2850,9 @@ pub async fn spawn_and_log_process(
                     first = false;
                 }
                 let _ = sender.send(line.clone());
+                if let Some(ref game_log) = game_log {
+                    game_log.line("stdout", &line);
+                }
                 if let Some(ref app) = app {
                     let _ = app.emit(
                         "game-process-event",
@@ -1934,6 +2872,7 @@ pub async fn spawn_and_log_process(
         let app = get_app_handle();
         let instance_id = instance_id_str.clone();
         let sender = stderr_sender.clone();
+        let game_log = game_log.clone();
         task::spawn(async move {
             let reader = BufReader::new(stderr);
             let mut lines = reader.lines();
@@ -1944,6 +2883,9 @@ pub async fn spawn_and_log_process(
                     first = false;
                 }
                 let _ = sender.send(line.clone());
+                if let Some(ref game_log) = game_log {
+                    game_log.line("stderr", &line);
+                }
                 if let Some(ref app) = app {
                     let _ = app.emit(
                         "game-process-event",
@@ -2077,3 +3019,414 @@ pub async fn spawn_and_log_process(
         command: format!("{:?}", cmd),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn game_process_gets_the_installation_proxy() {
+        let installation = crate::installations::kable_profiles::KableInstallation {
+            http_proxy: Some("http://proxy.local:3128".to_string()),
+            https_proxy: Some("http://secure.local:8443".to_string()),
+            ..Default::default()
+        };
+        let mut cmd = Command::new("java");
+        cmd.arg("net.minecraft.client.main.Main");
+        let spawned =
+            game_process_command(&cmd, ".", &serde_json::to_value(&installation).unwrap()).unwrap();
+
+        let envs: HashMap<String, String> = spawned
+            .as_std()
+            .get_envs()
+            .filter_map(|(k, v)| {
+                Some((
+                    k.to_string_lossy().to_string(),
+                    v?.to_string_lossy().to_string(),
+                ))
+            })
+            .collect();
+        assert_eq!(envs["http_proxy"], "http://proxy.local:3128/");
+        assert_eq!(envs["HTTP_PROXY"], "http://proxy.local:3128/");
+        assert_eq!(envs["https_proxy"], "http://secure.local:8443/");
+        assert_eq!(envs["HTTPS_PROXY"], "http://secure.local:8443/");
+
+        let args: Vec<String> = spawned
+            .as_std()
+            .get_args()
+            .map(|a| a.to_string_lossy().to_string())
+            .collect();
+        assert_eq!(args.last().unwrap(), "net.minecraft.client.main.Main");
+        assert!(args.contains(&"-Dhttps.proxyHost=secure.local".to_string()));
+    }
+
+    #[test]
+    fn normalize_java_args_keeps_last_memory_flags() {
+        let (cleaned, warnings) =
+            normalize_java_args(&args(&["-Xmx2G", "-Xms1G", "-Xmx4G", "-Xms2G"]));
+        assert_eq!(cleaned, args(&["-Xmx4G", "-Xms2G"]));
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[test]
+    fn normalize_java_args_keeps_last_gc_and_drops_g1_flags() {
+        let (cleaned, warnings) = normalize_java_args(&args(&[
+            "-XX:+UseG1GC",
+            "-XX:G1NewSizePercent=20",
+            "-XX:+UseZGC",
+        ]));
+        assert_eq!(cleaned, args(&["-XX:+UseZGC"]));
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("Conflicting garbage collectors")));
+        assert!(warnings.iter().any(|w| w.contains("G1-specific")));
+    }
+
+    #[test]
+    fn normalize_java_args_keeps_g1_flags_with_g1() {
+        let input = args(&["-XX:+UseG1GC", "-XX:G1NewSizePercent=20", "-Xmx2G"]);
+        let (cleaned, warnings) = normalize_java_args(&input);
+        assert_eq!(cleaned, input);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn normalize_java_args_collapses_duplicates() {
+        let (cleaned, warnings) = normalize_java_args(&args(&[
+            "-XX:+DisableExplicitGC",
+            " ",
+            "-XX:+DisableExplicitGC",
+        ]));
+        assert_eq!(cleaned, args(&["-XX:+DisableExplicitGC"]));
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn normalize_java_args_keeps_repeated_flags_with_values() {
+        let input = args(&[
+            "--add-opens",
+            "java.base/java.lang=ALL-UNNAMED",
+            "--add-opens",
+            "java.base/java.util=ALL-UNNAMED",
+        ]);
+        let (cleaned, warnings) = normalize_java_args(&input);
+        assert_eq!(cleaned, input);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn normalize_java_args_reports_xms_above_xmx() {
+        let (cleaned, warnings) = normalize_java_args(&args(&["-Xmx1G", "-Xms2048M"]));
+        assert_eq!(cleaned.len(), 2);
+        assert!(warnings.iter().any(|w| w.contains("refuse to start")));
+    }
+
+    #[test]
+    fn normalize_java_args_reports_epsilon_without_unlock() {
+        let (_, warnings) = normalize_java_args(&args(&["-XX:+UseEpsilonGC"]));
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("UnlockExperimentalVMOptions")));
+        let (_, warnings) = normalize_java_args(&args(&[
+            "-XX:+UnlockExperimentalVMOptions",
+            "-XX:+UseEpsilonGC",
+        ]));
+        assert!(warnings.is_empty());
+    }
+
+    fn classpath(entries: &[&str]) -> String {
+        entries.join(if cfg!(windows) { ";" } else { ":" })
+    }
+
+    #[test]
+    fn resolve_lwjgl_classpath_conflict_keeps_highest_set() {
+        let mixed = classpath(&[
+            "libraries/org/lwjgl/lwjgl/3.2.2/lwjgl-3.2.2.jar",
+            "libraries/org/lwjgl/lwjgl-glfw/3.2.2/lwjgl-glfw-3.2.2.jar",
+            "libraries/com/mojang/brigadier/1.0.18/brigadier-1.0.18.jar",
+            "libraries/org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3.jar",
+            "libraries/org/lwjgl/lwjgl-glfw/3.3.3/lwjgl-glfw-3.3.3.jar",
+            "libraries/org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-linux.jar",
+        ]);
+        let (cleaned, removed) = resolve_lwjgl_classpath_conflict(&mixed, None);
+        assert_eq!(
+            cleaned,
+            classpath(&[
+                "libraries/com/mojang/brigadier/1.0.18/brigadier-1.0.18.jar",
+                "libraries/org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3.jar",
+                "libraries/org/lwjgl/lwjgl-glfw/3.3.3/lwjgl-glfw-3.3.3.jar",
+                "libraries/org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-linux.jar",
+            ])
+        );
+        assert_eq!(
+            removed,
+            vec![
+                "libraries/org/lwjgl/lwjgl/3.2.2/lwjgl-3.2.2.jar".to_string(),
+                "libraries/org/lwjgl/lwjgl-glfw/3.2.2/lwjgl-glfw-3.2.2.jar".to_string(),
+            ]
+        );
+        assert_eq!(lwjgl_versions_on_classpath(&cleaned).len(), 1);
+    }
+
+    #[test]
+    fn resolve_lwjgl_classpath_conflict_leaves_consistent_classpath() {
+        let consistent = classpath(&[
+            "libraries/org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3.jar",
+            "libraries/org/lwjgl/lwjgl-glfw/3.3.3/lwjgl-glfw-3.3.3.jar",
+        ]);
+        let (cleaned, removed) = resolve_lwjgl_classpath_conflict(&consistent, None);
+        assert_eq!(cleaned, consistent);
+        assert!(removed.is_empty());
+    }
+
+    /// A fresh minecraft dir in the system temp dir with the given version jsons
+    fn temp_minecraft_dir(versions: &[(&str, Value)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("kable-test-{}", uuid::Uuid::new_v4()));
+        for (id, manifest) in versions {
+            let version_dir = dir.join("versions").join(id);
+            fs::create_dir_all(&version_dir).unwrap();
+            fs::write(
+                version_dir.join(format!("{}.json", id)),
+                manifest.to_string(),
+            )
+            .unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn manifests_inheriting_from_each_other_fail_with_a_loop_error() {
+        let dir = temp_minecraft_dir(&[
+            (
+                "loop-a",
+                serde_json::json!({ "id": "loop-a", "inheritsFrom": "loop-b" }),
+            ),
+            (
+                "loop-b",
+                serde_json::json!({ "id": "loop-b", "inheritsFrom": "loop-a" }),
+            ),
+        ]);
+        let result = load_and_merge_manifest_sync(&dir.to_string_lossy(), "loop-a", None);
+        let _ = fs::remove_dir_all(&dir);
+
+        let error = result.unwrap_err();
+        assert!(error.contains("loop"), "{}", error);
+        assert!(error.contains("loop-a -> loop-b -> loop-a"), "{}", error);
+    }
+
+    #[test]
+    fn overly_deep_inherits_chains_fail() {
+        let versions: Vec<(String, Value)> = (0..=MAX_INHERITS_DEPTH)
+            .map(|i| {
+                let id = format!("deep-{}", i);
+                let parent = format!("deep-{}", i + 1);
+                let manifest = serde_json::json!({ "id": id, "inheritsFrom": parent });
+                (id, manifest)
+            })
+            .collect();
+        let versions: Vec<(&str, Value)> = versions
+            .iter()
+            .map(|(id, manifest)| (id.as_str(), manifest.clone()))
+            .collect();
+        let dir = temp_minecraft_dir(&versions);
+        let result = load_and_merge_manifest_sync(&dir.to_string_lossy(), "deep-0", None);
+        let _ = fs::remove_dir_all(&dir);
+
+        let error = result.unwrap_err();
+        assert!(error.contains("inherits through more than"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn merged_manifest_stamp_changes_with_a_parent_json() {
+        let dir = temp_minecraft_dir(&[
+            (
+                "fabric-child",
+                serde_json::json!({ "id": "fabric-child", "inheritsFrom": "1.21" }),
+            ),
+            ("1.21", serde_json::json!({ "id": "1.21" })),
+        ]);
+        let before = manifest_chain_stamp(&dir, "fabric-child").await.unwrap();
+        let parent = fs::File::options()
+            .write(true)
+            .open(dir.join("versions").join("1.21").join("1.21.json"))
+            .unwrap();
+        parent
+            .set_modified(std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(60))
+            .unwrap();
+        drop(parent);
+        let after = manifest_chain_stamp(&dir, "fabric-child").await.unwrap();
+        let missing = manifest_chain_stamp(&dir, "not-downloaded").await;
+        let _ = fs::remove_dir_all(&dir);
+
+        let ids: Vec<&str> = before.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["fabric-child", "1.21"]);
+        assert_eq!(before[0], after[0]);
+        assert_ne!(before, after);
+        assert!(missing.is_none());
+    }
+
+    #[test]
+    fn protected_parameter_overrides_are_ignored_with_a_warning() {
+        let mut variables = HashMap::from([
+            ("auth_access_token".to_string(), "real-token".to_string()),
+            ("classpath".to_string(), "a.jar".to_string()),
+        ]);
+        let params = HashMap::from([
+            ("auth_access_token".to_string(), "copied-token".to_string()),
+            ("classpath".to_string(), "b.jar".to_string()),
+            ("resolution_width".to_string(), "1920".to_string()),
+            ("--fullscreen".to_string(), String::new()),
+        ]);
+
+        let warnings = apply_parameter_overrides(&mut variables, &params, false);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().any(|w| w.contains("${auth_access_token}")));
+        assert!(warnings.iter().any(|w| w.contains("${classpath}")));
+        // The token itself never ends up in a log line
+        assert!(warnings.iter().all(|w| !w.contains("copied-token")));
+        assert_eq!(variables["auth_access_token"], "real-token");
+        assert_eq!(variables["classpath"], "a.jar");
+        assert_eq!(variables["resolution_width"], "1920");
+        assert!(!variables.contains_key("--fullscreen"));
+    }
+
+    #[test]
+    fn protected_parameter_overrides_apply_when_allowed() {
+        let mut variables = HashMap::new();
+        let params = HashMap::from([("auth_uuid".to_string(), "uuid".to_string())]);
+
+        let warnings = apply_parameter_overrides(&mut variables, &params, true);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("applied"));
+        assert_eq!(variables["auth_uuid"], "uuid");
+    }
+
+    fn path_variables(game_directory: &str) -> HashMap<String, String> {
+        HashMap::from([
+            ("game_directory".to_string(), game_directory.to_string()),
+            (
+                "natives_directory".to_string(),
+                format!("{}/natives", game_directory),
+            ),
+        ])
+    }
+
+    #[test]
+    fn substitute_variables_keeps_spaces_and_unicode() {
+        for dir in [
+            "C:\\Users\\John Doe\\AppData\\Roaming\\.minecraft",
+            "/home/jörg/Мой Майнкрафт/日本語 フォルダ",
+        ] {
+            let variables = path_variables(dir);
+            assert_eq!(substitute_variables("${game_directory}", &variables), dir);
+            assert_eq!(
+                substitute_variables("-Djava.library.path=${natives_directory}", &variables),
+                format!("-Djava.library.path={}/natives", dir)
+            );
+        }
+    }
+
+    #[test]
+    fn substitute_variables_does_not_resubstitute_values() {
+        let variables = HashMap::from([
+            (
+                "game_directory".to_string(),
+                "/games/${version_name}".to_string(),
+            ),
+            ("version_name".to_string(), "1.21".to_string()),
+        ]);
+        assert_eq!(
+            substitute_variables("${game_directory}", &variables),
+            "/games/${version_name}"
+        );
+        assert_eq!(substitute_variables("${unknown}", &variables), "");
+    }
+
+    #[test]
+    fn normalize_system_property_only_trims_around_the_equals_sign() {
+        assert_eq!(normalize_system_property("-Dkey = value"), "-Dkey=value");
+        assert_eq!(normalize_system_property("-Dkey= value"), "-Dkey=value");
+        assert_eq!(normalize_system_property("-Dkey =value"), "-Dkey=value");
+        assert_eq!(
+            normalize_system_property("-Dkey=a value with spaces"),
+            "-Dkey=a value with spaces"
+        );
+        assert_eq!(normalize_system_property("--gameDir = x"), "--gameDir = x");
+    }
+
+    #[test]
+    fn process_arguments_keeps_paths_with_spaces_as_one_argument() {
+        let dir = "C:\\Users\\John Doe\\AppData\\Roaming\\.minecraft";
+        let args = vec![
+            Value::String("--gameDir".to_string()),
+            Value::String("${game_directory}".to_string()),
+            Value::String("-Djava.library.path = ${natives_directory}".to_string()),
+        ];
+        assert_eq!(
+            process_arguments(&args, &path_variables(dir)),
+            vec![
+                "--gameDir".to_string(),
+                dir.to_string(),
+                format!("-Djava.library.path={}/natives", dir),
+            ]
+        );
+    }
+
+    /// Writes a jar with the given entries to the system temp dir
+    fn temp_jar(entries: &[(&str, &str)]) -> PathBuf {
+        use std::io::Write;
+
+        let path = std::env::temp_dir().join(format!("kable-test-{}.jar", uuid::Uuid::new_v4()));
+        let mut zip = zip::ZipWriter::new(fs::File::create(&path).unwrap());
+        for (name, content) in entries {
+            zip.start_file(*name, zip::write::FullFileOptions::default())
+                .unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+        path
+    }
+
+    fn validate_temp_jar(entries: &[(&str, &str)]) -> Result<(), String> {
+        let jar = temp_jar(entries);
+        let result = validate_client_jar(&jar);
+        let _ = fs::remove_file(&jar);
+        result
+    }
+
+    #[test]
+    fn validate_client_jar_accepts_the_client_main_class() {
+        assert!(validate_temp_jar(&[("net/minecraft/client/main/Main.class", "")]).is_ok());
+    }
+
+    #[test]
+    fn validate_client_jar_accepts_bundler_jars() {
+        let jar = [
+            (
+                "META-INF/versions.list",
+                "0123abcd\t1.21.8\t1.21.8/client-1.21.8.jar\n",
+            ),
+            ("net/minecraft/bundler/Main.class", ""),
+        ];
+        assert!(validate_temp_jar(&jar).is_ok());
+    }
+
+    #[test]
+    fn validate_client_jar_accepts_a_main_class_manifest() {
+        let jar = [(
+            "META-INF/MANIFEST.MF",
+            "Manifest-Version: 1.0\r\nMain-Class: net.minecraft.bundler.Main\r\n",
+        )];
+        assert!(validate_temp_jar(&jar).is_ok());
+    }
+
+    #[test]
+    fn validate_client_jar_rejects_jars_without_an_entry_point() {
+        assert!(validate_temp_jar(&[("META-INF/MANIFEST.MF", "Manifest-Version: 1.0\n")]).is_err());
+        assert!(validate_temp_jar(&[("assets/minecraft/lang/en_us.json", "{}")]).is_err());
+    }
+}
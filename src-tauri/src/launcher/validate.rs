@@ -0,0 +1,424 @@
+use super::{context_for_installation, get_launchable_for_installation, LaunchContext, LoaderType};
+use crate::launcher::utils::{
+    evaluate_rules, lwjgl_versions_on_classpath, normalize_java_args, parse_jvm_memory,
+    pre_launch_java_native_compat_check, resolve_lwjgl_classpath_conflict, sha1_file_hex,
+    split_java_arg_markers, MAX_INHERITS_DEPTH,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchIssue {
+    /// 'command' | 'java' | 'java_args' | 'launch_extras' | 'native_compat' | 'libraries' | 'lwjgl'
+    /// | 'memory' | 'mods' | 'version_jar'
+    pub check: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchReadiness {
+    pub installation_id: String,
+    /// True when there are no errors, warnings do not block a launch
+    pub ready: bool,
+    pub errors: Vec<LaunchIssue>,
+    pub warnings: Vec<LaunchIssue>,
+}
+
+impl LaunchReadiness {
+    fn error(&mut self, check: &str, message: impl Into<String>) {
+        self.errors.push(LaunchIssue {
+            check: check.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn warning(&mut self, check: &str, message: impl Into<String>) {
+        self.warnings.push(LaunchIssue {
+            check: check.to_string(),
+            message: message.into(),
+        });
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LwjglConflictResolution {
+    /// False when the classpath already had a single LWJGL version and nothing changed
+    pub resolved: bool,
+    pub kept_version: Option<String>,
+    /// Classpath entries that are left out from now on
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionJarVerification {
+    /// The version whose jar was checked, the parent when the version inherits its jar
+    pub version_id: String,
+    pub present: bool,
+    pub sha1: Option<String>,
+    /// `downloads.client.sha1` of the version json, None for versions without one
+    pub expected_sha1: Option<String>,
+    /// True when the jar is present and matches, or there is nothing to compare with
+    pub valid: bool,
+}
+
+/// Runs every pre-launch check for an installation without downloading or launching anything
+pub async fn validate_launch(installation_id: &str) -> Result<LaunchReadiness, String> {
+    let context = context_for_installation(installation_id).await?;
+    let mut readiness = LaunchReadiness {
+        installation_id: installation_id.to_string(),
+        ready: false,
+        errors: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    let (java_args, _) = split_java_arg_markers(&context.installation.java_args);
+    let (_, java_arg_warnings) = normalize_java_args(&java_args);
+    for warning in java_arg_warnings {
+        readiness.warning("java_args", warning);
+    }
+    for warning in context.installation.missing_launch_extras() {
+        readiness.warning("launch_extras", warning);
+    }
+
+    let java_path = match crate::launcher::java::find_java_executable(
+        context.settings.general.java_path.as_ref(),
+    ) {
+        Ok(path) => Some(path),
+        Err(e) => {
+            readiness.error("java", e);
+            None
+        }
+    };
+
+    let loader = context.detect_loader_type().await;
+    let built = match get_launchable_for_installation(&context).await {
+        Ok(launchable) => launchable.build_command(&context).await,
+        Err(e) => Err(e),
+    };
+    let built = match built {
+        Ok(built) => built,
+        Err(e) => {
+            // Everything below inspects the built command
+            readiness.error("command", format!("Failed to build launch command: {}", e));
+            readiness.ready = readiness.errors.is_empty();
+            return Ok(readiness);
+        }
+    };
+
+    if let Some(java_path) = &java_path {
+        if let Err(e) = pre_launch_java_native_compat_check(
+            java_path,
+            &built.manifest,
+            Some(&context.installation.id),
+        ) {
+            readiness.error("native_compat", e);
+        }
+    }
+
+    check_libraries(&context, &built.manifest, &mut readiness);
+
+    let mut lwjgl_versions: Vec<String> = lwjgl_versions_on_classpath(&built.classpath)
+        .into_iter()
+        .filter(|v| v != "unknown")
+        .collect();
+    if lwjgl_versions.len() > 1 {
+        lwjgl_versions.sort();
+        readiness.warning(
+            "lwjgl",
+            format!(
+                "Multiple LWJGL versions on the classpath ({}), this can cause native/JNI conflicts",
+                lwjgl_versions.join(", ")
+            ),
+        );
+    }
+
+    let args: Vec<String> = built
+        .command
+        .get_args()
+        .map(|a| a.to_string_lossy().to_string())
+        .collect();
+    check_memory(&args, &mut readiness);
+
+    if let Ok(loader) = loader {
+        check_mod_loaders(&context, loader, &mut readiness);
+    }
+
+    // The manifest id is the concrete version, the installation may use a placeholder like latest-release
+    let version_id = built
+        .manifest
+        .get("id")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&context.installation.version_id);
+    if let Ok(jar) = verify_version_jar(version_id).await {
+        if !jar.present {
+            readiness.error(
+                "libraries",
+                format!("Missing version jar: {}.jar", jar.version_id),
+            );
+        } else if !jar.valid {
+            readiness.warning(
+                "version_jar",
+                format!(
+                    "The {} jar does not match the sha1 of its version json ({} != {}), repair it to download it again",
+                    jar.version_id,
+                    jar.sha1.unwrap_or_default(),
+                    jar.expected_sha1.unwrap_or_default()
+                ),
+            );
+        }
+    }
+
+    readiness.ready = readiness.errors.is_empty();
+    crate::logging::Logger::debug_global(
+        &format!(
+            "Launch validation: {} errors, {} warnings",
+            readiness.errors.len(),
+            readiness.warnings.len()
+        ),
+        Some(installation_id),
+    );
+    Ok(readiness)
+}
+
+/// Reports libraries from the manifest that are not on disk yet
+fn check_libraries(
+    context: &LaunchContext,
+    manifest: &serde_json::Value,
+    readiness: &mut LaunchReadiness,
+) {
+    let libraries_path = PathBuf::from(&context.minecraft_dir).join("libraries");
+    let mut missing = Vec::new();
+
+    if let Some(libs) = manifest.get("libraries").and_then(|v| v.as_array()) {
+        for lib in libs {
+            if let Some(rules) = lib.get("rules") {
+                if !evaluate_rules(rules).unwrap_or(true) {
+                    continue;
+                }
+            }
+            let Some(path) = lib
+                .get("downloads")
+                .and_then(|d| d.get("artifact"))
+                .and_then(|a| a.get("path"))
+                .and_then(|p| p.as_str())
+            else {
+                continue;
+            };
+            if !libraries_path.join(path).exists() {
+                let name = lib.get("name").and_then(|n| n.as_str()).unwrap_or(path);
+                missing.push(name.to_string());
+            }
+        }
+    }
+
+    for name in &missing {
+        readiness.error("libraries", format!("Missing library: {}", name));
+    }
+}
+
+/// Compares the effective -Xmx with the physical memory of this machine
+fn check_memory(args: &[String], readiness: &mut LaunchReadiness) {
+    let Some(xmx) = args
+        .iter()
+        .rev()
+        .find_map(|a| a.strip_prefix("-Xmx"))
+        .and_then(parse_jvm_memory)
+    else {
+        return;
+    };
+
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+    let total = system.total_memory();
+    if total == 0 {
+        return;
+    }
+
+    let to_mb = |bytes: u64| bytes / (1024 * 1024);
+    if xmx >= total {
+        readiness.error(
+            "memory",
+            format!(
+                "-Xmx is {} MB but this system only has {} MB of memory",
+                to_mb(xmx),
+                to_mb(total)
+            ),
+        );
+    } else if xmx > total / 4 * 3 {
+        readiness.warning(
+            "memory",
+            format!(
+                "-Xmx is {} MB, more than 75% of the {} MB of system memory",
+                to_mb(xmx),
+                to_mb(total)
+            ),
+        );
+    }
+}
+
+/// Warns about enabled mods that were built for a different loader than the installation uses
+fn check_mod_loaders(context: &LaunchContext, loader: LoaderType, readiness: &mut LaunchReadiness) {
+    let incompatible: &[&str] = match loader {
+        LoaderType::Fabric | LoaderType::IrisFabric => &["forge", "neoforge", "quilt"],
+        // Quilt also loads Fabric mods
+        LoaderType::Quilt => &["forge", "neoforge"],
+        LoaderType::Forge | LoaderType::NeoForge => &["fabric", "quilt"],
+        LoaderType::Vanilla => return,
+    };
+
+    let Ok(mods) = context.installation.get_mod_info() else {
+        return;
+    };
+    for jar in mods.iter().filter(|m| !m.disabled) {
+        if let Some(mod_loader) = jar.loader.as_deref() {
+            if incompatible.contains(&mod_loader) {
+                readiness.warning(
+                    "mods",
+                    format!(
+                        "{} is a {} mod and will not load on {:?}",
+                        jar.mod_name.as_deref().unwrap_or(&jar.file_name),
+                        mod_loader,
+                        loader
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Opt-in fix for the LWJGL warning of `validate_launch`. When the classpath mixes LWJGL versions,
+/// `resolve_lwjgl_conflicts` is enabled on the installation so every launch keeps only the
+/// highest LWJGL set.
+pub async fn resolve_lwjgl_conflict(
+    installation_id: &str,
+) -> Result<LwjglConflictResolution, String> {
+    let mut context = context_for_installation(installation_id).await?;
+    // Inspect the classpath as it is without the fix
+    context.installation.resolve_lwjgl_conflicts = false;
+    let launchable = get_launchable_for_installation(&context).await?;
+    let built = launchable.build_command(&context).await?;
+
+    let (classpath, removed) =
+        resolve_lwjgl_classpath_conflict(&built.classpath, Some(installation_id));
+    if removed.is_empty() {
+        return Ok(LwjglConflictResolution {
+            resolved: false,
+            kept_version: None,
+            removed,
+        });
+    }
+
+    let kept_version = lwjgl_versions_on_classpath(&classpath)
+        .into_iter()
+        .find(|v| v != "unknown");
+
+    let mut installation = context.installation;
+    installation.resolve_lwjgl_conflicts = true;
+    crate::installations::modify_installation(installation_id, installation).await?;
+
+    Ok(LwjglConflictResolution {
+        resolved: true,
+        kept_version,
+        removed,
+    })
+}
+
+/// Compares the client jar of a version with `downloads.client.sha1` of its version json.
+/// Versions without a jar of their own (most loader versions) are checked against the jar of
+/// the version they inherit from.
+pub async fn verify_version_jar(version_id: &str) -> Result<VersionJarVerification, String> {
+    let versions_dir = crate::get_default_minecraft_dir()?.join("versions");
+    let mut current = version_id.to_string();
+    for _ in 0..MAX_INHERITS_DEPTH {
+        let version_dir = versions_dir.join(&current);
+        let json_path = version_dir.join(format!("{}.json", current));
+        let content = tokio::fs::read_to_string(&json_path)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", json_path.display(), e))?;
+        let manifest: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", json_path.display(), e))?;
+        let jar_path = version_dir.join(format!("{}.jar", current));
+        let expected_sha1 = manifest
+            .get("downloads")
+            .and_then(|d| d.get("client"))
+            .and_then(|c| c.get("sha1"))
+            .and_then(|s| s.as_str())
+            .map(str::to_string);
+
+        let parent = manifest.get("inheritsFrom").and_then(|v| v.as_str());
+        if let (Some(parent), false, None) = (parent, jar_path.exists(), &expected_sha1) {
+            current = parent.to_string();
+            continue;
+        }
+
+        let present = jar_path.is_file();
+        let sha1 = if present {
+            Some(
+                tokio::task::spawn_blocking(move || sha1_file_hex(&jar_path))
+                    .await
+                    .map_err(|e| format!("Hash task join error: {}", e))??,
+            )
+        } else {
+            None
+        };
+        let valid = present
+            && match (&sha1, &expected_sha1) {
+                (Some(actual), Some(expected)) => actual.eq_ignore_ascii_case(expected),
+                _ => true,
+            };
+        return Ok(VersionJarVerification {
+            version_id: current,
+            present,
+            sha1,
+            expected_sha1,
+            valid,
+        });
+    }
+    Err(format!(
+        "inheritsFrom chain of '{}' is deeper than {} versions",
+        version_id, MAX_INHERITS_DEPTH
+    ))
+}
+
+/// Downloads the client jar of a version again when `verify_version_jar` finds it missing or
+/// mismatched, and returns the verification of the new jar.
+/// With an installation the download goes through its proxy settings, like a launch does.
+pub async fn repair_version_jar(
+    version_id: &str,
+    installation_id: Option<&str>,
+) -> Result<VersionJarVerification, String> {
+    let verification = verify_version_jar(version_id).await?;
+    if verification.valid {
+        return Ok(verification);
+    }
+    let client = match installation_id {
+        Some(id) => {
+            let installation = crate::installations::get_installation(id)
+                .await?
+                .ok_or_else(|| format!("Installation not found: {}", id))?;
+            crate::launcher::utils::http_client_for(&installation)?
+        }
+        None => reqwest::Client::new(),
+    };
+    let minecraft_dir = crate::get_default_minecraft_dir()?;
+    let jar_path = minecraft_dir
+        .join("versions")
+        .join(&verification.version_id)
+        .join(format!("{}.jar", verification.version_id));
+    if jar_path.exists() {
+        tokio::fs::remove_file(&jar_path)
+            .await
+            .map_err(|e| format!("Failed to remove {}: {}", jar_path.display(), e))?;
+    }
+    crate::launcher::utils::ensure_version_manifest_and_jar(
+        &verification.version_id,
+        &minecraft_dir.to_string_lossy(),
+        &client,
+    )
+    .await?;
+    crate::logging::info(&format!(
+        "Downloaded the {} jar again",
+        verification.version_id
+    ));
+    verify_version_jar(version_id).await
+}
@@ -1,6 +1,6 @@
 // launcher/vanilla.rs
 
-use super::{LaunchContext, LaunchResult, Launchable};
+use super::{LaunchCommand, LaunchContext, LaunchResult, Launchable};
 use crate::launcher::utils::build_variable_map;
 use async_trait::async_trait;
 use std::path::PathBuf;
@@ -19,63 +19,68 @@ impl LaunchContext {
 #[async_trait]
 impl Launchable for VanillaLaunchable {
     // TODO: Implement proper prepare logic... This is untested and may need adjustments
-    async fn prepare(&self, _context: &LaunchContext) -> Result<(), String> {
+    async fn prepare(&self, context: &LaunchContext) -> Result<(), String> {
         // Download manifest and jar, and libraries
-        let version_id = &_context.installation.version_id;
-        let minecraft_dir = &_context.minecraft_dir;
+        let version_id = &context.installation.version_id;
+        let minecraft_dir = &context.minecraft_dir;
+        let client = context.http_client()?;
         // ensure_version_manifest_and_jar now returns the resolved concrete version id
-        let resolved =
-            crate::launcher::utils::ensure_version_manifest_and_jar(version_id, minecraft_dir)
-                .await?;
+        let resolved = crate::launcher::utils::ensure_version_manifest_and_jar(
+            version_id,
+            minecraft_dir,
+            &client,
+        )
+        .await?;
         // Load manifest using the resolved id
         let manifest = crate::launcher::utils::load_and_merge_manifest_with_instance(
             minecraft_dir,
             &resolved,
-            Some(&_context.installation.id),
+            Some(&context.installation.id),
         )
         .await?;
         let libraries_path = std::path::PathBuf::from(minecraft_dir).join("libraries");
-        crate::launcher::utils::ensure_libraries(&manifest, &libraries_path).await?;
-        // Ensure minimal assets + sounds so UI and audio are available
+        crate::launcher::utils::ensure_libraries(
+            &manifest,
+            &libraries_path,
+            &client,
+            &context.progress,
+        )
+        .await?;
+        // Ensure minimal assets + sounds so UI and audio are available (all assets when isolated)
         crate::launcher::utils::ensure_assets_for_manifest(
-            minecraft_dir,
+            &context.assets_dir(),
             &manifest,
-            crate::launcher::utils::AssetMode::MinimalWithSounds,
-            Some(&_context.installation.id),
+            crate::launcher::utils::asset_mode_for(context),
+            Some(&context.installation.id),
+            &client,
+            &context.progress,
         )
         .await?;
         Ok(())
     }
 
+    async fn build_command(&self, context: &LaunchContext) -> Result<LaunchCommand, String> {
+        self.build_command_for_version(context, &context.installation.version_id)
+            .await
+    }
+
     async fn launch(&self, context: &LaunchContext) -> Result<LaunchResult, String> {
         println!("VANILLA::launch() -> {}", context.installation.name);
-        // 1. Load merged manifest (with inheritance)
+        // 1. Ensure manifest/jar exist and get resolved id (in case of latest-* placeholders)
         let version_id = &context.installation.version_id;
-        // Ensure manifest/jar exist and get resolved id (in case of latest-* placeholders)
         let resolved = crate::launcher::utils::ensure_version_manifest_and_jar(
             version_id,
             &context.minecraft_dir,
-        )
-        .await?;
-        let manifest = crate::launcher::utils::load_and_merge_manifest_with_instance(
-            &context.minecraft_dir,
-            &resolved,
-            Some(&context.installation.id),
+            &context.http_client()?,
         )
         .await?;
 
-        // 2. Build classpath (all libraries + version JAR)
-        let libraries_path = PathBuf::from(&context.minecraft_dir).join("libraries");
-        let version_jar_path = PathBuf::from(&context.minecraft_dir)
-            .join("versions")
-            .join(&resolved)
-            .join(format!("{}.jar", resolved));
-        let classpath = crate::launcher::utils::build_classpath_from_manifest_with_instance(
-            &manifest,
-            &libraries_path,
-            &version_jar_path,
-            Some(&context.installation.id),
-        );
+        // 2. Build the full command (manifest, classpath, variables, arguments)
+        let LaunchCommand {
+            command: cmd,
+            manifest,
+            classpath,
+        } = self.build_command_for_version(context, &resolved).await?;
 
         // Run pre-launch Java/native compatibility check. This may return Err to abort launch with
         // an actionable message (e.g., 32-bit Java vs 64-bit natives). Use configured java path.
@@ -95,8 +100,9 @@ impl Launchable for VanillaLaunchable {
             Some(&context.installation.id),
         );
 
-        // Clear natives folder to prevent version conflicts from previous launches
-        let natives_dir = PathBuf::from(&context.minecraft_dir).join("natives");
+        // 3. Clear natives folder to prevent version conflicts from previous launches
+        let libraries_path = PathBuf::from(&context.minecraft_dir).join("libraries");
+        let natives_dir = context.natives_dir();
         if natives_dir.exists() {
             if let Err(e) = std::fs::remove_dir_all(&natives_dir) {
                 crate::logging::Logger::warn_global(
@@ -128,6 +134,7 @@ impl Launchable for VanillaLaunchable {
                 &libraries,
                 &libraries_path,
                 &natives_dir,
+                crate::launcher::utils::native_arch_override(context),
                 Some(&context.installation.id),
             ) {
                 crate::logging::Logger::warn_global(
@@ -137,6 +144,64 @@ impl Launchable for VanillaLaunchable {
             }
         }
 
+        // Use spawn_and_log_process utility
+        let mut installation_json = serde_json::to_value(&context.installation)
+            .map_err(|e| format!("Failed to serialize installation: {}", e))?;
+        if let Some(obj) = installation_json.as_object_mut() {
+            obj.insert(
+                "path".to_string(),
+                serde_json::json!(context.installation_path().to_string_lossy().to_string()),
+            );
+        }
+        crate::launcher::utils::spawn_and_log_process(
+            cmd,
+            &context.minecraft_dir,
+            &context.installation.id,
+            &manifest,
+            &installation_json,
+            &context.settings,
+        )
+        .await
+    }
+}
+
+impl VanillaLaunchable {
+    /// Builds the command for an already resolved version id (no latest-* placeholders)
+    async fn build_command_for_version(
+        &self,
+        context: &LaunchContext,
+        version_id: &str,
+    ) -> Result<LaunchCommand, String> {
+        // 1. Load merged manifest (with inheritance)
+        let manifest = crate::launcher::utils::load_and_merge_manifest_with_instance(
+            &context.minecraft_dir,
+            version_id,
+            Some(&context.installation.id),
+        )
+        .await?;
+        // Placeholders are resolved while loading, the manifest id is the concrete version
+        let resolved = manifest
+            .get("id")
+            .and_then(|v| v.as_str())
+            .unwrap_or(version_id)
+            .to_string();
+
+        // 2. Build classpath (all libraries + version JAR)
+        let libraries_path = PathBuf::from(&context.minecraft_dir).join("libraries");
+        let version_jar_path = PathBuf::from(&context.minecraft_dir)
+            .join("versions")
+            .join(&resolved)
+            .join(format!("{}.jar", resolved));
+        let classpath = crate::launcher::utils::build_classpath_from_manifest_with_instance(
+            &manifest,
+            &libraries_path,
+            &version_jar_path,
+            Some(&context.installation.id),
+        );
+        // Opt-in: keep a single LWJGL version on the classpath
+        let classpath = crate::launcher::utils::apply_lwjgl_conflict_fix(context, classpath);
+        let classpath = crate::launcher::utils::apply_extra_classpath(context, classpath);
+
         // 3. Build variable map
         let variables = build_variable_map(
             context,
@@ -146,12 +211,16 @@ impl Launchable for VanillaLaunchable {
         );
 
         // 4. Build JVM and game arguments
-        let (mut jvm_args_vec, game_args_vec) =
+        let (mut jvm_args_vec, mut game_args_vec) =
             crate::launcher::utils::build_jvm_and_game_args_with_instance(
                 &manifest,
                 &variables,
                 Some(&context.installation.id),
             );
+        game_args_vec.extend(crate::launcher::utils::connect_server_args(context));
+        crate::launcher::utils::apply_demo_mode(context, &mut game_args_vec);
+        // Agents must come before the manifest args, java_args stay out of vanilla launches
+        jvm_args_vec.splice(0..0, crate::launcher::utils::java_agent_args(context));
 
         // 5. Add/overwrite with parameters_map (for --key style)
         for (k, v) in &context.installation.parameters_map {
@@ -180,23 +249,10 @@ impl Launchable for VanillaLaunchable {
         cmd.args(&game_args_vec);
         cmd.current_dir(&context.minecraft_dir);
 
-        // Use spawn_and_log_process utility
-        let mut installation_json = serde_json::to_value(&context.installation)
-            .map_err(|e| format!("Failed to serialize installation: {}", e))?;
-        if let Some(obj) = installation_json.as_object_mut() {
-            obj.insert(
-                "path".to_string(),
-                serde_json::json!(context.installation_path().to_string_lossy().to_string()),
-            );
-        }
-        crate::launcher::utils::spawn_and_log_process(
-            cmd,
-            &context.minecraft_dir,
-            &context.installation.id,
-            &manifest,
-            &installation_json,
-            &context.settings,
-        )
-        .await
+        Ok(LaunchCommand {
+            command: cmd,
+            manifest,
+            classpath,
+        })
     }
 }
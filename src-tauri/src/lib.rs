@@ -15,6 +15,7 @@ pub use kable_macros::*;
 pub mod auth;
 pub mod commands;
 pub mod discord;
+pub mod error;
 pub mod icons;
 pub mod installations;
 pub mod launcher;
@@ -23,6 +24,7 @@ pub mod mods;
 pub mod profile;
 pub mod resourcepacks;
 pub mod settings;
+pub mod setup;
 pub mod shaders;
 pub mod skins;
 pub mod sounds;
@@ -44,6 +46,7 @@ pub use commands::skins as commands_skins;
 pub use commands::symlinks as commands_symlinks;
 pub use commands::system as commands_system;
 pub use commands::updater as commands_updater;
+pub use error::*;
 pub use icons::*;
 pub use installations::*;
 pub use launcher::*;
@@ -52,6 +55,7 @@ pub use maps::*;
 pub use mods::*;
 pub use resourcepacks::*;
 pub use settings::*;
+pub use setup::*;
 pub use shaders::*;
 pub use skins::*;
 
@@ -112,6 +116,10 @@ pub fn run() {
                                 }
                             };
 
+                        // Launch with the installation's own account when it has one
+                        account =
+                            crate::launcher::account_for_installation(&installation, account).await;
+
                         // Refresh the account token to ensure it's still valid
                         eprintln!("Refreshing account token...");
                         account = match crate::auth::auth_util::refresh_microsoft_token(
@@ -191,7 +199,7 @@ pub fn run() {
                 }
             }
 
-            // Initialize Discord Rich Presence
+            // Initialize Discord Rich Presence, retrying in the background if Discord isn't open yet
             tauri::async_runtime::spawn(async {
                 if let Err(e) = crate::discord::initialize() {
                     Logger::warn_global(
@@ -201,10 +209,50 @@ pub fn run() {
                 } else {
                     Logger::info_global("[STARTUP] Discord Rich Presence initialized", None);
                 }
+                crate::discord::start_reconnect_loop();
+            });
+
+            // Remove temp files of writes and downloads a crash interrupted
+            tauri::async_runtime::spawn(async {
+                if let Err(e) = crate::installations::cleanup_temp_files().await {
+                    Logger::warn_global(
+                        &format!("[STARTUP] Failed to clean up temp files: {}", e),
+                        None,
+                    );
+                }
+            });
+
+            // Restore the filter and page size the mod browser used last, then warm its first page
+            tauri::async_runtime::spawn(async {
+                crate::mods::restore_mod_browser_state().await;
+                let prefetch_enabled = crate::settings::load_settings()
+                    .await
+                    .map(|s| s.content.prefetch_featured_mods)
+                    .unwrap_or(true);
+                if !prefetch_enabled {
+                    return;
+                }
+                if let Err(e) = crate::mods::prefetch_featured_mods().await {
+                    Logger::warn_global(
+                        &format!("[STARTUP] Failed to prefetch featured mods: {}", e),
+                        None,
+                    );
+                }
             });
 
             // Clean up any leftover symlinks from previous crashes/exits
             tauri::async_runtime::spawn(async {
+                let cleanup_enabled = crate::settings::load_settings()
+                    .await
+                    .map(|s| s.content.cleanup_symlinks_on_startup)
+                    .unwrap_or(true);
+                if !cleanup_enabled {
+                    Logger::info_global(
+                        "[STARTUP] Skipping symlink cleanup (disabled in settings)",
+                        None,
+                    );
+                    return;
+                }
                 if let Ok(minecraft_dir) = get_default_minecraft_dir() {
                     let symlink_manager =
                         crate::symlink_manager::SymlinkManager::new(minecraft_dir);
@@ -225,9 +273,21 @@ pub fn run() {
             Ok(())
         })
         .on_window_event(|window, event| {
+            use tauri::Emitter;
             // Clean up symlinks when the main window is closed
-            if let tauri::WindowEvent::CloseRequested { .. } = event {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
                 if window.label() == "main" {
+                    // Ask first when games are running, they still use the symlinked folders
+                    let running = crate::launcher::running_minecraft_pids();
+                    if !running.is_empty() && !crate::launcher::close_confirmed() {
+                        api.prevent_close();
+                        let _ = window.emit(
+                            "confirm-close-with-running-games",
+                            serde_json::json!({ "pids": running }),
+                        );
+                        return;
+                    }
+
                     // Clear Discord presence immediately (blocking to ensure it completes)
                     if let Err(e) = crate::discord::clear() {
                         Logger::warn_global(
@@ -242,6 +302,17 @@ pub fn run() {
                         );
                     }
 
+                    if !running.is_empty() {
+                        // The symlinks are cleaned up on the next start instead
+                        Logger::info_global(
+                            &format!(
+                                "[SHUTDOWN] {} game(s) still running, leaving symlinks in place",
+                                running.len()
+                            ),
+                            None,
+                        );
+                        return;
+                    }
                     tauri::async_runtime::spawn(async {
                         if let Ok(minecraft_dir) = get_default_minecraft_dir() {
                             let symlink_manager =
@@ -290,6 +361,7 @@ pub fn run() {
             // Settings commands
             settings::load_settings,
             settings::save_settings_command,
+            settings::validate_settings_command,
             settings::validate_minecraft_directory,
             settings::load_custom_css,
             settings::set_selected_css_theme,
@@ -302,16 +374,45 @@ pub fn run() {
             // Installation commands
             commands_installations::get_versions,
             commands_installations::get_all_versions,
+            commands_installations::get_local_versions,
             commands_installations::refresh_version_manifests,
+            commands_installations::is_loader_compatible,
             commands_installations::get_installations,
             commands_installations::get_installations_force,
             commands_installations::refresh_installations,
             commands_installations::get_installation,
+            commands_installations::get_installation_by_name,
+            commands_installations::search_installations,
+            commands_installations::render_installation_description,
+            commands_installations::get_playtime_stats,
+            commands_installations::get_installation_content_counts,
+            commands_installations::diff_installations,
+            commands_installations::prune_unused_game_files,
+            commands_installations::get_versions_in_use,
+            commands_installations::cleanup_temp_files,
+            commands_installations::get_storage_report,
+            commands_installations::get_game_options,
+            commands_installations::set_game_options,
+            commands_installations::detect_keybind_conflicts,
             commands_installations::modify_installation,
             commands_installations::delete_installation,
             commands_installations::create_installation,
             commands_installations::create_installation_from_existing,
+            commands_installations::start_create_installation_from_existing,
+            commands_installations::migrate_installation_version,
+            commands_installations::save_installation_template,
+            commands_installations::list_installation_templates,
+            commands_installations::delete_installation_template,
+            commands_installations::create_installation_from_template,
+            commands_installations::create_installation_from_manifest,
             commands_installations::get_mod_info,
+            commands_installations::set_mods_folder_mode,
+            commands_installations::relocate_installation_folder,
+            commands_installations::reset_installation_settings,
+            commands_installations::set_installation_parameter,
+            commands_installations::remove_installation_parameter,
+            commands_installations::read_mod_jar_info,
+            commands_installations::install_local_mod,
             commands_installations::disable_mod,
             commands_installations::enable_mod,
             commands_installations::toggle_mod_disabled,
@@ -330,38 +431,80 @@ pub fn run() {
             commands_installations::get_shaderpack_info_for_installation,
             commands_installations::get_global_shaderpacks,
             commands_installations::import,
+            commands_installations::import_installations,
             commands_installations::import_from_minecraft_folder,
+            commands_installations::start_import_from_minecraft_folder,
+            commands_installations::import_from_prism,
+            commands_installations::import_from_multimc,
+            commands_installations::import_curseforge_zip,
             commands_installations::export,
             commands_installations::duplicate,
             commands_installations::create_shortcut,
             commands_installations::select_installation_zip,
             commands_installations::select_minecraft_folder,
+            commands_installations::select_instance_folder,
+            commands_installations::select_curseforge_zip,
             // Launcher commands
             commands_launcher::launch_installation,
+            commands_launcher::start_launch_installation,
+            commands_launcher::preview_launch_command,
+            commands_launcher::get_launch_script,
+            commands_launcher::get_merged_manifest,
+            commands_launcher::validate_launch,
+            commands_launcher::resolve_lwjgl_conflict,
+            commands_launcher::verify_version_jar,
+            commands_launcher::repair_version_jar,
             commands_launcher::kill_minecraft_process,
+            commands_launcher::kill_installation,
             commands_launcher::get_running_minecraft_processes,
+            commands_launcher::get_running_minecraft_process_details,
             commands_launcher::is_minecraft_running,
+            commands_launcher::is_installation_running,
+            commands_launcher::confirm_close_with_running_games,
             commands_launcher::wait_for_minecraft_exit,
+            commands_launcher::answer_launch_behavior,
+            commands_launcher::cancel_operation,
             commands_launcher::auto_detect_java,
+            commands_launcher::probe_java,
+            commands_launcher::detect_system_java,
+            commands_launcher::get_required_java_version,
             commands_launcher::get_java_path,
+            commands_launcher::get_game_log,
+            commands_launcher::clear_game_logs,
             // Maps/Worlds commands
             maps::get_local_worlds,
             maps::delete_world,
             maps::backup_world,
             // Mods commands
             commands_mods::get_mods,
+            commands_mods::get_mods_page,
+            commands_mods::prefetch_featured_mods,
+            commands_mods::add_mod_bookmark,
+            commands_mods::remove_mod_bookmark,
+            commands_mods::get_mod_bookmarks,
             commands_mods::download_mod,
             commands_mods::download_or_prepare_mod,
             commands_mods::get_projects,
             commands_mods::get_project_versions,
+            commands_mods::modrinth_version_by_hash,
+            commands_mods::get_modrinth_categories,
+            commands_mods::get_modrinth_loaders,
+            commands_mods::get_modrinth_game_versions,
             commands_mods::set_provider_filter,
             commands_mods::set_provider_limit,
+            commands_mods::get_provider_filter,
+            commands_mods::get_provider_limit,
+            commands_mods::get_last_provider,
             commands_mods::clear_provider_cache,
             commands_mods::purge_stale_provider_cache,
             commands_mods::get_extended_mod_info,
+            commands_mods::get_extended_mod_info_batch,
             commands_mods::get_mod_metadata,
+            commands_mods::rollback_mod,
+            commands_mods::export_modlist,
             commands_mods::get_modpack_source_records,
             commands_mods::apply_modpack_selection,
+            commands_mods::start_apply_modpack_selection,
             // Shaders commands
             commands_shaders::get_installed_shaders,
             commands_shaders::toggle_shader,
@@ -396,10 +539,12 @@ pub fn run() {
             commands_discord::discord_set_browsing,
             commands_discord::discord_set_enabled,
             commands_discord::discord_clear,
+            commands_discord::discord_get_status,
             // Skins commands
             commands_skins::upload_skin_to_account,
             commands_skins::change_skin_model,
             commands_skins::get_skin_url_by_uuid,
+            commands_skins::resolve_player,
             commands_skins::get_current_skin_info,
             commands_skins::select_skin_file,
             commands_skins::get_all_account_skins,
@@ -412,6 +557,7 @@ pub fn run() {
             commands_skins::apply_cape,
             // Symlinks commands
             commands_symlinks::list_symlinks,
+            commands_symlinks::can_create_symlinks,
             commands_symlinks::create_custom_symlink,
             commands_symlinks::remove_symlink,
             commands_symlinks::toggle_symlink_disabled,
@@ -435,6 +581,7 @@ pub fn run() {
             // System commands
             commands_system::open_url,
             commands_system::open_path,
+            commands_system::run_first_run_checks,
             // Sounds commands
             commands::sounds::list_soundpacks,
             commands::sounds::get_soundpack_metadata,
@@ -794,6 +941,24 @@ pub async fn ensure_symlinks_enabled(minecraft_path: &Path) -> Result<(), String
     Ok(())
 }
 
+/// Windows refuses symlinks without this privilege (ERROR_PRIVILEGE_NOT_HELD)
+#[cfg(windows)]
+const ERROR_PRIVILEGE_NOT_HELD: i32 = 1314;
+
+/// Shown when Windows refuses to create a symlink
+pub const SYMLINK_PRIVILEGE_MESSAGE: &str = "Windows only allows creating symlinks with Developer \
+     Mode enabled or when running as administrator. Enable Developer Mode in Settings > System > \
+     For developers (Settings > Update & Security > For developers on Windows 10) and restart Kable.";
+
+#[cfg(windows)]
+fn symlink_error(kind: &str, e: std::io::Error) -> String {
+    if e.raw_os_error() == Some(ERROR_PRIVILEGE_NOT_HELD) {
+        SYMLINK_PRIVILEGE_MESSAGE.to_string()
+    } else {
+        format!("Failed to create {}: {}", kind, e)
+    }
+}
+
 /// Create a symbolic link from source to target directory
 #[cfg(windows)]
 pub async fn create_directory_symlink(source: &Path, target: &Path) -> Result<(), String> {
@@ -818,9 +983,7 @@ pub async fn create_directory_symlink(source: &Path, target: &Path) -> Result<()
     tokio::task::spawn_blocking({
         let source = source.to_path_buf();
         let target = target.to_path_buf();
-        move || {
-            symlink_dir(&source, &target).map_err(|e| format!("Failed to create symlink: {}", e))
-        }
+        move || symlink_dir(&source, &target).map_err(|e| symlink_error("symlink", e))
     })
     .await
     .map_err(|e| format!("Symlink creation task failed: {}", e))??;
@@ -851,10 +1014,7 @@ pub async fn create_file_symlink(source: &Path, target: &Path) -> Result<(), Str
     tokio::task::spawn_blocking({
         let source = source.to_path_buf();
         let target = target.to_path_buf();
-        move || {
-            symlink_file(&source, &target)
-                .map_err(|e| format!("Failed to create file symlink: {}", e))
-        }
+        move || symlink_file(&source, &target).map_err(|e| symlink_error("file symlink", e))
     })
     .await
     .map_err(|e| format!("File symlink creation task failed: {}", e))??;
@@ -899,6 +1059,48 @@ pub async fn create_file_symlink(source: &Path, target: &Path) -> Result<(), Str
     create_directory_symlink(source, target).await
 }
 
+/// Create a directory junction at target pointing to source. Unlike symlinks, junctions don't
+/// need Developer Mode, but only work for directories on local drives.
+#[cfg(windows)]
+pub async fn create_directory_junction(source: &Path, target: &Path) -> Result<(), String> {
+    if target.exists() || target.is_symlink() {
+        if target.is_symlink() {
+            remove_symlink_if_exists(target).await?;
+        } else {
+            return Err(format!(
+                "Target path exists and is not a link: {}",
+                target.display()
+            ));
+        }
+    }
+
+    ensure_parent_dir_exists_async(target).await?;
+
+    let output = tokio::process::Command::new("cmd")
+        .arg("/C")
+        .arg("mklink")
+        .arg("/J")
+        .arg(target)
+        .arg(source)
+        // CREATE_NO_WINDOW = 0x08000000
+        .creation_flags(0x08000000)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run mklink: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to create junction: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+pub async fn create_directory_junction(_source: &Path, _target: &Path) -> Result<(), String> {
+    Err("Directory junctions only exist on Windows".to_string())
+}
+
 /// Remove a symbolic link if it exists
 pub async fn remove_symlink_if_exists(path: &Path) -> Result<(), String> {
     if !path.exists() {
@@ -689,9 +689,21 @@ macro_rules! log_debug_global {
     };
 }
 
-/// Export logs to a file for debugging or support purposes
+/// Export logs as a zip for debugging or support purposes.
+///
+/// The export can be scoped to a single installation (`instance_id`) and/or a time range
+/// (`since`/`until` as RFC3339). For an installation the zip also contains its metadata and
+/// the latest crash report in the range, so it can be attached to a bug report as is.
+/// Compressed (.7z) log archives are not included.
+///
+/// # Returns
+/// The path of the created zip file.
 #[tauri::command]
-pub async fn export_logs(instance_id: Option<String>) -> Result<(), String> {
+pub async fn export_logs(
+    instance_id: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
+) -> Result<String, String> {
     // Get the logs directory from storage or fallback to default
     let logs_dir = if let Ok(storage_guard) = LOG_STORAGE.lock() {
         if let Some(storage) = storage_guard.as_ref() {
@@ -709,45 +721,176 @@ pub async fn export_logs(instance_id: Option<String>) -> Result<(), String> {
             .join("logs")
     };
 
+    let parse_time =
+        |value: &Option<String>, name: &str| -> Result<Option<DateTime<Utc>>, String> {
+            value
+                .as_deref()
+                .map(|v| {
+                    DateTime::parse_from_rfc3339(v)
+                        .map(|t| t.with_timezone(&Utc))
+                        .map_err(|e| format!("Invalid '{}' time '{}': {}", name, v, e))
+                })
+                .transpose()
+        };
+    let since = parse_time(&since, "since")?;
+    let until = parse_time(&until, "until")?;
+
     // Create exports directory (use async helper)
     let exports_dir = logs_dir.join("exports");
-    crate::ensure_parent_dir_exists_async(&exports_dir)
-        .await
-        .map_err(|e| format!("Failed to create exports directory parent: {}", e))?;
-    // Also ensure the directory itself exists
     crate::ensure_folder(&exports_dir)
         .await
         .map_err(|e| format!("Failed to ensure exports directory exists: {}", e))?;
 
-    let log_content = if let Some(ref id) = instance_id {
-        format!(
-            "Logs for instance: {}\n\n[Sample log entries for instance {}]",
-            id, id
-        )
-    } else {
-        "Global launcher logs\n\n[Sample global log entries]".to_string()
+    // Installation metadata is only available through the async installations cache
+    let installation_json = match instance_id.as_deref() {
+        Some(id) => crate::installations::get_installation(id)
+            .await?
+            .map(|i| serde_json::to_string_pretty(&i))
+            .transpose()
+            .map_err(|e| format!("Failed to serialize installation: {}", e))?,
+        None => None,
     };
-
-    let filename = if let Some(ref id) = instance_id {
-        format!("kable_logs_{}.txt", id)
-    } else {
-        "kable_logs_global.txt".to_string()
+    // Every launch uses the default .minecraft as its game directory, so crash reports of all
+    // installations end up in the same folder. The installation's game logs say which are its own.
+    let crash_report_source = match instance_id.as_deref() {
+        Some(id) => Some((
+            crate::launcher::game_log::game_log_path(id)?
+                .parent()
+                .map(Path::to_path_buf)
+                .ok_or("Game log path has no parent")?,
+            crate::get_default_minecraft_dir()?,
+        )),
+        None => None,
     };
 
+    let filename = format!(
+        "kable_logs_{}_{}.zip",
+        instance_id.as_deref().unwrap_or("global"),
+        Utc::now().format("%Y%m%d-%H%M%S")
+    );
     let export_path = exports_dir.join(&filename);
-    crate::ensure_parent_dir_exists_async(&export_path)
-        .await
-        .map_err(|e| format!("Failed to create parent for export file: {}", e))?;
 
-    crate::write_file_atomic_async(&export_path, log_content.as_bytes())
-        .await
-        .map_err(|e| format!("Failed to write log file: {}", e))?;
+    let export_path_for_task = export_path.clone();
+    let instance_for_task = instance_id.clone();
+    tokio::task::spawn_blocking(move || {
+        let source_dir = match instance_for_task.as_deref() {
+            Some(id) => logs_dir.join("installations").join(id),
+            None => logs_dir.join("launcher"),
+        };
+        let log_content = collect_log_lines(&source_dir, since, until);
+
+        let file = File::create(&export_path_for_task)
+            .map_err(|e| format!("Failed to create export file: {}", e))?;
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::FullFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+
+        zip.start_file("logs.txt", options.clone())
+            .map_err(|e| format!("Failed to write logs.txt: {}", e))?;
+        zip.write_all(log_content.as_bytes())
+            .map_err(|e| format!("Failed to write logs.txt: {}", e))?;
+
+        if let Some(installation_json) = installation_json {
+            zip.start_file("installation.json", options.clone())
+                .map_err(|e| format!("Failed to write installation.json: {}", e))?;
+            zip.write_all(installation_json.as_bytes())
+                .map_err(|e| format!("Failed to write installation.json: {}", e))?;
+        }
+
+        if let Some((game_log_dir, game_dir)) = &crash_report_source {
+            if let Some(report) = latest_crash_report(game_log_dir, game_dir, since, until) {
+                let name = report
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "crash-report.txt".to_string());
+                let contents =
+                    fs::read(&report).map_err(|e| format!("Failed to read crash report: {}", e))?;
+                zip.start_file(format!("crash-reports/{}", name), options.clone())
+                    .map_err(|e| format!("Failed to write crash report: {}", e))?;
+                zip.write_all(&contents)
+                    .map_err(|e| format!("Failed to write crash report: {}", e))?;
+            }
+        }
+
+        zip.finish()
+            .map_err(|e| format!("Failed to finish export zip: {}", e))?;
+        Ok::<(), String>(())
+    })
+    .await
+    .map_err(|e| format!("Log export join error: {}", e))??;
 
     Logger::info_global(
         &format!("Logs exported to: {}", export_path.display()),
         instance_id.as_deref(),
     );
-    Ok(())
+    Ok(export_path.to_string_lossy().to_string())
+}
+
+/// Reads the daily .log files in `dir` (oldest first) and keeps the lines within the time range.
+/// Lines without a timestamp (multi-line messages) follow the line before them.
+fn collect_log_lines(
+    dir: &Path,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> String {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().map(|e| e == "log").unwrap_or(false))
+                .collect()
+        })
+        .unwrap_or_default();
+    // File names end in the date (e.g. installations-2024-01-31.log), so name order is date order
+    files.sort();
+
+    let mut output = String::new();
+    for file in files {
+        let Ok(contents) = fs::read_to_string(&file) else {
+            continue;
+        };
+        let mut include = true;
+        for line in contents.lines() {
+            if let Some(timestamp) = parse_log_line_time(line) {
+                include =
+                    since.is_none_or(|s| timestamp >= s) && until.is_none_or(|u| timestamp <= u);
+            }
+            if include {
+                output.push_str(line);
+                output.push('\n');
+            }
+        }
+    }
+    output
+}
+
+/// Parses the `[2024-01-31 12:00:00.000 UTC]` prefix written by LogStorage
+fn parse_log_line_time(line: &str) -> Option<DateTime<Utc>> {
+    let end = line.find(" UTC]")?;
+    let raw = line.get(1..end)?;
+    chrono::NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S%.3f")
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+/// The crash report of the last crash in the installation's game logs within the time range.
+/// The game prints where it saved the report, relative paths are relative to its game directory.
+fn latest_crash_report(
+    game_log_dir: &Path,
+    game_dir: &Path,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> Option<PathBuf> {
+    const MARKER: &str = "Crash report saved to:";
+    let lines = collect_log_lines(game_log_dir, since, until);
+    let (_, saved_to) = lines
+        .lines()
+        .rev()
+        .find_map(|line| line.split_once(MARKER))?;
+    let report = game_dir.join(saved_to.trim().trim_start_matches("#@!@#").trim());
+    let is_report = report.is_file() && report.extension().map(|e| e == "txt").unwrap_or(false);
+    is_report.then_some(report)
 }
 
 /// Update logging configuration
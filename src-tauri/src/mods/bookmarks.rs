@@ -0,0 +1,161 @@
+use super::{CurseForgeInfo, ModInfoKind, ModrinthInfo, ProviderKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Bookmarked mods, stored in the kable dir so they don't belong to any installation
+pub const MOD_BOOKMARKS_FILE: &str = "mod_bookmarks.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModBookmark {
+    pub provider: ProviderKind,
+    pub project_id: String,
+    pub added_at: String,
+}
+
+/// Bookmarks a mod, bookmarking it again keeps the original entry
+pub async fn add_mod_bookmark(provider: ProviderKind, project_id: &str) -> Result<(), String> {
+    if project_id.trim().is_empty() {
+        return Err("Project id is empty".to_string());
+    }
+    let mut bookmarks = read_bookmarks().await?;
+    if bookmarks
+        .iter()
+        .any(|b| b.provider == provider && b.project_id == project_id)
+    {
+        return Ok(());
+    }
+    bookmarks.push(ModBookmark {
+        provider,
+        project_id: project_id.to_string(),
+        added_at: chrono::Utc::now().to_rfc3339(),
+    });
+    write_bookmarks(&bookmarks).await
+}
+
+pub async fn remove_mod_bookmark(provider: ProviderKind, project_id: &str) -> Result<(), String> {
+    let mut bookmarks = read_bookmarks().await?;
+    let before = bookmarks.len();
+    bookmarks.retain(|b| !(b.provider == provider && b.project_id == project_id));
+    if bookmarks.len() != before {
+        write_bookmarks(&bookmarks).await?;
+    }
+    Ok(())
+}
+
+/// Bookmarked mods in the order they were added. Mods found in the search caches are
+/// taken from there, the rest is fetched in one request per provider.
+/// Projects that can't be resolved (removed, or the provider is unreachable) are left out.
+pub async fn get_mod_bookmarks() -> Result<Vec<ModInfoKind>, String> {
+    let bookmarks = read_bookmarks().await?;
+    if bookmarks.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut modrinth = cached_modrinth_projects();
+    let mut curseforge = cached_curseforge_mods();
+
+    let missing_modrinth: Vec<String> = bookmarks
+        .iter()
+        .filter(|b| b.provider == ProviderKind::Modrinth && !modrinth.contains_key(&b.project_id))
+        .map(|b| b.project_id.clone())
+        .collect();
+    if !missing_modrinth.is_empty() {
+        match super::modrinth::get_projects(missing_modrinth).await {
+            Ok(projects) => {
+                for project in projects {
+                    modrinth.insert(project.slug.clone(), project.clone());
+                    modrinth.insert(project.project_id.clone(), project);
+                }
+            }
+            Err(e) => crate::logging::Logger::warn_global(
+                &format!("Failed to resolve Modrinth bookmarks: {}", e),
+                None,
+            ),
+        }
+    }
+
+    let missing_curseforge: Vec<u32> = bookmarks
+        .iter()
+        .filter(|b| {
+            b.provider == ProviderKind::CurseForge && !curseforge.contains_key(&b.project_id)
+        })
+        .filter_map(|b| b.project_id.parse().ok())
+        .collect();
+    if !missing_curseforge.is_empty() {
+        match super::curseforge::get_mods_by_ids(&missing_curseforge).await {
+            Ok(mods) => {
+                for info in mods {
+                    curseforge.insert(info.id.to_string(), info);
+                }
+            }
+            Err(e) => crate::logging::Logger::warn_global(
+                &format!("Failed to resolve CurseForge bookmarks: {}", e),
+                None,
+            ),
+        }
+    }
+
+    Ok(bookmarks
+        .iter()
+        .filter_map(|b| match b.provider {
+            ProviderKind::Modrinth => modrinth
+                .get(&b.project_id)
+                .cloned()
+                .map(ModInfoKind::Modrinth),
+            ProviderKind::CurseForge => curseforge
+                .get(&b.project_id)
+                .cloned()
+                .map(ModInfoKind::CurseForge),
+        })
+        .collect())
+}
+
+/// Every project in the Modrinth search cache, by project id and slug
+fn cached_modrinth_projects() -> HashMap<String, ModrinthInfo> {
+    let provider = super::MODRINTH.lock().unwrap();
+    let mut projects = HashMap::new();
+    for project in provider.cache.entries.values().flat_map(|e| e.value.iter()) {
+        projects.insert(project.slug.clone(), project.clone());
+        projects.insert(project.project_id.clone(), project.clone());
+    }
+    projects
+}
+
+/// Every mod in the CurseForge search cache, by mod id
+fn cached_curseforge_mods() -> HashMap<String, CurseForgeInfo> {
+    let provider = super::CURSEFORGE.lock().unwrap();
+    provider
+        .cache
+        .entries
+        .values()
+        .flat_map(|e| e.value.iter())
+        .map(|info| (info.id.to_string(), info.clone()))
+        .collect()
+}
+
+fn bookmarks_path() -> Result<PathBuf, String> {
+    Ok(crate::get_minecraft_kable_dir()?.join(MOD_BOOKMARKS_FILE))
+}
+
+async fn read_bookmarks() -> Result<Vec<ModBookmark>, String> {
+    let path = bookmarks_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", MOD_BOOKMARKS_FILE, e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", MOD_BOOKMARKS_FILE, e))
+}
+
+async fn write_bookmarks(bookmarks: &[ModBookmark]) -> Result<(), String> {
+    let path = bookmarks_path()?;
+    if let Some(parent) = path.parent() {
+        crate::ensure_folder(parent).await?;
+    }
+    let content = serde_json::to_string_pretty(bookmarks)
+        .map_err(|e| format!("Failed to serialize {}: {}", MOD_BOOKMARKS_FILE, e))?;
+    crate::write_file_atomic_async(&path, content.as_bytes()).await
+}
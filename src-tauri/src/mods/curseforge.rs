@@ -193,8 +193,9 @@ pub struct CurseForgeFile {
     pub file_length: u64,
     #[serde(rename = "downloadCount")]
     pub download_count: u64,
-    #[serde(rename = "downloadUrl")]
-    pub download_url: String,
+    /// None for files whose author disabled third-party distribution
+    #[serde(rename = "downloadUrl", default)]
+    pub download_url: Option<String>,
     #[serde(rename = "gameVersions", default)]
     pub game_versions: Vec<String>,
     #[serde(default)]
@@ -285,14 +286,14 @@ impl Default for CurseForgeProvider {
     }
 }
 
-#[async_trait::async_trait]
-impl ModProvider for CurseForgeProvider {
-    fn set_limit(&mut self, limit: usize) {
-        self.limit = limit;
-    }
-
-    #[log_result(log_values = true, max_length = 150)]
-    async fn get(&mut self, offset: usize) -> Result<Vec<ModInfoKind>, String> {
+impl CurseForgeProvider {
+    /// Search results at `offset` and their total hits, from the cache while it is fresh.
+    /// The total is only None for cached pages when `need_total` is false.
+    async fn search(
+        &mut self,
+        offset: usize,
+        need_total: bool,
+    ) -> Result<(Vec<CurseForgeInfo>, Option<usize>), String> {
         let cache_key = format!(
             "offset:{}:query:{}:category:{}:loader:{}:version:{}:sort:{}:order:{}",
             offset,
@@ -318,20 +319,21 @@ impl ModProvider for CurseForgeProvider {
         println!("[CurseForgeProvider] Using cache key: {}", cache_key);
 
         if let Some(entry) = self.cache.get(&cache_key) {
-            if !self.cache.is_stale(&cache_key) {
+            let total_hits = search_total(&cache_key);
+            if self.cache.is_stale(&cache_key) {
+                println!(
+                    "[CurseForgeProvider] Cache entry is stale for key: {}",
+                    cache_key
+                );
+            } else if total_hits.is_some() || !need_total {
                 println!(
                     "[CurseForgeProvider] Returning cached results for key: {}",
                     cache_key
                 );
-                return Ok(entry
-                    .value
-                    .clone()
-                    .into_iter()
-                    .map(ModInfoKind::CurseForge)
-                    .collect());
+                return Ok((entry.value.clone(), total_hits));
             } else {
                 println!(
-                    "[CurseForgeProvider] Cache entry is stale for key: {}",
+                    "[CurseForgeProvider] Total hits unknown for cached key: {}",
                     cache_key
                 );
             }
@@ -342,12 +344,37 @@ impl ModProvider for CurseForgeProvider {
             );
         }
 
-        let mods = search_mods(&self.filter, offset, self.limit).await?;
+        let (mods, total_hits) = search_mods(&self.filter, offset, self.limit).await?;
+        record_search_total(&cache_key, total_hits);
         self.cache.insert(cache_key.clone(), mods.clone());
         let _ = self.cache.save_to_disk(&self.cache_path);
+        Ok((mods, Some(total_hits)))
+    }
+}
+
+#[async_trait::async_trait]
+impl ModProvider for CurseForgeProvider {
+    fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+    }
+
+    #[log_result(log_values = true, max_length = 150)]
+    async fn get(&mut self, offset: usize) -> Result<Vec<ModInfoKind>, String> {
+        let (mods, _) = self.search(offset, false).await?;
         Ok(mods.into_iter().map(ModInfoKind::CurseForge).collect())
     }
 
+    async fn get_page(&mut self, offset: usize) -> Result<ModsPage, String> {
+        let (mods, total_hits) = self.search(offset, true).await?;
+        let items = mods.into_iter().map(ModInfoKind::CurseForge).collect();
+        Ok(ModsPage::new(
+            items,
+            total_hits.unwrap_or_default(),
+            offset,
+            self.limit,
+        ))
+    }
+
     fn filter(
         &mut self,
         installation: Option<&KableInstallation>,
@@ -455,8 +482,16 @@ impl ModProvider for CurseForgeProvider {
         // Download the file
         download_mod_file(&download_url, &mods_dir.join(&file.file_name)).await?;
 
-        // Save metadata
-        save_mod_metadata(&mods_dir, &file.file_name, mod_id, &file.display_name).await?;
+        // Record provenance
+        save_mod_metadata(
+            &mods_dir,
+            &file.file_name,
+            mod_id,
+            file.id,
+            &file.display_name,
+            &download_url,
+        )
+        .await?;
 
         Ok(())
     }
@@ -531,7 +566,7 @@ pub async fn search_mods(
     filter: &CurseForgeFilter,
     offset: usize,
     limit: usize,
-) -> Result<Vec<CurseForgeInfo>, String> {
+) -> Result<(Vec<CurseForgeInfo>, usize), String> {
     let api_key = get_api_key()?;
     let client = Client::new();
     let mut url = format!(
@@ -623,7 +658,10 @@ pub async fn search_mods(
         "[CurseForgeAPI] Received {} mods from API",
         search_response.data.len()
     );
-    Ok(search_response.data)
+    Ok((
+        search_response.data,
+        search_response.pagination.total_count as usize,
+    ))
 }
 
 /// Get all files for a given CurseForge mod ID
@@ -648,6 +686,60 @@ pub async fn get_mod_files(mod_id: u32) -> Result<Vec<CurseForgeFile>, String> {
     Ok(files_response.data)
 }
 
+/// Get several files at once by their file IDs
+#[log_result]
+pub async fn get_files_by_ids(file_ids: &[u32]) -> Result<Vec<CurseForgeFile>, String> {
+    let api_key = get_api_key()?;
+    let client = Client::new();
+
+    let resp = client
+        .post("https://api.curseforge.com/v1/mods/files")
+        .header("x-api-key", &api_key)
+        .json(&serde_json::json!({ "fileIds": file_ids }))
+        .send()
+        .await
+        .map_err(|e| format!("CurseForge get files by id failed: {}", e))?;
+
+    #[derive(Deserialize)]
+    struct FilesResponse {
+        data: Vec<CurseForgeFile>,
+    }
+
+    let files_response: FilesResponse = resp
+        .json()
+        .await
+        .map_err(|e| format!("CurseForge get files by id parse failed: {}", e))?;
+
+    Ok(files_response.data)
+}
+
+/// Get several mods (projects) at once by their mod IDs
+#[log_result]
+pub async fn get_mods_by_ids(mod_ids: &[u32]) -> Result<Vec<CurseForgeInfo>, String> {
+    let api_key = get_api_key()?;
+    let client = Client::new();
+
+    let resp = client
+        .post("https://api.curseforge.com/v1/mods")
+        .header("x-api-key", &api_key)
+        .json(&serde_json::json!({ "modIds": mod_ids }))
+        .send()
+        .await
+        .map_err(|e| format!("CurseForge get mods by id failed: {}", e))?;
+
+    #[derive(Deserialize)]
+    struct GetModsResponse {
+        data: Vec<CurseForgeInfo>,
+    }
+
+    let mods_response: GetModsResponse = resp
+        .json()
+        .await
+        .map_err(|e| format!("CurseForge get mods by id parse failed: {}", e))?;
+
+    Ok(mods_response.data)
+}
+
 /// Get download URL for a specific file
 #[log_result]
 pub async fn get_mod_file_download_url(mod_id: u32, file_id: u32) -> Result<String, String> {
@@ -678,24 +770,14 @@ pub async fn get_mod_file_download_url(mod_id: u32, file_id: u32) -> Result<Stri
     Ok(download_response.data)
 }
 
-/// Download a mod file from CurseForge and save to the given path
+/// Download a mod file from CurseForge and save to the given path.
+/// An interrupted download continues where it stopped on the next try.
 #[log_result]
 pub async fn download_mod_file(url: &str, save_path: &std::path::Path) -> Result<(), String> {
     let client = Client::new();
-    let resp = client
-        .get(url)
-        .send()
+    crate::launcher::utils::download_file_resumable(&client, url, save_path, None)
         .await
-        .map_err(|e| format!("CurseForge download failed: {}", e))?;
-    let bytes = resp
-        .bytes()
-        .await
-        .map_err(|e| format!("CurseForge download bytes failed: {}", e))?;
-    if let Some(parent) = save_path.parent() {
-        crate::ensure_parent_dir_exists_async(parent).await?;
-    }
-    crate::write_file_atomic_async(save_path, &bytes).await?;
-    Ok(())
+        .map_err(|e| format!("CurseForge download failed: {}", e))
 }
 
 /// Extract Minecraft version from a version_id string (same logic as Modrinth)
@@ -759,88 +841,30 @@ async fn disable_old_mod_versions(
     mods_dir: &std::path::Path,
     project_id: &str,
 ) -> Result<(), String> {
-    use tokio::fs;
-
-    // Read all files in the mods directory
-    let mut entries = fs::read_dir(mods_dir)
-        .await
-        .map_err(|e| format!("Failed to read mods directory: {}", e))?;
-
-    while let Some(entry) = entries
-        .next_entry()
-        .await
-        .map_err(|e| format!("Failed to read directory entry: {}", e))?
-    {
-        let path = entry.path();
-
-        // Check for .kable_metadata.json files
-        if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
-            if file_name.ends_with(".kable_metadata.json") {
-                // Read the metadata file
-                if let Ok(metadata_content) = fs::read_to_string(&path).await {
-                    if let Ok(metadata) =
-                        serde_json::from_str::<serde_json::Value>(&metadata_content)
-                    {
-                        // Check if this metadata belongs to the same project
-                        if let Some(stored_project_id) =
-                            metadata.get("project_id").and_then(|v| v.as_str())
-                        {
-                            if stored_project_id == project_id {
-                                // Get the associated jar file name
-                                if let Some(jar_file) =
-                                    metadata.get("file_name").and_then(|v| v.as_str())
-                                {
-                                    let jar_path = mods_dir.join(jar_file);
-
-                                    // Delete the old jar file completely
-                                    if jar_path.exists() {
-                                        fs::remove_file(&jar_path).await.map_err(|e| {
-                                            format!("Failed to remove old mod version: {}", e)
-                                        })?;
-
-                                        println!(
-                                            "[CurseForgeProvider] Removed old version: {} (project: {})",
-                                            jar_file, project_id
-                                        );
-                                    }
-
-                                    // Also remove the metadata file
-                                    let _ = fs::remove_file(&path).await;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
+    super::remove_project_mods(mods_dir, project_id).await?;
     Ok(())
 }
 
-/// Save metadata for a downloaded mod
+/// Record where a downloaded mod came from in the `.kable-mods.json` sidecar
 async fn save_mod_metadata(
     mods_dir: &std::path::Path,
     file_name: &str,
     project_id: &str,
+    file_id: u32,
     version_number: &str,
+    download_url: &str,
 ) -> Result<(), String> {
-    use tokio::fs;
-
-    let metadata = serde_json::json!({
-        "project_id": project_id,
-        "file_name": file_name,
-        "version_number": version_number,
-        "download_time": chrono::Utc::now().to_rfc3339(),
-    });
-
-    let metadata_path = mods_dir.join(format!("{}.kable_metadata.json", file_name));
-    let metadata_content = serde_json::to_string_pretty(&metadata)
-        .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
-
-    fs::write(&metadata_path, metadata_content)
-        .await
-        .map_err(|e| format!("Failed to write metadata file: {}", e))?;
-
-    Ok(())
+    super::record_mod_provenance(
+        mods_dir,
+        file_name,
+        super::ModProvenance {
+            provider: super::ProviderKind::CurseForge,
+            project_id: project_id.to_string(),
+            version_id: Some(file_id.to_string()),
+            version_number: Some(version_number.to_string()),
+            download_url: Some(download_url.to_string()),
+            downloaded_at: chrono::Utc::now().to_rfc3339(),
+        },
+    )
+    .await
 }
@@ -1,7 +1,10 @@
 use crate::installations::kable_profiles::KableInstallation;
 use crate::mods::curseforge::CurseForgeInfo;
 use crate::mods::modrinth::ModrinthInfo;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
 
 #[async_trait::async_trait]
 pub trait ModProvider {
@@ -11,6 +14,9 @@ pub trait ModProvider {
     /// Get mods/projects at a given offset (pagination)
     async fn get(&mut self, offset: usize) -> Result<Vec<ModInfoKind>, String>;
 
+    /// Same as `get`, together with the total number of results for pagination
+    async fn get_page(&mut self, offset: usize) -> Result<ModsPage, String>;
+
     /// Apply filters (by installation, loader, version, etc.)
     fn filter(&mut self, installation: Option<&KableInstallation>, filter: Option<ModFilter>);
 
@@ -30,6 +36,43 @@ pub trait ModProvider {
     }
 }
 
+/// One page of search results
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModsPage {
+    pub items: Vec<ModInfoKind>,
+    pub total_hits: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub has_more: bool,
+}
+
+impl ModsPage {
+    pub fn new(items: Vec<ModInfoKind>, total_hits: usize, offset: usize, limit: usize) -> Self {
+        let has_more = offset + items.len() < total_hits;
+        Self {
+            items,
+            total_hits,
+            offset,
+            limit,
+            has_more,
+        }
+    }
+}
+
+/// Total hits of searches by provider cache key, the result caches only hold the page itself
+static SEARCH_TOTALS: Lazy<Mutex<HashMap<String, usize>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn record_search_total(cache_key: &str, total_hits: usize) {
+    if let Ok(mut totals) = SEARCH_TOTALS.lock() {
+        totals.insert(cache_key.to_string(), total_hits);
+    }
+}
+
+pub fn search_total(cache_key: &str) -> Option<usize> {
+    SEARCH_TOTALS.lock().ok()?.get(cache_key).copied()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[allow(clippy::large_enum_variant)]
 pub enum ModInfoKind {
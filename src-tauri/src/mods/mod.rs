@@ -94,7 +94,12 @@ pub async fn download_or_prepare_mod(
                 std::fs::create_dir_all(&temp_dir)
                     .map_err(|e| format!("Failed to create temp dir: {e}"))?;
                 let mrpack_path = temp_dir.join(&mrpack_file.filename);
-                crate::mods::modrinth::download_mod_file(&mrpack_file.url, &mrpack_path).await?;
+                crate::mods::modrinth::download_mod_file(
+                    &mrpack_file.url,
+                    &mrpack_path,
+                    &mrpack_file.hashes,
+                )
+                .await?;
                 // Use detailed manifest for modal
                 let extracted_dir = temp_dir.join("extracted");
                 std::fs::create_dir_all(&extracted_dir)
@@ -142,6 +147,7 @@ pub async fn apply_modpack_selection(
     installation: crate::installations::kable_profiles::KableInstallation,
     selection: ModpackSelection,
     context: ModpackContext,
+    progress: &crate::launcher::progress::ProgressReporter,
 ) -> Result<(), String> {
     use sha1::Sha1;
     use sha2::{Digest, Sha512};
@@ -218,14 +224,23 @@ pub async fn apply_modpack_selection(
             }
         }
 
-        let bytes = verified_bytes.ok_or_else(|| {
-            format!(
+        let Some(bytes) = verified_bytes else {
+            let error = format!(
                 "Download failed for {} ({} URLs tried): {}",
                 file.path,
                 file.downloads.len(),
                 last_error.unwrap_or_else(|| "unknown error".to_string())
-            )
-        })?;
+            );
+            crate::launcher::emit_download_failed(
+                &file.path,
+                file.downloads
+                    .first()
+                    .map(String::as_str)
+                    .unwrap_or_default(),
+                &error,
+            );
+            return Err(error);
+        };
 
         if let Some(parent) = target.parent() {
             tokio::fs::create_dir_all(parent)
@@ -289,51 +304,7 @@ pub async fn apply_modpack_selection(
         mods_dir: &std::path::Path,
         project_id: &str,
     ) -> Result<(), String> {
-        for dir in [mods_dir.to_path_buf(), mods_dir.join("disabled")] {
-            if !dir.exists() {
-                continue;
-            }
-
-            let mut entries = tokio::fs::read_dir(&dir)
-                .await
-                .map_err(|e| format!("Failed to read mods folder {}: {e}", dir.display()))?;
-
-            while let Some(entry) = entries
-                .next_entry()
-                .await
-                .map_err(|e| format!("Failed to iterate mods folder {}: {e}", dir.display()))?
-            {
-                let path = entry.path();
-                if path.extension().and_then(|e| e.to_str()) != Some("jar") {
-                    continue;
-                }
-
-                let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
-                    continue;
-                };
-
-                let metadata_path = dir.join(format!("{}.kable_metadata.json", file_name));
-                if !metadata_path.exists() {
-                    continue;
-                }
-
-                let metadata_content = match tokio::fs::read_to_string(&metadata_path).await {
-                    Ok(content) => content,
-                    Err(_) => continue,
-                };
-
-                let metadata = match serde_json::from_str::<ModMetadata>(&metadata_content) {
-                    Ok(m) => m,
-                    Err(_) => continue,
-                };
-
-                if metadata.project_id == project_id {
-                    let _ = tokio::fs::remove_file(&path).await;
-                    let _ = tokio::fs::remove_file(&metadata_path).await;
-                }
-            }
-        }
-
+        crate::mods::remove_project_mods(mods_dir, project_id).await?;
         Ok(())
     }
 
@@ -395,19 +366,19 @@ pub async fn apply_modpack_selection(
 
                 if let Some((project_id, version_id)) = identity {
                     if let Some(file_name) = target.file_name().and_then(|n| n.to_str()) {
-                        let metadata_dir = target.parent().unwrap_or(mods_root);
                         let version_number =
                             resolve_modrinth_version_number(client, &version_id, version_cache)
                                 .await
                                 .unwrap_or_else(|| version_id.clone());
 
-                        // Best-effort metadata write so installed detection can be identity-based.
+                        // Best-effort provenance write so installed detection can be identity-based.
                         let _ = crate::mods::modrinth::save_mod_metadata(
-                            metadata_dir,
+                            mods_root,
                             file_name,
                             &project_id,
                             &version_number,
                             &version_id,
+                            file.downloads.first().map(String::as_str),
                         )
                         .await;
                     }
@@ -486,6 +457,7 @@ pub async fn apply_modpack_selection(
         .map_err(|e| format!("Failed to build HTTP client: {e}"))?;
     let mut version_cache: HashMap<String, Option<String>> = HashMap::new();
 
+    progress.report("modpack", 0, Some(4), Some("mods"));
     install_group(
         &selection.mods,
         "mods",
@@ -496,6 +468,7 @@ pub async fn apply_modpack_selection(
         &mut version_cache,
     )
     .await?;
+    progress.report("modpack", 1, Some(4), Some("resourcepacks"));
     install_group(
         &selection.resourcepacks,
         "resourcepacks",
@@ -506,6 +479,7 @@ pub async fn apply_modpack_selection(
         &mut version_cache,
     )
     .await?;
+    progress.report("modpack", 2, Some(4), Some("shaderpacks"));
     install_group(
         &selection.shaderpacks,
         "shaderpacks",
@@ -548,6 +522,7 @@ pub async fn apply_modpack_selection(
         .await?;
     }
 
+    progress.report("modpack", 3, Some(4), Some("overrides"));
     mrpack::extract_overrides_to_profile(&extracted_dir, &installation_root)
         .map_err(|e| format!("Failed to apply modpack overrides: {e}"))?;
 
@@ -589,17 +564,27 @@ pub async fn apply_modpack_selection(
 
     Ok(())
 }
+pub mod bookmarks;
 pub mod cache;
 pub mod curseforge;
 pub mod manager;
+pub mod modlist;
 pub mod modrinth;
+pub mod modrinth_tags;
 pub mod modrinth_versions_cache;
+pub mod provenance;
+pub mod rollback;
 
+pub use self::bookmarks::*;
 pub use self::cache::*;
 pub use self::curseforge::{CurseForgeFilter, CurseForgeInfo, CurseForgeProvider};
 pub use self::manager::*;
+pub use self::modlist::*;
 pub use self::modrinth::*;
+pub use self::modrinth_tags::*;
 pub use self::modrinth_versions_cache::*;
+pub use self::provenance::*;
+pub use self::rollback::*;
 
 use crate::installations::kable_profiles::KableInstallation;
 
@@ -643,6 +628,91 @@ pub fn set_provider_limit(provider: ProviderKind, limit: usize) {
     }
 }
 
+/// The filter a provider searches with, None when no user filter is set
+pub fn get_provider_filter(provider: ProviderKind) -> Option<ModFilter> {
+    match provider {
+        ProviderKind::Modrinth => MODRINTH
+            .lock()
+            .unwrap()
+            .user_filters
+            .clone()
+            .map(ModFilter::Modrinth),
+        ProviderKind::CurseForge => Some(ModFilter::CurseForge(
+            CURSEFORGE.lock().unwrap().filter.clone(),
+        )),
+    }
+}
+
+pub fn get_provider_limit(provider: ProviderKind) -> usize {
+    match provider {
+        ProviderKind::Modrinth => MODRINTH.lock().unwrap().limit,
+        ProviderKind::CurseForge => CURSEFORGE.lock().unwrap().limit,
+    }
+}
+
+/// The provider the mod browser used last, if it was used before
+pub async fn get_last_provider() -> Result<Option<ProviderKind>, String> {
+    Ok(crate::settings::load_settings()
+        .await?
+        .content
+        .mod_browser
+        .provider)
+}
+
+/// Stores the filter in the settings so `restore_mod_browser_state` brings it back
+pub async fn remember_provider_filter(provider: ProviderKind, filter: Option<ModFilter>) {
+    update_mod_browser_state(|state| {
+        state.provider = Some(provider);
+        state.filter = filter;
+    })
+    .await;
+}
+
+/// Stores the limit in the settings so `restore_mod_browser_state` brings it back
+pub async fn remember_provider_limit(provider: ProviderKind, limit: usize) {
+    update_mod_browser_state(|state| {
+        state.provider = Some(provider);
+        state.limit = Some(limit);
+    })
+    .await;
+}
+
+async fn update_mod_browser_state(update: impl FnOnce(&mut crate::settings::ModBrowserState)) {
+    let result = async {
+        let mut settings = crate::settings::load_settings().await?;
+        update(&mut settings.content.mod_browser);
+        crate::settings::save_settings(settings).await
+    }
+    .await;
+    if let Err(e) = result {
+        crate::logging::Logger::warn_global(
+            &format!("Failed to save the mod browser state: {}", e),
+            None,
+        );
+    }
+}
+
+/// Puts the last used filter and limit back into their provider, called once at startup
+pub async fn restore_mod_browser_state() {
+    let state = match crate::settings::load_settings().await {
+        Ok(settings) => settings.content.mod_browser,
+        Err(e) => {
+            crate::logging::Logger::warn_global(
+                &format!("Failed to load the mod browser state: {}", e),
+                None,
+            );
+            return;
+        }
+    };
+    let provider = state.provider.unwrap_or(ProviderKind::Modrinth);
+    if state.filter.is_some() {
+        set_provider_filter(provider, None, state.filter);
+    }
+    if let Some(limit) = state.limit {
+        set_provider_limit(provider, limit);
+    }
+}
+
 pub async fn get_mods(provider: ProviderKind, offset: usize) -> Result<Vec<ModInfoKind>, String> {
     match provider {
         ProviderKind::Modrinth => {
@@ -662,6 +732,59 @@ pub async fn get_mods(provider: ProviderKind, offset: usize) -> Result<Vec<ModIn
     }
 }
 
+/// Same as `get_mods`, with the total hit count so the UI can paginate
+pub async fn get_mods_page(provider: ProviderKind, offset: usize) -> Result<ModsPage, String> {
+    match provider {
+        ProviderKind::Modrinth => {
+            let mut prov = {
+                let prov_guard = MODRINTH.lock().unwrap();
+                prov_guard.clone()
+            };
+            prov.get_page(offset).await
+        }
+        ProviderKind::CurseForge => {
+            let mut prov = {
+                let prov_guard = CURSEFORGE.lock().unwrap();
+                prov_guard.clone()
+            };
+            prov.get_page(offset).await
+        }
+    }
+}
+
+/// Loads the first Modrinth page the mod browser opens on, and the most downloaded projects,
+/// into the provider cache so the browser has content right away. Pages that are still fresh
+/// in the cache are not fetched again. Returns how many projects were loaded.
+pub async fn prefetch_featured_mods() -> Result<usize, String> {
+    let current = MODRINTH.lock().unwrap().clone();
+    let mut most_downloaded = current.clone();
+    most_downloaded.set_index(Some("downloads".to_string()));
+
+    let mut loaded = 0;
+    for mut prov in [current, most_downloaded] {
+        loaded += prov.get_page(0).await?.items.len();
+        // The provider was cloned, hand the fetched pages back to the one the browser uses
+        let mut global = MODRINTH.lock().unwrap();
+        for (key, entry) in prov.cache.entries {
+            if global
+                .cache
+                .get(&key)
+                .is_none_or(|existing| existing.last_updated < entry.last_updated)
+            {
+                global.cache.entries.insert(key, entry);
+            }
+        }
+    }
+    crate::logging::Logger::debug_global(
+        &format!(
+            "Prefetched {} Modrinth projects for the mod browser",
+            loaded
+        ),
+        None,
+    );
+    Ok(loaded)
+}
+
 pub async fn download_mod(
     provider: ProviderKind,
     mod_id: &str,
@@ -824,42 +947,8 @@ async fn collect_installed_project_ids(
     installation: &KableInstallation,
 ) -> Result<std::collections::HashSet<String>, String> {
     let mods_dir = installation.find_mods_dir()?;
-    let mut project_ids = std::collections::HashSet::new();
-
-    for dir in [mods_dir.clone(), mods_dir.join("disabled")] {
-        if !dir.exists() {
-            continue;
-        }
-
-        let mut entries = tokio::fs::read_dir(&dir)
-            .await
-            .map_err(|e| format!("Failed to read mods folder {}: {}", dir.display(), e))?;
-
-        while let Some(entry) = entries
-            .next_entry()
-            .await
-            .map_err(|e| format!("Failed to iterate mods folder {}: {}", dir.display(), e))?
-        {
-            let path = entry.path();
-            let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
-                continue;
-            };
-            if !file_name.ends_with(".kable_metadata.json") {
-                continue;
-            }
-
-            let content = match tokio::fs::read_to_string(&path).await {
-                Ok(c) => c,
-                Err(_) => continue,
-            };
-
-            if let Ok(meta) = serde_json::from_str::<ModMetadata>(&content) {
-                project_ids.insert(meta.project_id);
-            }
-        }
-    }
-
-    Ok(project_ids)
+    let provenance = load_mod_provenance(&mods_dir).await?;
+    Ok(provenance.into_values().map(|p| p.project_id).collect())
 }
 
 pub async fn get_modpack_source_records(
@@ -894,51 +983,121 @@ pub async fn get_mod_metadata(
     installation: &KableInstallation,
     jar_filename: &str,
 ) -> Result<ModMetadata, String> {
-    use tokio::fs;
-
     let mods_dir = installation.find_mods_dir()?;
+    find_mod_provenance(&mods_dir, jar_filename)
+        .await?
+        .map(|p| p.to_metadata(jar_filename))
+        .ok_or_else(|| format!("No provenance recorded for {}", jar_filename))
+}
+
+/// How many Modrinth searches `get_extended_mod_info_batch` runs at once
+const MOD_INFO_CONCURRENCY: usize = 8;
 
-    // Check active mods directory first
-    let metadata_path = mods_dir.join(format!("{}.kable_metadata.json", jar_filename));
-
-    // If not found in active directory, check disabled directory
-    let metadata_path = if !metadata_path.exists() {
-        let disabled_path = mods_dir
-            .join("disabled")
-            .join(format!("{}.kable_metadata.json", jar_filename));
-        if disabled_path.exists() {
-            disabled_path
-        } else {
-            return Err(format!(
-                "No metadata file found for {} in active or disabled folder",
-                jar_filename
-            ));
+pub async fn get_extended_mod_info(
+    mod_jar_info: crate::ModJarInfo,
+) -> Result<ExtendedModInfo, String> {
+    if let Some(found) = find_cached_mod_info(&mod_jar_info) {
+        return Ok(extended_mod_info(mod_jar_info, &found));
+    }
+
+    // Not found in cache, try Modrinth API search (no lock held here)
+    let query = mod_info_query(&mod_jar_info);
+    match search_modrinth_mod(&query).await? {
+        Some(info) => {
+            cache_mod_search(&query, &info);
+            save_modrinth_cache();
+            Ok(extended_mod_info(mod_jar_info, &info))
         }
-    } else {
-        metadata_path
-    };
+        None => Err(format!(
+            "Mod '{}' not found in Modrinth cache or API",
+            mod_jar_info.mod_name.as_deref().unwrap_or("")
+        )),
+    }
+}
 
-    let content = fs::read_to_string(&metadata_path)
-        .await
-        .map_err(|e| format!("Failed to read metadata file: {}", e))?;
+/// Resolves many jars at once, in the order given (None where nothing was found).
+/// Jars with the same name share one search, Modrinth has no bulk search so the misses
+/// are searched concurrently and the cache is written once at the end.
+pub async fn get_extended_mod_info_batch(
+    jars: Vec<crate::ModJarInfo>,
+) -> Result<Vec<Option<ExtendedModInfo>>, String> {
+    let mut resolved: Vec<Option<crate::modrinth::ModrinthInfo>> =
+        jars.iter().map(find_cached_mod_info).collect();
+
+    let queries: std::collections::HashSet<String> = jars
+        .iter()
+        .zip(&resolved)
+        .filter(|(_, found)| found.is_none())
+        .map(|(jar, _)| mod_info_query(jar))
+        .collect();
+
+    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(MOD_INFO_CONCURRENCY));
+    let mut searches = tokio::task::JoinSet::new();
+    for query in queries {
+        let semaphore = semaphore.clone();
+        searches.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = search_modrinth_mod(&query).await;
+            (query, result)
+        });
+    }
 
-    let metadata: ModMetadata = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse metadata file: {}", e))?;
+    let mut found_by_query = std::collections::HashMap::new();
+    while let Some(joined) = searches.join_next().await {
+        let Ok((query, result)) = joined else {
+            continue;
+        };
+        match result {
+            Ok(Some(info)) => {
+                cache_mod_search(&query, &info);
+                found_by_query.insert(query, info);
+            }
+            Ok(None) => {}
+            Err(e) => crate::logging::Logger::debug_global(
+                &format!("Mod info search for '{}' failed: {}", query, e),
+                None,
+            ),
+        }
+    }
+    if !found_by_query.is_empty() {
+        save_modrinth_cache();
+    }
 
-    Ok(metadata)
+    for (jar, found) in jars.iter().zip(resolved.iter_mut()) {
+        if found.is_none() {
+            *found = found_by_query.get(&mod_info_query(jar)).cloned();
+        }
+    }
+    Ok(jars
+        .into_iter()
+        .zip(resolved)
+        .map(|(jar, found)| found.map(|info| extended_mod_info(jar, &info)))
+        .collect())
 }
 
-pub async fn get_extended_mod_info(
+fn extended_mod_info(
     mod_jar_info: crate::ModJarInfo,
-) -> Result<ExtendedModInfo, String> {
-    // Only hold the lock for the cache lookup
-    let (found_info, mod_name, _loader) = {
-        let modrinth = MODRINTH.lock().unwrap();
-        let mod_name = mod_jar_info.mod_name.as_deref().unwrap_or("");
-        let loader = mod_jar_info.loader.as_deref();
-        let mut found_info = None;
-        for entry in modrinth.cache.entries.values() {
-            if let Some(found) = entry.value.iter().find(|info| {
+    info: &crate::modrinth::ModrinthInfo,
+) -> ExtendedModInfo {
+    ExtendedModInfo {
+        mod_jar_info,
+        icon_uri: info.icon_url.clone(),
+        description: Some(info.description.clone()),
+        authors: vec![info.author.clone()],
+        page_uri: info.source_url.clone(),
+    }
+}
+
+/// A cached Modrinth project with the jar's name and loader, holding the lock only for the lookup
+fn find_cached_mod_info(mod_jar_info: &crate::ModJarInfo) -> Option<crate::modrinth::ModrinthInfo> {
+    let modrinth = MODRINTH.lock().unwrap();
+    let mod_name = mod_jar_info.mod_name.as_deref().unwrap_or("");
+    let loader = mod_jar_info.loader.as_deref();
+    modrinth.cache.entries.values().find_map(|entry| {
+        entry
+            .value
+            .iter()
+            .find(|info| {
                 let name_match = info.title.eq_ignore_ascii_case(mod_name)
                     || info.slug.eq_ignore_ascii_case(mod_name);
                 let loader_match = if let Some(loader) = loader {
@@ -949,36 +1108,20 @@ pub async fn get_extended_mod_info(
                     true
                 };
                 name_match && loader_match
-            }) {
-                found_info = Some(found.clone());
-                break;
-            }
-        }
-        (
-            found_info,
-            mod_name.to_string(),
-            loader.map(|s| s.to_string()),
-        )
-    };
+            })
+            .cloned()
+    })
+}
 
-    if let Some(found) = found_info {
-        return Ok(ExtendedModInfo {
-            mod_jar_info,
-            icon_uri: found.icon_url.clone(),
-            description: Some(found.description.clone()),
-            authors: vec![found.author.clone()],
-            page_uri: found.source_url.clone(),
-        });
+fn mod_info_query(mod_jar_info: &crate::ModJarInfo) -> String {
+    match mod_jar_info.mod_name.as_deref() {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => mod_jar_info.file_name.clone(),
     }
+}
 
-    // Not found in cache, try Modrinth API search (no lock held here)
-    let query = if !mod_name.is_empty() {
-        &mod_name
-    } else if let Some(name) = mod_jar_info.mod_name.as_deref() {
-        name
-    } else {
-        mod_jar_info.file_name.as_str()
-    };
+/// The best Modrinth search hit for `query`, None when there is none
+async fn search_modrinth_mod(query: &str) -> Result<Option<crate::modrinth::ModrinthInfo>, String> {
     let url = format!(
         "https://api.modrinth.com/v2/search?query={}&limit=1",
         urlencoding::encode(query)
@@ -996,37 +1139,30 @@ pub async fn get_extended_mod_info(
     }
     let json: serde_json::Value = serde_json::from_str(&text)
         .map_err(|e| format!("Modrinth API parse error: {}\nResponse body: {}", e, text))?;
-    if let Some(hits) = json.get("hits").and_then(|v| v.as_array()) {
-        if let Some(hit) = hits.first() {
-            if let Ok(info) = serde_json::from_value::<crate::modrinth::ModrinthInfo>(hit.clone()) {
-                // Cache the result to avoid repeated API calls
-                {
-                    let mut modrinth = MODRINTH.lock().unwrap();
-                    let cache_key = format!("search:{}", query);
-                    // If an entry exists, update it; otherwise, insert a new Vec
-                    let mut mods = if let Some(entry) = modrinth.cache.entries.get(&cache_key) {
-                        entry.value.clone()
-                    } else {
-                        Vec::new()
-                    };
-                    if !mods.iter().any(|m| m.project_id == info.project_id) {
-                        mods.push(info.clone());
-                    }
-                    modrinth.cache.insert(cache_key, mods);
-                    let _ = modrinth.cache.save_to_disk(&modrinth.cache_path);
-                }
-                return Ok(ExtendedModInfo {
-                    mod_jar_info,
-                    icon_uri: info.icon_url.clone(),
-                    description: Some(info.description.clone()),
-                    authors: vec![info.author.clone()],
-                    page_uri: info.source_url.clone(),
-                });
-            }
-        }
+    Ok(json
+        .get("hits")
+        .and_then(|v| v.as_array())
+        .and_then(|hits| hits.first())
+        .and_then(|hit| serde_json::from_value(hit.clone()).ok()))
+}
+
+/// Caches a search result to avoid repeated API calls, call `save_modrinth_cache` afterwards
+fn cache_mod_search(query: &str, info: &crate::modrinth::ModrinthInfo) {
+    let mut modrinth = MODRINTH.lock().unwrap();
+    let cache_key = format!("search:{}", query);
+    // If an entry exists, update it; otherwise, insert a new Vec
+    let mut mods = if let Some(entry) = modrinth.cache.entries.get(&cache_key) {
+        entry.value.clone()
+    } else {
+        Vec::new()
+    };
+    if !mods.iter().any(|m| m.project_id == info.project_id) {
+        mods.push(info.clone());
     }
-    Err(format!(
-        "Mod '{}' not found in Modrinth cache or API",
-        mod_name
-    ))
+    modrinth.cache.insert(cache_key, mods);
+}
+
+fn save_modrinth_cache() {
+    let modrinth = MODRINTH.lock().unwrap();
+    let _ = modrinth.cache.save_to_disk(&modrinth.cache_path);
 }
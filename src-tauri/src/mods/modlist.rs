@@ -0,0 +1,247 @@
+use super::{ModProvenance, ProviderKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModlistFormat {
+    Markdown,
+    Csv,
+    Html,
+}
+
+impl ModlistFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ModlistFormat::Markdown => "md",
+            ModlistFormat::Csv => "csv",
+            ModlistFormat::Html => "html",
+        }
+    }
+}
+
+/// One row of an exported modlist, mods that can't be resolved only have a file name
+#[derive(Debug, Clone, Default)]
+struct ModlistEntry {
+    file_name: String,
+    name: Option<String>,
+    version: Option<String>,
+    authors: Vec<String>,
+    url: Option<String>,
+    disabled: bool,
+}
+
+/// Writes a human-readable list of the installation's mods to the exports folder.
+/// Returns the path to the exported file.
+pub async fn export_modlist(
+    installation_id: &str,
+    format: ModlistFormat,
+) -> Result<String, String> {
+    let installation = crate::installations::get_installation(installation_id)
+        .await?
+        .ok_or_else(|| format!("Installation not found: {}", installation_id))?;
+    let mods_dir = installation.find_mods_dir()?;
+    let provenance = super::load_mod_provenance(&mods_dir)
+        .await
+        .unwrap_or_default();
+
+    let jars = {
+        let installation = installation.clone();
+        tokio::task::spawn_blocking(move || installation.get_mod_info())
+            .await
+            .map_err(|e| format!("Failed to read mods: {}", e))??
+    };
+
+    // Resolve all Modrinth mods with recorded provenance in one request
+    let modrinth_ids: Vec<String> = provenance
+        .values()
+        .filter(|p| p.provider == ProviderKind::Modrinth)
+        .map(|p| p.project_id.clone())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let projects: HashMap<String, super::ModrinthInfo> =
+        super::modrinth::get_projects(modrinth_ids)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| (p.project_id.clone(), p))
+            .collect();
+
+    let mut entries = Vec::with_capacity(jars.len());
+    for jar in jars {
+        let jar_provenance = provenance.get(&jar.file_name);
+        entries.push(resolve_entry(jar, jar_provenance, &projects).await);
+    }
+    entries.sort_by_key(|e| display_name(e).to_lowercase());
+
+    let content = match format {
+        ModlistFormat::Markdown => render_markdown(&installation.name, &entries),
+        ModlistFormat::Csv => render_csv(&entries),
+        ModlistFormat::Html => render_html(&installation.name, &entries),
+    };
+
+    let exports_dir = crate::get_minecraft_kable_dir()?.join("exports");
+    crate::ensure_folder(&exports_dir)
+        .await
+        .map_err(|e| format!("Failed to create exports directory: {}", e))?;
+    let export_path = exports_dir.join(format!(
+        "{}_modlist.{}",
+        installation.id,
+        format.extension()
+    ));
+    crate::write_file_atomic_async(&export_path, content.as_bytes()).await?;
+
+    crate::logging::Logger::info_global(
+        &format!(
+            "Exported modlist with {} mods to {}",
+            entries.len(),
+            export_path.display()
+        ),
+        Some(installation_id),
+    );
+    Ok(export_path.to_string_lossy().to_string())
+}
+
+async fn resolve_entry(
+    jar: crate::ModJarInfo,
+    provenance: Option<&ModProvenance>,
+    projects: &HashMap<String, super::ModrinthInfo>,
+) -> ModlistEntry {
+    let mut entry = ModlistEntry {
+        file_name: jar.file_name.clone(),
+        name: jar.mod_name.clone(),
+        version: jar.mod_version.clone(),
+        disabled: jar.disabled,
+        ..Default::default()
+    };
+
+    if let Some(provenance) = provenance {
+        if provenance.version_number.is_some() {
+            entry.version = provenance.version_number.clone();
+        }
+        match provenance.provider {
+            ProviderKind::Modrinth => {
+                if let Some(project) = projects.get(&provenance.project_id) {
+                    entry.name = Some(project.title.clone());
+                    entry.authors = vec![project.author.clone()];
+                    entry.url = Some(format!("https://modrinth.com/mod/{}", project.slug));
+                } else {
+                    entry.url = Some(format!(
+                        "https://modrinth.com/mod/{}",
+                        provenance.project_id
+                    ));
+                }
+            }
+            ProviderKind::CurseForge => {
+                entry.url = Some(format!(
+                    "https://www.curseforge.com/projects/{}",
+                    provenance.project_id
+                ));
+            }
+        }
+        return entry;
+    }
+
+    // Without provenance only mods that have a name in their jar can be looked up
+    if jar.mod_name.is_some() {
+        if let Ok(info) = super::get_extended_mod_info(jar).await {
+            entry.authors = info.authors;
+            entry.url = info.page_uri;
+        }
+    }
+    entry
+}
+
+fn display_name(entry: &ModlistEntry) -> &str {
+    entry.name.as_deref().unwrap_or(&entry.file_name)
+}
+
+fn render_markdown(installation_name: &str, entries: &[ModlistEntry]) -> String {
+    fn cell(value: &str) -> String {
+        value.replace('|', "\\|")
+    }
+
+    let mut out = format!(
+        "# {}\n\n{} mods\n\n| Mod | Version | Author | Link |\n| --- | --- | --- | --- |\n",
+        installation_name,
+        entries.len()
+    );
+    for entry in entries {
+        let mut name = cell(display_name(entry));
+        if entry.disabled {
+            name.push_str(" (disabled)");
+        }
+        let link = entry
+            .url
+            .as_deref()
+            .map(|url| format!("[link]({})", url))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            name,
+            cell(entry.version.as_deref().unwrap_or("")),
+            cell(&entry.authors.join(", ")),
+            link
+        ));
+    }
+    out
+}
+
+fn render_csv(entries: &[ModlistEntry]) -> String {
+    fn field(value: &str) -> String {
+        if value.contains([',', '"', '\n']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    let mut out = String::from("name,version,author,url,file_name,disabled\n");
+    for entry in entries {
+        out.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            field(display_name(entry)),
+            field(entry.version.as_deref().unwrap_or("")),
+            field(&entry.authors.join(", ")),
+            field(entry.url.as_deref().unwrap_or("")),
+            field(&entry.file_name),
+            entry.disabled
+        ));
+    }
+    out
+}
+
+fn render_html(installation_name: &str, entries: &[ModlistEntry]) -> String {
+    fn escape(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    let title = escape(installation_name);
+    let mut rows = String::new();
+    for entry in entries {
+        let name = escape(display_name(entry));
+        let name = match entry.url.as_deref() {
+            Some(url) => format!("<a href=\"{}\">{}</a>", escape(url), name),
+            None => name,
+        };
+        let disabled = if entry.disabled { " (disabled)" } else { "" };
+        rows.push_str(&format!(
+            "      <tr><td>{}{}</td><td>{}</td><td>{}</td></tr>\n",
+            name,
+            disabled,
+            escape(entry.version.as_deref().unwrap_or("")),
+            escape(&entry.authors.join(", "))
+        ));
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n  <meta charset=\"utf-8\">\n  <title>{title}</title>\n</head>\n<body>\n  <h1>{title}</h1>\n  <p>{count} mods</p>\n  <table>\n    <thead><tr><th>Mod</th><th>Version</th><th>Author</th></tr></thead>\n    <tbody>\n{rows}    </tbody>\n  </table>\n</body>\n</html>\n",
+        title = title,
+        count = entries.len(),
+        rows = rows
+    )
+}
@@ -183,6 +183,8 @@ pub struct ModerationMessage {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ModrinthVersion {
     pub id: String,
+    #[serde(default)]
+    pub project_id: String,
     pub name: String,
     pub version_number: String,
     pub changelog: Option<String>,
@@ -259,13 +261,14 @@ impl Default for ModrinthProvider {
     }
 }
 
-#[async_trait::async_trait]
-impl ModProvider for ModrinthProvider {
-    fn set_limit(&mut self, limit: usize) {
-        self.limit = limit;
-    }
-    #[log_result(log_values = true, max_length = 150)]
-    async fn get(&mut self, offset: usize) -> Result<Vec<ModInfoKind>, String> {
+impl ModrinthProvider {
+    /// Search results at `offset` and their total hits, from the cache while it is fresh.
+    /// The total is only None for cached pages when `need_total` is false.
+    async fn search(
+        &mut self,
+        offset: usize,
+        need_total: bool,
+    ) -> Result<(Vec<ModrinthInfo>, Option<usize>), String> {
         // Generate cache key from user filters + installation context
         let cache_key = format!(
             "offset:{}:index:{}:filters:{:?}:loader:{}:mc_version:{}",
@@ -278,20 +281,21 @@ impl ModProvider for ModrinthProvider {
         println!("[ModrinthProvider] Using cache key: {}", cache_key);
 
         if let Some(entry) = self.cache.get(&cache_key) {
-            if !self.cache.is_stale(&cache_key) {
+            let total_hits = search_total(&cache_key);
+            if self.cache.is_stale(&cache_key) {
+                println!(
+                    "[ModrinthProvider] Cache entry is stale for key: {}",
+                    cache_key
+                );
+            } else if total_hits.is_some() || !need_total {
                 println!(
                     "[ModrinthProvider] Returning cached results for key: {}",
                     cache_key
                 );
-                return Ok(entry
-                    .value
-                    .clone()
-                    .into_iter()
-                    .map(ModInfoKind::Modrinth)
-                    .collect());
+                return Ok((entry.value.clone(), total_hits));
             } else {
                 println!(
-                    "[ModrinthProvider] Cache entry is stale for key: {}",
+                    "[ModrinthProvider] Total hits unknown for cached key: {}",
                     cache_key
                 );
             }
@@ -303,7 +307,7 @@ impl ModProvider for ModrinthProvider {
         }
 
         // Build facets using the new method
-        let mods = if let Some(ref user_filters) = self.user_filters {
+        let (mods, total_hits) = if let Some(ref user_filters) = self.user_filters {
             println!("[ModrinthProvider] Making filtered API call with user filters");
             let facets =
                 user_filters.to_modrinth_facets(self.loader.as_deref(), self.mc_version.as_deref());
@@ -326,11 +330,35 @@ impl ModProvider for ModrinthProvider {
             println!("[ModrinthProvider] Making unfiltered API call");
             get_all_mods_with_index(offset, self.limit, self.index.as_deref()).await?
         };
+        record_search_total(&cache_key, total_hits);
         self.cache.insert(cache_key.clone(), mods.clone());
         let _ = self.cache.save_to_disk(&self.cache_path);
+        Ok((mods, Some(total_hits)))
+    }
+}
+
+#[async_trait::async_trait]
+impl ModProvider for ModrinthProvider {
+    fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+    }
+    #[log_result(log_values = true, max_length = 150)]
+    async fn get(&mut self, offset: usize) -> Result<Vec<ModInfoKind>, String> {
+        let (mods, _) = self.search(offset, false).await?;
         Ok(mods.into_iter().map(ModInfoKind::Modrinth).collect())
     }
 
+    async fn get_page(&mut self, offset: usize) -> Result<ModsPage, String> {
+        let (mods, total_hits) = self.search(offset, true).await?;
+        let items = mods.into_iter().map(ModInfoKind::Modrinth).collect();
+        Ok(ModsPage::new(
+            items,
+            total_hits.unwrap_or_default(),
+            offset,
+            self.limit,
+        ))
+    }
+
     fn filter(
         &mut self,
         installation: Option<&KableInstallation>,
@@ -430,7 +458,12 @@ impl ModProvider for ModrinthProvider {
                 let instance_id = &installation.id;
                 let temp_dir = crate::get_temp_dir(instance_id, mod_id)?;
                 crate::ensure_parent_dir_exists_async(&temp_dir).await?;
-                download_mod_file(&mrpack_file.url, &temp_dir.join(&mrpack_file.filename)).await?;
+                download_mod_file(
+                    &mrpack_file.url,
+                    &temp_dir.join(&mrpack_file.filename),
+                    &mrpack_file.hashes,
+                )
+                .await?;
                 // Do not save metadata or resolve dependencies for modpacks here
                 return Ok(());
             }
@@ -456,15 +489,16 @@ impl ModProvider for ModrinthProvider {
                 .ok_or("No mod file found")?;
 
             // Download the file to appropriate directory (active or disabled)
-            download_mod_file(&file.url, &download_dir.join(&file.filename)).await?;
+            download_mod_file(&file.url, &download_dir.join(&file.filename), &file.hashes).await?;
 
-            // Save metadata in the same directory as the mod file
+            // Record provenance in the mods root, disabled jars keep their entry
             save_mod_metadata(
-                &download_dir,
+                &mods_dir,
                 &file.filename,
                 mod_id,
                 &version.version_number,
                 &version.id,
+                Some(&file.url),
             )
             .await?;
 
@@ -536,15 +570,16 @@ impl ModProvider for ModrinthProvider {
             .ok_or("No mod file found")?;
 
         // Download the file to appropriate directory (active or disabled)
-        download_mod_file(&file.url, &download_dir.join(&file.filename)).await?;
+        download_mod_file(&file.url, &download_dir.join(&file.filename), &file.hashes).await?;
 
-        // Save metadata in the same directory as the mod file
+        // Record provenance in the mods root, disabled jars keep their entry
         save_mod_metadata(
-            &download_dir,
+            &mods_dir,
             &file.filename,
             mod_id,
             &version.version_number,
             &version.id,
+            Some(&file.url),
         )
         .await?;
 
@@ -575,7 +610,7 @@ pub async fn get_all_mods_with_index(
     offset: usize,
     limit: usize,
     index: Option<&str>,
-) -> Result<Vec<ModrinthInfo>, String> {
+) -> Result<(Vec<ModrinthInfo>, usize), String> {
     let client = Client::new();
     let mut url = format!(
         "https://api.modrinth.com/v2/search?limit={}&offset={}",
@@ -605,7 +640,7 @@ pub async fn get_all_mods_with_index(
         .filter_map(|hit| serde_json::from_value(hit.clone()).ok())
         .collect();
     println!("[ModrinthAPI] Received {} mods from API", mods.len());
-    Ok(mods)
+    Ok((mods, search_total_hits(&json)))
 }
 
 /// Fetch mods with properly structured facets array
@@ -616,7 +651,7 @@ pub async fn get_mods_with_facets(
     limit: usize,
     index: Option<&str>,
     query: Option<&str>,
-) -> Result<Vec<ModrinthInfo>, String> {
+) -> Result<(Vec<ModrinthInfo>, usize), String> {
     let client = Client::new();
     let mut url = format!(
         "https://api.modrinth.com/v2/search?limit={}&offset={}",
@@ -677,7 +712,14 @@ pub async fn get_mods_with_facets(
         "[ModrinthAPI] Received {} mods with facets from API",
         mods.len()
     );
-    Ok(mods)
+    Ok((mods, search_total_hits(&json)))
+}
+
+/// `total_hits` of a Modrinth search response
+fn search_total_hits(json: &serde_json::Value) -> usize {
+    json.get("total_hits")
+        .and_then(|v| v.as_u64())
+        .unwrap_or_default() as usize
 }
 
 /// Fetch mods by category, loader, and/or Minecraft version (with pagination and optional index)
@@ -802,6 +844,42 @@ pub async fn get_mod_versions(mod_id: &str) -> Result<Vec<ModrinthVersion>, Stri
     Ok(versions)
 }
 
+/// The version a file belongs to, looked up by the sha1 of the file.
+/// Ok(None) when Modrinth doesn't know the file. Answers are cached like version lists.
+pub async fn modrinth_version_by_hash(sha1: &str) -> Result<Option<ModrinthVersion>, String> {
+    let sha1 = sha1.trim().to_lowercase();
+    if sha1.len() != 40 || !sha1.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("'{}' is not a sha1 hash", sha1));
+    }
+    if let Some(cached) = get_cached_version_by_hash(&sha1) {
+        return Ok(cached);
+    }
+
+    let url = format!(
+        "https://api.modrinth.com/v2/version_file/{}?algorithm=sha1",
+        sha1
+    );
+    let resp = Client::new()
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Modrinth version by hash failed: {e}"))?;
+    let version = if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        None
+    } else {
+        let resp = resp
+            .error_for_status()
+            .map_err(|e| format!("Modrinth version by hash failed: {e}"))?;
+        Some(
+            resp.json::<ModrinthVersion>()
+                .await
+                .map_err(|e| format!("Modrinth version by hash parse failed: {e}"))?,
+        )
+    };
+    set_cached_version_by_hash(&sha1, version.clone());
+    Ok(version)
+}
+
 /// Get multiple projects by their IDs
 /// See: https://docs.modrinth.com/api/operations/getprojects/
 #[log_result]
@@ -844,6 +922,29 @@ pub async fn get_projects(project_ids: Vec<String>) -> Result<Vec<ModrinthInfo>,
     Ok(projects)
 }
 
+/// Get multiple versions by their IDs, unknown ids are left out
+/// See: https://docs.modrinth.com/api/operations/getversions/
+pub async fn get_versions_by_ids(version_ids: Vec<String>) -> Result<Vec<ModrinthVersion>, String> {
+    if version_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ids_param = serde_json::to_string(&version_ids)
+        .map_err(|e| format!("Failed to serialize version IDs: {e}"))?;
+    let url = format!(
+        "https://api.modrinth.com/v2/versions?ids={}",
+        urlencoding::encode(&ids_param)
+    );
+    Client::new()
+        .get(&url)
+        .send()
+        .await
+        .and_then(|resp| resp.error_for_status())
+        .map_err(|e| format!("Modrinth get versions failed: {e}"))?
+        .json::<Vec<ModrinthVersion>>()
+        .await
+        .map_err(|e| format!("Modrinth get versions parse failed: {e}"))
+}
+
 /// Get versions for a Modrinth project filtered by loaders and game versions
 /// See: https://docs.modrinth.com/api/operations/getprojectversions/
 pub async fn get_project_versions_filtered(
@@ -986,24 +1087,42 @@ fn compare_version_strings(a: &str, b: &str) -> std::cmp::Ordering {
     a_parts.len().cmp(&b_parts.len())
 }
 
-/// Download a mod file from Modrinth and save to the given path
+/// Download a mod file from Modrinth and save to the given path, checked against the sha1 and
+/// sha512 of `hashes` (a file's `hashes`) when present. A file that doesn't match is deleted.
+/// An interrupted download continues where it stopped on the next try.
 #[log_result]
-pub async fn download_mod_file(url: &str, save_path: &std::path::Path) -> Result<(), String> {
+pub async fn download_mod_file(
+    url: &str,
+    save_path: &std::path::Path,
+    hashes: &std::collections::HashMap<String, String>,
+) -> Result<(), String> {
     let client = Client::new();
-    let resp = client
-        .get(url)
-        .send()
+    let sha1 = hashes.get("sha1").map(String::as_str);
+    crate::launcher::utils::download_file_resumable(&client, url, save_path, sha1)
         .await
         .map_err(|e| format!("Modrinth download failed: {e}"))?;
-    let bytes = resp
-        .bytes()
-        .await
-        .map_err(|e| format!("Modrinth download bytes failed: {e}"))?;
-    if let Some(parent) = save_path.parent() {
-        crate::ensure_parent_dir_exists_async(parent).await?;
+    match hashes.get("sha512") {
+        Some(expected) => verify_sha512(save_path, expected).await,
+        None => Ok(()),
+    }
+}
+
+/// Removes a downloaded file whose sha512 isn't `expected`
+async fn verify_sha512(path: &std::path::Path, expected: &str) -> Result<(), String> {
+    let to_hash = path.to_path_buf();
+    let actual =
+        tokio::task::spawn_blocking(move || crate::launcher::utils::sha512_file_hex(&to_hash))
+            .await
+            .map_err(|e| format!("Hash task join error: {e}"))??;
+    if !actual.eq_ignore_ascii_case(expected) {
+        let _ = tokio::fs::remove_file(path).await;
+        return Err(format!(
+            "Modrinth download failed: SHA512 mismatch for {} (expected {}, got {})",
+            path.display(),
+            expected,
+            actual
+        ));
     }
-    // Atomically write downloaded bytes to save_path
-    crate::write_file_atomic_async(save_path, &bytes).await?;
     Ok(())
 }
 
@@ -1082,113 +1201,31 @@ async fn disable_old_mod_versions(
     mods_dir: &std::path::Path,
     project_id: &str,
 ) -> Result<bool, String> {
-    use tokio::fs;
-
-    let mut was_disabled = false;
-
-    // Check both active mods directory and disabled subdirectory
-    let dirs_to_check = vec![
-        (mods_dir.to_path_buf(), false),
-        (mods_dir.join("disabled"), true),
-    ];
-
-    for (dir, is_disabled_dir) in dirs_to_check {
-        if !dir.exists() {
-            continue;
-        }
-
-        // Read all files in the directory
-        let mut entries = fs::read_dir(&dir)
-            .await
-            .map_err(|e| format!("Failed to read mods directory: {}", e))?;
-
-        while let Some(entry) = entries
-            .next_entry()
-            .await
-            .map_err(|e| format!("Failed to read directory entry: {}", e))?
-        {
-            let path = entry.path();
-
-            // Check for .kable_metadata.json files
-            if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
-                if file_name.ends_with(".kable_metadata.json") {
-                    // Read the metadata file
-                    if let Ok(metadata_content) = fs::read_to_string(&path).await {
-                        if let Ok(metadata) =
-                            serde_json::from_str::<serde_json::Value>(&metadata_content)
-                        {
-                            // Check if this metadata belongs to the same project
-                            if let Some(stored_project_id) =
-                                metadata.get("project_id").and_then(|v| v.as_str())
-                            {
-                                if stored_project_id == project_id {
-                                    // Get the associated jar file name
-                                    if let Some(jar_file) =
-                                        metadata.get("file_name").and_then(|v| v.as_str())
-                                    {
-                                        let jar_path = dir.join(jar_file);
-
-                                        // Delete the old jar file completely
-                                        if jar_path.exists() {
-                                            fs::remove_file(&jar_path).await.map_err(|e| {
-                                                format!("Failed to remove old mod version: {}", e)
-                                            })?;
-
-                                            println!(
-                                                "[ModrinthProvider] Removed old version: {} from {} (project: {})",
-                                                jar_file,
-                                                if is_disabled_dir { "disabled" } else { "active" },
-                                                project_id
-                                            );
-
-                                            // Track if we removed from disabled folder
-                                            if is_disabled_dir {
-                                                was_disabled = true;
-                                            }
-                                        }
-
-                                        // Also remove the metadata file
-                                        let _ = fs::remove_file(&path).await;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    Ok(was_disabled)
+    super::remove_project_mods(mods_dir, project_id).await
 }
 
-/// Save metadata for a downloaded mod
+/// Record where a downloaded mod came from in the `.kable-mods.json` sidecar
 pub async fn save_mod_metadata(
     mods_dir: &std::path::Path,
     file_name: &str,
     project_id: &str,
     version_number: &str,
     modrinth_version_id: &str,
+    download_url: Option<&str>,
 ) -> Result<(), String> {
-    use tokio::fs;
-
-    let metadata = serde_json::json!({
-        "project_id": project_id,
-        "file_name": file_name,
-        "version_number": version_number,
-        "modrinth_version_id": modrinth_version_id,
-        "download_time": chrono::Utc::now().to_rfc3339(),
-    });
-
-    let metadata_path = mods_dir.join(format!("{}.kable_metadata.json", file_name));
-    let metadata_content = serde_json::to_string_pretty(&metadata)
-        .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
-
-    fs::write(&metadata_path, metadata_content)
-        .await
-        .map_err(|e| format!("Failed to write metadata file: {}", e))?;
-
-    Ok(())
+    super::record_mod_provenance(
+        mods_dir,
+        file_name,
+        super::ModProvenance {
+            provider: super::ProviderKind::Modrinth,
+            project_id: project_id.to_string(),
+            version_id: Some(modrinth_version_id.to_string()),
+            version_number: Some(version_number.to_string()),
+            download_url: download_url.map(str::to_string),
+            downloaded_at: chrono::Utc::now().to_rfc3339(),
+        },
+    )
+    .await
 }
 
 /// Get all dependencies for a project
@@ -1254,31 +1291,14 @@ pub async fn resolve_and_install_dependencies(
     // Get mods directory to check what's already installed
     let mods_dir: std::path::PathBuf = installation.find_mods_dir()?;
 
-    // Load installed mods metadata to check by project_id
-    let mut installed_project_ids = std::collections::HashSet::new();
-    if mods_dir.exists() {
-        if let Ok(entries) = std::fs::read_dir(&mods_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().map(|e| e == "json").unwrap_or(false)
-                    && path
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .map(|s| s.ends_with(".kable_metadata.json"))
-                        .unwrap_or(false)
-                {
-                    // Read metadata file
-                    if let Ok(content) = std::fs::read_to_string(&path) {
-                        if let Ok(metadata) =
-                            serde_json::from_str::<crate::mods::ModMetadata>(&content)
-                        {
-                            installed_project_ids.insert(metadata.project_id);
-                        }
-                    }
-                }
-            }
-        }
-    }
+    // Load installed mods provenance to check by project_id
+    let installed_project_ids: std::collections::HashSet<String> =
+        super::load_mod_provenance(&mods_dir)
+            .await
+            .unwrap_or_default()
+            .into_values()
+            .map(|p| p.project_id)
+            .collect();
 
     // Process each dependency project
     for dep_project in deps.projects {
@@ -1337,3 +1357,31 @@ pub async fn resolve_and_install_dependencies(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABC_SHA512: &str = "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f";
+
+    #[tokio::test]
+    async fn sha512_mismatch_removes_the_download() {
+        let dir = std::env::temp_dir().join(format!("kable-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let good = dir.join("good.jar");
+        let bad = dir.join("bad.jar");
+        std::fs::write(&good, "abc").unwrap();
+        std::fs::write(&bad, "abd").unwrap();
+
+        let good_result = verify_sha512(&good, &ABC_SHA512.to_uppercase()).await;
+        let bad_result = verify_sha512(&bad, ABC_SHA512).await;
+        let (good_kept, bad_kept) = (good.exists(), bad.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(good_result.is_ok(), "{:?}", good_result);
+        assert!(good_kept);
+        let error = bad_result.unwrap_err();
+        assert!(error.contains("SHA512 mismatch"), "{}", error);
+        assert!(!bad_kept);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::mods::cache::ModCache;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// Tags change with Modrinth releases, a day is fresh enough for filter dropdowns
+const TAG_CACHE_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Raw `/tag/{kind}` responses by kind
+static MODRINTH_TAGS_CACHE: Lazy<Mutex<ModCache<serde_json::Value>>> = Lazy::new(|| {
+    let cache = ModCache::load_from_disk(&tags_cache_path())
+        .unwrap_or_else(|_| ModCache::new(TAG_CACHE_TTL_SECS));
+    Mutex::new(cache)
+});
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModrinthCategory {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: String,
+    /// SVG markup
+    #[serde(default)]
+    pub icon: String,
+    /// 'mod' | 'shader' | 'resourcepack' | 'modpack' | ...
+    pub project_type: String,
+    /// Group of the category, e.g. "categories", "features", "resolutions"
+    #[serde(default)]
+    pub header: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModrinthLoaderTag {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: String,
+    /// SVG markup
+    #[serde(default)]
+    pub icon: String,
+    #[serde(default)]
+    pub supported_project_types: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModrinthGameVersionTag {
+    pub version: String,
+    /// 'release' | 'snapshot' | 'alpha' | 'beta'
+    pub version_type: String,
+    #[serde(default)]
+    pub date: String,
+    #[serde(default)]
+    pub major: bool,
+}
+
+fn tags_cache_path() -> PathBuf {
+    match crate::get_minecraft_kable_dir() {
+        Ok(dir) => dir.join("modrinth_tags_cache.json"),
+        Err(_) => PathBuf::from("modrinth_tags_cache.json"),
+    }
+}
+
+/// "data-pack" -> "Data Pack", the tag names are lowercase slugs
+fn display_name(name: &str) -> String {
+    name.split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// `/tag/{kind}` from the cache, fetched when the cached one is missing or stale.
+/// A stale copy is still used when Modrinth can't be reached.
+async fn fetch_tag<T: for<'de> Deserialize<'de>>(kind: &str) -> Result<Vec<T>, String> {
+    let cached = {
+        let cache = MODRINTH_TAGS_CACHE.lock().unwrap();
+        cache
+            .get(kind)
+            .map(|entry| (entry.value.clone(), cache.is_stale(kind)))
+    };
+    let value = match cached {
+        Some((value, false)) => value,
+        stale => {
+            let url = format!("https://api.modrinth.com/v2/tag/{}", kind);
+            let fetched = async {
+                Client::new()
+                    .get(&url)
+                    .send()
+                    .await
+                    .and_then(|resp| resp.error_for_status())
+                    .map_err(|e| format!("Modrinth tag {} request failed: {e}", kind))?
+                    .json::<serde_json::Value>()
+                    .await
+                    .map_err(|e| format!("Modrinth tag {} response read failed: {e}", kind))
+            }
+            .await;
+            match (fetched, stale) {
+                (Ok(value), _) => {
+                    let mut cache = MODRINTH_TAGS_CACHE.lock().unwrap();
+                    cache.insert(kind.to_string(), value.clone());
+                    let _ = cache.save_to_disk(&tags_cache_path());
+                    value
+                }
+                (Err(e), Some((stale, _))) => {
+                    crate::logging::Logger::warn_global(
+                        &format!("{}, using the cached tags", e),
+                        None,
+                    );
+                    stale
+                }
+                (Err(e), None) => return Err(e),
+            }
+        }
+    };
+    serde_json::from_value(value).map_err(|e| format!("Modrinth tag {} parse failed: {e}", kind))
+}
+
+/// Categories to filter by, only those of `project_type` when given (e.g. "mod" or "shader")
+pub async fn get_modrinth_categories(
+    project_type: Option<&str>,
+) -> Result<Vec<ModrinthCategory>, String> {
+    let mut categories: Vec<ModrinthCategory> = fetch_tag("category").await?;
+    categories.retain(|c| project_type.is_none_or(|t| c.project_type == t));
+    for category in &mut categories {
+        category.display_name = display_name(&category.name);
+    }
+    Ok(categories)
+}
+
+/// Loaders to filter by, only those supporting `project_type` when given
+pub async fn get_modrinth_loaders(
+    project_type: Option<&str>,
+) -> Result<Vec<ModrinthLoaderTag>, String> {
+    let mut loaders: Vec<ModrinthLoaderTag> = fetch_tag("loader").await?;
+    loaders
+        .retain(|l| project_type.is_none_or(|t| l.supported_project_types.iter().any(|s| s == t)));
+    for loader in &mut loaders {
+        loader.display_name = display_name(&loader.name);
+    }
+    Ok(loaders)
+}
+
+/// Game versions to filter by, newest first, releases only unless `include_snapshots`
+pub async fn get_modrinth_game_versions(
+    include_snapshots: bool,
+) -> Result<Vec<ModrinthGameVersionTag>, String> {
+    let mut versions: Vec<ModrinthGameVersionTag> = fetch_tag("game_version").await?;
+    versions.retain(|v| include_snapshots || v.version_type == "release");
+    Ok(versions)
+}
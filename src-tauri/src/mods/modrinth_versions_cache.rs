@@ -47,3 +47,32 @@ pub fn clear_versions_cache() {
     };
     let _ = cache.save_to_disk(&cache_path);
 }
+
+/// sha1 -> version the file belongs to, None for files Modrinth doesn't know
+static MODRINTH_HASH_CACHE: Lazy<Mutex<ModCache<Option<ModrinthVersion>>>> = Lazy::new(|| {
+    let cache = ModCache::load_from_disk(&hash_cache_path())
+        .unwrap_or_else(|_| ModCache::new(VERSION_CACHE_TTL_SECS));
+    Mutex::new(cache)
+});
+
+fn hash_cache_path() -> PathBuf {
+    match crate::get_minecraft_kable_dir() {
+        Ok(dir) => dir.join("modrinth_hash_cache.json"),
+        Err(_) => PathBuf::from("modrinth_hash_cache.json"),
+    }
+}
+
+/// Outer None when the hash wasn't looked up recently, inner None when Modrinth didn't know it
+pub fn get_cached_version_by_hash(sha1: &str) -> Option<Option<ModrinthVersion>> {
+    let cache = MODRINTH_HASH_CACHE.lock().unwrap();
+    match cache.get(sha1) {
+        Some(entry) if !cache.is_stale(sha1) => Some(entry.value.clone()),
+        _ => None,
+    }
+}
+
+pub fn set_cached_version_by_hash(sha1: &str, version: Option<ModrinthVersion>) {
+    let mut cache = MODRINTH_HASH_CACHE.lock().unwrap();
+    cache.insert(sha1.to_string(), version);
+    let _ = cache.save_to_disk(&hash_cache_path());
+}
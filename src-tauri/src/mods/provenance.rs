@@ -0,0 +1,265 @@
+use super::ProviderKind;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Sidecar in the root of a mods folder recording where each jar came from, keyed by file name.
+/// Jars in `disabled/` keep their file name, so they share the entry.
+pub const MOD_PROVENANCE_FILE: &str = ".kable-mods.json";
+
+// Per-jar metadata written by older versions, still read so existing installs keep working
+const LEGACY_METADATA_SUFFIX: &str = ".kable_metadata.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModProvenance {
+    pub provider: ProviderKind,
+    pub project_id: String,
+    /// Modrinth version id or CurseForge file id
+    pub version_id: Option<String>,
+    #[serde(default)]
+    pub version_number: Option<String>,
+    #[serde(default)]
+    pub download_url: Option<String>,
+    pub downloaded_at: String,
+}
+
+pub type ModProvenanceMap = BTreeMap<String, ModProvenance>;
+
+// Serializes read-modify-write cycles on the sidecar, one lock per mods folder
+static MOD_PROVENANCE_LOCKS: Lazy<Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn mod_provenance_lock(mods_dir: &Path) -> Arc<tokio::sync::Mutex<()>> {
+    MOD_PROVENANCE_LOCKS
+        .lock()
+        .unwrap()
+        .entry(mods_dir.to_path_buf())
+        .or_default()
+        .clone()
+}
+
+/// Drops the lock of a mods folder once no other update holds or waits for it
+fn release_mod_provenance_lock(mods_dir: &Path, lock: Arc<tokio::sync::Mutex<()>>) {
+    let mut locks = MOD_PROVENANCE_LOCKS.lock().unwrap();
+    // One reference is the map's, the other this update's
+    if Arc::strong_count(&lock) == 2 {
+        locks.remove(mods_dir);
+    }
+}
+
+/// Reads the sidecar of a mods folder, an absent file is an empty map
+pub async fn read_mod_provenance_file(mods_dir: &Path) -> Result<ModProvenanceMap, String> {
+    let path = mods_dir.join(MOD_PROVENANCE_FILE);
+    if !path.exists() {
+        return Ok(ModProvenanceMap::new());
+    }
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", MOD_PROVENANCE_FILE, e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", MOD_PROVENANCE_FILE, e))
+}
+
+async fn write_mod_provenance_file(mods_dir: &Path, map: &ModProvenanceMap) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(map)
+        .map_err(|e| format!("Failed to serialize {}: {}", MOD_PROVENANCE_FILE, e))?;
+    crate::write_file_atomic_async(&mods_dir.join(MOD_PROVENANCE_FILE), content.as_bytes()).await
+}
+
+/// Provenance of every mod in the folder: the sidecar, completed with legacy per-jar
+/// metadata files (active and disabled) for jars the sidecar doesn't know yet.
+/// Entries of jars that were deleted outside of Kable are left out.
+pub async fn load_mod_provenance(mods_dir: &Path) -> Result<ModProvenanceMap, String> {
+    let mut map = read_mod_provenance_file(mods_dir).await?;
+    map.retain(|file_name, _| {
+        mods_dir.join(file_name).exists() || mods_dir.join("disabled").join(file_name).exists()
+    });
+
+    for dir in [mods_dir.to_path_buf(), mods_dir.join("disabled")] {
+        let Ok(mut entries) = tokio::fs::read_dir(&dir).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            let Some(file_name) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_suffix(LEGACY_METADATA_SUFFIX))
+            else {
+                continue;
+            };
+            if map.contains_key(file_name) {
+                continue;
+            }
+            let Ok(content) = tokio::fs::read_to_string(&path).await else {
+                continue;
+            };
+            if let Ok(legacy) = serde_json::from_str::<super::ModMetadata>(&content) {
+                map.insert(file_name.to_string(), from_legacy(legacy));
+            }
+        }
+    }
+    Ok(map)
+}
+
+/// Provenance of a single jar, None when it wasn't downloaded through Kable
+pub async fn find_mod_provenance(
+    mods_dir: &Path,
+    file_name: &str,
+) -> Result<Option<ModProvenance>, String> {
+    Ok(load_mod_provenance(mods_dir).await?.remove(file_name))
+}
+
+/// Records where a jar came from, replacing an older record for the same file name
+pub async fn record_mod_provenance(
+    mods_dir: &Path,
+    file_name: &str,
+    provenance: ModProvenance,
+) -> Result<(), String> {
+    let lock = mod_provenance_lock(mods_dir);
+    let result: Result<(), String> = async {
+        let _guard = lock.lock().await;
+        let mut map = read_mod_provenance_file(mods_dir).await?;
+        map.insert(file_name.to_string(), provenance);
+        write_mod_provenance_file(mods_dir, &map).await
+    }
+    .await;
+    release_mod_provenance_lock(mods_dir, lock);
+    result
+}
+
+/// Drops the records (and legacy metadata files) of jars that were removed
+pub async fn forget_mod_provenance(mods_dir: &Path, file_names: &[String]) -> Result<(), String> {
+    if file_names.is_empty() {
+        return Ok(());
+    }
+    for file_name in file_names {
+        for dir in [mods_dir.to_path_buf(), mods_dir.join("disabled")] {
+            let legacy = dir.join(format!("{}{}", file_name, LEGACY_METADATA_SUFFIX));
+            let _ = tokio::fs::remove_file(legacy).await;
+        }
+    }
+    let lock = mod_provenance_lock(mods_dir);
+    let result: Result<(), String> = async {
+        let _guard = lock.lock().await;
+        let mut map = read_mod_provenance_file(mods_dir).await?;
+        let before = map.len();
+        map.retain(|name, _| !file_names.contains(name));
+        if map.len() != before {
+            write_mod_provenance_file(mods_dir, &map).await?;
+        }
+        Ok(())
+    }
+    .await;
+    release_mod_provenance_lock(mods_dir, lock);
+    result
+}
+
+/// Removes every jar of a project (active or disabled) before another version is installed.
+/// Returns true if one of them was disabled, so the new version can stay disabled too.
+pub async fn remove_project_mods(mods_dir: &Path, project_id: &str) -> Result<bool, String> {
+    let map = load_mod_provenance(mods_dir).await?;
+    let mut was_disabled = false;
+    let mut removed = Vec::new();
+
+    for (file_name, provenance) in map.iter().filter(|(_, p)| p.project_id == project_id) {
+        for (dir, disabled) in [
+            (mods_dir.to_path_buf(), false),
+            (mods_dir.join("disabled"), true),
+        ] {
+            let jar_path = dir.join(file_name);
+            if !jar_path.exists() {
+                continue;
+            }
+            tokio::fs::remove_file(&jar_path)
+                .await
+                .map_err(|e| format!("Failed to remove old mod version: {}", e))?;
+            crate::logging::debug(&format!(
+                "Removed old version {} of project {} ({:?})",
+                file_name, project_id, provenance.provider
+            ));
+            was_disabled |= disabled;
+        }
+        removed.push(file_name.clone());
+    }
+
+    forget_mod_provenance(mods_dir, &removed).await?;
+    Ok(was_disabled)
+}
+
+fn from_legacy(legacy: super::ModMetadata) -> ModProvenance {
+    // Only the Modrinth provider stored a version id, CurseForge project ids are numeric
+    let provider = if legacy.modrinth_version_id.is_some()
+        || !legacy.project_id.chars().all(|c| c.is_ascii_digit())
+    {
+        ProviderKind::Modrinth
+    } else {
+        ProviderKind::CurseForge
+    };
+    ModProvenance {
+        provider,
+        project_id: legacy.project_id,
+        version_id: legacy.modrinth_version_id,
+        version_number: Some(legacy.version_number),
+        download_url: None,
+        downloaded_at: legacy.download_time,
+    }
+}
+
+impl ModProvenance {
+    /// The shape `get_mod_metadata` has always returned to the frontend
+    pub fn to_metadata(&self, file_name: &str) -> super::ModMetadata {
+        super::ModMetadata {
+            project_id: self.project_id.clone(),
+            file_name: file_name.to_string(),
+            version_number: self
+                .version_number
+                .clone()
+                .or_else(|| self.version_id.clone())
+                .unwrap_or_default(),
+            modrinth_version_id: match self.provider {
+                ProviderKind::Modrinth => self.version_id.clone(),
+                ProviderKind::CurseForge => None,
+            },
+            download_time: self.downloaded_at.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_records_are_all_kept() {
+        let dir = std::env::temp_dir().join(format!("kable-test-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+
+        let tasks: Vec<_> = (0..16)
+            .map(|i| {
+                let dir = dir.clone();
+                tokio::spawn(async move {
+                    let provenance = ModProvenance {
+                        provider: ProviderKind::Modrinth,
+                        project_id: format!("project-{}", i),
+                        version_id: Some(format!("version-{}", i)),
+                        version_number: None,
+                        download_url: None,
+                        downloaded_at: chrono::Utc::now().to_rfc3339(),
+                    };
+                    record_mod_provenance(&dir, &format!("mod-{}.jar", i), provenance).await
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+        let map = read_mod_provenance_file(&dir).await.unwrap();
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+
+        assert_eq!(map.len(), 16);
+        assert!(!MOD_PROVENANCE_LOCKS.lock().unwrap().contains_key(&dir));
+    }
+}
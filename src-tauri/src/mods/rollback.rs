@@ -0,0 +1,143 @@
+use super::{curseforge, modrinth, ModProvenance, ProviderKind};
+use std::collections::HashMap;
+use tauri::Emitter;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ModRollbackProgress {
+    pub installation_id: String,
+    pub file_name: String,
+    /// 'resolving' | 'downloading' | 'replacing' | 'done'
+    pub stage: String,
+}
+
+/// Replaces a mod with an older (or any other) version of the same project.
+/// The mod has to have recorded provenance, a disabled mod stays disabled.
+/// Returns the file name of the installed version.
+pub async fn rollback_mod(
+    installation_id: &str,
+    file_name: &str,
+    version_id: &str,
+) -> Result<String, String> {
+    let installation = crate::installations::get_installation(installation_id)
+        .await?
+        .ok_or_else(|| format!("Installation not found: {}", installation_id))?;
+    let mods_dir = installation.find_mods_dir()?;
+
+    let provenance = super::find_mod_provenance(&mods_dir, file_name)
+        .await?
+        .ok_or_else(|| {
+            format!(
+                "No provenance recorded for {}, it can't be rolled back",
+                file_name
+            )
+        })?;
+
+    let disabled = !mods_dir.join(file_name).exists();
+    let target_dir = if disabled {
+        mods_dir.join("disabled")
+    } else {
+        mods_dir.clone()
+    };
+    if !target_dir.join(file_name).exists() {
+        return Err(format!("Mod file not found: {}", file_name));
+    }
+
+    emit_progress(installation_id, file_name, "resolving");
+    let (url, new_file_name, version_number, hashes) =
+        resolve_version_file(&provenance, version_id).await?;
+
+    // Download next to the old jar first, so a failed download leaves the mod untouched
+    emit_progress(installation_id, file_name, "downloading");
+    modrinth::download_mod_file(&url, &target_dir.join(&new_file_name), &hashes).await?;
+
+    emit_progress(installation_id, file_name, "replacing");
+    if new_file_name != file_name {
+        tokio::fs::remove_file(target_dir.join(file_name))
+            .await
+            .map_err(|e| format!("Failed to remove {}: {}", file_name, e))?;
+        super::forget_mod_provenance(&mods_dir, &[file_name.to_string()]).await?;
+    }
+    super::record_mod_provenance(
+        &mods_dir,
+        &new_file_name,
+        ModProvenance {
+            provider: provenance.provider,
+            project_id: provenance.project_id.clone(),
+            version_id: Some(version_id.to_string()),
+            version_number: Some(version_number),
+            download_url: Some(url),
+            downloaded_at: chrono::Utc::now().to_rfc3339(),
+        },
+    )
+    .await?;
+
+    crate::logging::Logger::info_global(
+        &format!(
+            "Rolled back {} to {} ({})",
+            file_name, new_file_name, version_id
+        ),
+        Some(installation_id),
+    );
+    emit_progress(installation_id, file_name, "done");
+    Ok(new_file_name)
+}
+
+/// Download url, file name, version number and Modrinth hashes (none for CurseForge) of a
+/// version of the provenance's project
+async fn resolve_version_file(
+    provenance: &ModProvenance,
+    version_id: &str,
+) -> Result<(String, String, String, HashMap<String, String>), String> {
+    match provenance.provider {
+        ProviderKind::Modrinth => {
+            let version = modrinth::get_mod_versions(&provenance.project_id)
+                .await?
+                .into_iter()
+                .find(|v| v.id == version_id)
+                .ok_or_else(|| format!("Version {} not found for this mod", version_id))?;
+            let file = version
+                .files
+                .iter()
+                .find(|f| f.primary)
+                .or_else(|| version.files.first())
+                .ok_or("No mod file found")?;
+            Ok((
+                file.url.clone(),
+                file.filename.clone(),
+                version.version_number.clone(),
+                file.hashes.clone(),
+            ))
+        }
+        ProviderKind::CurseForge => {
+            let mod_id: u32 = provenance
+                .project_id
+                .parse()
+                .map_err(|_| format!("Invalid mod ID: {}", provenance.project_id))?;
+            let file_id: u32 = version_id
+                .parse()
+                .map_err(|_| format!("Invalid version ID: {}", version_id))?;
+            let file = curseforge::get_mod_files(mod_id)
+                .await?
+                .into_iter()
+                .find(|f| f.id == file_id)
+                .ok_or_else(|| format!("Version {} not found for this mod", version_id))?;
+            let url = curseforge::get_mod_file_download_url(mod_id, file_id).await?;
+            Ok((url, file.file_name, file.display_name, HashMap::new()))
+        }
+    }
+}
+
+fn emit_progress(installation_id: &str, file_name: &str, stage: &str) {
+    if let Ok(handle_guard) = crate::logging::GLOBAL_APP_HANDLE.lock() {
+        if let Some(app_handle) = handle_guard.as_ref() {
+            let _ = app_handle.emit(
+                "mod-rollback-progress",
+                ModRollbackProgress {
+                    installation_id: installation_id.to_string(),
+                    file_name: file_name.to_string(),
+                    stage: stage.to_string(),
+                },
+            );
+        }
+    }
+}
@@ -35,6 +35,11 @@ pub struct GeneralSettings {
     /// 'restart' | 'open_logs' | 'open_home' | 'exit' | 'minimize' | 'ask'
     #[serde(default = "default_on_game_crash")]
     pub on_game_crash: String,
+    /// Exit codes treated as a normal close instead of a crash.
+    /// Defaults to 0, 130 (Ctrl+C) and 143 (SIGTERM); add codes here if a mod or JVM
+    /// configuration exits with a benign non-zero code.
+    #[serde(default = "default_clean_exit_codes")]
+    pub clean_exit_codes: Vec<i32>,
     /// 'keep_open' | 'exit' | 'open_logs' | 'minimize' | 'ask'
     #[serde(default = "default_on_game_launch")]
     pub on_game_launch: String,
@@ -58,6 +63,10 @@ fn default_on_game_crash() -> String {
     "open_logs".to_string()
 }
 
+fn default_clean_exit_codes() -> Vec<i32> {
+    vec![0, 130, 143]
+}
+
 fn default_on_game_launch() -> String {
     "open_logs".to_string()
 }
@@ -81,6 +90,7 @@ impl Default for GeneralSettings {
             game_directory: None,
             on_game_close: default_on_game_close(),
             on_game_crash: default_on_game_crash(),
+            clean_exit_codes: default_clean_exit_codes(),
             on_game_launch: default_on_game_launch(),
             auto_update_launcher: default_auto_update(),
             show_ads: false,
@@ -213,6 +223,9 @@ pub struct LoggingSettings {
     pub dedupe_window_size: Option<u32>,
     #[serde(default = "default_enable_dedupe")]
     pub enable_dedupe: Option<bool>,
+    /// Game logs (one per run) kept per installation, older ones are removed after a run
+    #[serde(default = "default_game_logs_to_keep")]
+    pub game_logs_to_keep: u32,
 }
 
 fn default_show_logs_in_nav() -> bool {
@@ -247,6 +260,10 @@ fn default_enable_dedupe() -> Option<bool> {
     Some(true)
 }
 
+fn default_game_logs_to_keep() -> u32 {
+    5
+}
+
 impl Default for LoggingSettings {
     fn default() -> Self {
         Self {
@@ -260,6 +277,7 @@ impl Default for LoggingSettings {
             max_memory_logs: Some(5000),
             dedupe_window_size: Some(50),
             enable_dedupe: Some(true),
+            game_logs_to_keep: default_game_logs_to_keep(),
         }
     }
 }
@@ -306,12 +324,57 @@ pub struct ContentSettings {
     pub use_per_installation_mods_folder: bool,
     #[serde(default)]
     pub use_per_installation_resource_packs: bool,
+    /// Remove leftover Kable-created symlinks (from crashes) when the launcher starts
+    #[serde(default = "default_cleanup_symlinks_on_startup")]
+    pub cleanup_symlinks_on_startup: bool,
+    /// How dedicated folders are linked when symlinks aren't allowed (Windows without Developer
+    /// Mode): 'auto' (junctions for folders, copies for files) | 'copy' | 'none'
+    #[serde(default = "default_link_fallback")]
+    pub link_fallback: String,
+    /// How a dedicated config folder is used at launch: 'replace' links it in place of
+    /// .minecraft/config | 'overlay' copies its files over the global configs, so configs it
+    /// doesn't have stay shared
+    #[serde(default = "default_config_folder_mode")]
+    pub config_folder_mode: String,
+    /// Load the first mod browser page in the background after startup
+    #[serde(default = "default_prefetch_featured_mods")]
+    pub prefetch_featured_mods: bool,
+    /// What the mod browser showed last, restored into the providers at startup
+    #[serde(default)]
+    pub mod_browser: ModBrowserState,
+}
+
+/// Last used provider, filter and page size of the mod browser
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModBrowserState {
+    #[serde(default)]
+    pub provider: Option<crate::mods::ProviderKind>,
+    #[serde(default)]
+    pub filter: Option<crate::mods::ModFilter>,
+    #[serde(default)]
+    pub limit: Option<usize>,
 }
 
 fn default_max_world_backups() -> serde_json::Value {
     serde_json::json!(5)
 }
 
+fn default_cleanup_symlinks_on_startup() -> bool {
+    true
+}
+
+fn default_prefetch_featured_mods() -> bool {
+    true
+}
+
+fn default_link_fallback() -> String {
+    "auto".to_string()
+}
+
+fn default_config_folder_mode() -> String {
+    "replace".to_string()
+}
+
 impl Default for ContentSettings {
     fn default() -> Self {
         Self {
@@ -319,6 +382,11 @@ impl Default for ContentSettings {
             auto_backup_worlds: false,
             use_per_installation_mods_folder: false,
             use_per_installation_resource_packs: false,
+            cleanup_symlinks_on_startup: true,
+            link_fallback: default_link_fallback(),
+            config_folder_mode: default_config_folder_mode(),
+            prefetch_featured_mods: true,
+            mod_browser: ModBrowserState::default(),
         }
     }
 }
@@ -337,6 +405,17 @@ pub struct AdvancedSettings {
     pub show_advanced_page: bool,
     #[serde(default)]
     pub check_nightly_updates: bool,
+    /// How many launches may download/prepare at the same time, others wait in the launch queue.
+    /// Running games are not limited by this.
+    #[serde(default = "default_concurrent_launch_prepares")]
+    pub concurrent_launch_prepares: u32,
+    /// Launching an installation that is already running: 'refuse' | 'warn' | 'allow'.
+    /// Both games would share the installation's natives and configs.
+    #[serde(default = "default_duplicate_launch")]
+    pub duplicate_launch: String,
+    /// Apply installation parameters that override auth or classpath variables instead of ignoring them
+    #[serde(default)]
+    pub allow_protected_parameter_overrides: bool,
     #[serde(default)]
     pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -349,6 +428,14 @@ fn default_auto_save_interval() -> u32 {
     30
 }
 
+fn default_concurrent_launch_prepares() -> u32 {
+    1
+}
+
+fn default_duplicate_launch() -> String {
+    "refuse".to_string()
+}
+
 impl Default for AdvancedSettings {
     fn default() -> Self {
         Self {
@@ -358,6 +445,9 @@ impl Default for AdvancedSettings {
             auto_save_interval: 30,
             show_advanced_page: false,
             check_nightly_updates: false,
+            concurrent_launch_prepares: default_concurrent_launch_prepares(),
+            duplicate_launch: default_duplicate_launch(),
+            allow_protected_parameter_overrides: false,
             extra: serde_json::Map::new(),
         }
     }
@@ -369,6 +459,50 @@ pub struct MiscSettings {
     pub use_titlebar: bool,
     #[serde(default = "default_auth_preference")]
     pub auth_preference: String, // 'code' | 'device_code'
+    #[serde(default)]
+    pub discord_presence: DiscordPresenceTemplates,
+}
+
+/// Texts shown in Discord Rich Presence, `{name}` variables are filled in when shown
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiscordPresenceTemplates {
+    /// Variables: {installation}, {version}, {loader}
+    #[serde(default = "default_playing_details")]
+    pub playing_details: String,
+    #[serde(default = "default_playing_state")]
+    pub playing_state: String,
+    /// Variables: {section}, e.g. "Browsing Mods"
+    #[serde(default = "default_browsing_details")]
+    pub browsing_details: String,
+    #[serde(default = "default_browsing_state")]
+    pub browsing_state: String,
+}
+
+fn default_playing_details() -> String {
+    "Playing Minecraft {version}".to_string()
+}
+
+fn default_playing_state() -> String {
+    "{installation} • {loader}".to_string()
+}
+
+fn default_browsing_details() -> String {
+    "In Launcher".to_string()
+}
+
+fn default_browsing_state() -> String {
+    "{section}".to_string()
+}
+
+impl Default for DiscordPresenceTemplates {
+    fn default() -> Self {
+        Self {
+            playing_details: default_playing_details(),
+            playing_state: default_playing_state(),
+            browsing_details: default_browsing_details(),
+            browsing_state: default_browsing_state(),
+        }
+    }
 }
 
 fn default_use_titlebar() -> bool {
@@ -384,6 +518,7 @@ impl Default for MiscSettings {
         Self {
             use_titlebar: true,
             auth_preference: "code".to_string(),
+            discord_presence: DiscordPresenceTemplates::default(),
         }
     }
 }
@@ -396,6 +531,7 @@ impl Default for CategorizedLauncherSettings {
                 game_directory: None,
                 on_game_close: "open_home".to_string(),
                 on_game_crash: "open_logs".to_string(),
+                clean_exit_codes: vec![0, 130, 143],
                 on_game_launch: "open_logs".to_string(),
                 auto_update_launcher: true,
                 show_ads: false,
@@ -430,6 +566,7 @@ impl Default for CategorizedLauncherSettings {
                 max_memory_logs: Some(5000),
                 dedupe_window_size: Some(50),
                 enable_dedupe: Some(true),
+                game_logs_to_keep: default_game_logs_to_keep(),
             },
             network: NetworkSettings {
                 parallel_downloads: 3,
@@ -441,6 +578,11 @@ impl Default for CategorizedLauncherSettings {
                 auto_backup_worlds: false,
                 use_per_installation_mods_folder: false,
                 use_per_installation_resource_packs: false,
+                cleanup_symlinks_on_startup: true,
+                link_fallback: default_link_fallback(),
+                config_folder_mode: default_config_folder_mode(),
+                prefetch_featured_mods: true,
+                mod_browser: ModBrowserState::default(),
             },
             advanced: AdvancedSettings {
                 enable_experimental_features: false,
@@ -449,11 +591,15 @@ impl Default for CategorizedLauncherSettings {
                 auto_save_interval: 30, // in seconds, 0 means no auto save
                 show_advanced_page: false,
                 check_nightly_updates: false,
+                concurrent_launch_prepares: 1,
+                duplicate_launch: default_duplicate_launch(),
+                allow_protected_parameter_overrides: false,
                 extra: serde_json::Map::new(),
             },
             misc: MiscSettings {
                 use_titlebar: true,
                 auth_preference: "code".to_string(),
+                discord_presence: DiscordPresenceTemplates::default(),
             },
         }
     }
@@ -505,12 +651,282 @@ pub async fn save_settings(settings: CategorizedLauncherSettings) -> Result<(),
     Ok(())
 }
 
-// Public tauri command wrapper so the generate_handler macro can reference
-// a stable command symbol. This simply forwards to the async save_settings
-// implementation.
+/// A problem with one settings field, `field` is the dotted path e.g. "advanced.default_memory"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsValidationError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Result of validating settings. Errors block saving, warnings are only reported.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SettingsValidation {
+    pub errors: Vec<SettingsValidationError>,
+    pub warnings: Vec<SettingsValidationError>,
+}
+
+impl SettingsValidation {
+    fn error(&mut self, field: &str, message: impl Into<String>) {
+        self.errors.push(SettingsValidationError {
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn warning(&mut self, field: &str, message: impl Into<String>) {
+        self.warnings.push(SettingsValidationError {
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn check_choice(&mut self, field: &str, value: &str, allowed: &[&str]) {
+        if !allowed.contains(&value) {
+            self.error(
+                field,
+                format!("'{}' is not one of: {}", value, allowed.join(", ")),
+            );
+        }
+    }
+
+    /// For the JSON values that hold either a number or a keyword like "disabled"
+    fn check_number_or(&mut self, field: &str, value: &serde_json::Value, keyword: &str) {
+        let valid = match value {
+            serde_json::Value::Number(n) => n.as_f64().map(|n| n >= 0.0).unwrap_or(false),
+            serde_json::Value::String(s) => s == keyword,
+            _ => false,
+        };
+        if !valid {
+            self.error(
+                field,
+                format!("Must be a non-negative number or \"{}\"", keyword),
+            );
+        }
+    }
+}
+
+// Memory below this can't start any recent Minecraft version
+const MIN_MEMORY_MB: u32 = 512;
+// Higher launch concurrency mostly just competes for bandwidth and disk
+const MAX_CONCURRENT_LAUNCH_PREPARES: u32 = 8;
+
+/// Checks settings field by field before they are persisted
+pub fn validate_settings(settings: &CategorizedLauncherSettings) -> SettingsValidation {
+    let mut validation = SettingsValidation::default();
+
+    let general = &settings.general;
+    if let Some(java_path) = general
+        .java_path
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+    {
+        let path = PathBuf::from(java_path);
+        if !path.exists() {
+            validation.error("general.java_path", "Java executable does not exist");
+        } else if path.is_dir() {
+            validation.error(
+                "general.java_path",
+                "Must point to the java executable, not a folder",
+            );
+        }
+    }
+    if let Some(game_dir) = general
+        .game_directory
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+    {
+        let path = PathBuf::from(game_dir);
+        if !path.is_dir() {
+            validation.error("general.game_directory", "Directory does not exist");
+        } else if !path.join("versions").is_dir() {
+            validation.warning(
+                "general.game_directory",
+                "No versions folder found, this may not be a Minecraft directory",
+            );
+        }
+    }
+    validation.check_choice(
+        "general.on_game_close",
+        &general.on_game_close,
+        &["open_logs", "open_home", "exit", "minimize", "ask"],
+    );
+    validation.check_choice(
+        "general.on_game_crash",
+        &general.on_game_crash,
+        &[
+            "restart",
+            "open_logs",
+            "open_home",
+            "exit",
+            "minimize",
+            "ask",
+        ],
+    );
+    validation.check_choice(
+        "general.on_game_launch",
+        &general.on_game_launch,
+        &["keep_open", "exit", "open_logs", "minimize", "ask"],
+    );
+    validation.check_choice(
+        "general.update_mode",
+        &general.update_mode,
+        &["instant", "on_restart", "on_confirm"],
+    );
+    validation.check_choice(
+        "general.update_notification_style",
+        &general.update_notification_style,
+        &["modal", "notification"],
+    );
+    if !general.clean_exit_codes.contains(&0) {
+        validation.warning(
+            "general.clean_exit_codes",
+            "Exit code 0 is missing, every normal close will be treated as a crash",
+        );
+    }
+
+    let logging = &settings.logging;
+    validation.check_number_or(
+        "logging.log_file_size_limit_mb",
+        &logging.log_file_size_limit_mb,
+        "disabled",
+    );
+    validation.check_number_or(
+        "logging.log_retention_days",
+        &logging.log_retention_days,
+        "disabled",
+    );
+    if logging.game_logs_to_keep == 0 {
+        validation.error(
+            "logging.game_logs_to_keep",
+            "Must be at least 1, the log of the last run is always kept",
+        );
+    }
+    for level in &logging.default_log_levels {
+        validation.check_choice(
+            "logging.default_log_levels",
+            level,
+            &["debug", "info", "warn", "error"],
+        );
+    }
+
+    let network = &settings.network;
+    if network.parallel_downloads == 0 {
+        validation.error("network.parallel_downloads", "Must be at least 1");
+    }
+    if network.connection_timeout == 0 {
+        validation.error("network.connection_timeout", "Must be at least 1 second");
+    }
+    validation.check_number_or(
+        "network.download_speed_limit",
+        &network.download_speed_limit,
+        "unlimited",
+    );
+
+    validation.check_number_or(
+        "content.max_world_backups",
+        &settings.content.max_world_backups,
+        "disabled",
+    );
+    validation.check_choice(
+        "content.link_fallback",
+        &settings.content.link_fallback,
+        &["auto", "copy", "none"],
+    );
+    validation.check_choice(
+        "content.config_folder_mode",
+        &settings.content.config_folder_mode,
+        &["replace", "overlay"],
+    );
+
+    let advanced = &settings.advanced;
+    if advanced.default_memory < MIN_MEMORY_MB {
+        validation.error(
+            "advanced.default_memory",
+            format!("Must be at least {} MB", MIN_MEMORY_MB),
+        );
+    } else {
+        let mut system = sysinfo::System::new();
+        system.refresh_memory();
+        let total_mb = system.total_memory() / (1024 * 1024);
+        if total_mb > 0 && advanced.default_memory as u64 > total_mb {
+            validation.warning(
+                "advanced.default_memory",
+                format!("More than the {} MB of memory this system has", total_mb),
+            );
+        }
+    }
+    validation.check_choice(
+        "advanced.duplicate_launch",
+        &advanced.duplicate_launch,
+        &["refuse", "warn", "allow"],
+    );
+    if advanced.concurrent_launch_prepares == 0 {
+        validation.error("advanced.concurrent_launch_prepares", "Must be at least 1");
+    } else if advanced.concurrent_launch_prepares > MAX_CONCURRENT_LAUNCH_PREPARES {
+        validation.error(
+            "advanced.concurrent_launch_prepares",
+            format!("Must be at most {}", MAX_CONCURRENT_LAUNCH_PREPARES),
+        );
+    }
+
+    validation.check_choice(
+        "misc.auth_preference",
+        &settings.misc.auth_preference,
+        &["code", "device_code"],
+    );
+    let presence = &settings.misc.discord_presence;
+    for (field, template) in [
+        ("playing_details", &presence.playing_details),
+        ("playing_state", &presence.playing_state),
+        ("browsing_details", &presence.browsing_details),
+        ("browsing_state", &presence.browsing_state),
+    ] {
+        if template.len() > crate::discord::DISCORD_TEXT_MAX_LEN {
+            validation.error(
+                &format!("misc.discord_presence.{}", field),
+                format!(
+                    "Longer than the {} bytes Discord can show",
+                    crate::discord::DISCORD_TEXT_MAX_LEN
+                ),
+            );
+        }
+    }
+
+    validation
+}
+
+/// Validates and saves the settings. Nothing is written when there are errors,
+/// the returned validation lists them per field together with any warnings.
+#[tauri::command]
+pub async fn save_settings_command(
+    settings: CategorizedLauncherSettings,
+) -> Result<SettingsValidation, String> {
+    let validation = validate_settings(&settings);
+    if !validation.errors.is_empty() {
+        Logger::warn_global(
+            &format!(
+                "Settings not saved, {} invalid field(s)",
+                validation.errors.len()
+            ),
+            None,
+        );
+        return Ok(validation);
+    }
+    // The mod browser state is written by the provider setters, the copy the UI sends may be stale
+    let mut settings = settings;
+    if let Ok(current) = load_settings().await {
+        settings.content.mod_browser = current.content.mod_browser;
+    }
+    save_settings(settings).await?;
+    Ok(validation)
+}
+
+/// Validates settings without saving them, so the UI can flag fields while editing
 #[tauri::command]
-pub async fn save_settings_command(settings: CategorizedLauncherSettings) -> Result<(), String> {
-    save_settings(settings).await
+pub async fn validate_settings_command(
+    settings: CategorizedLauncherSettings,
+) -> Result<SettingsValidation, String> {
+    Ok(validate_settings(&settings))
 }
 
 // Validate Minecraft directory
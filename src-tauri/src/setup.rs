@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Hosts the launcher needs for versions, game files and mods
+const REACHABILITY_URLS: &[(&str, &str)] = &[
+    (
+        "Mojang",
+        "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json",
+    ),
+    ("Modrinth", "https://api.modrinth.com/v2/tag/loader"),
+];
+const REACHABILITY_TIMEOUT: Duration = Duration::from_secs(10);
+/// Oldest Java that still runs a recent Minecraft version
+const RECOMMENDED_JAVA_MAJOR: u32 = 17;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// Outcome of one first-run check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResult {
+    /// 'minecraft_dir' | 'java' | 'network_mojang' | 'network_modrinth' | 'symlinks'
+    pub check: String,
+    pub status: CheckStatus,
+    pub message: String,
+    /// What the user can do about a warning or error
+    pub action: Option<String>,
+}
+
+impl CheckResult {
+    fn ok(check: &str, message: impl Into<String>) -> Self {
+        Self {
+            check: check.to_string(),
+            status: CheckStatus::Ok,
+            message: message.into(),
+            action: None,
+        }
+    }
+
+    fn problem(
+        check: &str,
+        status: CheckStatus,
+        message: impl Into<String>,
+        action: impl Into<String>,
+    ) -> Self {
+        Self {
+            check: check.to_string(),
+            status,
+            message: message.into(),
+            action: Some(action.into()),
+        }
+    }
+}
+
+/// Everything the launcher needs to work, checked once so onboarding can guide the user
+pub async fn run_first_run_checks() -> Vec<CheckResult> {
+    let mut results = vec![check_minecraft_dir().await, check_java().await];
+    let client = reqwest::Client::builder()
+        .timeout(REACHABILITY_TIMEOUT)
+        .user_agent("kable/1.0 (+https://github.com/LuckyLuuk12/kable)")
+        .build();
+    for (name, url) in REACHABILITY_URLS {
+        results.push(match &client {
+            Ok(client) => check_reachable(client, name, url).await,
+            Err(e) => CheckResult::problem(
+                &format!("network_{}", name.to_lowercase()),
+                CheckStatus::Error,
+                format!("Failed to build HTTP client: {}", e),
+                "Restart the launcher",
+            ),
+        });
+    }
+    results.push(check_symlinks().await);
+    results
+}
+
+async fn check_minecraft_dir() -> CheckResult {
+    const CHECK: &str = "minecraft_dir";
+    let dir = match crate::get_default_minecraft_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            return CheckResult::problem(
+                CHECK,
+                CheckStatus::Error,
+                e,
+                "Make sure your user has a home directory",
+            )
+        }
+    };
+    if let Err(e) = crate::ensure_folder(&dir).await {
+        return CheckResult::problem(
+            CHECK,
+            CheckStatus::Error,
+            format!("Can't create {}: {}", dir.display(), e),
+            "Check the permissions of the folder it should be in",
+        );
+    }
+    let probe = dir.join(".kable-write-test");
+    let written = crate::write_file_atomic_async(&probe, b"ok").await;
+    let _ = tokio::fs::remove_file(&probe).await;
+    match written {
+        Ok(()) => CheckResult::ok(CHECK, format!("{} is writable", dir.display())),
+        Err(e) => CheckResult::problem(
+            CHECK,
+            CheckStatus::Error,
+            format!("{} is not writable: {}", dir.display(), e),
+            "Give your user write access to the folder or run the launcher as its owner",
+        ),
+    }
+}
+
+async fn check_java() -> CheckResult {
+    const CHECK: &str = "java";
+    let java_path = crate::settings::load_settings()
+        .await
+        .ok()
+        .and_then(|s| s.general.java_path);
+    let found = tokio::task::spawn_blocking(move || {
+        let path = crate::launcher::java::find_java_executable(java_path.as_ref())?;
+        let major = crate::launcher::java::java_major_version(&path);
+        Ok::<_, String>((path, major))
+    })
+    .await
+    .map_err(|e| format!("Java detection task failed: {}", e))
+    .and_then(|r| r);
+
+    match found {
+        Ok((path, Some(major))) if major >= RECOMMENDED_JAVA_MAJOR => {
+            CheckResult::ok(CHECK, format!("Java {} at {}", major, path))
+        }
+        Ok((path, Some(major))) => CheckResult::problem(
+            CHECK,
+            CheckStatus::Warning,
+            format!(
+                "Java {} at {} only runs older Minecraft versions",
+                major, path
+            ),
+            format!(
+                "Install Java {} or newer for Minecraft 1.18 and later",
+                RECOMMENDED_JAVA_MAJOR
+            ),
+        ),
+        Ok((path, None)) => CheckResult::problem(
+            CHECK,
+            CheckStatus::Warning,
+            format!("Found {} but could not read its version", path),
+            "Check that it is a working Java executable or pick another one in settings",
+        ),
+        Err(e) => CheckResult::problem(
+            CHECK,
+            CheckStatus::Error,
+            e,
+            format!(
+                "Install Java {} or newer, or set the Java path in settings",
+                RECOMMENDED_JAVA_MAJOR
+            ),
+        ),
+    }
+}
+
+async fn check_reachable(client: &reqwest::Client, name: &str, url: &str) -> CheckResult {
+    let check = format!("network_{}", name.to_lowercase());
+    match client.get(url).send().await {
+        Ok(resp) if resp.status().is_success() => {
+            CheckResult::ok(&check, format!("{} is reachable", name))
+        }
+        Ok(resp) => CheckResult::problem(
+            &check,
+            CheckStatus::Warning,
+            format!("{} answered with HTTP {}", name, resp.status()),
+            "The service may be down, try again later",
+        ),
+        Err(e) => CheckResult::problem(
+            &check,
+            CheckStatus::Error,
+            format!("Can't reach {}: {}", name, e),
+            "Check your internet connection, firewall or the proxy settings of your installations",
+        ),
+    }
+}
+
+/// Dedicated mod/resourcepack/config folders are linked into .minecraft
+async fn check_symlinks() -> CheckResult {
+    const CHECK: &str = "symlinks";
+    match crate::symlink_manager::can_create_symlinks().await {
+        Ok(true) => CheckResult::ok(CHECK, "Symlinks can be created"),
+        Ok(false) => CheckResult::problem(
+            CHECK,
+            CheckStatus::Warning,
+            "Symlinks can't be created, dedicated folders use the link fallback from the content settings",
+            crate::symlink_manager::symlink_unavailable_message(),
+        ),
+        Err(e) => CheckResult::problem(
+            CHECK,
+            CheckStatus::Error,
+            format!("Could not test symlinks: {}", e),
+            "Check that the .minecraft folder is writable",
+        ),
+    }
+}
@@ -1,7 +1,8 @@
 use crate::auth::{get_minecraft_account, AuthMethod, LauncherAccount};
 use crate::logging::{LogLevel, Logger};
-use crate::skins::types::{AccountCape, PlayerProfile};
+use crate::skins::types::{AccountCape, PlayerProfile, ResolvedPlayer};
 use crate::skins::types::{AccountSkin, CurrentSkin, SkinModel};
+use crate::KableError;
 use base64::{engine::general_purpose, Engine as _};
 use serde_json::Value;
 use std::fs;
@@ -508,3 +509,144 @@ pub async fn get_active_cape() -> Result<Option<AccountCape>, String> {
 
     Ok(active_cape)
 }
+
+/// When a player was resolved and the result, None for players that don't exist
+type CachedPlayer = (std::time::Instant, Option<ResolvedPlayer>);
+
+/// Resolved players by lowercase name or uuid
+static RESOLVED_PLAYERS: once_cell::sync::Lazy<
+    std::sync::Mutex<std::collections::HashMap<String, CachedPlayer>>,
+> = once_cell::sync::Lazy::new(Default::default);
+
+// Mojang allows a few hundred profile lookups per minute per IP, names rarely change
+const RESOLVED_PLAYER_TTL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+static MOJANG_CLIENT: once_cell::sync::Lazy<reqwest::Client> =
+    once_cell::sync::Lazy::new(reqwest::Client::new);
+
+/// Looks a player up by uuid (with or without dashes) or by name and returns their current
+/// name, skin and cape. Mojang no longer serves name history, only the current name.
+/// Lookups are cached for a few minutes, players that don't exist included.
+pub async fn resolve_player(uuid_or_name: &str) -> Result<ResolvedPlayer, KableError> {
+    let input = uuid_or_name.trim();
+    let undashed = input.replace('-', "");
+    let is_uuid = undashed.len() == 32 && undashed.chars().all(|c| c.is_ascii_hexdigit());
+    let is_name = (1..=16).contains(&input.len())
+        && input.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_uuid && !is_name {
+        return Err(KableError::validation(format!(
+            "'{}' is neither a player name nor a uuid",
+            input
+        )));
+    }
+    let key = if is_uuid { undashed } else { input.to_string() }.to_lowercase();
+
+    let cached = RESOLVED_PLAYERS.lock().unwrap().get(&key).cloned();
+    if let Some((fetched_at, player)) = &cached {
+        if fetched_at.elapsed() < RESOLVED_PLAYER_TTL {
+            return player
+                .clone()
+                .ok_or_else(|| KableError::not_found(format!("Player '{}' not found", input)));
+        }
+    }
+
+    let player = match fetch_resolved_player(&key, is_uuid).await {
+        Ok(player) => player,
+        // Rate limited or offline, a stale answer is better than none
+        Err(e) => match cached {
+            Some((_, Some(player))) => return Ok(player),
+            _ => return Err(e),
+        },
+    };
+    {
+        let mut cache = RESOLVED_PLAYERS.lock().unwrap();
+        let now = std::time::Instant::now();
+        if let Some(player) = &player {
+            cache.insert(player.uuid.to_lowercase(), (now, Some(player.clone())));
+            cache.insert(player.name.to_lowercase(), (now, Some(player.clone())));
+        }
+        cache.insert(key, (now, player.clone()));
+    }
+    player.ok_or_else(|| KableError::not_found(format!("Player '{}' not found", input)))
+}
+
+/// Ok(None) when Mojang doesn't know the player
+async fn fetch_resolved_player(
+    key: &str,
+    is_uuid: bool,
+) -> Result<Option<ResolvedPlayer>, KableError> {
+    async fn get_json(url: &str) -> Result<Option<Value>, KableError> {
+        // codeql[rust/cleartext-transmission] - Minecraft names and UUIDs are public data
+        let response = MOJANG_CLIENT
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| KableError::network(format!("Failed to fetch profile: {}", e)))?;
+        match response.status() {
+            reqwest::StatusCode::NO_CONTENT | reqwest::StatusCode::NOT_FOUND => Ok(None),
+            reqwest::StatusCode::TOO_MANY_REQUESTS => Err(KableError::network(
+                "Mojang is rate limiting profile lookups, try again in a minute",
+            )),
+            status if !status.is_success() => Err(KableError::network(format!(
+                "Profile request failed with status: {}",
+                status
+            ))),
+            _ => response.json().await.map(Some).map_err(|e| {
+                KableError::network(format!("Failed to parse profile response: {}", e))
+            }),
+        }
+    }
+
+    let uuid = if is_uuid {
+        key.to_string()
+    } else {
+        let url = format!("https://api.mojang.com/users/profiles/minecraft/{}", key);
+        match get_json(&url).await? {
+            Some(profile) => match profile.get("id").and_then(|id| id.as_str()) {
+                Some(id) => id.to_string(),
+                None => return Ok(None),
+            },
+            None => return Ok(None),
+        }
+    };
+
+    let url = format!(
+        "https://sessionserver.mojang.com/session/minecraft/profile/{}",
+        uuid
+    );
+    let Some(profile) = get_json(&url).await? else {
+        return Ok(None);
+    };
+    let textures = profile
+        .get("properties")
+        .and_then(|p| p.as_array())
+        .and_then(|properties| {
+            properties
+                .iter()
+                .find(|p| p.get("name").and_then(|n| n.as_str()) == Some("textures"))
+        })
+        .and_then(|p| p.get("value")?.as_str())
+        .and_then(|value| general_purpose::STANDARD.decode(value).ok())
+        .and_then(|decoded| serde_json::from_slice::<Value>(&decoded).ok());
+    let texture_url = |kind: &str| {
+        textures
+            .as_ref()
+            .and_then(|t| t.get("textures")?.get(kind)?.get("url")?.as_str())
+            .map(str::to_string)
+    };
+
+    Ok(Some(ResolvedPlayer {
+        uuid: profile
+            .get("id")
+            .and_then(|id| id.as_str())
+            .unwrap_or(&uuid)
+            .to_string(),
+        name: profile
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        skin_url: texture_url("SKIN"),
+        cape_url: texture_url("CAPE"),
+    }))
+}
@@ -15,6 +15,16 @@ pub struct PlayerProfile {
     pub skins: Vec<AccountSkin>,
     pub capes: Vec<AccountCape>,
 }
+
+/// Any player's current name and textures, from `resolve_player`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResolvedPlayer {
+    /// Without dashes, as Mojang returns it
+    pub uuid: String,
+    pub name: String,
+    pub skin_url: Option<String>,
+    pub cape_url: Option<String>,
+}
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
@@ -1,6 +1,12 @@
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use tokio::fs as async_fs;
+use tokio::sync::Mutex;
+
+/// Outcome of the test symlink, probed once per session. Enabling Developer Mode on Windows
+/// takes a restart of the launcher to be picked up.
+static SYMLINKS_SUPPORTED: tokio::sync::OnceCell<bool> = tokio::sync::OnceCell::const_new();
 
 // ===== CUSTOM SYMLINK STRUCTURES =====
 
@@ -28,6 +34,9 @@ pub struct SymlinkInfo {
     pub symlink_type: String,
     pub is_disabled: bool,
     pub exists: bool,
+    /// How the link was made, see `LinkStrategy`
+    #[serde(default)]
+    pub strategy: LinkStrategy,
 }
 
 // ===== CONFIG FILE FUNCTIONS =====
@@ -83,6 +92,273 @@ fn get_symlink_destination(symlink: &CustomSymlink) -> Result<PathBuf, String> {
     Ok(dest_parent.join(filename))
 }
 
+// ===== MANAGED SYMLINK MANIFEST =====
+
+/// A symlink created by the SymlinkManager. Cleanup only ever removes links listed here,
+/// so symlinks users created themselves in .minecraft are left alone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagedSymlink {
+    /// Path of the link itself
+    pub path: String,
+    /// Path the link points to
+    pub source: String,
+    /// 'shader' | 'resourcepack'
+    #[serde(default)]
+    pub kind: String,
+    /// Installation the link was created for
+    #[serde(default)]
+    pub installation_id: Option<String>,
+    #[serde(default)]
+    pub created: String,
+    #[serde(default)]
+    pub strategy: LinkStrategy,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymlinkManifest {
+    pub symlinks: Vec<ManagedSymlink>,
+}
+
+// Serializes read-modify-write cycles on the manifest file
+static SYMLINK_MANIFEST_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+fn get_symlink_manifest_path() -> Result<PathBuf, String> {
+    let kable_dir = crate::get_minecraft_kable_dir()?;
+    Ok(kable_dir.join("symlinks.json"))
+}
+
+/// Returns None when no manifest has been written yet (e.g. first start after updating)
+async fn read_symlink_manifest_file() -> Result<Option<SymlinkManifest>, String> {
+    let manifest_path = get_symlink_manifest_path()?;
+
+    if !manifest_path.exists() {
+        return Ok(None);
+    }
+
+    let contents = async_fs::read_to_string(&manifest_path)
+        .await
+        .map_err(|e| format!("Failed to read symlink manifest: {}", e))?;
+
+    match serde_json::from_str(&contents) {
+        Ok(manifest) => Ok(Some(manifest)),
+        Err(e) => {
+            crate::logging::Logger::warn_global(
+                &format!("Failed to parse symlink manifest, starting fresh: {}", e),
+                None,
+            );
+            Ok(Some(SymlinkManifest::default()))
+        }
+    }
+}
+
+/// Reads the manifest, creating it on first use. Older versions did not keep a manifest,
+/// so any symlink in shaderpacks/resourcepacks that points into the .kable directory is
+/// adopted as managed, since only Kable creates those.
+/// Callers must hold SYMLINK_MANIFEST_LOCK.
+async fn read_symlink_manifest(minecraft_dir: &Path) -> Result<SymlinkManifest, String> {
+    if let Some(manifest) = read_symlink_manifest_file().await? {
+        return Ok(manifest);
+    }
+
+    let kable_dir = crate::get_minecraft_kable_dir()?;
+    let mut manifest = SymlinkManifest::default();
+    for (dir_name, kind) in [("shaderpacks", "shader"), ("resourcepacks", "resourcepack")] {
+        let Ok(entries) = std::fs::read_dir(minecraft_dir.join(dir_name)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_symlink() {
+                continue;
+            }
+            if let Ok(target) = std::fs::read_link(&path) {
+                if target.starts_with(&kable_dir) {
+                    manifest.symlinks.push(ManagedSymlink {
+                        path: path.to_string_lossy().to_string(),
+                        source: target.to_string_lossy().to_string(),
+                        kind: kind.to_string(),
+                        installation_id: extract_installation_from_path(&target),
+                        created: chrono::Utc::now().to_rfc3339(),
+                        strategy: LinkStrategy::Symlink,
+                    });
+                }
+            }
+        }
+    }
+
+    crate::logging::Logger::info_global(
+        &format!(
+            "Created symlink manifest, adopted {} existing Kable symlinks",
+            manifest.symlinks.len()
+        ),
+        None,
+    );
+    write_symlink_manifest(&manifest).await?;
+    Ok(manifest)
+}
+
+async fn write_symlink_manifest(manifest: &SymlinkManifest) -> Result<(), String> {
+    let manifest_path = get_symlink_manifest_path()?;
+    let contents = serde_json::to_string_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize symlink manifest: {}", e))?;
+    crate::write_file_atomic_async(&manifest_path, contents.as_bytes()).await
+}
+
+/// Whether symlinks can be created in the kable dir, tried with a test link that is removed again.
+/// On Windows this needs Developer Mode or admin rights, see `symlink_unavailable_message`.
+pub async fn can_create_symlinks() -> Result<bool, String> {
+    SYMLINKS_SUPPORTED
+        .get_or_try_init(probe_symlinks)
+        .await
+        .copied()
+}
+
+async fn probe_symlinks() -> Result<bool, String> {
+    let test_dir =
+        crate::get_minecraft_kable_dir()?.join(format!(".symlink-test-{}", uuid::Uuid::new_v4()));
+    let source = test_dir.join("source");
+    crate::ensure_folder(&source).await?;
+    let created = crate::create_directory_symlink(&source, &test_dir.join("link")).await;
+    if let Err(e) = async_fs::remove_dir_all(&test_dir).await {
+        crate::logging::Logger::debug_global(
+            &format!("Failed to remove {}: {}", test_dir.display(), e),
+            None,
+        );
+    }
+    match created {
+        Ok(()) => Ok(true),
+        Err(e) => {
+            crate::logging::Logger::debug_global(&format!("Test symlink failed: {}", e), None);
+            Ok(false)
+        }
+    }
+}
+
+/// What to tell the user when `can_create_symlinks` is false
+pub fn symlink_unavailable_message() -> String {
+    if cfg!(windows) {
+        crate::SYMLINK_PRIVILEGE_MESSAGE.to_string()
+    } else {
+        "Symlinks can't be created in the .minecraft folder, dedicated folders need a file \
+         system that supports them"
+            .to_string()
+    }
+}
+
+/// How a link is made. Real symlinks need Developer Mode or admin rights on Windows,
+/// the fallbacks (setting `content.link_fallback`) don't:
+/// - Junction: behaves like a directory symlink, but only for directories on local drives
+/// - Copy: works for anything but costs disk space and time. The game changes the copy, so it is
+///   copied back over its source when Kable removes it, changes made to the source meanwhile are lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkStrategy {
+    #[default]
+    Symlink,
+    Junction,
+    Copy,
+}
+
+/// Strategy for a new link: a symlink when possible, otherwise the configured fallback.
+/// Fails with `symlink_unavailable_message` when the fallback is 'none'.
+pub async fn link_strategy(is_dir: bool) -> Result<LinkStrategy, String> {
+    if can_create_symlinks().await? {
+        return Ok(LinkStrategy::Symlink);
+    }
+    let fallback = crate::settings::load_settings()
+        .await
+        .map(|s| s.content.link_fallback)
+        .unwrap_or_else(|_| "auto".to_string());
+    match fallback.as_str() {
+        "none" => Err(symlink_unavailable_message()),
+        "auto" if is_dir && cfg!(windows) => Ok(LinkStrategy::Junction),
+        _ => Ok(LinkStrategy::Copy),
+    }
+}
+
+/// Links `link` to `source` with the given strategy
+pub async fn create_link(
+    source: &Path,
+    link: &Path,
+    is_dir: bool,
+    strategy: LinkStrategy,
+) -> Result<(), String> {
+    match (strategy, is_dir) {
+        (LinkStrategy::Symlink, true) => crate::create_directory_symlink(source, link).await,
+        (LinkStrategy::Symlink, false) => crate::create_file_symlink(source, link).await,
+        (LinkStrategy::Junction, _) => crate::create_directory_junction(source, link).await,
+        (LinkStrategy::Copy, true) => crate::copy_dir_recursive(source, link).await,
+        (LinkStrategy::Copy, false) => {
+            crate::ensure_parent_dir_exists_async(link).await?;
+            async_fs::copy(source, link)
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))
+        }
+    }
+}
+
+/// Removes a managed link. Copied files are first written back to their source when the
+/// game changed them, so nothing made while playing is lost.
+async fn remove_managed_link(entry: &ManagedSymlink) -> Result<(), String> {
+    let path = Path::new(&entry.path);
+    if entry.strategy != LinkStrategy::Copy {
+        return remove_link(path).await;
+    }
+    if !path.is_file() {
+        return Ok(());
+    }
+    let source = Path::new(&entry.source);
+    if copy_changed(path, source).await {
+        async_fs::copy(path, source).await.map_err(|e| {
+            format!(
+                "Failed to copy {} back to {}: {}",
+                path.display(),
+                source.display(),
+                e
+            )
+        })?;
+    }
+    async_fs::remove_file(path)
+        .await
+        .map_err(|e| format!("Failed to remove copy {}: {}", path.display(), e))
+}
+
+async fn copy_changed(copy: &Path, source: &Path) -> bool {
+    match (async_fs::read(copy).await, async_fs::read(source).await) {
+        (Ok(copy), Ok(source)) => copy != source,
+        (Ok(_), Err(_)) => true,
+        _ => false,
+    }
+}
+
+/// Removes a symlink, including dangling ones whose source no longer exists
+async fn remove_link(path: &Path) -> Result<(), String> {
+    if !path.is_symlink() {
+        return Ok(());
+    }
+    if path.exists() {
+        return crate::remove_symlink_if_exists(path).await;
+    }
+    if async_fs::remove_file(path).await.is_err() {
+        async_fs::remove_dir(path).await.map_err(|e| {
+            format!(
+                "Failed to remove dangling symlink {}: {}",
+                path.display(),
+                e
+            )
+        })?;
+    }
+    Ok(())
+}
+
+/// Returns every symlink Kable currently has recorded as created by itself
+pub async fn list_managed_symlinks() -> Result<Vec<ManagedSymlink>, String> {
+    let minecraft_dir = crate::get_default_minecraft_dir()?;
+    let _guard = SYMLINK_MANIFEST_LOCK.lock().await;
+    Ok(read_symlink_manifest(&minecraft_dir).await?.symlinks)
+}
+
 // ===== HELPER FUNCTIONS FOR SCANNING =====
 
 fn should_skip_directory(dir_name: &str) -> bool {
@@ -213,6 +489,7 @@ fn scan_directory_for_managed_symlinks(
                         symlink_type,
                         is_disabled,
                         exists: target.exists(),
+                        strategy: LinkStrategy::Symlink,
                     });
                 }
             }
@@ -256,16 +533,47 @@ pub async fn list_all_symlinks() -> Result<Vec<SymlinkInfo>, String> {
             symlink_type: "custom".to_string(),
             is_disabled: !custom.enabled,
             exists,
+            strategy: LinkStrategy::Symlink,
+        });
+    }
+
+    // Symlinks Kable created for installations, as recorded in the manifest
+    let managed = {
+        let _guard = SYMLINK_MANIFEST_LOCK.lock().await;
+        read_symlink_manifest(&minecraft_dir).await?.symlinks
+    };
+    let mut scanned = Vec::new();
+    for entry in &managed {
+        let dest_path = PathBuf::from(&entry.path);
+        symlinks.push(SymlinkInfo {
+            id: None,
+            source: entry.source.clone(),
+            destination: entry.path.clone(),
+            is_global: entry.installation_id.is_none(),
+            installation_id: entry.installation_id.clone(),
+            symlink_type: if entry.kind.is_empty() {
+                determine_symlink_type(&dest_path, &minecraft_dir)
+            } else {
+                entry.kind.clone()
+            },
+            is_disabled: entry.path.ends_with(".disabled"),
+            exists: match entry.strategy {
+                LinkStrategy::Copy => dest_path.exists(),
+                _ => dest_path.is_symlink() && Path::new(&entry.source).exists(),
+            },
+            strategy: entry.strategy,
         });
     }
 
-    // Scan for managed symlinks (from dedicated folders)
+    // Scan for any other symlinks in the dedicated folders
     scan_directory_for_managed_symlinks(
         &minecraft_dir,
         &minecraft_dir,
         &custom_config,
-        &mut symlinks,
+        &mut scanned,
     )?;
+    scanned.retain(|s| !managed.iter().any(|m| m.path == s.destination));
+    symlinks.extend(scanned);
 
     Ok(symlinks)
 }
@@ -517,6 +825,13 @@ impl SymlinkManager {
     /// Setup symlinks for an installation before launching
     /// This removes all existing symlinks and creates new ones for the current installation
     pub async fn setup_for_installation(&self, installation_id: &str) -> Result<(), String> {
+        if !can_create_symlinks().await? {
+            crate::logging::Logger::info_global(
+                "Symlinks aren't available, dedicated folders use the configured fallback",
+                Some(installation_id),
+            );
+        }
+
         crate::logging::Logger::warn_global(
             &format!(
                 "[SYMLINK] setup_for_installation start installation_id={} minecraft_dir={}",
@@ -530,11 +845,15 @@ impl SymlinkManager {
         self.cleanup_for_installation_switch(installation_id)
             .await?;
 
+        // Decided once for all links, an error only matters once a link is made
+        let strategy = link_strategy(false).await;
+
         // Setup shader symlinks for this installation
-        self.setup_shader_symlinks(installation_id).await?;
+        self.setup_shader_symlinks(installation_id, &strategy)
+            .await?;
 
         // Setup resource pack symlinks for this installation
-        self.setup_resourcepack_symlinks(installation_id).await?;
+        self.link_resourcepacks(installation_id, &strategy).await?;
 
         // Apply custom symlinks for this installation (includes global ones)
         apply_custom_symlinks(Some(installation_id)).await?;
@@ -553,13 +872,8 @@ impl SymlinkManager {
     /// Clean up installation-specific symlinks when switching installations
     /// Global custom symlinks are preserved
     async fn cleanup_for_installation_switch(&self, installation_id: &str) -> Result<(), String> {
-        // Remove all symlinks from shaderpacks directory
-        self.cleanup_directory_symlinks(&self.minecraft_dir.join("shaderpacks"))
-            .await?;
-
-        // Remove all symlinks from resourcepacks directory
-        self.cleanup_directory_symlinks(&self.minecraft_dir.join("resourcepacks"))
-            .await?;
+        // Remove the shader/resource pack symlinks created for the previous installation
+        self.cleanup_managed_symlinks().await?;
 
         // Cleanup installation-specific custom symlinks only (preserve global)
         cleanup_installation_symlinks(installation_id).await?;
@@ -567,16 +881,11 @@ impl SymlinkManager {
         Ok(())
     }
 
-    /// Clean up ALL symlinks (including global custom symlinks)
+    /// Clean up all shader/resource pack symlinks Kable created (custom symlinks are kept)
     /// Used on app startup/shutdown
     pub async fn cleanup_all_symlinks(&self) -> Result<(), String> {
-        // Remove all symlinks from shaderpacks directory
-        self.cleanup_directory_symlinks(&self.minecraft_dir.join("shaderpacks"))
-            .await?;
-
-        // Remove all symlinks from resourcepacks directory
-        self.cleanup_directory_symlinks(&self.minecraft_dir.join("resourcepacks"))
-            .await?;
+        // Remove every shader/resource pack symlink Kable created
+        self.cleanup_managed_symlinks().await?;
 
         // Note: We could optionally cleanup ALL custom symlinks here,
         // but for now we'll just let them stay since they're harmless
@@ -585,30 +894,69 @@ impl SymlinkManager {
         Ok(())
     }
 
-    /// Remove all symlinks from a specific directory
-    async fn cleanup_directory_symlinks(&self, dir: &PathBuf) -> Result<(), String> {
-        if !dir.exists() {
-            return Ok(());
+    /// Remove all symlinks recorded in the manifest and clear it.
+    /// Entries that could not be removed stay in the manifest so the next cleanup retries them.
+    async fn cleanup_managed_symlinks(&self) -> Result<(), String> {
+        let _guard = SYMLINK_MANIFEST_LOCK.lock().await;
+        let manifest = read_symlink_manifest(&self.minecraft_dir).await?;
+
+        let mut remaining = Vec::new();
+        for entry in manifest.symlinks {
+            if let Err(e) = remove_managed_link(&entry).await {
+                crate::logging::Logger::warn_global(
+                    &format!("Failed to remove managed symlink {}: {}", entry.path, e),
+                    None,
+                );
+                remaining.push(entry);
+            }
         }
 
-        let entries = std::fs::read_dir(dir)
-            .map_err(|e| format!("Failed to read directory {:?}: {}", dir, e))?;
+        write_symlink_manifest(&SymlinkManifest {
+            symlinks: remaining,
+        })
+        .await
+    }
 
-        for entry in entries {
-            let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-            let path = entry.path();
+    /// Create a file symlink (or the fallback copy) and record it in the manifest.
+    /// The entry is written before the link is created, so a crash in between can never
+    /// leave an untracked link behind.
+    async fn create_managed_file_symlink(
+        &self,
+        source: &Path,
+        link: &Path,
+        kind: &str,
+        installation_id: &str,
+        strategy: &Result<LinkStrategy, String>,
+    ) -> Result<(), String> {
+        let strategy = strategy.clone()?;
+        let _guard = SYMLINK_MANIFEST_LOCK.lock().await;
+        let mut manifest = read_symlink_manifest(&self.minecraft_dir).await?;
+        let link_str = link.to_string_lossy().to_string();
+        manifest.symlinks.retain(|s| s.path != link_str);
+        manifest.symlinks.push(ManagedSymlink {
+            path: link_str.clone(),
+            source: source.to_string_lossy().to_string(),
+            kind: kind.to_string(),
+            installation_id: Some(installation_id.to_string()),
+            created: chrono::Utc::now().to_rfc3339(),
+            strategy,
+        });
+        write_symlink_manifest(&manifest).await?;
 
-            // Check if it's a symlink
-            if path.is_symlink() {
-                crate::remove_symlink_if_exists(&path).await?;
-            }
+        if let Err(e) = create_link(source, link, false, strategy).await {
+            manifest.symlinks.retain(|s| s.path != link_str);
+            write_symlink_manifest(&manifest).await?;
+            return Err(e);
         }
-
         Ok(())
     }
 
     /// Setup shader symlinks for a specific installation
-    async fn setup_shader_symlinks(&self, installation_id: &str) -> Result<(), String> {
+    async fn setup_shader_symlinks(
+        &self,
+        installation_id: &str,
+        strategy: &Result<LinkStrategy, String>,
+    ) -> Result<(), String> {
         let kable_dir = crate::get_minecraft_kable_dir()?;
 
         // Read the installation to get the dedicated shaders folder path
@@ -658,7 +1006,14 @@ impl SymlinkManager {
 
                     // Only create if doesn't exist
                     if !target_link.exists() {
-                        crate::create_file_symlink(&path, &target_link).await?;
+                        self.create_managed_file_symlink(
+                            &path,
+                            &target_link,
+                            "shader",
+                            installation_id,
+                            strategy,
+                        )
+                        .await?;
                     }
                 }
             }
@@ -674,6 +1029,16 @@ impl SymlinkManager {
     ///
     /// Falls back to legacy behavior if subfolders don't exist
     pub async fn setup_resourcepack_symlinks(&self, installation_id: &str) -> Result<(), String> {
+        let strategy = link_strategy(false).await;
+        self.link_resourcepacks(installation_id, &strategy).await
+    }
+
+    /// `setup_resourcepack_symlinks` with the link strategy already decided
+    async fn link_resourcepacks(
+        &self,
+        installation_id: &str,
+        strategy: &Result<LinkStrategy, String>,
+    ) -> Result<(), String> {
         use crate::logging::Logger;
 
         Logger::warn_global(
@@ -827,7 +1192,14 @@ impl SymlinkManager {
                     if target_link.exists() {
                         crate::remove_symlink_if_exists(&target_link).await?;
                     }
-                    crate::create_file_symlink(&merged_path, &target_link).await?;
+                    self.create_managed_file_symlink(
+                        &merged_path,
+                        &target_link,
+                        "resourcepack",
+                        installation_id,
+                        strategy,
+                    )
+                    .await?;
                 } else {
                     // No merged source packs left; remove stale merged symlink if present.
                     let target_link = resourcepacks_dir.join("kable-merged.zip");
@@ -866,7 +1238,14 @@ impl SymlinkManager {
                                     }
                                 }
 
-                                crate::create_file_symlink(&path, &target_link).await?;
+                                self.create_managed_file_symlink(
+                                    &path,
+                                    &target_link,
+                                    "resourcepack",
+                                    installation_id,
+                                    strategy,
+                                )
+                                .await?;
                                 linked_count += 1;
                             }
                         }
@@ -933,7 +1312,14 @@ impl SymlinkManager {
                     if target_link.exists() {
                         crate::remove_symlink_if_exists(&target_link).await?;
                     }
-                    crate::create_file_symlink(&merged_path, &target_link).await?;
+                    self.create_managed_file_symlink(
+                        &merged_path,
+                        &target_link,
+                        "resourcepack",
+                        installation_id,
+                        strategy,
+                    )
+                    .await?;
                 }
             } else {
                 // Individual pack symlinks (original behavior)
@@ -953,7 +1339,14 @@ impl SymlinkManager {
 
                             // Only create if doesn't exist
                             if !target_link.exists() {
-                                crate::create_file_symlink(&path, &target_link).await?;
+                                self.create_managed_file_symlink(
+                                    &path,
+                                    &target_link,
+                                    "resourcepack",
+                                    installation_id,
+                                    strategy,
+                                )
+                                .await?;
                             }
                         }
                     }
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { invokeKable } from "./errors";
 import type {
   LauncherAccount,
   LauncherAccountsJson,
@@ -16,33 +17,33 @@ import type {
 export async function refreshMicrosoftToken(
   localId: string,
 ): Promise<LauncherAccount> {
-  return await invoke("refresh_microsoft_token", { localId });
+  return await invokeKable("refresh_microsoft_token", { localId });
 }
 
 // Main Authentication Functions
 export async function getMinecraftAccount(
   authMethod?: AuthMethod,
 ): Promise<LauncherAccount> {
-  return await invoke("get_minecraft_account", { authMethod });
+  return await invokeKable("get_minecraft_account", { authMethod });
 }
 
 export async function getLaunchAuthAccount(): Promise<LauncherAccount> {
-  return await invoke("get_launch_auth_account");
+  return await invokeKable("get_launch_auth_account");
 }
 
 export async function refreshMinecraftAccount(): Promise<LauncherAccount> {
-  return await invoke("refresh_minecraft_account");
+  return await invokeKable("refresh_minecraft_account");
 }
 
 // Device Code Flow
 export async function startMicrosoftDeviceAuth(): Promise<DeviceCodeResponse> {
-  return await invoke("start_microsoft_device_auth");
+  return await invokeKable("start_microsoft_device_auth");
 }
 
 export async function pollMicrosoftDeviceAuth(
   deviceCode: string,
 ): Promise<MicrosoftToken | null> {
-  return await invoke("poll_microsoft_device_auth", { deviceCode });
+  return await invokeKable("poll_microsoft_device_auth", { deviceCode });
 }
 
 export async function completeMicrosoftAuth(
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import type { DiscordStatus } from "../types";
 
 /**
  * Discord Rich Presence API
@@ -26,3 +27,11 @@ export async function setEnabled(enabled: boolean): Promise<void> {
 export async function clear(): Promise<void> {
   return await invoke("discord_clear");
 }
+
+/**
+ * Whether Discord Rich Presence is enabled and connected to a running Discord.
+ * Changes are also emitted as the "discord-status-changed" event.
+ */
+export async function getStatus(): Promise<DiscordStatus> {
+  return await invoke<DiscordStatus>("discord_get_status");
+}
@@ -0,0 +1,43 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { KableErrorCode, KableErrorPayload } from "../types";
+
+/**
+ * Error thrown by commands that return a KableError ({ code, message, details }).
+ * `message` is the string those commands used to reject with, so existing handlers keep working.
+ */
+export class KableError extends Error {
+  code: KableErrorCode;
+  details: string | null;
+
+  constructor(payload: KableErrorPayload) {
+    super(payload.message);
+    this.name = "KableError";
+    this.code = payload.code;
+    this.details = payload.details ?? null;
+  }
+
+  toString(): string {
+    return this.message;
+  }
+}
+
+function isKableErrorPayload(value: unknown): value is KableErrorPayload {
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    "code" in value &&
+    "message" in value
+  );
+}
+
+// invoke() for commands returning KableError, rejects with a KableError instance
+export async function invokeKable<T>(
+  cmd: string,
+  args?: Record<string, unknown>,
+): Promise<T> {
+  try {
+    return await invoke<T>(cmd, args);
+  } catch (error) {
+    throw isKableErrorPayload(error) ? new KableError(error) : error;
+  }
+}
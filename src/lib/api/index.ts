@@ -4,10 +4,12 @@
  */
 
 export * from "./auth";
+export * from "./errors";
 export * from "./installations";
 export * from "./launcher";
 export * from "./minecraft";
 export * from "./mods";
+export * from "./progress";
 export * from "./resourcepacks";
 export * from "./settings";
 export * from "./shaders";
@@ -1,11 +1,45 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { KableInstallation, ModJarInfo, VersionData } from "../types";
+import { listen } from "@tauri-apps/api/event";
+import { invokeWithProgress } from "./progress";
+import type {
+  BatchImportReport,
+  CurseForgePackImport,
+  ExportContent,
+  ExportProgress,
+  ImportProgress,
+  InstallationContentCounts,
+  InstallationDiff,
+  InstallationSearchResult,
+  InstallationTemplate,
+  KableInstallation,
+  KeybindConflict,
+  LoaderCompatibility,
+  LoaderKind,
+  ModJarInfo,
+  LocalModInstall,
+  ModsTransfer,
+  FolderKind,
+  PlaytimeStats,
+  ProgressEvent,
+  PruneCategory,
+  PruneReport,
+  VersionUsage,
+  ResetScope,
+  StorageReport,
+  VersionData,
+  VersionMigrationReport,
+} from "../types";
 
 // Get all versions (optionally force refresh)
 export async function getAllVersions(force = false): Promise<VersionData[]> {
   return await invoke("get_all_versions", { force });
 }
 
+// Versions with a json in .minecraft/versions, works offline
+export async function getLocalVersions(): Promise<VersionData[]> {
+  return await invoke("get_local_versions");
+}
+
 // Force refresh version manifests from the network
 export async function refreshVersionManifests(): Promise<VersionData[]> {
   return await invoke("refresh_version_manifests");
@@ -18,6 +52,19 @@ export async function getVersion(
   return await invoke("get_version", { version_id });
 }
 
+// Check whether a loader build supports a game version, with the reason when it doesn't
+export async function isLoaderCompatible(
+  loader: LoaderKind,
+  loaderVersion: string,
+  gameVersion: string,
+): Promise<LoaderCompatibility> {
+  return await invoke("is_loader_compatible", {
+    loader,
+    loaderVersion,
+    gameVersion,
+  });
+}
+
 // Get all Kable installations
 export async function getInstallations(): Promise<KableInstallation[]> {
   return await invoke("get_installations");
@@ -35,6 +82,91 @@ export async function getInstallation(
   return await invoke("get_installation", { id });
 }
 
+// Get a single Kable installation by name (case-insensitive), fails when several match
+export async function getInstallationByName(
+  name: string,
+): Promise<KableInstallation | null> {
+  return await invoke("get_installation_by_name", { name });
+}
+
+// Fuzzy-search installations by name, version, description and tags
+export async function searchInstallations(
+  query: string,
+): Promise<InstallationSearchResult[]> {
+  return await invoke("search_installations", { query });
+}
+
+// Get the sanitized HTML of an installation's description (rendered from markdown when enabled)
+export async function renderInstallationDescription(
+  id: string,
+): Promise<string> {
+  return await invoke("render_installation_description", { id });
+}
+
+// Get aggregated playtime and launch statistics across all installations
+export async function getPlaytimeStats(): Promise<PlaytimeStats> {
+  return await invoke("get_playtime_stats");
+}
+
+// Compare two installations, "added" means only in b and "removed" only in a
+export async function diffInstallations(
+  aId: string,
+  bId: string,
+): Promise<InstallationDiff> {
+  return await invoke("diff_installations", { aId, bId });
+}
+
+// Count the mods, resource packs and shaders in an installation's folders
+export async function getInstallationContentCounts(
+  installationId: string,
+): Promise<InstallationContentCounts> {
+  return await invoke("get_installation_content_counts", { installationId });
+}
+
+// Find (and unless dryRun remove) versions, libraries and assets no installation uses
+export async function pruneUnusedGameFiles(
+  dryRun: boolean,
+): Promise<PruneReport> {
+  return await invoke("prune_unused_game_files", { dryRun });
+}
+
+// Versions installations use with how many use them, loader versions and their parents included
+export async function getVersionsInUse(): Promise<VersionUsage[]> {
+  return await invoke("get_versions_in_use");
+}
+
+// Remove temp files left behind by interrupted writes and downloads
+export async function cleanupTempFiles(): Promise<PruneCategory> {
+  return await invoke("cleanup_temp_files");
+}
+
+// Disk usage of game files, installation folders, caches, logs, exports and backups
+export async function getStorageReport(): Promise<StorageReport> {
+  return await invoke("get_storage_report");
+}
+
+// Read the installation's options.txt as key/value pairs (empty before the first launch)
+export async function getGameOptions(
+  installationId: string,
+): Promise<Record<string, string>> {
+  return await invoke("get_game_options", { installationId });
+}
+
+// Change options in the installation's options.txt, other lines are kept as they are
+export async function setGameOptions(
+  installationId: string,
+  changes: Record<string, string>,
+): Promise<void> {
+  return await invoke("set_game_options", { installationId, changes });
+}
+
+// Keys bound to more than one action in the installation's options.txt
+export async function detectKeybindConflicts(
+  installationId: string,
+): Promise<KeybindConflict[]> {
+  return await invoke("detect_keybind_conflicts", { installationId });
+}
+
 // Modify an existing Kable installation
 export async function modifyInstallation(
   id: string,
@@ -77,12 +209,164 @@ export async function createInstallationFromExisting(
   });
 }
 
+// Same as createInstallationFromExisting, reporting the "mods" and "mod_downloads" stages to onProgress
+export async function startCreateInstallationFromExisting(
+  versionId: string,
+  sourceInstallationId: string,
+  options: {
+    copyMods: boolean;
+    copyResourcePacks: boolean;
+    copyShaders: boolean;
+  },
+  onProgress?: (event: ProgressEvent) => void,
+): Promise<void> {
+  return await invokeWithProgress(
+    "start_create_installation_from_existing",
+    {
+      versionId,
+      sourceInstallationId,
+      copyMods: options.copyMods,
+      copyResourcePacks: options.copyResourcePacks,
+      copyShaders: options.copyShaders,
+    },
+    onProgress,
+  );
+}
+
+// Move an installation to another Minecraft version, updateMods backs up and updates its mods
+export async function migrateInstallationVersion(
+  id: string,
+  newVersionId: string,
+  updateMods: boolean,
+): Promise<VersionMigrationReport> {
+  return await invoke("migrate_installation_version", {
+    id,
+    newVersionId,
+    updateMods,
+  });
+}
+
+// Save an installation's settings (without its content) as a named template
+export async function saveInstallationTemplate(
+  installationId: string,
+  templateName: string,
+): Promise<InstallationTemplate> {
+  return await invoke("save_installation_template", {
+    installationId,
+    templateName,
+  });
+}
+
+// List the saved installation templates, sorted by name
+export async function listInstallationTemplates(): Promise<
+  InstallationTemplate[]
+> {
+  return await invoke("list_installation_templates");
+}
+
+// Delete a saved installation template
+export async function deleteInstallationTemplate(
+  templateName: string,
+): Promise<void> {
+  return await invoke("delete_installation_template", { templateName });
+}
+
+// Create a new Kable installation with the settings of a saved template
+export async function createInstallationFromTemplate(
+  versionId: string,
+  templateName: string,
+): Promise<KableInstallation> {
+  return await invoke("create_installation_from_template", {
+    versionId,
+    templateName,
+  });
+}
+
+// Create an installation of a hand-made version json, optionally with its own jar
+export async function createInstallationFromManifest(
+  manifestPath: string,
+  jarPath: string | null = null,
+): Promise<KableInstallation> {
+  return await invoke("create_installation_from_manifest", {
+    manifestPath,
+    jarPath,
+  });
+}
+
 export async function getModInfo(
   installation: KableInstallation,
 ): Promise<ModJarInfo[] | null> {
   return await invoke("get_mod_info", { installation });
 }
 
+// Reset JVM flags, parameters or window/quick play settings, content is left alone
+export async function resetInstallationSettings(
+  id: string,
+  what: ResetScope,
+): Promise<KableInstallation> {
+  return await invoke("reset_installation_settings", { id, what });
+}
+
+// Set a parameters_map entry, "--key" adds a game argument, other keys override a launch
+// variable. Keys the launcher sets itself (classpath, game_directory, auth_*) are rejected
+export async function setInstallationParameter(
+  id: string,
+  key: string,
+  value: string,
+): Promise<KableInstallation> {
+  return await invoke("set_installation_parameter", { id, key, value });
+}
+
+// Remove a parameters_map entry
+export async function removeInstallationParameter(
+  id: string,
+  key: string,
+): Promise<KableInstallation> {
+  return await invoke("remove_installation_parameter", { id, key });
+}
+
+// Switch between a dedicated and the global mods folder, transfer says what happens to the
+// jars in .minecraft/mods when switching to dedicated
+export async function setModsFolderMode(
+  installationId: string,
+  dedicated: boolean,
+  transfer: ModsTransfer = "none",
+): Promise<KableInstallation> {
+  return await invoke("set_mods_folder_mode", {
+    installationId,
+    dedicated,
+    transfer,
+  });
+}
+
+// Move a dedicated folder to an absolute path, e.g. on another drive, the destination has to be
+// missing or empty
+export async function relocateInstallationFolder(
+  id: string,
+  which: FolderKind,
+  newPath: string,
+): Promise<KableInstallation> {
+  return await invoke("relocate_installation_folder", { id, which, newPath });
+}
+
+// Read the mod identity of any jar, rejects files that aren't readable jars
+export async function readModJarInfo(path: string): Promise<ModJarInfo> {
+  return await invoke("read_mod_jar_info", { path });
+}
+
+// Copy a jar into an installation's mods folder, rejects a duplicate mod id unless replace is set
+export async function installLocalMod(
+  installationId: string,
+  jarPath: string,
+  replace = false,
+): Promise<LocalModInstall> {
+  return await invoke("install_local_mod", {
+    installationId,
+    jarPath,
+    replace,
+  });
+}
+
 // Disable a mod by moving the jar into the installation's disabled/ subfolder
 export async function disableMod(
   installation: KableInstallation,
@@ -263,6 +547,23 @@ export async function importInstallation(
   return await invoke("import", { path });
 }
 
+// Import several export zips at once, onProgress gets the import-progress events
+export async function importInstallations(
+  paths: string[],
+  onProgress?: (progress: ImportProgress) => void,
+): Promise<BatchImportReport> {
+  const unlisten = onProgress
+    ? await listen<ImportProgress>("import-progress", (event) =>
+        onProgress(event.payload),
+      )
+    : undefined;
+  try {
+    return await invoke("import_installations", { paths });
+  } finally {
+    unlisten?.();
+  }
+}
+
 // Import installations from a .minecraft folder
 export async function importFromMinecraftFolder(
   path: string,
@@ -270,11 +571,59 @@ export async function importFromMinecraftFolder(
   return await invoke("import_from_minecraft_folder", { path });
 }
 
-// Export an installation as a string (serialized)
+// Same as importFromMinecraftFolder, reporting the "import_files" stage to onProgress.
+// The copy can be cancelled through the "minecraft_import" operation heartbeat.
+export async function startImportFromMinecraftFolder(
+  path: string,
+  onProgress?: (event: ProgressEvent) => void,
+): Promise<void> {
+  return await invokeWithProgress(
+    "start_import_from_minecraft_folder",
+    { path },
+    onProgress,
+  );
+}
+
+// Import a Prism/MultiMC instance, or every instance in an `instances` folder
+export async function importFromPrism(
+  path: string,
+): Promise<KableInstallation[]> {
+  return await invoke("import_from_prism", { path });
+}
+
+// Import a CurseForge modpack zip, non-distributable files are returned as manual downloads
+export async function importCurseForgeZip(
+  path: string,
+): Promise<CurseForgePackImport> {
+  return await invoke("import_curseforge_zip", { path });
+}
+
+export async function importFromMultiMC(
+  path: string,
+): Promise<KableInstallation[]> {
+  return await invoke("import_from_multimc", { path });
+}
+
+// Export an installation as a string (serialized), onProgress gets the export-progress events
+// content defaults to "full"
 export async function exportInstallation(
   installation: KableInstallation,
+  onProgress?: (progress: ExportProgress) => void,
+  content?: ExportContent,
 ): Promise<string> {
-  return await invoke("export", { installation });
+  if (!onProgress) {
+    return await invoke("export", { installation, content });
+  }
+  const unlisten = await listen<ExportProgress>("export-progress", (event) => {
+    if (event.payload.installation_id === installation.id) {
+      onProgress(event.payload);
+    }
+  });
+  try {
+    return await invoke("export", { installation, content });
+  } finally {
+    unlisten();
+  }
 }
 
 // Duplicate an installation and return the new list of installations
@@ -300,3 +649,11 @@ export async function selectInstallationZip(): Promise<string | null> {
 export async function selectMinecraftFolder(): Promise<string | null> {
   return await invoke("select_minecraft_folder");
 }
+
+export async function selectInstanceFolder(): Promise<string | null> {
+  return await invoke("select_instance_folder");
+}
+
+export async function selectCurseForgeZip(): Promise<string | null> {
+  return await invoke("select_curseforge_zip");
+}
@@ -3,8 +3,18 @@ import type {
   CategorizedLauncherSettings,
   LauncherAccount,
   LaunchResult,
+  LaunchCommandPreview,
+  LaunchReadiness,
+  JavaInstallation,
+  LwjglConflictResolution,
+  VersionJarVerification,
+  ProgressEvent,
+  TrackedProcess,
+  KillInstallationResult,
 } from "$lib";
 import { invoke } from "@tauri-apps/api/core";
+import { invokeKable } from "./errors";
+import { invokeWithProgress } from "./progress";
 
 // Types matching backend LaunchResult
 // export interface LaunchResult {
@@ -14,17 +24,114 @@ import { invoke } from "@tauri-apps/api/core";
 // }
 
 /**
- * Launch a Minecraft installation (matches tauri::command launch_installation)
+ * Launch a Minecraft installation (matches tauri::command launch_installation).
+ * suppressLaunchBehaviors skips the on_game_launch/close/crash behaviors for this launch.
  */
 export async function launchInstallation(
   installation: KableInstallation,
   settings: CategorizedLauncherSettings,
   account: LauncherAccount,
+  suppressLaunchBehaviors = false,
 ): Promise<LaunchResult> {
-  return await invoke<LaunchResult>("launch_installation", {
+  return await invokeKable<LaunchResult>("launch_installation", {
     installation,
     settings,
     account,
+    suppressLaunchBehaviors,
+  });
+}
+
+/**
+ * Launch in the background and follow its progress (matches tauri::command start_launch_installation)
+ */
+export async function startLaunchInstallation(
+  installation: KableInstallation,
+  settings: CategorizedLauncherSettings,
+  account: LauncherAccount,
+  onProgress?: (event: ProgressEvent) => void,
+): Promise<void> {
+  return await invokeWithProgress(
+    "start_launch_installation",
+    { installation, settings, account },
+    onProgress,
+  );
+}
+
+/**
+ * Preview the command a launch would run without launching (matches tauri::command preview_launch_command)
+ */
+export async function previewLaunchCommand(
+  installationId: string,
+): Promise<LaunchCommandPreview> {
+  return await invoke<LaunchCommandPreview>("preview_launch_command", {
+    installationId,
+  });
+}
+
+/**
+ * Write the launch command as a .sh/.bat script to run in a terminal, the access token is
+ * replaced by "offline". Returns the script path (matches tauri::command get_launch_script)
+ */
+export async function getLaunchScript(installationId: string): Promise<string> {
+  return await invoke<string>("get_launch_script", { installationId });
+}
+
+/**
+ * Get the fully merged manifest (inherited Fabric/Forge manifests included) a version launches
+ * with, optionally also written to dumpPath (matches tauri::command get_merged_manifest)
+ */
+export async function getMergedManifest(
+  versionId: string,
+  dumpPath?: string,
+): Promise<Record<string, unknown>> {
+  return await invoke<Record<string, unknown>>("get_merged_manifest", {
+    versionId,
+    dumpPath,
+  });
+}
+
+/**
+ * Run all pre-launch checks without launching (matches tauri::command validate_launch)
+ */
+export async function validateLaunch(
+  installationId: string,
+): Promise<LaunchReadiness> {
+  return await invoke<LaunchReadiness>("validate_launch", { installationId });
+}
+
+/**
+ * Keep only the highest LWJGL version on an installation's classpath (matches tauri::command resolve_lwjgl_conflict)
+ */
+export async function resolveLwjglConflict(
+  installationId: string,
+): Promise<LwjglConflictResolution> {
+  return await invoke<LwjglConflictResolution>("resolve_lwjgl_conflict", {
+    installationId,
+  });
+}
+
+/**
+ * Compare a version's client jar with the sha1 in its version json (matches tauri::command verify_version_jar)
+ */
+export async function verifyVersionJar(
+  versionId: string,
+): Promise<VersionJarVerification> {
+  return await invoke<VersionJarVerification>("verify_version_jar", {
+    versionId,
+  });
+}
+
+/**
+ * Download a version's client jar again when it is missing or mismatched (matches tauri::command repair_version_jar)
+ * With an installation id the download uses that installation's proxy settings
+ */
+export async function repairVersionJar(
+  versionId: string,
+  installationId?: string,
+): Promise<VersionJarVerification> {
+  return await invoke<VersionJarVerification>("repair_version_jar", {
+    versionId,
+    installationId,
   });
 }
 
@@ -35,6 +142,20 @@ export async function killMinecraftProcess(processId: number): Promise<void> {
   return await invoke("kill_minecraft_process", { processId });
 }
 
+/**
+ * Stop every running game of an installation, force (default) kills instead of asking
+ * them to close (matches tauri::command kill_installation)
+ */
+export async function killInstallation(
+  installationId: string,
+  force = true,
+): Promise<KillInstallationResult> {
+  return await invoke<KillInstallationResult>("kill_installation", {
+    installationId,
+    force,
+  });
+}
+
 /**
  * Get all running Minecraft process IDs (matches tauri::command get_running_minecraft_processes)
  */
@@ -42,6 +163,18 @@ export async function getRunningMinecraftProcesses(): Promise<number[]> {
   return await invoke<number[]>("get_running_minecraft_processes");
 }
 
+/**
+ * Get the running Minecraft processes with their installation, oldest first
+ * (matches tauri::command get_running_minecraft_process_details)
+ */
+export async function getRunningMinecraftProcessDetails(): Promise<
+  TrackedProcess[]
+> {
+  return await invoke<TrackedProcess[]>(
+    "get_running_minecraft_process_details",
+  );
+}
+
 /**
  * Check if any Minecraft process is running (matches tauri::command is_minecraft_running)
  */
@@ -49,6 +182,15 @@ export async function isMinecraftRunning(): Promise<boolean> {
   return await invoke<boolean>("is_minecraft_running");
 }
 
+/**
+ * Check if a game of one installation is running (matches tauri::command is_installation_running)
+ */
+export async function isInstallationRunning(
+  installationId: string,
+): Promise<boolean> {
+  return await invoke<boolean>("is_installation_running", { installationId });
+}
+
 /**
  * Wait for a Minecraft process to exit (matches tauri::command wait_for_minecraft_exit)
  */
@@ -56,9 +198,82 @@ export async function waitForMinecraftExit(processId: number): Promise<void> {
   return await invoke("wait_for_minecraft_exit", { processId });
 }
 
+/**
+ * Java major version a version needs, null when its manifest isn't downloaded yet
+ * (matches tauri::command get_required_java_version)
+ */
+export async function getRequiredJavaVersion(
+  versionId: string,
+): Promise<number | null> {
+  return await invoke<number | null>("get_required_java_version", {
+    versionId,
+  });
+}
+
+/**
+ * Answer an ask-launch/close/crash-behavior event, the backend applies the choice
+ * (matches tauri::command answer_launch_behavior)
+ */
+export async function answerLaunchBehavior(
+  requestId: string,
+  choice: string,
+): Promise<void> {
+  return await invoke("answer_launch_behavior", { requestId, choice });
+}
+
+/**
+ * Answer the confirm-close-with-running-games event, true closes the launcher anyway
+ * (matches tauri::command confirm_close_with_running_games)
+ */
+export async function confirmCloseWithRunningGames(
+  close: boolean,
+): Promise<void> {
+  return await invoke("confirm_close_with_running_games", { close });
+}
+
+/**
+ * Cancel a long running operation by its heartbeat operation id, false when it already finished
+ * (matches tauri::command cancel_operation)
+ */
+export async function cancelOperation(operationId: string): Promise<boolean> {
+  return await invoke<boolean>("cancel_operation", { operationId });
+}
+
 /**
  * Auto-detect Java executable path (matches tauri::command auto_detect_java)
  */
 export async function autoDetectJava(): Promise<string> {
   return await invoke<string>("auto_detect_java");
 }
+
+/**
+ * Version, vendor and arch of a Java executable, rejects when it isn't a runnable Java
+ * (matches tauri::command probe_java)
+ */
+export async function probeJava(path: string): Promise<JavaInstallation> {
+  return await invoke<JavaInstallation>("probe_java", { path });
+}
+
+/**
+ * Java runtimes installed on the system, newest first
+ * (matches tauri::command detect_system_java)
+ */
+export async function detectSystemJava(): Promise<JavaInstallation[]> {
+  return await invoke<JavaInstallation[]>("detect_system_java");
+}
+
+/**
+ * Merged, timestamped stdout/stderr of the last run of an instance
+ * (matches tauri::command get_game_log)
+ */
+export async function getGameLog(instanceId: string): Promise<string> {
+  return await invoke<string>("get_game_log", { instanceId });
+}
+
+/**
+ * Remove the game logs of an installation, the current one is kept while games run
+ * (matches tauri::command clear_game_logs)
+ */
+export async function clearGameLogs(installationId: string): Promise<void> {
+  return await invoke<void>("clear_game_logs", { installationId });
+}
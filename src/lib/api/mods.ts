@@ -6,10 +6,17 @@ import type {
   ModJarInfo,
   ModpackContext,
   ModpackPrepareResult,
+  ModrinthCategory,
+  ModrinthGameVersionTag,
+  ModrinthLoaderTag,
   ModrinthVersion,
+  ModsPage,
+  ProgressEvent,
   ProviderKind,
 } from "$lib";
 import { invoke } from "@tauri-apps/api/core";
+import { invokeKable } from "./errors";
+import { invokeWithProgress } from "./progress";
 
 // Unified mod/modpack download/prepare API
 export async function downloadOrPrepareMod(
@@ -18,7 +25,7 @@ export async function downloadOrPrepareMod(
   versionId: string | null,
   installation: KableInstallation,
 ): Promise<ModpackPrepareResult> {
-  return await invoke("download_or_prepare_mod", {
+  return await invokeKable("download_or_prepare_mod", {
     provider,
     modId,
     versionId,
@@ -33,13 +40,27 @@ export async function applyModpackSelection(
   selection: ModpackSelection,
   context: ModpackContext,
 ): Promise<void> {
-  return await invoke("apply_modpack_selection", {
+  return await invokeKable("apply_modpack_selection", {
     installation,
     selection,
     context,
   });
 }
 
+// Same as applyModpackSelection, reporting the "modpack" stage to onProgress
+export async function startApplyModpackSelection(
+  installation: KableInstallation,
+  selection: ModpackSelection,
+  context: ModpackContext,
+  onProgress?: (event: ProgressEvent) => void,
+): Promise<void> {
+  return await invokeWithProgress(
+    "start_apply_modpack_selection",
+    { installation, selection, context },
+    onProgress,
+  );
+}
+
 export async function getMods(
   provider: ProviderKind,
   offset: number,
@@ -58,6 +79,19 @@ export async function getMods(
   return result;
 }
 
+// Same as getMods, with the total hit count for pagination controls
+export async function getModsPage(
+  provider: ProviderKind,
+  offset: number,
+): Promise<ModsPage> {
+  return invoke("get_mods_page", { provider, offset });
+}
+
+// Loads the first mod browser page into the cache, returns how many projects were loaded
+export async function prefetchFeaturedMods(): Promise<number> {
+  return invoke("prefetch_featured_mods");
+}
+
 export async function downloadMod(
   provider: ProviderKind,
   modId: string,
@@ -107,6 +141,39 @@ export async function getProjectVersions(
   return result;
 }
 
+// Identify a jar by its sha1, null when Modrinth doesn't know the file
+export async function modrinthVersionByHash(
+  sha1: string,
+): Promise<ModrinthVersion | null> {
+  return await invoke("modrinth_version_by_hash", { sha1 });
+}
+
+// Modrinth categories for filter dropdowns, only those of projectType ("mod", "shader",
+// "resourcepack", ...) when given. Cached for a day by the backend.
+export async function getModrinthCategories(
+  projectType?: string,
+): Promise<ModrinthCategory[]> {
+  return await invoke("get_modrinth_categories", {
+    projectType: projectType ?? null,
+  });
+}
+
+// Modrinth loaders, only those supporting projectType when given
+export async function getModrinthLoaders(
+  projectType?: string,
+): Promise<ModrinthLoaderTag[]> {
+  return await invoke("get_modrinth_loaders", {
+    projectType: projectType ?? null,
+  });
+}
+
+// Game versions known to Modrinth, newest first
+export async function getModrinthGameVersions(
+  includeSnapshots = false,
+): Promise<ModrinthGameVersionTag[]> {
+  return await invoke("get_modrinth_game_versions", { includeSnapshots });
+}
+
 export async function setProviderFilter(
   provider: ProviderKind,
   installation: KableInstallation | null,
@@ -134,6 +201,22 @@ export async function setProviderLimit(
   return invoke("set_provider_limit", { provider, limit });
 }
 
+// Filter the provider searches with, restored from the last session at startup
+export async function getProviderFilter(
+  provider: ProviderKind,
+): Promise<ModFilter | null> {
+  return await invoke("get_provider_filter", { provider });
+}
+
+export async function getProviderLimit(provider: ProviderKind): Promise<number> {
+  return await invoke("get_provider_limit", { provider });
+}
+
+// Provider the mod browser used last, null before it was ever used
+export async function getLastProvider(): Promise<ProviderKind | null> {
+  return await invoke("get_last_provider");
+}
+
 export async function clearProviderCache(
   provider: ProviderKind,
 ): Promise<void> {
@@ -152,6 +235,13 @@ export async function getExtendedModInfo(
   return invoke("get_extended_mod_info", { modJarInfo });
 }
 
+// Resolve many jars at once, results are in the order of the jars (null when not found)
+export async function getExtendedModInfoBatch(
+  jars: ModJarInfo[],
+): Promise<(ExtendedModInfo | null)[]> {
+  return invoke("get_extended_mod_info_batch", { jars });
+}
+
 export interface ModMetadata {
   project_id: string;
   file_name: string;
@@ -160,6 +250,14 @@ export interface ModMetadata {
   download_time: string;
 }
 
+export type ModlistFormat = "Markdown" | "Csv" | "Html";
+
+export interface ModRollbackProgress {
+  installation_id: string;
+  file_name: string;
+  stage: "resolving" | "downloading" | "replacing" | "done";
+}
+
 export interface ModpackSourceRecord {
   provider: ProviderKind;
   mod_id: string;
@@ -170,6 +268,26 @@ export interface ModpackSourceRecord {
   managed_project_ids: string[];
 }
 
+// Bookmark a mod to find it again later, independent of installations
+export async function addModBookmark(
+  provider: ProviderKind,
+  projectId: string,
+): Promise<void> {
+  return invoke("add_mod_bookmark", { provider, projectId });
+}
+
+export async function removeModBookmark(
+  provider: ProviderKind,
+  projectId: string,
+): Promise<void> {
+  return invoke("remove_mod_bookmark", { provider, projectId });
+}
+
+// Bookmarked mods in the order they were added
+export async function getModBookmarks(): Promise<ModInfoKind[]> {
+  return invoke("get_mod_bookmarks");
+}
+
 export async function getModMetadata(
   installation: KableInstallation,
   jarFilename: string,
@@ -177,6 +295,23 @@ export async function getModMetadata(
   return invoke("get_mod_metadata", { installation, jarFilename });
 }
 
+// Replace a mod with another version of its project, returns the new file name
+export async function rollbackMod(
+  installationId: string,
+  fileName: string,
+  versionId: string,
+): Promise<string> {
+  return invoke("rollback_mod", { installationId, fileName, versionId });
+}
+
+// Write a list of the installation's mods to the exports folder, returns the file path
+export async function exportModlist(
+  installationId: string,
+  format: ModlistFormat,
+): Promise<string> {
+  return invoke("export_modlist", { installationId, format });
+}
+
 export async function getModpackSourceRecords(
   installation: KableInstallation,
 ): Promise<ModpackSourceRecord[]> {
@@ -0,0 +1,47 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { ProgressEvent } from "../types";
+
+/** Event every progress report is emitted under (matches launcher::progress::PROGRESS_EVENT) */
+export const PROGRESS_EVENT = "progress";
+
+/**
+ * Invokes a command that returns a progress token and reports its events to `onProgress`.
+ * Resolves once the "done" event arrives, rejects with the message of a "failed" event.
+ * Listens before invoking so early events of the token are not missed.
+ */
+export async function invokeWithProgress(
+  cmd: string,
+  args: Record<string, unknown>,
+  onProgress?: (event: ProgressEvent) => void,
+): Promise<void> {
+  let token: string | null = null;
+  const early: ProgressEvent[] = [];
+  let settle: (event: ProgressEvent) => void = (event) => {
+    early.push(event);
+  };
+
+  const unlisten = await listen<ProgressEvent>(PROGRESS_EVENT, (event) => {
+    if (token === null) {
+      early.push(event.payload);
+    } else if (event.payload.token === token) {
+      settle(event.payload);
+    }
+  });
+
+  try {
+    token = await invoke<string>(cmd, args);
+    await new Promise<void>((resolve, reject) => {
+      settle = (event) => {
+        onProgress?.(event);
+        if (event.stage === "done") resolve();
+        if (event.stage === "failed") {
+          reject(new Error(event.message ?? `${cmd} failed`));
+        }
+      };
+      early.filter((event) => event.token === token).forEach(settle);
+    });
+  } finally {
+    unlisten();
+  }
+}
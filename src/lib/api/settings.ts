@@ -1,5 +1,8 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { CategorizedLauncherSettings } from "../types";
+import type {
+  CategorizedLauncherSettings,
+  SettingsValidation,
+} from "../types";
 
 /**
  * Settings API
@@ -10,12 +13,19 @@ export async function loadSettings(): Promise<CategorizedLauncherSettings> {
   return await invoke("load_settings");
 }
 
+// Nothing is saved when the returned validation has errors
 export async function saveSettings(
   settings: CategorizedLauncherSettings,
-): Promise<void> {
+): Promise<SettingsValidation> {
   return await invoke("save_settings_command", { settings });
 }
 
+export async function validateSettings(
+  settings: CategorizedLauncherSettings,
+): Promise<SettingsValidation> {
+  return await invoke("validate_settings_command", { settings });
+}
+
 export async function loadCustomCss(themeName: string): Promise<string> {
   return await invoke("load_custom_css", { themeName });
 }
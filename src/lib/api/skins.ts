@@ -35,7 +35,9 @@ export async function modifySkinById(
   });
 }
 import { invoke } from "@tauri-apps/api/core";
+import { invokeKable } from "./errors";
 import type {
+  ResolvedPlayer,
   SkinUploadConfig,
   SkinUploadResponse,
   CurrentSkin,
@@ -123,3 +125,14 @@ export async function applyCape(capeId: string | null): Promise<string> {
 export async function getSkinUrlByUuid(uuid: string): Promise<string> {
   return await invoke("get_skin_url_by_uuid", { uuid });
 }
+
+/**
+ * Look a player up by uuid or name, rejects with a KableError with code "not_found" for
+ * players that don't exist
+ * @param uuidOrName A Minecraft uuid (with or without dashes) or player name
+ */
+export async function resolvePlayer(
+  uuidOrName: string,
+): Promise<ResolvedPlayer> {
+  return await invokeKable("resolve_player", { uuidOrName });
+}
@@ -9,6 +9,7 @@ export interface SymlinkInfo {
   symlink_type: "resourcepack" | "shader" | "world" | "mod" | "custom";
   is_disabled: boolean;
   exists: boolean;
+  strategy: "symlink" | "junction" | "copy";
 }
 
 export class SymlinksAPI {
@@ -19,6 +20,13 @@ export class SymlinksAPI {
     return invoke<SymlinkInfo[]>("list_symlinks");
   }
 
+  /**
+   * Try whether symlinks can be created (false on Windows without Developer Mode)
+   */
+  static async canCreateSymlinks(): Promise<boolean> {
+    return invoke<boolean>("can_create_symlinks");
+  }
+
   /**
    * Create a custom symlink
    * @param source - The file or folder to link from
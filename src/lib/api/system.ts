@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
 import { writeText } from "@tauri-apps/plugin-clipboard-manager";
+import type { FirstRunCheckResult } from "../types";
 /**
  * System API
  * Pure Tauri invoke calls for system-level operations
@@ -16,3 +17,8 @@ export async function openUrl(url: string): Promise<void> {
 export async function openPath(path: string): Promise<void> {
   return await invoke("open_path", { path });
 }
+
+// Minecraft dir, Java, network and symlink checks for the onboarding screen
+export async function runFirstRunChecks(): Promise<FirstRunCheckResult[]> {
+  return await invoke("run_first_run_checks");
+}
@@ -6,7 +6,8 @@ import {
   isAuthenticating,
 } from "../stores/auth";
 import { get } from "svelte/store";
-import type { LauncherAccount } from "../types";
+import { listen } from "@tauri-apps/api/event";
+import type { AccountsChangedEvent, LauncherAccount } from "../types";
 import * as systemApi from "$lib";
 
 /**
@@ -54,6 +55,7 @@ export class AuthService {
     }
     console.log("🔐 Initializing authentication service...");
     this.isInitialized = true;
+    this.listenForAccountChanges();
     try {
       // Try to get existing account with valid token
       const account = await authApi.getLaunchAuthAccount();
@@ -75,6 +77,27 @@ export class AuthService {
     }
   }
 
+  /**
+   * Keep the account stores in sync when accounts change in another window or page
+   */
+  private static listenForAccountChanges(): void {
+    listen<AccountsChangedEvent>("accounts-changed", async (event) => {
+      const { active_account_id } = event.payload;
+      if (get(currentAccount)?.local_id !== active_account_id) {
+        try {
+          currentAccount.set(
+            active_account_id ? await authApi.getActiveLauncherAccount() : null,
+          );
+        } catch (error) {
+          console.error("❌ Failed to load the changed active account:", error);
+        }
+      }
+      await this.refreshAvailableAccounts();
+    }).catch((error) =>
+      console.error("❌ Failed to listen for account changes:", error),
+    );
+  }
+
   /**
    * Start background refresh timer (checks every 5 minutes)
    */
@@ -12,6 +12,10 @@ import {
   type LoaderKind,
   type ModJarInfo,
   type ExtendedModInfo,
+  type ProgressEvent,
+  type ExportContent,
+  type ExportProgress,
+  type ImportProgress,
   LogsService,
   openPath,
   NotificationService,
@@ -26,6 +30,7 @@ export class InstallationService {
   private static _versionsListenerUnsubscribe: (() => void) | null = null;
   private static _installationsListenerUnsubscribe: (() => void) | null = null;
   private static _installationUpdatedUnsubscribe: (() => void) | null = null;
+  private static _cacheInvalidatedUnsubscribe: (() => void) | null = null;
 
   /**
    * Initialize event listeners for progressive loading
@@ -143,6 +148,36 @@ export class InstallationService {
 
       this._installationUpdatedUnsubscribe = unsubscribe;
     }
+
+    // Refetch the whole list whenever the backend cache is replaced (create/modify/delete)
+    if (!this._cacheInvalidatedUnsubscribe) {
+      const unsubscribe = await listen(
+        "installations-cache-invalidated",
+        async (event: any) => {
+          const { reason, installation_id } = event.payload;
+          console.log(
+            `[InstallationService] Installations cache invalidated (${reason}):`,
+            installation_id,
+          );
+          try {
+            const fresh = await installationsApi.getInstallations();
+            installations.set(fresh);
+            // Keep the current selection unless it was deleted
+            const selectedId = get(selectedInstallation)?.id;
+            selectedInstallation.set(
+              fresh.find((i) => i.id === selectedId) || fresh[0] || null,
+            );
+          } catch (error) {
+            console.error(
+              "[InstallationService] Failed to refetch installations:",
+              error,
+            );
+          }
+        },
+      );
+
+      this._cacheInvalidatedUnsubscribe = unsubscribe;
+    }
   }
 
   /**
@@ -525,10 +560,18 @@ export class InstallationService {
     return (await installationsApi.getModInfo(installation)) || [];
   }
 
-  static async exportInstallation(installation: KableInstallation) {
+  static async exportInstallation(
+    installation: KableInstallation,
+    onProgress?: (progress: ExportProgress) => void,
+    content?: ExportContent,
+  ) {
     try {
       console.log("Exporting installation:", installation);
-      const path = await installationsApi.exportInstallation(installation);
+      const path = await installationsApi.exportInstallation(
+        installation,
+        onProgress,
+        content,
+      );
       // Open the file location in the system file explorer
       if (path) {
         console.log("Exported installation to:", path);
@@ -586,24 +629,53 @@ export class InstallationService {
     }
   }
 
-  static async importFromMinecraftFolder(path: string): Promise<void> {
+  static async importInstallations(
+    paths: string[],
+    onProgress?: (progress: ImportProgress) => void,
+  ): Promise<void> {
+    const report = await installationsApi.importInstallations(
+      paths,
+      onProgress,
+    );
+    const failed = report.results.filter((result) => result.error);
+    for (const result of failed) {
+      LogsService.emitLauncherEvent(
+        `✗ Failed to import installation from ${result.path}: ${result.error}`,
+        "error",
+      );
+    }
+    if (report.installations.length > 0) {
+      NotificationService.success(
+        `Imported ${report.installations.length} installation(s)`,
+      );
+      await this.refreshInstallations();
+    }
+    if (failed.length > 0) {
+      NotificationService.error(
+        `Failed to import ${failed.length} of ${paths.length} file(s)`,
+      );
+    }
+  }
+
+  static async importFromMinecraftFolder(
+    path: string,
+    onProgress?: (event: ProgressEvent) => void,
+  ): Promise<void> {
     try {
       console.log(
         "[InstallationService] Starting import from .minecraft folder:",
         path,
       );
-      const newInstallations =
-        await installationsApi.importFromMinecraftFolder(path);
-      const count = newInstallations.length;
+      await installationsApi.startImportFromMinecraftFolder(path, onProgress);
       console.log(
-        `[InstallationService] Successfully imported ${count} installation(s) from .minecraft folder`,
+        "[InstallationService] Successfully imported installations from .minecraft folder",
       );
       LogsService.emitLauncherEvent(
-        `✓ Successfully imported ${count} installation(s) from ${path}`,
+        `✓ Successfully imported installations from ${path}`,
         "info",
       );
       NotificationService.success(
-        `Imported ${count} installation(s) from .minecraft folder`,
+        "Imported installations from .minecraft folder",
       );
 
       // Reload installations to show the new ones (force refresh)
@@ -625,6 +697,89 @@ export class InstallationService {
     }
   }
 
+  /**
+   * Import Prism Launcher (or MultiMC) instances from an instance or `instances` folder.
+   */
+  static async importFromPrism(path: string): Promise<void> {
+    try {
+      const newInstallations = await installationsApi.importFromPrism(path);
+      const count = newInstallations.length;
+      LogsService.emitLauncherEvent(
+        `✓ Successfully imported ${count} installation(s) from ${path}`,
+        "info",
+      );
+      NotificationService.success(
+        `Imported ${count} installation(s) from Prism/MultiMC`,
+      );
+
+      // Reload installations to show the new ones (force refresh)
+      await this.refreshInstallations();
+    } catch (error) {
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      console.error(
+        "[InstallationService] Failed to import Prism/MultiMC instance:",
+        errorMsg,
+      );
+      LogsService.emitLauncherEvent(
+        `✗ Failed to import Prism/MultiMC instance ${path}: ${errorMsg}`,
+        "error",
+      );
+      NotificationService.error(
+        `Failed to import Prism/MultiMC instance: ${errorMsg}`,
+      );
+      throw error;
+    }
+  }
+
+  /**
+   * Import a CurseForge modpack zip. Files the pack author doesn't allow launchers to
+   * download are listed so the user can grab them from their project page.
+   */
+  static async importCurseForgeZip(path: string): Promise<void> {
+    try {
+      const result = await installationsApi.importCurseForgeZip(path);
+      LogsService.emitLauncherEvent(
+        `✓ Imported "${result.installation.name}" (${result.downloaded.length} files downloaded)`,
+        "info",
+      );
+      for (const manual of result.manual_downloads) {
+        LogsService.emitLauncherEvent(
+          `Download ${manual.file_name} manually from ${manual.url} into ${manual.target_dir}`,
+          "warn",
+        );
+      }
+      for (const failure of result.failed) {
+        LogsService.emitLauncherEvent(`✗ ${failure}`, "error");
+      }
+      if (result.manual_downloads.length > 0) {
+        NotificationService.warning(
+          `Imported "${result.installation.name}", ${result.manual_downloads.length} file(s) must be downloaded manually (see logs)`,
+        );
+      } else {
+        NotificationService.success(
+          `Imported "${result.installation.name}" successfully`,
+        );
+      }
+
+      // Reload installations to show the new one (force refresh)
+      await this.refreshInstallations();
+    } catch (error) {
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      console.error(
+        "[InstallationService] Failed to import CurseForge modpack:",
+        errorMsg,
+      );
+      LogsService.emitLauncherEvent(
+        `✗ Failed to import CurseForge modpack ${path}: ${errorMsg}`,
+        "error",
+      );
+      NotificationService.error(
+        `Failed to import CurseForge modpack: ${errorMsg}`,
+      );
+      throw error;
+    }
+  }
+
   static async duplicateInstallation(
     installation: KableInstallation,
   ): Promise<void> {
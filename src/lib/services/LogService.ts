@@ -3,7 +3,14 @@ import { listen } from "@tauri-apps/api/event";
 import type { UnlistenFn } from "@tauri-apps/api/event";
 import { get } from "svelte/store";
 import { LogsManager, gameInstances } from "../stores/logs";
-import type { GameInstance, LogEntry } from "../types";
+import { runningOperations } from "../stores/launcher";
+import { NotificationService } from "./NotificationService";
+import type {
+  DownloadFailedEvent,
+  GameInstance,
+  LogEntry,
+  OperationHeartbeat,
+} from "../types";
 
 export class LogsService {
   /**
@@ -91,6 +98,61 @@ export class LogsService {
       }
     });
 
+    // Listen for launch queue events (only one launch prepares at a time by default)
+    const queuedListener = await listen("launch-queued", (event) => {
+      const { installation_id, active } = event.payload as {
+        installation_id: string;
+        active: number;
+      };
+      LogsManager.addLauncherLog(
+        `Launch queued, waiting for ${active} other launch(es) to finish preparing`,
+        "info",
+        installation_id,
+      );
+    });
+    const startedListener = await listen("launch-started", (event) => {
+      const { installation_id, was_queued } = event.payload as {
+        installation_id: string;
+        was_queued: boolean;
+      };
+      if (was_queued) {
+        LogsManager.addLauncherLog(
+          "Launch left the queue, preparing",
+          "info",
+          installation_id,
+        );
+      }
+    });
+
+    // Heartbeats of long operations, the entry is removed again when the operation finishes
+    const heartbeatListener = await listen("operation-heartbeat", (event) => {
+      const heartbeat = event.payload as OperationHeartbeat;
+      runningOperations.update((ops) => ({
+        ...ops,
+        [heartbeat.operation_id]: heartbeat,
+      }));
+    });
+    const operationFinishedListener = await listen(
+      "operation-finished",
+      (event) => {
+        const { operation_id } = event.payload as { operation_id: string };
+        runningOperations.update((ops) => {
+          const { [operation_id]: _, ...rest } = ops;
+          return rest;
+        });
+      },
+    );
+
+    // Failed downloads, also those of background work no page is waiting for
+    const downloadFailedListener = await listen("download-failed", (event) => {
+      const { item, url, error } = event.payload as DownloadFailedEvent;
+      LogsManager.addLauncherLog(
+        `Download of ${item} failed (${url}): ${error}`,
+        "error",
+      );
+      NotificationService.error(`Download of ${item} failed`);
+    });
+
     // Listen for game process events
     const processListener = await listen("game-process-event", (event) => {
       try {
@@ -275,6 +337,11 @@ export class LogsService {
     });
 
     this.listeners.set("game-launched", launchListener);
+    this.listeners.set("launch-queued", queuedListener);
+    this.listeners.set("launch-started", startedListener);
+    this.listeners.set("operation-heartbeat", heartbeatListener);
+    this.listeners.set("operation-finished", operationFinishedListener);
+    this.listeners.set("download-failed", downloadFailedListener);
     this.listeners.set("game-process-event", processListener);
     this.listeners.set("launcher-log-batch", launcherLogBatchListener);
     this.listeners.set("launcher-log", launcherLogListener);
@@ -425,15 +492,35 @@ export class LogsService {
     return joined;
   }
 
-  async exportLogs(instanceId?: string): Promise<void> {
+  /** Exports a zip of the logs, optionally scoped to an installation and an RFC3339 time range */
+  async exportLogs(
+    instanceId?: string,
+    since?: string,
+    until?: string,
+  ): Promise<void> {
     try {
-      await invoke("export_logs", { instanceId });
-      LogsManager.addLauncherLog("Logs exported successfully", "info");
+      const path = await invoke<string>("export_logs", {
+        instanceId,
+        since,
+        until,
+      });
+      LogsManager.addLauncherLog(`Logs exported to ${path}`, "info");
     } catch (error) {
       LogsManager.addLauncherLog(`Failed to export logs: ${error}`, "error");
     }
   }
 
+  /** Copies the full game.log of an instance's last run (stdout and stderr) to the clipboard */
+  async copyGameLog(instanceId: string): Promise<void> {
+    try {
+      const log = await invoke<string>("get_game_log", { instanceId });
+      await navigator.clipboard.writeText(log);
+      LogsManager.addLauncherLog("Game log copied to clipboard", "info");
+    } catch (error) {
+      LogsManager.addLauncherLog(`Failed to copy game log: ${error}`, "error");
+    }
+  }
+
   async clearLogs(instanceId?: string): Promise<void> {
     LogsManager.clearLogs(instanceId);
     LogsManager.addLauncherLog(
@@ -487,10 +487,30 @@ export class ModsService {
 
   async initialize() {
     if (this.initialized) return;
+    await this.restoreProviderState();
     await this.loadMods();
     this.initialized = true;
   }
 
+  // Reflect the filter and limit the backend restored from the last session
+  async restoreProviderState() {
+    const provider = get(modsProvider);
+    if (!provider) return;
+    try {
+      const [filter, limit] = await Promise.all([
+        modsApi.getProviderFilter(provider),
+        modsApi.getProviderLimit(provider),
+      ]);
+      modsFilter.set(filter);
+      modsLimit.set(limit);
+    } catch (e: any) {
+      console.warn(
+        `[ModsService] Failed to restore ${provider} provider state:`,
+        e.message || "Unknown error",
+      );
+    }
+  }
+
   async loadMods() {
     modsLoading.set(true);
     modsError.set(null);
@@ -755,6 +775,31 @@ export class ModsService {
     });
   }
 
+  // Fetch extended info for many jars in one request, failed lookups are stored as null
+  static async getExtendedModInfoBatch(
+    jars: ModJarInfo[],
+  ): Promise<(ExtendedModInfo | null)[]> {
+    const known = get(extendedModInfo);
+    const missing = jars.filter((jar) => !known[jar.file_name]);
+    if (missing.length > 0) {
+      let results: (ExtendedModInfo | null)[];
+      try {
+        results = await modsApi.getExtendedModInfoBatch(missing);
+      } catch (e) {
+        console.warn(
+          `[ModsService] Failed to fetch extended mod info for ${missing.length} mods:`,
+          e instanceof Error ? e.message : "Unknown error",
+        );
+        results = missing.map(() => null);
+      }
+      const updated = { ...get(extendedModInfo) };
+      missing.forEach((jar, i) => (updated[jar.file_name] = results[i]));
+      extendedModInfo.set(updated);
+    }
+    const all = get(extendedModInfo);
+    return jars.map((jar) => all[jar.file_name] || null);
+  }
+
   static #runQueue() {
     while (
       ModsService.#inFlight < ModsService.#MAX_CONCURRENT &&
@@ -1,4 +1,7 @@
-import type { CategorizedLauncherSettings } from "../types";
+import type {
+  CategorizedLauncherSettings,
+  SettingsValidation,
+} from "../types";
 import * as settingsApi from "../api/settings";
 import { get } from "svelte/store";
 import * as minecraftApi from "../api/minecraft";
@@ -7,6 +10,7 @@ import {
   isSettingsLoading,
   settingsError,
   isSettingsInitialized,
+  settingsValidation,
   minecraftDirectoryInfo,
   isMinecraftFound,
   defaultCategorizedSettings,
@@ -107,7 +111,14 @@ export class SettingsService {
   ): Promise<void> {
     try {
       const currentSettings = newSettings || get(settings);
-      await settingsApi.saveSettings(currentSettings);
+      const validation = await settingsApi.saveSettings(currentSettings);
+      settingsValidation.set(validation);
+      if (validation.errors.length > 0) {
+        throw new Error(
+          validation.errors.map((e) => `${e.field}: ${e.message}`).join("; "),
+        );
+      }
+      settingsError.set(null);
     } catch (error) {
       console.error("❌ Failed to save settings:", error);
       settingsError.set(`Failed to save settings: ${error}`);
@@ -190,8 +201,23 @@ export class SettingsService {
 
   static async saveSettings(
     settings: CategorizedLauncherSettings,
-  ): Promise<void> {
-    return await settingsApi.saveSettings(settings);
+  ): Promise<SettingsValidation> {
+    const validation = await settingsApi.saveSettings(settings);
+    settingsValidation.set(validation);
+    return validation;
+  }
+
+  /**
+   * Validate settings without saving them
+   */
+  static async validate(
+    newSettings: CategorizedLauncherSettings | null = null,
+  ): Promise<SettingsValidation> {
+    const validation = await settingsApi.validateSettings(
+      newSettings || get(settings),
+    );
+    settingsValidation.set(validation);
+    return validation;
   }
 
   /**
@@ -1,5 +1,5 @@
 import { writable } from "svelte/store";
-import type { KableInstallation } from "../types";
+import type { KableInstallation, OperationHeartbeat } from "../types";
 
 // Installations that have been launched and the one that is currently launching
 export const launchedInstallations = writable<KableInstallation[]>([]);
@@ -14,5 +14,11 @@ export const launchError = writable<string | null>(null);
 // Timeout handle used to auto-clear launching UI after a max wait
 export const launchTimeoutHandle = writable<number | null>(null);
 
+// Long running operations (asset/library downloads, loader installs) by operation id,
+// updated by their heartbeats so the UI can tell "still working" from "stuck"
+export const runningOperations = writable<Record<string, OperationHeartbeat>>(
+  {},
+);
+
 // Java state
 export const javaStatus = writable<string>("Checking...");
@@ -9,6 +9,7 @@ import type {
   AdvancedSettings,
   MiscSettings,
   MinecraftDirectoryInfo,
+  SettingsValidation,
 } from "../types";
 
 /**
@@ -33,6 +34,11 @@ export const settings = writable<CategorizedLauncherSettings>(
 export const isSettingsLoading = writable(false);
 export const settingsError = writable<string | null>(null);
 export const isSettingsInitialized = writable(false);
+// Field-level problems from the last save, to highlight the offending settings
+export const settingsValidation = writable<SettingsValidation>({
+  errors: [],
+  warnings: [],
+});
 
 // Minecraft directory info
 export const minecraftDirectoryInfo = writable<MinecraftDirectoryInfo | null>(
@@ -46,6 +52,7 @@ export function defaultGeneralSettings(): GeneralSettings {
     game_directory: undefined,
     on_game_close: "open_home",
     on_game_crash: "open_logs",
+    clean_exit_codes: [0, 130, 143],
     on_game_launch: "open_logs",
     auto_update_launcher: true,
     show_ads: true,
@@ -103,6 +110,10 @@ export function defaultContentSettings(): ContentSettings {
     auto_backup_worlds: true,
     use_per_installation_mods_folder: true,
     use_per_installation_resource_packs: true,
+    cleanup_symlinks_on_startup: true,
+    link_fallback: "auto",
+    config_folder_mode: "replace",
+    prefetch_featured_mods: true,
   };
 }
 
@@ -113,6 +124,9 @@ export function defaultAdvancedSettings(): AdvancedSettings {
     separate_logs_window: false,
     auto_save_interval: 10,
     show_advanced_page: false,
+    concurrent_launch_prepares: 1,
+    duplicate_launch: "refuse",
+    allow_protected_parameter_overrides: false,
     extra: {},
   };
 }
@@ -121,5 +135,11 @@ export function defaultMiscSettings(): MiscSettings {
   return {
     use_titlebar: true,
     auth_preference: "code",
+    discord_presence: {
+      playing_details: "Playing Minecraft {version}",
+      playing_state: "{installation} • {loader}",
+      browsing_details: "In Launcher",
+      browsing_state: "{section}",
+    },
   };
 }
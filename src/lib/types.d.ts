@@ -49,6 +49,15 @@ export interface PlayerProfile {
   skins: AccountSkin[];
   capes: AccountCape[];
 }
+
+/** Any player's current name and textures, from resolve_player */
+export interface ResolvedPlayer {
+  /** Without dashes */
+  uuid: string;
+  name: string;
+  skin_url?: string | null;
+  cape_url?: string | null;
+}
 // Microsoft authentication types
 export interface MicrosoftToken {
   access_token: string;
@@ -247,6 +256,26 @@ export interface MinecraftDirectoryInfo {
   launcher_profiles?: string;
 }
 
+/** Payload of the `operation-heartbeat` event, sent every few seconds by long operations */
+export interface OperationHeartbeat {
+  operation_id: string;
+  kind: "libraries" | "assets" | "fabric_install" | string;
+  installation_id: string | null;
+  elapsed_ms: number;
+}
+
+/** A problem with one settings field, `field` is a dotted path like "advanced.default_memory" */
+export interface SettingsValidationError {
+  field: string;
+  message: string;
+}
+
+/** Errors block saving settings, warnings are only reported */
+export interface SettingsValidation {
+  errors: SettingsValidationError[];
+  warnings: SettingsValidationError[];
+}
+
 // Additional type definitions for managers
 export interface LocalWorld {
   id: string;
@@ -486,6 +515,8 @@ export interface GeneralSettings {
     | "exit"
     | "minimize"
     | "ask";
+  /** Exit codes that count as a normal close instead of a crash (default 0, 130, 143) */
+  clean_exit_codes: number[];
   /** Whether to keep the launcher open after launching the game */
   on_game_launch: "keep_open" | "exit" | "open_logs" | "minimize" | "ask";
   /** Whether to automatically check for updates on startup */
@@ -641,6 +672,8 @@ export interface LoggingSettings {
   dedupe_window_size?: number;
   /** Enable automatic deduplication of log messages (default: true) */
   enable_dedupe?: boolean;
+  /** Game logs (one per run) kept per installation (default: 5) */
+  game_logs_to_keep?: number;
 }
 
 /** Network Settings for the launcher
@@ -680,6 +713,23 @@ export interface ContentSettings {
   use_per_installation_mods_folder: boolean;
   /** Whether to have per-installation resource packs in the kable directory (this zips, copies and moves resource packs and is quite HEAVY) */
   use_per_installation_resource_packs: boolean;
+  /** Whether to remove leftover symlinks Kable created when the launcher starts (user-created symlinks are never touched) */
+  cleanup_symlinks_on_startup: boolean;
+  /** What to do when symlinks can't be created: 'auto' uses junctions for folders and copies for files, 'copy' always copies, 'none' fails */
+  link_fallback: "auto" | "copy" | "none";
+  /** How a dedicated config folder is used: 'replace' links it in place of .minecraft/config, 'overlay' copies its files over the global configs */
+  config_folder_mode: "replace" | "overlay";
+  /** Whether to load the first mod browser page in the background after startup */
+  prefetch_featured_mods: boolean;
+  /** What the mod browser used last, written by the provider setters */
+  mod_browser?: ModBrowserState;
+}
+
+/** Last used provider, filter and page size of the mod browser */
+export interface ModBrowserState {
+  provider?: ProviderKind | null;
+  filter?: ModFilter | null;
+  limit?: number | null;
 }
 
 /** Advanced Settings for the launcher
@@ -706,6 +756,12 @@ export interface AdvancedSettings {
   show_advanced_page: boolean;
   /** Whether to check for nightly/prerelease updates */
   check_nightly_updates?: boolean;
+  /** How many launches may download/prepare at once, others wait in the launch queue */
+  concurrent_launch_prepares?: number;
+  /** Launching an installation that is already running: refuse, warn in the logs or allow */
+  duplicate_launch?: "refuse" | "warn" | "allow";
+  /** Apply installation parameters that override auth or classpath variables instead of ignoring them */
+  allow_protected_parameter_overrides?: boolean;
   /** A map with string keys and any type of values for really advanced stuff */
   extra?: Record<string, any>;
 }
@@ -723,6 +779,18 @@ export interface MiscSettings {
   use_titlebar: boolean;
   /** Authentication preference (code flow is recommended) */
   auth_preference: "code" | "device_code";
+  /** Discord Rich Presence texts */
+  discord_presence: DiscordPresenceTemplates;
+}
+
+/** Discord Rich Presence texts, `{name}` variables are filled in when shown (max 128 bytes) */
+export interface DiscordPresenceTemplates {
+  /** Variables: {installation}, {version}, {loader} */
+  playing_details: string;
+  playing_state: string;
+  /** Variables: {section} */
+  browsing_details: string;
+  browsing_state: string;
 }
 
 // _____________________________________________________________________________
@@ -756,6 +824,18 @@ export interface VersionData {
   display_name: string;
   is_stable: boolean;
   extra: any;
+  /** Loaders with builds for this game version, only set on vanilla versions */
+  supports_loaders?: LoaderKind[];
+  /** 'local' when only known from a version json on disk */
+  source?: "remote" | "local";
+  /** Its version json is in .minecraft/versions */
+  installed?: boolean;
+}
+
+/** Result of is_loader_compatible, reason is set when the build doesn't support the game version */
+export interface LoaderCompatibility {
+  compatible: boolean;
+  reason?: string | null;
 }
 
 /** KableInstallation struct
@@ -798,6 +878,240 @@ export interface KableInstallation {
   enable_pack_merging?: boolean;
   pack_order?: string[];
   merged_packs?: string[];
+  tags?: string[];
+  /** Server to join directly on launch, "host:port" */
+  connect_server?: string | null;
+  /** Force natives for this architecture instead of the host's */
+  native_arch_override?: "x86" | "x86_64" | "arm64" | null;
+  /** Keep only the highest LWJGL version on the classpath at launch */
+  resolve_lwjgl_conflicts?: boolean;
+  /** How the description is rendered, plain by default */
+  description_format?: "plain" | "markdown" | null;
+  /** Own assets and natives folders instead of the shared .minecraft ones */
+  isolated_game_files?: boolean;
+  /** Proxy for the game and the game files its launches download, e.g. "http://proxy:8080".
+   * Mod browsing and mod downloads don't use it */
+  http_proxy?: string | null;
+  https_proxy?: string | null;
+  /** Extra jars or folders appended to the classpath */
+  extra_classpath?: string[];
+  /** Java agent jars passed as -javaagent:, "path=options" passes agent options */
+  java_agents?: string[];
+  /** Per-installation overrides of the global on_game_launch/close/crash settings */
+  on_game_launch?: string | null;
+  on_game_close?: string | null;
+  on_game_crash?: string | null;
+  /** local_id of the account to launch with instead of the active one */
+  preferred_account_id?: string | null;
+  /** Java executable for this installation, overrides the general Java path */
+  java_path?: string | null;
+  /** gameDir of the launcher profile it was imported from */
+  game_directory?: string | null;
+  /** Uses .minecraft/mods instead of a dedicated mods folder */
+  use_global_mods?: boolean;
+  /** Launch with --demo, online demo play depends on the account's state */
+  demo_mode?: boolean;
+}
+
+/** What set_mods_folder_mode does with the jars in .minecraft/mods when switching to dedicated */
+export type ModsTransfer = "none" | "copy" | "move";
+
+/** Dedicated folder moved by relocate_installation_folder */
+export type FolderKind = "mods" | "resourcepacks" | "shaders" | "config";
+
+/** What reset_installation_settings puts back to its default */
+export type ResetScope =
+  | "java_args"
+  | "parameters"
+  | "window_and_quick_play"
+  | "all";
+
+/** A modpack file that has to be downloaded by hand from its project page */
+export interface CurseForgeManualDownload {
+  project_id: number;
+  file_id: number;
+  name: string;
+  file_name: string;
+  url: string;
+  /** Folder of the installation the file belongs in */
+  target_dir: string;
+}
+
+/** Result of importing a CurseForge modpack zip */
+export interface CurseForgePackImport {
+  installation: KableInstallation;
+  downloaded: string[];
+  manual_downloads: CurseForgeManualDownload[];
+  failed: string[];
+}
+
+/** Half-open [start, end) range of character indices that matched a search query */
+export interface MatchRange {
+  start: number;
+  end: number;
+}
+
+export interface InstallationFieldMatch {
+  field: "name" | "version_id" | "description" | "tag";
+  /** Index into `installation.tags` when `field` is 'tag' */
+  tag_index?: number | null;
+  ranges: MatchRange[];
+}
+
+export interface InstallationSearchResult {
+  installation: KableInstallation;
+  score: number;
+  matches: InstallationFieldMatch[];
+}
+
+export interface LoaderPlaytimeStats {
+  loader: LoaderKind;
+  installation_count: number;
+  total_time_played_ms: number;
+  times_launched: number;
+}
+
+export interface MostPlayedInstallation {
+  id: string;
+  name: string;
+  version_id: string;
+  total_time_played_ms: number;
+  times_launched: number;
+}
+
+export interface PlaytimeStats {
+  installation_count: number;
+  total_time_played_ms: number;
+  total_launches: number;
+  per_loader: LoaderPlaytimeStats[];
+  most_played?: MostPlayedInstallation | null;
+}
+
+/** Names only in a (removed) or only in b (added) */
+export interface ListDiff {
+  added: string[];
+  removed: string[];
+}
+
+/** A mod both installations have in another version or enabled state */
+export interface ModChange {
+  id: string;
+  a: ModJarInfo;
+  b: ModJarInfo;
+}
+
+export interface InstallationDiff {
+  mods: { added: ModJarInfo[]; removed: ModJarInfo[]; changed: ModChange[] };
+  resourcepacks: ListDiff;
+  shaders: ListDiff;
+  java_args: ListDiff;
+  settings: { field: string; a: unknown; b: unknown }[];
+}
+
+/** Jars of a mods folder update, by file name */
+export interface ModUpdateReport {
+  updated: string[];
+  /** No compatible version, copied unchanged into the disabled folder */
+  incompatible: string[];
+}
+
+export interface VersionMigrationReport {
+  installation: KableInstallation;
+  /** Where the mods were backed up, null when they weren't updated */
+  backup_dir?: string | null;
+  mods: ModUpdateReport;
+}
+
+export interface InstallationContentCounts {
+  /** Enabled and disabled mods */
+  mods: number;
+  enabled_mods: number;
+  resourcepacks: number;
+  shaders: number;
+  /** Folders that are the global .minecraft one, their counts include other installations */
+  shared_folders: ("mods" | "resourcepacks" | "shaderpacks")[];
+}
+
+export interface PruneCategory {
+  paths: string[];
+  bytes: number;
+}
+
+/** Settings of an installation saved under a name, without its content */
+export interface InstallationTemplate {
+  name: string;
+  created: string;
+  java_args: string[];
+  parameters_map: Record<string, string>;
+  tags: string[];
+  connect_server?: string | null;
+  native_arch_override?: string | null;
+  resolve_lwjgl_conflicts: boolean;
+  isolated_game_files: boolean;
+  enable_pack_merging: boolean;
+  http_proxy?: string | null;
+  https_proxy?: string | null;
+  extra_classpath: string[];
+  java_agents: string[];
+  on_game_launch?: string | null;
+  on_game_close?: string | null;
+  on_game_crash?: string | null;
+}
+
+/** A key bound to more than one action in options.txt */
+export interface KeybindConflict {
+  key: string;
+  actions: string[];
+}
+
+/** A version from get_versions_in_use */
+export interface VersionUsage {
+  version_id: string;
+  /** Installations of this version or of a version inheriting from it */
+  installation_count: number;
+}
+
+/** Unused versions, libraries and assets found (or removed) by prune_unused_game_files */
+export interface PruneReport {
+  dry_run: boolean;
+  versions: PruneCategory;
+  libraries: PruneCategory;
+  assets: PruneCategory;
+  /** Isolated assets/natives folders of deleted installations */
+  isolated: PruneCategory;
+  reclaimable_bytes: number;
+  /** Why a category was left alone */
+  skipped: string[];
+}
+
+export interface StorageCategory {
+  paths: string[];
+  bytes: number;
+}
+
+export interface InstallationStorage {
+  installation_id: string;
+  name: string;
+  /** Dedicated mods/resourcepacks/shaderpacks/config folders, isolated assets/natives and game logs */
+  paths: string[];
+  bytes: number;
+}
+
+/** Disk usage overview returned by get_storage_report */
+export interface StorageReport {
+  /** RFC 3339 time the report was computed */
+  generated_at: string;
+  versions: StorageCategory;
+  libraries: StorageCategory;
+  assets: StorageCategory;
+  /** Largest first */
+  installations: InstallationStorage[];
+  caches: StorageCategory;
+  /** Launcher logs, game logs are counted per installation */
+  logs: StorageCategory;
+  exports: StorageCategory;
+  world_backups: StorageCategory;
+  total_bytes: number;
 }
 
 /** LauncherProfile struct
@@ -865,6 +1179,26 @@ export interface ModJarInfo {
   disabled?: boolean;
 }
 
+/** Where a jar in a mods folder came from, recorded in its .kable-mods.json */
+export interface ModProvenance {
+  provider: ProviderKind;
+  project_id: string;
+  /** Modrinth version id or CurseForge file id */
+  version_id?: string | null;
+  version_number?: string | null;
+  download_url?: string | null;
+  downloaded_at: string;
+}
+
+/** Result of install_local_mod */
+export interface LocalModInstall {
+  info: ModJarInfo;
+  /** Set when Modrinth knows the file, the mod then updates like a downloaded one */
+  provenance?: ModProvenance | null;
+  /** Jars with the same mod id that the new jar replaced */
+  replaced: string[];
+}
+
 // _____________________________________________________________________________
 //|                                                                             |
 //|                              Launcher Types                                 |
@@ -885,6 +1219,82 @@ export interface LaunchResult {
   error?: string;
 }
 
+export interface ProcessKillOutcome {
+  pid: number;
+  success: boolean;
+  error: string | null;
+}
+
+/** Result of kill_installation */
+export interface KillInstallationResult {
+  installation_id: string;
+  /** Processes that were signaled successfully */
+  signaled: number;
+  outcomes: ProcessKillOutcome[];
+}
+
+/** A game process started by the launcher */
+export interface TrackedProcess {
+  pid: number;
+  installation_id: string;
+  /** RFC 3339 */
+  started_at: string;
+  /** The command line it was started with */
+  command: string;
+}
+
+/** The java command a launch would run, access token redacted */
+export interface LaunchCommandPreview {
+  java_path: string;
+  args: string[];
+  /** Full command line, quoted so it can be pasted into a shell */
+  command: string;
+  working_dir: string;
+}
+
+export interface LaunchIssue {
+  /** 'command' | 'java' | 'java_args' | 'launch_extras' | 'native_compat' | 'libraries' | 'lwjgl' | 'memory' | 'mods' | 'version_jar' */
+  check: string;
+  message: string;
+}
+
+/** Result of resolve_lwjgl_conflict */
+export interface LwjglConflictResolution {
+  /** false when there was no conflict and nothing changed */
+  resolved: boolean;
+  kept_version?: string | null;
+  removed: string[];
+}
+
+/** Result of verify_version_jar and repair_version_jar */
+export interface VersionJarVerification {
+  /** The version whose jar was checked, the parent when the jar is inherited */
+  version_id: string;
+  present: boolean;
+  sha1?: string | null;
+  expected_sha1?: string | null;
+  valid: boolean;
+}
+
+/** Result of validate_launch, warnings do not block a launch */
+export interface LaunchReadiness {
+  installation_id: string;
+  ready: boolean;
+  errors: LaunchIssue[];
+  warnings: LaunchIssue[];
+}
+
+/** What a Java executable reports about itself, from probe_java */
+export interface JavaInstallation {
+  path: string;
+  version: string;
+  major_version: number;
+  vendor?: string | null;
+  /** 'x86_64' | 'arm64' | 'x86' */
+  arch: string;
+  is_64bit: boolean;
+}
+
 // _____________________________________________________________________________
 //|                                                                             |
 //|                                Mods Types                                   |
@@ -902,6 +1312,15 @@ export type ModInfoKind =
   | { Modrinth: ModrinthInfo }
   | { CurseForge: CurseForgeInfo };
 
+/** One page of provider search results (get_mods_page) */
+export interface ModsPage {
+  items: ModInfoKind[];
+  total_hits: number;
+  offset: number;
+  limit: number;
+  has_more: boolean;
+}
+
 /** Discriminated union for mod filters for each provider.
  * Uses Rust externally tagged enum format for serde compatibility.
  * ```ts
@@ -1434,6 +1853,7 @@ export interface CurseForgeFilter {
  */
 export interface ModrinthVersion {
   id: string;
+  project_id: string;
   name: string;
   version_number: string;
   changelog?: string;
@@ -1442,6 +1862,32 @@ export interface ModrinthVersion {
   loaders: string[];
 }
 
+/** A Modrinth category from /tag/category, icon is SVG markup */
+export interface ModrinthCategory {
+  name: string;
+  display_name: string;
+  icon: string;
+  project_type: string;
+  /** Group of the category, e.g. "categories", "features", "resolutions" */
+  header: string;
+}
+
+/** A Modrinth loader from /tag/loader, icon is SVG markup */
+export interface ModrinthLoaderTag {
+  name: string;
+  display_name: string;
+  icon: string;
+  supported_project_types: string[];
+}
+
+/** A game version from Modrinth's /tag/game_version */
+export interface ModrinthGameVersionTag {
+  version: string;
+  version_type: "release" | "snapshot" | "alpha" | "beta";
+  date: string;
+  major: boolean;
+}
+
 /** File info for a Modrinth mod version.
  * ```ts
  * export interface ModrinthFile {
@@ -1554,6 +2000,114 @@ export interface MrPackDetailed {
 //|                            Settings Event Types                             |
 //|_____________________________________________________________________________|
 
+/** Code of a KableError returned by launch, download and auth commands, "unknown" where the backend doesn't set one yet */
+export type KableErrorCode =
+  | "network"
+  | "not_found"
+  | "io"
+  | "auth"
+  | "validation"
+  | "cancelled"
+  | "unknown";
+
+/** Serialized backend KableError, thrown as the KableError class by invokeKable */
+export interface KableErrorPayload {
+  code: KableErrorCode;
+  message: string;
+  details?: string | null;
+}
+
+/** Payload of the "progress" event, emitted for commands that return a progress token */
+export interface ProgressEvent {
+  token: string;
+  /** e.g. "libraries", "assets", "loader_install", "modpack"; ends with "done" or "failed" */
+  stage: string;
+  current: number;
+  /** null when the amount of work isn't known */
+  total: number | null;
+  message: string | null;
+}
+
+/**
+ * What an export bundles besides kable_export.json. "mods_as_refs" stores mods downloaded
+ * from Modrinth as project/version ids, they are downloaded again on import.
+ */
+export type ExportContent = "full" | "metadata_only" | "mods_as_refs";
+
+/** Payload of the "import-progress" event, emitted before each file of a batch import */
+export interface ImportProgress {
+  /** Files imported so far, failed ones included */
+  current: number;
+  total: number;
+  /** File that is imported next */
+  path: string;
+}
+
+export interface ImportFileResult {
+  path: string;
+  /** null when the import failed */
+  installation_id: string | null;
+  error: string | null;
+}
+
+export interface BatchImportReport {
+  /** One result per path, in the order they were given */
+  results: ImportFileResult[];
+  installations: KableInstallation[];
+}
+
+/** Payload of the "export-progress" event, emitted while an installation is exported */
+export interface ExportProgress {
+  installation_id: string;
+  bytes_written: number;
+  /** Size of the exported files, zip headers not included */
+  total_bytes: number;
+  /** e.g. "mods.zip/sodium.jar" */
+  current_file: string;
+}
+
+/** Payload of the "download-failed" event, emitted once a download failed for good */
+export interface DownloadFailedEvent {
+  /** File name, e.g. a mod jar, library jar or asset hash */
+  item: string;
+  url: string;
+  error: string;
+}
+
+/** Payload of the "accounts-changed" event, emitted when an account is written, removed or made active */
+export interface AccountsChangedEvent {
+  change: "written" | "removed" | "activated";
+  account_id: string;
+  /** Empty when no account is left */
+  active_account_id: string;
+}
+
+/** Discord Rich Presence state, also the payload of "discord-status-changed" */
+export interface DiscordStatus {
+  enabled: boolean;
+  connected: boolean;
+  /** null while not connected */
+  current_activity: {
+    details: string;
+    state: string;
+    playing: boolean;
+  } | null;
+}
+
+/** Result of one check of run_first_run_checks (backend CheckResult) */
+export interface FirstRunCheckResult {
+  check:
+    | "minecraft_dir"
+    | "java"
+    | "network_mojang"
+    | "network_modrinth"
+    | "symlinks";
+  status: "ok" | "warning" | "error";
+  message: string;
+  /** What the user can do about a warning or error */
+  action: string | null;
+}
+
 /** Event payload for navigation events */
 export interface NavigationEventPayload {
   reason: string;
@@ -1561,6 +2115,8 @@ export interface NavigationEventPayload {
 
 /** Event payload for behavior choice requests */
 export interface BehaviorChoiceEventPayload {
+  /** Pass back to answer_launch_behavior with the choice */
+  request_id: string;
   options: string[];
   exit_code?: number;
 }